@@ -0,0 +1,36 @@
+/*
+ * Interface C de construction et de validation des messages du protocole de
+ * l'interface Gantt fréquence/temps.
+ *
+ * Bibliothèque : cargo build --release --features cdylib --manifest-path src/interface/Cargo.toml
+ * (libegui_test.so, libegui_test.dylib ou egui_test.dll dans src/interface/target/release).
+ *
+ * Les chaînes retournées sont allouées par la bibliothèque et doivent être
+ * libérées avec gantt_free_string. En cas d'erreur, les fonctions retournent
+ * NULL (ou -1) et gantt_last_error décrit l'erreur.
+ */
+#ifndef GANTT_PROTOCOL_H
+#define GANTT_PROTOCOL_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+char *gantt_task_message(const char *name, const char *amplifier,
+                         double freq_start, double freq_end,
+                         double time_start, double time_end);
+char *gantt_rx_guard_message(double duration_ms);
+char *gantt_exclusion_message(const char *name, double freq_start, double freq_end,
+                              double time_start, double time_end);
+char *gantt_phase_message(const char *name, double t_start, double t_end);
+char *gantt_remove_where_message(const char *expression);
+int gantt_validate_message(const char *json);
+char *gantt_schema(void);
+const char *gantt_last_error(void);
+void gantt_free_string(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GANTT_PROTOCOL_H */
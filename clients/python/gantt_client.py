@@ -0,0 +1,137 @@
+"""Client Python de référence de l'interface Gantt fréquence/temps.
+
+Les messages sont construits et validés par la bibliothèque de l'interface
+(fonctionnalité ``cdylib``) chargée avec ``ctypes``, puis envoyés ligne par ligne
+sur l'entrée standard de l'interface.
+
+Construction de la bibliothèque ::
+
+    cargo build --release --features cdylib --manifest-path src/interface/Cargo.toml
+
+Exemple ::
+
+    with GanttClient() as client:
+        client.send(client.protocol.task("Init capteurs", "A20_500", 100, 300, 0, 300))
+        client.send(client.protocol.rx_guard(150))
+"""
+
+import ctypes
+import json
+import subprocess
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[2]
+MANIFEST = ROOT / "src" / "interface" / "Cargo.toml"
+
+
+def default_library_path():
+    """Chemin de la bibliothèque construite en mode release."""
+    name = {"win32": "egui_test.dll", "darwin": "libegui_test.dylib"}.get(sys.platform, "libegui_test.so")
+    return ROOT / "src" / "interface" / "target" / "release" / name
+
+
+class ProtocolError(Exception):
+    """Message refusé par la bibliothèque."""
+
+
+class Protocol:
+    """Construction et validation des messages par la bibliothèque de l'interface."""
+
+    def __init__(self, library_path=None):
+        lib = ctypes.CDLL(str(library_path or default_library_path()))
+        c_str, c_double = ctypes.c_char_p, ctypes.c_double
+        # Les chaînes allouées par la bibliothèque sont reçues en c_void_p pour pouvoir être libérées
+        signatures = {
+            "gantt_task_message": [c_str, c_str, c_double, c_double, c_double, c_double],
+            "gantt_rx_guard_message": [c_double],
+            "gantt_exclusion_message": [c_str, c_double, c_double, c_double, c_double],
+            "gantt_phase_message": [c_str, c_double, c_double],
+            "gantt_remove_where_message": [c_str],
+            "gantt_schema": [],
+        }
+        for name, args in signatures.items():
+            getattr(lib, name).argtypes = args
+            getattr(lib, name).restype = ctypes.c_void_p
+        lib.gantt_validate_message.argtypes = [c_str]
+        lib.gantt_validate_message.restype = ctypes.c_int
+        lib.gantt_last_error.restype = c_str
+        lib.gantt_free_string.argtypes = [ctypes.c_void_p]
+        self._lib = lib
+
+    def _take(self, pointer):
+        """Copie et libère une chaîne retournée par la bibliothèque."""
+        if not pointer:
+            error = self._lib.gantt_last_error()
+            raise ProtocolError(error.decode() if error else "erreur inconnue")
+        try:
+            return ctypes.string_at(pointer).decode()
+        finally:
+            self._lib.gantt_free_string(pointer)
+
+    def task(self, name, amplifier, freq_start, freq_end, time_start, time_end, **optional):
+        """Message de tâche ; les attributs optionnels (platform, effect…) sont ajoutés puis validés."""
+        message = self._take(self._lib.gantt_task_message(
+            name.encode(), amplifier.encode(), freq_start, freq_end, time_start, time_end))
+        if not optional:
+            return message
+        return self.validate(json.dumps({**json.loads(message), **optional}))
+
+    def rx_guard(self, duration_ms):
+        """Message de configuration de la garde Rx."""
+        return self._take(self._lib.gantt_rx_guard_message(duration_ms))
+
+    def exclusion(self, name, freq_start, freq_end, time_start=0.0, time_end=1000.0):
+        """Message d'ajout de masque d'exclusion."""
+        return self._take(self._lib.gantt_exclusion_message(name.encode(), freq_start, freq_end, time_start, time_end))
+
+    def phase(self, name, t_start, t_end):
+        """Message d'ajout de phase de mission."""
+        return self._take(self._lib.gantt_phase_message(name.encode(), t_start, t_end))
+
+    def remove_where(self, expression):
+        """Message de suppression des tâches satisfaisant une expression de filtrage."""
+        return self._take(self._lib.gantt_remove_where_message(expression.encode()))
+
+    def validate(self, message):
+        """Valide un message écrit à la main et le retourne, ou lève ProtocolError."""
+        if self._lib.gantt_validate_message(message.encode()) != 0:
+            raise ProtocolError(self._lib.gantt_last_error().decode())
+        return message
+
+    def schema(self):
+        """Schéma JSON de l'ensemble des messages du protocole."""
+        return json.loads(self._take(self._lib.gantt_schema()))
+
+
+class GanttClient:
+    """Lance l'interface et lui envoie des messages sur son entrée standard."""
+
+    def __init__(self, library_path=None, command=None):
+        self.protocol = Protocol(library_path)
+        command = command or ["cargo", "run", "--release", "--manifest-path", str(MANIFEST)]
+        self._process = subprocess.Popen(command, stdin=subprocess.PIPE, text=True)
+
+    def send(self, message):
+        """Envoie un message (déjà construit ou validé) sur une ligne."""
+        self._process.stdin.write(message + "\n")
+        self._process.stdin.flush()
+
+    def close(self):
+        """Ferme l'entrée standard et attend la fermeture de l'interface."""
+        self._process.stdin.close()
+        self._process.wait()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        self.close()
+
+
+if __name__ == "__main__":
+    with GanttClient() as client:
+        p = client.protocol
+        client.send(p.rx_guard(100))
+        client.send(p.phase("Pénétration", 300, 700))
+        client.send(p.task("Transmission", "A1000_2500", 1000, 2500, 300, 600, platform="Rafale 1", effect="Deny"))
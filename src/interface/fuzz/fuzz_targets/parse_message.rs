@@ -0,0 +1,33 @@
+//! Point d’entrée de fuzzing de l’analyseur du protocole.
+//!
+//! Lancement (depuis `src/interface`) :
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_message
+//! ```
+//!
+//! Toute entrée, même mal formée ou hostile, doit être lue et refusée ou acceptée
+//! sans paniquer ni bloquer.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use egui_test::tools::protocol::{parse_message, read_message, IncomingMessage};
+use egui_test::tools::query::Query;
+
+fuzz_target!(|data: &[u8]| {
+    let mut reader = data;
+    while let Ok(Some(line)) = read_message(&mut reader) {
+        let Ok(line) = line else { continue };
+        match parse_message(&line) {
+            Ok(IncomingMessage::RemoveWhere(message)) => {
+                let _ = Query::parse(&message.remove_where);
+            }
+            Ok(IncomingMessage::ConfirmWhere(message)) => {
+                let _ = Query::parse(&message.confirm_where);
+            }
+            _ => {}
+        }
+    }
+});
@@ -0,0 +1,184 @@
+//! Interface C de construction et de validation des messages du protocole.
+//!
+//! Les fonctions construisent le JSON d’un message, le valident avec
+//! l’analyseur de l’interface ([`parse_message`]) et le retournent sous forme de
+//! chaîne C allouée par la bibliothèque, à libérer avec [`gantt_free_string`].
+//! En cas d’erreur, elles retournent un pointeur nul (ou `-1`) et le message
+//! d’erreur est disponible par [`gantt_last_error`].
+//!
+//! L’en-tête `clients/c/gantt_protocol.h` déclare ces fonctions ; le client
+//! Python de référence (`clients/python/gantt_client.py`) les charge avec `ctypes`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+
+use serde_json::{json, Value};
+
+use crate::tools::protocol::{parse_message, schema_json};
+use crate::tools::query::Query;
+
+thread_local! {
+    /// Dernière erreur survenue sur le thread appelant.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Mémorise l’erreur pour [`gantt_last_error`].
+fn set_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Convertit une chaîne C en `&str`.
+///
+/// # Safety
+///
+/// `text` doit être nul ou pointer vers une chaîne C valide terminée par un octet nul.
+unsafe fn to_str<'a>(text: *const c_char, field: &str) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err(format!("{} : pointeur nul", field));
+    }
+    CStr::from_ptr(text).to_str().map_err(|_| format!("{} : UTF-8 invalide", field))
+}
+
+/// Valide un message et le retourne sous forme de chaîne C, ou un pointeur nul en cas d’erreur.
+fn finish(message: Result<Value, String>) -> *mut c_char {
+    let json = message.and_then(|value| {
+        let json = value.to_string();
+        parse_message(&json)?;
+        Ok(json)
+    });
+    match json.map(CString::new) {
+        Ok(Ok(json)) => json.into_raw(),
+        Ok(Err(_)) => {
+            set_error("Message contenant un octet nul".into());
+            ptr::null_mut()
+        }
+        Err(e) => {
+            set_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Construit un message de tâche.
+///
+/// # Safety
+///
+/// `name` et `amplifier` doivent être des chaînes C valides.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_task_message(
+    name: *const c_char,
+    amplifier: *const c_char,
+    freq_start: f64,
+    freq_end: f64,
+    time_start: f64,
+    time_end: f64,
+) -> *mut c_char {
+    let fields = to_str(name, "name").and_then(|name| Ok((name, to_str(amplifier, "amplifier")?)));
+    finish(fields.map(|(name, amplifier)| {
+        json!({
+            "name": name,
+            "amplifier": amplifier,
+            "freq_start": freq_start,
+            "freq_end": freq_end,
+            "time_start": time_start,
+            "time_end": time_end,
+        })
+    }))
+}
+
+/// Construit un message de configuration de la garde Rx.
+#[no_mangle]
+pub extern "C" fn gantt_rx_guard_message(duration_ms: f64) -> *mut c_char {
+    finish(Ok(json!({ "rx_guard_ms": duration_ms })))
+}
+
+/// Construit un message d’ajout de masque d’exclusion.
+///
+/// # Safety
+///
+/// `name` doit être une chaîne C valide.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_exclusion_message(
+    name: *const c_char,
+    freq_start: f64,
+    freq_end: f64,
+    time_start: f64,
+    time_end: f64,
+) -> *mut c_char {
+    finish(to_str(name, "name").map(|name| {
+        json!({ "exclusion": {
+            "name": name,
+            "freq_start": freq_start,
+            "freq_end": freq_end,
+            "time_start": time_start,
+            "time_end": time_end,
+        }})
+    }))
+}
+
+/// Construit un message d’ajout de phase de mission.
+///
+/// # Safety
+///
+/// `name` doit être une chaîne C valide.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_phase_message(name: *const c_char, t_start: f64, t_end: f64) -> *mut c_char {
+    finish(to_str(name, "name").map(|name| json!({ "phase": { "name": name, "t_start": t_start, "t_end": t_end } })))
+}
+
+/// Construit un message de suppression des tâches satisfaisant une expression de filtrage.
+///
+/// # Safety
+///
+/// `expression` doit être une chaîne C valide.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_remove_where_message(expression: *const c_char) -> *mut c_char {
+    finish(to_str(expression, "expression").and_then(|expression| {
+        Query::parse(expression)?;
+        Ok(json!({ "remove_where": expression }))
+    }))
+}
+
+/// Valide un message JSON écrit par l’appelant. Retourne `0` s’il est valide, `-1` sinon.
+///
+/// # Safety
+///
+/// `json` doit être une chaîne C valide.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_validate_message(json: *const c_char) -> c_int {
+    match to_str(json, "json").and_then(parse_message) {
+        Ok(_) => 0,
+        Err(e) => {
+            set_error(e);
+            -1
+        }
+    }
+}
+
+/// Retourne le schéma JSON de l’ensemble des messages du protocole.
+#[no_mangle]
+pub extern "C" fn gantt_schema() -> *mut c_char {
+    CString::new(schema_json()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Retourne la dernière erreur du thread appelant, ou un pointeur nul.
+///
+/// La chaîne reste valide jusqu’au prochain appel d’une fonction de la bibliothèque.
+#[no_mangle]
+pub extern "C" fn gantt_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// Libère une chaîne retournée par la bibliothèque.
+///
+/// # Safety
+///
+/// `text` doit être nul ou provenir d’une fonction de la bibliothèque, et n’être libéré qu’une fois.
+#[no_mangle]
+pub unsafe extern "C" fn gantt_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
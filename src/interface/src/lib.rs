@@ -0,0 +1,12 @@
+//! Bibliothèque de l’interface Gantt fréquence/temps.
+//!
+//! Expose les modules de l’application à d’autres programmes Rust — dont le
+//! widget [`tools::widget::FreqTimeGantt`], intégrable dans d’autres applications
+//! egui — et, avec la fonctionnalité `cdylib`, une interface C ([`ffi`])
+//! permettant aux systèmes de planification écrits dans d’autres langages de
+//! construire et de valider les messages du protocole sans écrire le JSON à la main.
+
+pub mod tools;
+
+#[cfg(feature = "cdylib")]
+pub mod ffi;
@@ -1,16 +1,133 @@
 /// Module gérant les zones de fond du graphe (background).
+///
+/// Les modules sont aussi compilés par la bibliothèque : les éléments qu’elle
+/// seule expose (widget, constructeur de tâches…) ne sont pas utilisés ici.
+#[allow(dead_code)]
 mod tools {
     pub mod background;
     pub mod task;
     pub mod utils;
     pub mod app;
+    pub mod constraints;
+    pub mod history;
+    pub mod repair;
+    pub mod plan;
+    pub mod report;
+    pub mod cli;
+    pub mod briefing;
+    pub mod csv_import;
+    pub mod gantt_export;
+    pub mod timefmt;
+    pub mod inspector;
+    pub mod coloring;
+    pub mod filter;
+    pub mod rx_guard;
+    pub mod exclusion;
+    pub mod zone_editor;
+    pub mod geometry;
+    pub mod task_mesh;
+    pub mod analysis;
+    pub mod storage;
+    pub mod query;
+    pub mod system;
+    pub mod platform;
+    pub mod geo_export;
+    pub mod effect;
+    pub mod phase;
+    pub mod hud;
+    pub mod refresh;
+    pub mod screenshot;
+    pub mod protocol;
+    pub mod headless;
+    pub mod rate_limit;
+    pub mod dedup;
+    pub mod animation;
+    pub mod plan_hash;
+    pub mod compression;
+    pub mod transport;
+    pub mod mirror;
+    pub mod wall;
+    pub mod kiosk;
+    pub mod markup;
+    pub mod harmonics;
+    pub mod intermod;
+    pub mod antenna;
+    pub mod presets;
+    pub mod notes;
+    pub mod attachment;
+    pub mod baseline;
+    pub mod sandbox;
+    pub mod split;
+    pub mod align;
+    pub mod schedule;
+    pub mod robustness;
+    pub mod timeline;
+    pub mod tooltip;
+    pub mod pinned;
+    pub mod hit_stack;
+    pub mod mini_overlay;
+    pub mod marginal;
+    pub mod amp_timeline;
+    pub mod watch;
+    pub mod layers;
+    pub mod detection;
+    pub mod alerts;
+    pub mod alarm;
+    pub mod amplifiers;
+    pub mod widget;
+    pub mod view;
+    pub mod events;
+    pub mod watchdog;
+    pub mod hierarchy;
+    pub mod profile;
+    pub mod config_watch;
+    pub mod diagnostics;
+    pub mod task_log;
+    pub mod trash;
+    pub mod placement;
+    pub mod guides;
+    pub mod headroom;
+    pub mod range;
+    pub mod revisions;
+    pub mod git_import;
 }
 
-use crossbeam_queue::SegQueue;
-use std::io::{self, BufRead};
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use tools::app::MyApp;
+use tools::events::{EventBus, TransportEvent};
+use tools::protocol::read_message;
+
+/// Démarre le thread dédié à la lecture de stdin.
+///
+/// Chaque ligne (de taille bornée) est publiée sur le bus d’événements et un rafraîchissement de
+/// l’interface est demandé, celle-ci ne se redessinant pas en continu. Le
+/// rafraîchissement est différé de `wake_interval_ms` afin de regrouper les
+/// messages reçus en rafale.
+fn spawn_stdin_reader(bus: EventBus, ctx: egui::Context, wake_interval_ms: Arc<AtomicU64>) {
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        loop {
+            match read_message(&mut stdin) {
+                Ok(Some(Ok(l))) => {
+                    eprintln!("stdin -> bus : {}", l);
+                    bus.publish(TransportEvent::message(l));
+                    ctx.request_repaint_after(Duration::from_millis(wake_interval_ms.load(Ordering::Relaxed)));
+                }
+                // Ligne trop longue ou illisible : ignorée, la lecture continue
+                Ok(Some(Err(e))) => eprintln!("{}", e),
+                Ok(None) => break,
+                Err(e) => {
+                    eprintln!("Erreur lecture stdin : {}", e);
+                    break;
+                }
+            }
+        }
+    });
+}
 
 /// Point d’entrée de l’application : initialise l’UI eframe et lance le rendu.
 ///
@@ -21,38 +138,114 @@ fn main() -> eframe::Result<()> {
     // Initialisation du logger (env_logger) pour le debug et les logs runtime.
     env_logger::init();
 
-    // Création de la queue partagée
-    let msg_queue = Arc::new(SegQueue::<String>::new());
-
-    // Thread dédié à la lecture de stdin
-    {
-        let queue = Arc::clone(&msg_queue);
-        thread::spawn(move || {
-            let stdin = io::stdin();
-            for line in stdin.lock().lines() {
-                match line {
-                    Ok(l) => {
-                        queue.push(l.clone());
-                        eprintln!("stdin -> queue : {}", l);
-                    }
-                    Err(e) => {
-                        eprintln!("Erreur lecture stdin : {}", e);
-                        break;
-                    }
-                }
-            }
-        });
+    // Sous-commandes sans interface graphique (ex. `compare`)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = tools::cli::run_headless(&args) {
+        std::process::exit(code);
     }
 
+    // Création du bus d’événements partagé
+    let bus = EventBus::default();
+
     eprintln!("Lancement de l'application...");
 
+    // Stockage du plan (`--db <fichier>` pour une base SQLite persistante)
+    let store = match tools::cli::open_store(&args) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Transport réseau (`--listen <adresse>` ou fichier de configuration)
+    let transport = match tools::cli::transport_config(&args) {
+        Ok(transport) => transport,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Mode miroir (`--mirror` ou `--follow <adresse>`)
+    let mirror = match tools::cli::mirror(&args) {
+        Ok(mirror) => mirror,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+    if mirror.role == tools::mirror::MirrorRole::Primary && transport.listen.is_none() {
+        eprintln!("Erreur : --mirror nécessite un transport réseau (--listen <adresse>)");
+        std::process::exit(1);
+    }
+    let follow = match &mirror.role {
+        tools::mirror::MirrorRole::Follower { address, token } => Some((address.clone(), token.clone(), mirror.inbox.clone())),
+        _ => None,
+    };
+
+    // Mur d’images (`--band <amplificateur>|<fmin>:<fmax>` [`--hide-controls`])
+    let wall = match tools::cli::wall_tile(&args) {
+        Ok(wall) => wall,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Surveillance d’un fichier de plan (`--watch <plan>`)
+    let watch = match tools::cli::watch_path(&args) {
+        Ok(watch) => watch,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Tubes nommés des couches prévue, exécutée et observée
+    let pipes = match tools::cli::layer_pipes(&args) {
+        Ok(pipes) => pipes,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Profil d’affichage (`--profile <nom>`)
+    let profile = match tools::cli::profile_name(&args) {
+        Ok(profile) => profile,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Création de l’application
-    let app = MyApp::new(msg_queue.clone());
+    let mut app = MyApp::new(bus.clone(), store);
+    app.profiles = tools::profile::load_profile_config();
+    if let Some(name) = profile {
+        if let Err(e) = app.select_profile(&name) {
+            eprintln!("Erreur : {}", e);
+            std::process::exit(1);
+        }
+    }
+    app.mirror = mirror;
+    app.wall = wall;
+    // Mode kiosque (`--kiosk`) : plein écran sans décorations ni fenêtres de dialogue
+    let kiosk = args.iter().any(|a| a == "--kiosk");
+    app.kiosk = tools::kiosk::Kiosk::new(kiosk);
+    if let Some(amplifier) = wall.and_then(|tile| tile.amplifier) {
+        app.band_filter.tasks_of = Some(amplifier);
+    }
+    let wake_interval_ms = app.refresh.wake_interval_ms.clone();
+    let subscribers = app.subscribers.clone();
 
     // Configuration des options natives eframe (taille de la fenêtre, etc.)
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([960., 700.]),
+            .with_inner_size([960., 700.])
+            .with_fullscreen(wall.is_some() || kiosk)
+            .with_decorations(!kiosk),
         ..Default::default()
     };
 
@@ -60,10 +253,58 @@ fn main() -> eframe::Result<()> {
     //
     // - "Représentation GANTT du plan de brouillage" : titre de la fenêtre
     // - `options` : configuration
-    // - `Box::new(|_cc| Ok(Box::new(app)))` : factory créant l'instance de l'app
+    // - factory créant l'instance de l'app et démarrant la lecture de stdin et du
+    //   transport réseau, qui demandent un rafraîchissement à la réception des messages
     eframe::run_native(
         "Représentation GANTT du plan de brouillage",
         options,
-        Box::new(|_cc| Ok(Box::new(app))),
+        Box::new(move |cc| {
+            spawn_stdin_reader(bus.clone(), cc.egui_ctx.clone(), wake_interval_ms.clone());
+            if let Err(e) = app.config_watcher.start(&cc.egui_ctx) {
+                eprintln!("Rechargement de la configuration désactivé : {}", e);
+            }
+            if let Some(path) = watch {
+                let (bus, ctx, wake) = (bus.clone(), cc.egui_ctx.clone(), wake_interval_ms.clone());
+                let watching = tools::watch::spawn_plan_watcher(path, move |event| {
+                    bus.publish(TransportEvent::Plan(event));
+                    ctx.request_repaint_after(Duration::from_millis(wake.load(Ordering::Relaxed)));
+                });
+                if let Err(e) = watching {
+                    eprintln!("Surveillance du plan désactivée : {}", e);
+                }
+            }
+            for (layer, path) in pipes {
+                let (bus, ctx, wake) = (bus.clone(), cc.egui_ctx.clone(), wake_interval_ms.clone());
+                tools::layers::spawn_pipe_reader(path, move |line| {
+                    match layer {
+                        tools::layers::PlanLayer::Planned => bus.publish(TransportEvent::message(line)),
+                        _ => bus.publish(TransportEvent::Layer { layer, line }),
+                    }
+                    ctx.request_repaint_after(Duration::from_millis(wake.load(Ordering::Relaxed)));
+                });
+            }
+            let ctx = cc.egui_ctx.clone();
+            let listening = tools::transport::spawn_listener(&transport, subscribers, move |source, json| {
+                bus.publish(TransportEvent::Message { source, json });
+                ctx.request_repaint_after(Duration::from_millis(wake_interval_ms.load(Ordering::Relaxed)));
+            });
+            if let Err(e) = &listening {
+                eprintln!("Transport réseau désactivé : {}", e);
+            }
+            // Diagnostics de démarrage : fenêtre ouverte d’emblée en cas d’échec
+            let renderer = tools::diagnostics::renderer_info(cc.gl.as_deref());
+            app.diagnostics = tools::diagnostics::Diagnostics::run(
+                &transport,
+                tools::diagnostics::TransportState::Started(&listening),
+                Some(renderer),
+            );
+            eprintln!("Diagnostics : {}", app.diagnostics.summary());
+            app.diagnostics_open = !app.diagnostics.passed();
+            if let Some((address, token, inbox)) = follow {
+                let ctx = cc.egui_ctx.clone();
+                tools::mirror::spawn_follower(address, token, inbox, move || ctx.request_repaint());
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
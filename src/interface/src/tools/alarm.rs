@@ -0,0 +1,57 @@
+//! Module du bandeau d’alarme.
+//!
+//! Tant que des alertes sont levées, un bandeau en haut de la fenêtre résume
+//! leur nombre par gravité et le message de l’alerte la plus récente. Le bandeau
+//! clignote tant qu’une alerte critique est active (risque de fratricide), pour ne
+//! pas laisser l’opérateur passer à côté d’un message noyé dans la console. Un
+//! clic sur une alerte amène le graphe sur les tâches ou la région en cause.
+
+use std::time::Duration;
+
+use egui::{Color32, RichText};
+
+use crate::tools::alerts::{Alert, Severity};
+
+/// Demi-période du clignotement des alertes critiques.
+const FLASH_PERIOD: Duration = Duration::from_millis(500);
+
+/// Affiche le bandeau d’alarme s’il y a des alertes actives et retourne l’alerte
+/// cliquée.
+pub fn show_alarm_banner<'a>(ctx: &egui::Context, alerts: &'a [Alert]) -> Option<&'a Alert> {
+    let highest = alerts.iter().map(|a| a.severity).max()?;
+    let latest = alerts.iter().max_by_key(|a| a.raised)?;
+    let flash = highest == Severity::Critical && ((ctx.input(|i| i.time) / FLASH_PERIOD.as_secs_f64()) as u64).is_multiple_of(2);
+    if highest == Severity::Critical {
+        ctx.request_repaint_after(FLASH_PERIOD);
+    }
+    let fill = if flash { highest.color() } else { highest.color().gamma_multiply(0.35) };
+
+    let mut clicked = None;
+    egui::TopBottomPanel::top("alarm_banner").frame(egui::Frame::side_top_panel(&ctx.style()).fill(fill)).show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            for severity in [Severity::Critical, Severity::Warning, Severity::Info] {
+                let count = alerts.iter().filter(|a| a.severity == severity).count();
+                if count > 0 {
+                    let chip = RichText::new(format!(" {} {} ", count, severity.label())).color(Color32::WHITE).background_color(severity.color());
+                    ui.label(chip.strong());
+                }
+            }
+            ui.separator();
+            let text = RichText::new(format!("{} : {}", latest.rule, latest.message)).color(Color32::WHITE).strong();
+            if ui.link(text).on_hover_text("Aller aux tâches en cause").clicked() {
+                clicked = Some(latest);
+            }
+            if alerts.len() > 1 {
+                ui.menu_button(format!("Toutes ({})", alerts.len()), |ui| {
+                    for alert in alerts {
+                        if ui.button(RichText::new(format!("{} : {}", alert.rule, alert.message)).color(alert.severity.color())).clicked() {
+                            clicked = Some(alert);
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+        });
+    });
+    clicked
+}
@@ -0,0 +1,359 @@
+//! Module du moteur de règles d’alerte.
+//!
+//! Les règles sont lues depuis un fichier JSON ([`ALERTS_PATH`]) relu
+//! automatiquement lorsqu’il est modifié (module `config_watch`), par exemple :
+//!
+//! ```json
+//! [
+//!   {"name": "GPS L1 brouillé", "severity": "critical", "kind": "band_overlap", "band": "GPS L1"},
+//!   {"name": "A20_500 inactif en phase 2", "severity": "warning", "kind": "amplifier_idle",
+//!    "amplifier": "A20_500", "min_idle_ms": 300, "phase": "Pénétration"},
+//!   {"name": "Tâche provisoire prioritaire", "severity": "info", "kind": "any_task",
+//!    "query": "tentative == true && priority >= 5"}
+//! ]
+//! ```
+//!
+//! Les expressions de filtrage sont analysées une fois, au chargement des règles.
+//! Les règles sont réévaluées à chaque révision du plan (ou modification des
+//! phases, des bandes de référence ou des règles) ; une règle qui se déclenche
+//! lève une notification et colore le badge d’alerte du panneau latéral selon la
+//! gravité la plus élevée. Les alertes actives sont aussi résumées par le bandeau
+//! d’alarme (module `alarm`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use egui::Color32;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::tools::amp_timeline::busy_intervals;
+use crate::tools::phase::Phase;
+use crate::tools::presets::BandPreset;
+use crate::tools::query::{Query, QueryContext};
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::MAX_TIME;
+
+/// Chemin du fichier des règles d’alerte.
+pub const ALERTS_PATH: &str = "alerts.json";
+
+/// Durée d’affichage d’une notification.
+pub const NOTIFICATION_DURATION: Duration = Duration::from_secs(6);
+
+/// Gravité d’une alerte.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Information.
+    Info,
+    /// Avertissement.
+    Warning,
+    /// Alerte critique.
+    Critical,
+}
+
+impl Severity {
+    /// Libellé de la gravité.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "Information",
+            Severity::Warning => "Avertissement",
+            Severity::Critical => "Critique",
+        }
+    }
+
+    /// Couleur associée à la gravité.
+    pub fn color(&self) -> Color32 {
+        match self {
+            Severity::Info => Color32::from_rgb(90, 160, 230),
+            Severity::Warning => Color32::from_rgb(255, 170, 0),
+            Severity::Critical => Color32::from_rgb(230, 50, 50),
+        }
+    }
+}
+
+/// Condition de déclenchement d’une règle.
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// Une tâche recouvre la bande de référence nommée `band`.
+    BandOverlap { band: String },
+    /// Une tâche satisfait l’expression de filtrage `query`, analysée au chargement.
+    AnyTask {
+        #[serde(deserialize_with = "deserialize_query")]
+        query: Query,
+    },
+    /// L’amplificateur reste inactif plus de `min_idle_ms` ms d’affilée, sur
+    /// toute la mission ou pendant la phase `phase`.
+    AmplifierIdle {
+        amplifier: Amplifier,
+        min_idle_ms: f64,
+        #[serde(default)]
+        phase: Option<String>,
+    },
+}
+
+/// Analyse l’expression de filtrage d’une règle.
+fn deserialize_query<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Query, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    Query::parse(&text).map_err(serde::de::Error::custom)
+}
+
+/// Règle d’alerte.
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct AlertRule {
+    /// Nom de la règle.
+    pub name: String,
+    /// Gravité de l’alerte levée.
+    pub severity: Severity,
+    /// Condition de déclenchement.
+    #[serde(flatten)]
+    pub condition: RuleCondition,
+}
+
+/// Alerte levée par une règle.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Alert {
+    /// Nom de la règle.
+    pub rule: String,
+    /// Gravité de l’alerte.
+    pub severity: Severity,
+    /// Description de la cause.
+    pub message: String,
+    /// Clés des tâches en cause.
+    pub tasks: Vec<String>,
+    /// Région en cause `[freq_start, time_start, freq_end, time_end]` (MHz, ms).
+    pub region: Option<[f64; 4]>,
+    /// Instant de la levée de l’alerte, renseigné par le moteur.
+    pub raised: Option<Instant>,
+}
+
+/// Contexte d’évaluation des règles.
+pub struct AlertContext<'a> {
+    /// Bandes de référence connues.
+    pub presets: &'a [BandPreset],
+    /// Phases de la mission.
+    pub phases: &'a [Phase],
+    /// Garde Rx (expressions de filtrage).
+    pub rx_guard: RxGuard,
+}
+
+impl AlertRule {
+    /// Alerte de la règle, de cause `message`.
+    fn alert(&self, message: String, tasks: Vec<String>, region: Option<[f64; 4]>) -> Alert {
+        Alert { rule: self.name.clone(), severity: self.severity, message, tasks, region, raised: None }
+    }
+
+    /// Évalue la règle et retourne l’alerte levée, s’il y a lieu.
+    pub fn evaluate(&self, tasks: &[Task], ctx: &AlertContext) -> Option<Alert> {
+        match &self.condition {
+            RuleCondition::BandOverlap { band } => {
+                let Some(preset) = ctx.presets.iter().find(|p| p.name == *band) else {
+                    return Some(self.alert(format!("bande de référence « {} » inconnue", band), vec![], None));
+                };
+                let hits: Vec<&Task> = tasks
+                    .iter()
+                    .filter(|t| t.freq_start < preset.freq_end && t.freq_end > preset.freq_start)
+                    .collect();
+                let names: Vec<&str> = hits.iter().map(|t| t.name.as_str()).collect();
+                let region = [preset.freq_start, 0., preset.freq_end, MAX_TIME];
+                (!hits.is_empty()).then(|| {
+                    self.alert(format!("{} recouvre {}", names.join(", "), band), keys(&hits), Some(region))
+                })
+            }
+            RuleCondition::AnyTask { query } => {
+                let query_ctx = QueryContext { rx_guard: ctx.rx_guard };
+                let hits: Vec<&Task> = tasks.iter().filter(|t| query.matches(t, &query_ctx)).collect();
+                let names: Vec<&str> = hits.iter().map(|t| t.name.as_str()).collect();
+                (!hits.is_empty()).then(|| {
+                    self.alert(format!("{} tâche(s) concernée(s) : {}", hits.len(), names.join(", ")), keys(&hits), None)
+                })
+            }
+            RuleCondition::AmplifierIdle { amplifier, min_idle_ms, phase } => {
+                let (start, end) = match phase {
+                    Some(name) => match ctx.phases.iter().find(|p| p.name == *name) {
+                        Some(phase) => (phase.t_start, phase.t_end),
+                        None => return Some(self.alert(format!("phase « {} » inconnue", name), vec![], None)),
+                    },
+                    None => (0., MAX_TIME),
+                };
+                let (idle_start, idle_end) = longest_idle(&busy_intervals(tasks, *amplifier), start, end);
+                let (freq_start, freq_end) = amplifier.band();
+                (idle_end - idle_start > *min_idle_ms).then(|| {
+                    self.alert(
+                        format!("{} inactif pendant {:.0} ms", amplifier.label(), idle_end - idle_start),
+                        vec![],
+                        Some([freq_start, idle_start, freq_end, idle_end]),
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Clés des tâches.
+fn keys(tasks: &[&Task]) -> Vec<String> {
+    tasks.iter().map(|t| t.key().to_string()).collect()
+}
+
+/// Plus longue période sans activité `(début, fin)` dans `[start, end]`, les
+/// périodes d’activité `busy` étant triées et disjointes.
+fn longest_idle(busy: &[(f64, f64)], start: f64, end: f64) -> (f64, f64) {
+    let mut longest = (start, start);
+    let mut cursor = start;
+    for &(busy_start, busy_end) in busy {
+        if busy_end <= start || busy_start >= end {
+            continue;
+        }
+        if busy_start.max(start) - cursor > longest.1 - longest.0 {
+            longest = (cursor, busy_start.max(start));
+        }
+        cursor = cursor.max(busy_end.min(end));
+    }
+    if end - cursor > longest.1 - longest.0 {
+        longest = (cursor, end);
+    }
+    longest
+}
+
+/// Évalue toutes les règles et retourne les alertes levées.
+pub fn evaluate(rules: &[AlertRule], tasks: &[Task], ctx: &AlertContext) -> Vec<Alert> {
+    rules.iter().filter_map(|rule| rule.evaluate(tasks, ctx)).collect()
+}
+
+/// Charge les règles d’alerte depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible, mal formé ou si une
+/// expression de filtrage est invalide.
+pub fn load_alert_rules(path: &Path) -> Result<Vec<AlertRule>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Règles d'alerte {} invalides : {}", path.display(), e))
+}
+
+/// Moteur d’alerte : règles relues à chaque modification du fichier, alertes
+/// actives et notifications récentes.
+pub struct AlertEngine {
+    /// Fichier des règles.
+    path: PathBuf,
+    /// Règles chargées.
+    pub rules: Vec<AlertRule>,
+    /// Révision du plan, phases et bandes de référence de la dernière évaluation
+    /// (`None` si les règles doivent être réévaluées).
+    evaluated: Option<(u64, Vec<Phase>, Vec<BandPreset>)>,
+    /// Alertes actuellement levées.
+    pub active: Vec<Alert>,
+    /// Notifications des alertes nouvellement levées et instant de leur levée.
+    notifications: Vec<(Alert, Instant)>,
+}
+
+impl AlertEngine {
+    /// Crée un moteur lisant ses règles dans `path`, chargées immédiatement.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let mut engine = Self {
+            path: path.into(),
+            rules: vec![],
+            evaluated: None,
+            active: vec![],
+            notifications: vec![],
+        };
+        if let Err(e) = engine.reload() {
+            eprintln!("{}", e);
+        }
+        engine
+    }
+
+    /// Relit le fichier des règles ; un fichier absent ne définit aucune règle.
+    /// En cas d’erreur, les règles précédentes sont conservées.
+    pub fn reload(&mut self) -> Result<String, String> {
+        self.evaluated = None;
+        if !self.path.exists() {
+            self.rules.clear();
+            return Ok("fichier absent, aucune règle".into());
+        }
+        load_alert_rules(&self.path).map(|rules| {
+            self.rules = rules;
+            format!("{} règle(s) d'alerte", self.rules.len())
+        })
+    }
+
+    /// Réévalue les règles si le plan (de révision `revision`), les phases, les
+    /// bandes de référence ou les règles ont changé depuis la dernière évaluation,
+    /// notifie les alertes nouvellement levées et retire les notifications expirées.
+    pub fn update(&mut self, revision: u64, tasks: &[Task], ctx: &AlertContext, now: Instant) {
+        self.notifications.retain(|(_, raised)| now.duration_since(*raised) < NOTIFICATION_DURATION);
+        let unchanged = self.evaluated.as_ref().is_some_and(|(evaluated, phases, presets)| {
+            *evaluated == revision && phases.as_slice() == ctx.phases && presets.as_slice() == ctx.presets
+        });
+        if unchanged {
+            return;
+        }
+        self.evaluated = Some((revision, ctx.phases.to_vec(), ctx.presets.to_vec()));
+        let mut alerts = evaluate(&self.rules, tasks, ctx);
+        for alert in &mut alerts {
+            match self.active.iter().find(|a| a.rule == alert.rule) {
+                Some(previous) => alert.raised = previous.raised,
+                None => {
+                    eprintln!("Alerte ({}) {} : {}", alert.severity.label(), alert.rule, alert.message);
+                    alert.raised = Some(now);
+                    self.notifications.push((alert.clone(), now));
+                }
+            }
+        }
+        self.active = alerts;
+    }
+
+    /// Gravité la plus élevée parmi les alertes actives.
+    pub fn highest(&self) -> Option<Severity> {
+        self.active.iter().map(|a| a.severity).max()
+    }
+
+    /// Indique si des notifications sont affichées (rafraîchissement nécessaire).
+    pub fn has_notifications(&self) -> bool {
+        !self.notifications.is_empty()
+    }
+
+    /// Affiche le badge d’alerte, coloré selon la gravité la plus élevée, et la
+    /// liste des alertes actives au survol.
+    pub fn show_badge(&self, ui: &mut egui::Ui) {
+        let (text, color) = match self.highest() {
+            Some(severity) => (format!("⚠ {} alerte(s)", self.active.len()), severity.color()),
+            None => ("✔ Aucune alerte".to_string(), Color32::from_gray(140)),
+        };
+        let badge = ui.label(egui::RichText::new(text).color(Color32::WHITE).background_color(color.gamma_multiply(0.8)));
+        badge.on_hover_ui(|ui| {
+            if self.active.is_empty() {
+                ui.label(format!("{} règle(s) chargée(s) depuis {}", self.rules.len(), self.path.display()));
+            }
+            for alert in &self.active {
+                ui.colored_label(alert.severity.color(), format!("{} : {}", alert.rule, alert.message));
+            }
+        });
+    }
+
+    /// Affiche les notifications récentes dans le coin inférieur droit.
+    pub fn show_notifications(&self, ctx: &egui::Context) {
+        if self.notifications.is_empty() {
+            return;
+        }
+        egui::Area::new(egui::Id::new("alert_notifications"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12., -12.))
+            .show(ctx, |ui| {
+                for (alert, _) in &self.notifications {
+                    egui::Frame::popup(ui.style()).stroke(egui::Stroke::new(2., alert.severity.color())).show(ui, |ui| {
+                        ui.colored_label(alert.severity.color(), format!("{} – {}", alert.severity.label(), alert.rule));
+                        ui.label(&alert.message);
+                    });
+                }
+            });
+    }
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self::new(ALERTS_PATH)
+    }
+}
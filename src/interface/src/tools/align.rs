@@ -0,0 +1,119 @@
+//! Module d’alignement et de répartition des tâches sélectionnées.
+//!
+//! Comme dans les logiciels de dessin vectoriel, les tâches d’une sélection
+//! multiple (Maj+clic sur le graphe) peuvent être alignées sur le même début ou
+//! la même fin, réparties à intervalles réguliers ou placées bout à bout. Les
+//! tâches sont déplacées dans le temps sans changer de durée.
+
+use crate::tools::task::Task;
+
+/// Opération d’alignement ou de répartition.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Alignment {
+    /// Aligne les débuts sur le plus précoce.
+    Start,
+    /// Aligne les fins sur la plus tardive.
+    End,
+    /// Répartit les tâches entre la première et la dernière avec des écarts égaux.
+    Distribute,
+    /// Place les tâches bout à bout à partir de la première.
+    Abut,
+}
+
+impl Alignment {
+    /// Liste de toutes les opérations.
+    pub const ALL: [Alignment; 4] = [Alignment::Start, Alignment::End, Alignment::Distribute, Alignment::Abut];
+
+    /// Libellé de l’opération.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Alignment::Start => "Aligner les débuts",
+            Alignment::End => "Aligner les fins",
+            Alignment::Distribute => "Répartir dans le temps",
+            Alignment::Abut => "Mettre bout à bout",
+        }
+    }
+
+    /// Nombre minimal de tâches sélectionnées pour l’opération.
+    pub fn min_count(&self) -> usize {
+        match self {
+            Alignment::Distribute => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// Sélection multiple de tâches, désignées par leur clé.
+#[derive(Default)]
+pub struct Selection {
+    /// Clés des tâches sélectionnées.
+    pub keys: Vec<String>,
+}
+
+impl Selection {
+    /// Ajoute la tâche à la sélection, ou l’en retire si elle y figure déjà.
+    pub fn toggle(&mut self, task: &Task) {
+        match self.keys.iter().position(|k| k == task.key()) {
+            Some(i) => {
+                self.keys.remove(i);
+            }
+            None => self.keys.push(task.key().to_string()),
+        }
+    }
+
+    /// Indique si la tâche est sélectionnée.
+    pub fn contains(&self, task: &Task) -> bool {
+        self.keys.iter().any(|k| k == task.key())
+    }
+
+    /// Indices des tâches sélectionnées encore présentes dans le plan.
+    pub fn indices(&self, tasks: &[Task]) -> Vec<usize> {
+        (0..tasks.len()).filter(|&i| self.contains(&tasks[i])).collect()
+    }
+}
+
+/// Applique l’opération aux tâches d’indices `selection`.
+pub fn align(tasks: &mut [Task], selection: &[usize], alignment: Alignment) {
+    if selection.len() < alignment.min_count() {
+        return;
+    }
+    let mut order = selection.to_vec();
+    order.sort_by(|&a, &b| tasks[a].time_start.total_cmp(&tasks[b].time_start));
+    let duration = |t: &Task| t.time_end - t.time_start;
+    match alignment {
+        Alignment::Start => {
+            let start = tasks[order[0]].time_start;
+            for &i in &order {
+                let delta = start - tasks[i].time_start;
+                tasks[i].shift_time(delta);
+            }
+        }
+        Alignment::End => {
+            let end = order.iter().map(|&i| tasks[i].time_end).fold(f64::NEG_INFINITY, f64::max);
+            for &i in &order {
+                let delta = end - tasks[i].time_end;
+                tasks[i].shift_time(delta);
+            }
+        }
+        Alignment::Distribute => {
+            let first = tasks[order[0]].time_start;
+            let last = order.iter().map(|&i| tasks[i].time_end).fold(f64::NEG_INFINITY, f64::max);
+            let busy: f64 = order.iter().map(|&i| duration(&tasks[i])).sum();
+            let gap = (last - first - busy) / (order.len() - 1) as f64;
+            let mut cursor = first;
+            for &i in &order {
+                let delta = cursor - tasks[i].time_start;
+                tasks[i].shift_time(delta);
+                cursor = tasks[i].time_end + gap;
+            }
+        }
+        Alignment::Abut => {
+            let mut cursor = tasks[order[0]].time_start;
+            for &i in &order {
+                let delta = cursor - tasks[i].time_start;
+                tasks[i].shift_time(delta);
+                cursor = tasks[i].time_end;
+            }
+        }
+    }
+}
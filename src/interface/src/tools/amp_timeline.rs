@@ -0,0 +1,55 @@
+//! Module des mini frises d’occupation des amplificateurs.
+//!
+//! Sous chaque amplificateur du panneau latéral, une frise d’une ligne montre les
+//! périodes d’activité de l’amplificateur sur le temps de mission. Un clic sur la
+//! frise zoome le graphe sur la bande de l’amplificateur et sur la période
+//! d’activité cliquée (ou sur l’ensemble de ses tâches si le clic tombe entre deux
+//! périodes).
+
+use egui::{Color32, Rect, Sense, Stroke, Ui};
+
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::MAX_TIME;
+
+/// Hauteur d’une frise en points.
+const TIMELINE_HEIGHT: f32 = 8.;
+
+/// Périodes d’activité de l’amplificateur, fusionnées et triées.
+pub fn busy_intervals(tasks: &[Task], amp: Amplifier) -> Vec<(f64, f64)> {
+    let mut intervals: Vec<(f64, f64)> = tasks
+        .iter()
+        .filter(|t| t.amplifier == amp)
+        .map(|t| (t.time_start, t.time_end))
+        .collect();
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Affiche la frise des périodes `intervals` et retourne, en cas de clic,
+/// l’intervalle de temps sur lequel zoomer.
+pub fn show_amp_timeline(ui: &mut Ui, intervals: &[(f64, f64)], color: Color32) -> Option<(f64, f64)> {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), TIMELINE_HEIGHT), Sense::click());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 1., ui.visuals().extreme_bg_color);
+    let x = |t: f64| rect.left() + (t.clamp(0., MAX_TIME) / MAX_TIME) as f32 * rect.width();
+    for &(start, end) in intervals {
+        let bar = Rect::from_x_y_ranges(x(start)..=x(end).max(x(start) + 1.), rect.y_range());
+        painter.rect_filled(bar, 0., color);
+    }
+    painter.rect_stroke(rect, 1., Stroke::new(0.5, Color32::from_gray(90)), egui::StrokeKind::Inside);
+
+    let response = response.on_hover_text("Clic : zoom sur la bande et la période d'activité");
+    if !response.clicked() || intervals.is_empty() {
+        return None;
+    }
+    let time = response.interact_pointer_pos().map(|p| ((p.x - rect.left()) / rect.width()) as f64 * MAX_TIME)?;
+    let whole = (intervals[0].0, intervals[intervals.len() - 1].1);
+    Some(intervals.iter().copied().find(|&(start, end)| (start..=end).contains(&time)).unwrap_or(whole))
+}
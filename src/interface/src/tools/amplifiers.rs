@@ -0,0 +1,207 @@
+//! Module du registre des amplificateurs.
+//!
+//! Les couleurs et les cycles de service des amplificateurs ne sont pas codés en
+//! dur dans [`Amplifier`] : ils sont lus depuis un fichier de configuration JSON
+//! ([`AMPLIFIERS_PATH`]), par exemple :
+//!
+//! ```json
+//! {"colors": {"A20_500": [0, 187, 221], "A2400_6000": [200, 60, 60]}, "generate": false,
+//!  "duty_cycles": {"A960_1215": {"max_on": 300, "cooldown": 50}, "A2400_6000": null}}
+//! ```
+//!
+//! Les amplificateurs sans couleur configurée reçoivent leur couleur par défaut,
+//! ou, si `generate` est vrai, une couleur d’une palette de teintes distinctes
+//! générée pour l’ensemble des amplificateurs. Les amplificateurs absents de
+//! `duty_cycles` gardent leur cycle de service par défaut ; `null` le supprime.
+//!
+//! La liste des amplificateurs et leurs bandes restent celles de l’énumération
+//! [`Amplifier`].
+//!
+//! Le registre peut être remplacé en cours d’exécution ([`set_amplifiers`])
+//! lorsque le fichier est modifié.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use egui::Color32;
+use serde::Deserialize;
+
+use crate::tools::coloring::palette;
+use crate::tools::constraints::DutyCycle;
+use crate::tools::task::Amplifier;
+
+/// Chemin du fichier de configuration des amplificateurs.
+pub const AMPLIFIERS_PATH: &str = "amplifiers.json";
+
+/// Couleurs par défaut, dans l’ordre de [`Amplifier::ALL`].
+pub const DEFAULT_COLORS: [Color32; 5] = [
+    Color32::from_rgb(0, 187, 221),
+    Color32::from_rgb(255, 163, 0),
+    Color32::from_rgb(124, 127, 171),
+    Color32::from_rgb(0, 171, 142),
+    Color32::from_rgb(174, 37, 115),
+];
+
+/// Cycles de service par défaut, dans l’ordre de [`Amplifier::ALL`].
+pub const DEFAULT_DUTY_CYCLES: [Option<DutyCycle>; 5] = [
+    None,
+    None,
+    Some(DutyCycle { max_on: 300., cooldown: 50. }),
+    Some(DutyCycle { max_on: 500., cooldown: 100. }),
+    Some(DutyCycle { max_on: 400., cooldown: 150. }),
+];
+
+/// Configuration des amplificateurs.
+#[derive(Deserialize, Default, Debug)]
+pub struct AmplifierConfig {
+    /// Couleurs RVB imposées, par nom d’amplificateur (ex. `A20_500`).
+    #[serde(default)]
+    pub colors: BTreeMap<String, [u8; 3]>,
+    /// Génère une palette de teintes distinctes pour les amplificateurs sans
+    /// couleur imposée, au lieu des couleurs par défaut.
+    #[serde(default)]
+    pub generate: bool,
+    /// Cycles de service imposés, par nom d’amplificateur ; `null` supprime la
+    /// contrainte de l’amplificateur.
+    #[serde(default)]
+    pub duty_cycles: BTreeMap<String, Option<DutyCycle>>,
+}
+
+/// Couleur et cycle de service de chaque amplificateur.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AmplifierRegistry {
+    /// Couleurs, dans l’ordre de [`Amplifier::ALL`].
+    colors: Vec<Color32>,
+    /// Cycles de service, dans l’ordre de [`Amplifier::ALL`].
+    duty_cycles: Vec<Option<DutyCycle>>,
+}
+
+impl Default for AmplifierRegistry {
+    fn default() -> Self {
+        Self { colors: DEFAULT_COLORS.to_vec(), duty_cycles: DEFAULT_DUTY_CYCLES.to_vec() }
+    }
+}
+
+impl AmplifierRegistry {
+    /// Résout les couleurs et les cycles de service de tous les amplificateurs
+    /// selon la configuration.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si la configuration désigne un amplificateur
+    /// inconnu ou un cycle de service invalide.
+    pub fn from_config(config: &AmplifierConfig) -> Result<Self, String> {
+        let names = config.colors.keys().chain(config.duty_cycles.keys());
+        if let Some(name) = names.into_iter().find(|name| Amplifier::from_str(name).is_none()) {
+            return Err(format!("Amplificateur « {} » inconnu", name));
+        }
+        if let Some((name, _)) = config.duty_cycles.iter().find(|(_, duty)| duty.is_some_and(|d| !d.is_valid())) {
+            return Err(format!("Cycle de service de « {} » invalide : durées positives et finies attendues", name));
+        }
+        let generated = palette(Amplifier::ALL.len());
+        let colors = Amplifier::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, amp)| match config.colors.get(&format!("{:?}", amp)) {
+                Some(&[r, g, b]) => Color32::from_rgb(r, g, b),
+                None if config.generate => generated[i],
+                None => DEFAULT_COLORS[i],
+            })
+            .collect();
+        let duty_cycles = Amplifier::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, amp)| config.duty_cycles.get(&format!("{:?}", amp)).copied().unwrap_or(DEFAULT_DUTY_CYCLES[i]))
+            .collect();
+        Ok(Self { colors, duty_cycles })
+    }
+
+    /// Couleur de l’amplificateur.
+    pub fn color(&self, amp: Amplifier) -> Color32 {
+        self.colors[amp as usize]
+    }
+
+    /// Contrainte de cycle de service de l’amplificateur, si elle existe.
+    pub fn duty_cycle(&self, amp: Amplifier) -> Option<DutyCycle> {
+        self.duty_cycles[amp as usize]
+    }
+}
+
+/// Charge la configuration des amplificateurs depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible, mal formé, désigne
+/// un amplificateur inconnu ou un cycle de service invalide.
+pub fn load_amplifiers(path: &Path) -> Result<AmplifierRegistry, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let config: AmplifierConfig =
+        serde_json::from_str(&content).map_err(|e| format!("Configuration des amplificateurs {} invalide : {}", path.display(), e))?;
+    AmplifierRegistry::from_config(&config).map_err(|e| format!("{} : {}", path.display(), e))
+}
+
+/// Charge la configuration des amplificateurs, ou la configuration par défaut si
+/// le fichier est absent ou invalide.
+pub fn load_amplifier_config() -> AmplifierRegistry {
+    let path = Path::new(AMPLIFIERS_PATH);
+    if !path.exists() {
+        return AmplifierRegistry::default();
+    }
+    load_amplifiers(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        AmplifierRegistry::default()
+    })
+}
+
+/// Registre des amplificateurs, chargé à la première utilisation.
+static REGISTRY: OnceLock<RwLock<AmplifierRegistry>> = OnceLock::new();
+
+/// Registre des amplificateurs, chargé si nécessaire.
+fn registry() -> &'static RwLock<AmplifierRegistry> {
+    REGISTRY.get_or_init(|| RwLock::new(load_amplifier_config()))
+}
+
+/// Couleur de l’amplificateur selon le registre.
+pub fn amplifier_color(amp: Amplifier) -> Color32 {
+    registry().read().unwrap_or_else(|e| e.into_inner()).color(amp)
+}
+
+/// Cycle de service de l’amplificateur selon le registre.
+pub fn amplifier_duty_cycle(amp: Amplifier) -> Option<DutyCycle> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).duty_cycle(amp)
+}
+
+/// Remplace le registre (rechargement de la configuration).
+pub fn set_amplifiers(amplifiers: AmplifierRegistry) {
+    *registry().write().unwrap_or_else(|e| e.into_inner()) = amplifiers;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duty_cycles_are_read_from_the_configuration() {
+        let config: AmplifierConfig = serde_json::from_str(
+            r#"{"duty_cycles": {"A20_500": {"max_on": 1000, "cooldown": 200}, "A960_1215": null}}"#,
+        )
+        .unwrap();
+        let registry = AmplifierRegistry::from_config(&config).unwrap();
+
+        assert_eq!(registry.duty_cycle(Amplifier::A20_500), Some(DutyCycle { max_on: 1000., cooldown: 200. }));
+        assert_eq!(registry.duty_cycle(Amplifier::A960_1215), None);
+        assert_eq!(registry.duty_cycle(Amplifier::A1000_2500), DEFAULT_DUTY_CYCLES[Amplifier::A1000_2500 as usize]);
+    }
+
+    #[test]
+    fn unknown_amplifiers_and_invalid_duty_cycles_are_rejected() {
+        let unknown: AmplifierConfig = serde_json::from_str(r#"{"duty_cycles": {"A1_2": null}}"#).unwrap();
+        let invalid: AmplifierConfig = serde_json::from_str(r#"{"duty_cycles": {"A20_500": {"max_on": 0, "cooldown": 10}}}"#).unwrap();
+
+        assert!(AmplifierRegistry::from_config(&unknown).is_err());
+        assert!(AmplifierRegistry::from_config(&invalid).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Module d’analyse du plan en arrière-plan.
+//!
+//! La détection des conflits et des produits d’intermodulation (quadratiques en
+//! nombre de tâches), les cycles de service, la garde Rx, la compatibilité des
+//! antennes et les statistiques de couverture sont calculés par un thread dédié
+//! afin de ne pas bloquer le rendu. L’application soumet une copie
+//! du plan à chaque modification ; le thread ne traite que la dernière soumission
+//! en attente et renvoie le résultat par un canal, puis demande un rafraîchissement.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use crate::tools::antenna::{antenna_loads, antenna_violations, Antenna, AntennaLoad, AntennaViolation};
+use crate::tools::constraints::{check_duty_cycles, find_conflicts, Conflict, DutyCycleViolation};
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::intermod::{find_intermod, IntermodWarning};
+use crate::tools::report::{plan_stats_with, system_loads, PlanStats, SystemLoad};
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::system::System;
+use crate::tools::task::Task;
+
+/// Résultat de l’analyse d’un plan.
+#[derive(Clone)]
+pub struct Analysis {
+    /// Violations de cycle de service.
+    pub violations: Vec<DutyCycleViolation>,
+    /// Paires de tâches en conflit.
+    pub conflicts: Vec<Conflict>,
+    /// Indices des tâches émettant pendant la garde Rx sans être rx-safe.
+    pub rx_violations: Vec<usize>,
+    /// Produits d’intermodulation tombant dans une bande protégée, ou dans une bande écoutée pendant la garde Rx.
+    pub intermods: Vec<IntermodWarning>,
+    /// Tâches affectées à une antenne inconnue ou hors de sa bande.
+    pub antenna_violations: Vec<AntennaViolation>,
+    /// Statistiques de couverture et charge des amplificateurs.
+    pub stats: PlanStats,
+    /// Charge cumulée de chaque système.
+    pub systems: Vec<SystemLoad>,
+    /// Occupation de chaque antenne.
+    pub antennas: Vec<AntennaLoad>,
+    /// Indices des tâches de fréquence nulle, négative ou non finie, ramenées à
+    /// la fréquence minimale en échelle logarithmique.
+    pub invalid_freqs: Vec<usize>,
+}
+
+impl Analysis {
+    /// Analyse un plan.
+    pub fn compute(
+        tasks: &[Task],
+        rx_guard: &RxGuard,
+        systems: &[System],
+        exclusions: &[ExclusionMask],
+        antennas: &[Antenna],
+    ) -> Self {
+        let violations = check_duty_cycles(tasks);
+        let conflicts = find_conflicts(tasks);
+        let stats = plan_stats_with(tasks, conflicts.len(), violations.len());
+        Self {
+            violations,
+            conflicts,
+            rx_violations: rx_guard.violations(tasks),
+            intermods: find_intermod(tasks, exclusions, rx_guard),
+            antenna_violations: antenna_violations(tasks, antennas),
+            stats,
+            systems: system_loads(systems, tasks),
+            antennas: antenna_loads(antennas, tasks),
+            invalid_freqs: tasks.iter().enumerate().filter(|(_, t)| !t.is_log_safe()).map(|(i, _)| i).collect(),
+        }
+    }
+}
+
+/// Données d’entrée d’une analyse : plan, garde Rx, systèmes, masques et antennes.
+#[derive(Clone)]
+struct AnalysisInput {
+    tasks: Vec<Task>,
+    rx_guard: RxGuard,
+    systems: Vec<System>,
+    exclusions: Vec<ExclusionMask>,
+    antennas: Vec<Antenna>,
+}
+
+impl AnalysisInput {
+    /// Indique si les données diffèrent de celles fournies.
+    fn differs(&self, tasks: &[Task], rx_guard: &RxGuard, systems: &[System], exclusions: &[ExclusionMask], antennas: &[Antenna]) -> bool {
+        self.tasks.as_slice() != tasks
+            || self.rx_guard != *rx_guard
+            || self.systems.as_slice() != systems
+            || self.exclusions.as_slice() != exclusions
+            || self.antennas.as_slice() != antennas
+    }
+}
+
+/// Demande d’analyse envoyée au thread.
+struct Job {
+    revision: u64,
+    input: AnalysisInput,
+    ctx: egui::Context,
+}
+
+/// Thread d’analyse et dernier résultat reçu.
+pub struct AnalysisWorker {
+    jobs: Sender<Job>,
+    results: Receiver<(u64, Analysis)>,
+    /// Données de la dernière soumission, pour détecter les modifications.
+    submitted: Option<AnalysisInput>,
+    revision: u64,
+    latest: Option<(u64, Analysis)>,
+}
+
+impl Default for AnalysisWorker {
+    fn default() -> Self {
+        let (jobs, job_rx) = channel::<Job>();
+        let (result_tx, results) = channel();
+        thread::spawn(move || {
+            while let Ok(mut job) = job_rx.recv() {
+                // Seule la dernière version du plan mérite d’être analysée
+                while let Ok(newer) = job_rx.try_recv() {
+                    job = newer;
+                }
+                let input = &job.input;
+                let analysis = Analysis::compute(&input.tasks, &input.rx_guard, &input.systems, &input.exclusions, &input.antennas);
+                if result_tx.send((job.revision, analysis)).is_err() {
+                    break;
+                }
+                job.ctx.request_repaint();
+            }
+        });
+        Self { jobs, results, submitted: None, revision: 0, latest: None }
+    }
+}
+
+impl AnalysisWorker {
+    /// Soumet le plan au thread s’il a changé depuis la dernière soumission et
+    /// récupère les résultats disponibles.
+    pub fn update(
+        &mut self,
+        tasks: &[Task],
+        rx_guard: &RxGuard,
+        systems: &[System],
+        exclusions: &[ExclusionMask],
+        antennas: &[Antenna],
+        ctx: &egui::Context,
+    ) {
+        let changed = self.submitted.as_ref().is_none_or(|input| input.differs(tasks, rx_guard, systems, exclusions, antennas));
+        if changed {
+            self.revision += 1;
+            let input = AnalysisInput {
+                tasks: tasks.to_vec(),
+                rx_guard: *rx_guard,
+                systems: systems.to_vec(),
+                exclusions: exclusions.to_vec(),
+                antennas: antennas.to_vec(),
+            };
+            self.submitted = Some(input.clone());
+            let job = Job { revision: self.revision, input, ctx: ctx.clone() };
+            if self.jobs.send(job).is_err() {
+                eprintln!("Thread d'analyse arrêté");
+            }
+        }
+        while let Ok(result) = self.results.try_recv() {
+            self.latest = Some(result);
+        }
+    }
+
+    /// Oublie la dernière soumission : le plan sera réanalysé à la prochaine mise à
+    /// jour (ex. après un changement de la configuration des amplificateurs).
+    pub fn invalidate(&mut self) {
+        self.submitted = None;
+    }
+
+    /// Révision du plan, incrémentée à chaque modification détectée.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Indique si une analyse du plan courant est en cours.
+    pub fn pending(&self) -> bool {
+        !matches!(&self.latest, Some((revision, _)) if *revision == self.revision)
+    }
+
+    /// Retourne le dernier résultat s’il porte sur la révision courante du plan
+    /// (les indices qu’il contient sont alors valides), sinon une analyse vide.
+    pub fn result(&self) -> Analysis {
+        match &self.latest {
+            Some((revision, analysis)) if *revision == self.revision => analysis.clone(),
+            _ => Analysis::compute(&[], &RxGuard::default(), &[], &[], &[]),
+        }
+    }
+}
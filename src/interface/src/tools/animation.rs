@@ -0,0 +1,213 @@
+//! Module d’animation des tâches mises à jour par l’émetteur.
+//!
+//! Lorsqu’une tâche déjà affichée est reçue de nouveau, elle est mise à jour sur
+//! place ; pendant [`UPDATE_ANIMATION`], le contour de son ancienne position
+//! s’efface progressivement tandis qu’un halo entoure la nouvelle, afin que
+//! l’opérateur repère la modification.
+//!
+//! Lorsque le plan entier est remplacé (chargement d’un fichier, plan rediffusé),
+//! [`PlanTransition`] fond l’ancien plan dans le nouveau : les tâches supprimées
+//! s’estompent, les nouvelles apparaissent progressivement et les tâches modifiées
+//! glissent de leur ancienne position à la nouvelle.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::tools::task::Task;
+
+/// Durée de l’animation d’une mise à jour.
+pub const UPDATE_ANIMATION: Duration = Duration::from_millis(800);
+
+/// Mise à jour d’une tâche en cours d’animation.
+pub struct TaskUpdate {
+    /// Clé de la tâche mise à jour.
+    pub key: String,
+    /// Tâche avant la mise à jour.
+    pub previous: Task,
+    /// Instant de la mise à jour.
+    pub at: Instant,
+}
+
+/// Animations des mises à jour récentes.
+#[derive(Default)]
+pub struct UpdateAnimations {
+    updates: Vec<TaskUpdate>,
+}
+
+impl UpdateAnimations {
+    /// Démarre l’animation de la mise à jour d’une tâche (en remplaçant une animation
+    /// en cours pour la même tâche).
+    pub fn record(&mut self, previous: Task, now: Instant) {
+        let key = previous.key().to_string();
+        self.updates.retain(|u| u.key != key);
+        self.updates.push(TaskUpdate { key, previous, at: now });
+    }
+
+    /// Supprime les animations terminées.
+    pub fn prune(&mut self, now: Instant) {
+        self.updates.retain(|u| now.duration_since(u.at) < UPDATE_ANIMATION);
+    }
+
+    /// Indique si des animations sont en cours.
+    pub fn is_animating(&self) -> bool {
+        !self.updates.is_empty()
+    }
+
+    /// Animations en cours avec leur opacité (1 au début de l’animation, 0 à la fin).
+    pub fn active(&self, now: Instant) -> impl Iterator<Item = (&TaskUpdate, f32)> {
+        self.updates.iter().filter_map(move |u| {
+            let progress = now.duration_since(u.at).as_secs_f32() / UPDATE_ANIMATION.as_secs_f32();
+            (progress < 1.).then_some((u, 1. - progress))
+        })
+    }
+}
+
+/// Durée par défaut d’une transition entre deux plans, en ms.
+pub const DEFAULT_TRANSITION_MS: u64 = 600;
+
+/// État d’une image de transition entre deux plans.
+pub struct TransitionFrame {
+    /// Tâches du nouveau plan, les tâches modifiées étant à une position intermédiaire.
+    pub tasks: Vec<Task>,
+    /// Opacité de chaque tâche du nouveau plan (inférieure à 1 pour les tâches ajoutées).
+    pub opacity: Vec<f32>,
+    /// Tâches supprimées, en cours d’effacement.
+    pub removed: Vec<Task>,
+    /// Opacité des tâches supprimées.
+    pub removed_opacity: f32,
+}
+
+/// Transition animée lors du remplacement du plan.
+pub struct PlanTransition {
+    /// Durée de la transition en ms (0 : remplacement instantané).
+    pub duration_ms: u64,
+    /// Tâches de l’ancien plan absentes du nouveau.
+    removed: Vec<Task>,
+    /// Clés des tâches absentes de l’ancien plan.
+    added: HashSet<String>,
+    /// Ancienne version des tâches modifiées, par clé.
+    moved: HashMap<String, Task>,
+    /// Début de la transition en cours.
+    started: Option<Instant>,
+}
+
+impl Default for PlanTransition {
+    fn default() -> Self {
+        Self { duration_ms: DEFAULT_TRANSITION_MS, removed: Vec::new(), added: HashSet::new(), moved: HashMap::new(), started: None }
+    }
+}
+
+/// Interpole linéairement une tâche de `from` vers `to` (contour compris s’ils
+/// ont le même nombre de sommets).
+fn lerp_task(from: &Task, to: &Task, t: f64) -> Task {
+    let lerp = |a: f64, b: f64| a + (b - a) * t;
+    let mut task = to.clone();
+    task.freq_start = lerp(from.freq_start, to.freq_start);
+    task.freq_end = lerp(from.freq_end, to.freq_end);
+    task.time_start = lerp(from.time_start, to.time_start);
+    task.time_end = lerp(from.time_end, to.time_end);
+    task.shape = match (&from.shape, &to.shape) {
+        (Some(a), Some(b)) if a.len() == b.len() => {
+            Some(a.iter().zip(b).map(|(p, q)| [lerp(p[0], q[0]), lerp(p[1], q[1])]).collect())
+        }
+        _ => None,
+    };
+    task
+}
+
+impl PlanTransition {
+    /// Démarre la transition du plan `old` vers le plan `new`.
+    pub fn start(&mut self, old: &[Task], new: &[Task], now: Instant) {
+        if self.duration_ms == 0 {
+            return;
+        }
+        let previous: HashMap<&str, &Task> = old.iter().map(|t| (t.key(), t)).collect();
+        let kept: HashSet<&str> = new.iter().map(Task::key).collect();
+        self.removed = old.iter().filter(|t| !kept.contains(t.key())).cloned().collect();
+        self.added = new.iter().filter(|t| !previous.contains_key(t.key())).map(|t| t.key().to_string()).collect();
+        self.moved = new
+            .iter()
+            .filter_map(|t| previous.get(t.key()).filter(|p| ***p != *t).map(|p| (t.key().to_string(), (*p).clone())))
+            .collect();
+        self.started = Some(now);
+    }
+
+    /// Avancement lissé de la transition (de 0 à 1), ou `None` hors transition.
+    pub fn progress(&self, now: Instant) -> Option<f32> {
+        let duration = Duration::from_millis(self.duration_ms).as_secs_f32();
+        let t = now.duration_since(self.started?).as_secs_f32() / duration.max(f32::EPSILON);
+        (t < 1.).then_some(t * t * (3. - 2. * t))
+    }
+
+    /// Termine la transition si sa durée est écoulée.
+    pub fn prune(&mut self, now: Instant) {
+        if self.started.is_some() && self.progress(now).is_none() {
+            *self = Self { duration_ms: self.duration_ms, ..Self::default() };
+        }
+    }
+
+    /// Indique si une transition est en cours.
+    pub fn is_animating(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// État à l’instant `now` de la transition vers le plan `tasks`, ou `None` hors transition.
+    pub fn frame(&self, tasks: &[Task], now: Instant) -> Option<TransitionFrame> {
+        let p = self.progress(now)?;
+        let tasks = tasks
+            .iter()
+            .map(|t| match self.moved.get(t.key()) {
+                Some(previous) => lerp_task(previous, t, p as f64),
+                None => t.clone(),
+            })
+            .collect::<Vec<_>>();
+        let opacity = tasks.iter().map(|t| if self.added.contains(t.key()) { p } else { 1. }).collect();
+        Some(TransitionFrame { tasks, opacity, removed: self.removed.clone(), removed_opacity: 1. - p })
+    }
+
+    /// Affiche le réglage de la durée de transition.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Transition de plan :");
+            ui.add(egui::DragValue::new(&mut self.duration_ms).range(0..=3000).suffix(" ms"))
+                .on_hover_text("Fondu lors du remplacement du plan (0 : instantané)");
+        });
+    }
+}
+
+/// Demi-période du clignotement des tâches mises en évidence.
+pub const BLINK_HALF_PERIOD: Duration = Duration::from_millis(250);
+
+/// Tâches mises en évidence par un contrôleur externe.
+#[derive(Default)]
+pub struct Highlights {
+    /// Clé de chaque tâche mise en évidence, instant de début et de fin du clignotement.
+    active: Vec<(String, Instant, Instant)>,
+}
+
+impl Highlights {
+    /// Fait clignoter la tâche de clé `key` pendant `duration` (en remplaçant une
+    /// mise en évidence en cours pour la même tâche).
+    pub fn start(&mut self, key: &str, duration: Duration, now: Instant) {
+        self.active.retain(|(k, _, _)| k != key);
+        self.active.push((key.to_string(), now, now + duration));
+    }
+
+    /// Supprime les mises en évidence terminées.
+    pub fn prune(&mut self, now: Instant) {
+        self.active.retain(|(_, _, end)| now < *end);
+    }
+
+    /// Indique si des tâches clignotent.
+    pub fn is_animating(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// Clés des tâches allumées à l’instant `now` (phase visible du clignotement).
+    pub fn lit(&self, now: Instant) -> impl Iterator<Item = &str> {
+        self.active.iter().filter_map(move |(key, start, end)| {
+            let phase = now.duration_since(*start).as_millis() / BLINK_HALF_PERIOD.as_millis().max(1);
+            (now < *end && phase.is_multiple_of(2)).then_some(key.as_str())
+        })
+    }
+}
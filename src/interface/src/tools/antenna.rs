@@ -0,0 +1,162 @@
+//! Module des antennes (ou faisceaux) d’émission.
+//!
+//! Une tâche peut préciser l’antenne qui la rayonne (`"antenna": "Cornet S"`).
+//! Les antennes disponibles, leur plage de fréquences et leur gain sont lus
+//! depuis un fichier de configuration JSON ([`ANTENNAS_PATH`]). L’analyse du plan
+//! signale les tâches hors de la plage de leur antenne (ou affectées à une
+//! antenne inconnue) et calcule l’occupation de chaque antenne ; le panneau
+//! latéral permet de filtrer et de colorer les tâches par antenne.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::report::{peak_concurrency, union_length};
+use crate::tools::task::Task;
+use crate::tools::utils::MAX_TIME;
+
+/// Chemin du fichier de configuration des antennes.
+pub const ANTENNAS_PATH: &str = "antennas.json";
+
+/// Antenne d’émission.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Antenna {
+    /// Nom de l’antenne, référencé par le champ `antenna` des tâches.
+    pub name: String,
+    /// Fréquence minimale en MHz.
+    pub freq_min: f64,
+    /// Fréquence maximale en MHz.
+    pub freq_max: f64,
+    /// Gain en dBi.
+    #[serde(default)]
+    pub gain_dbi: f64,
+}
+
+impl Antenna {
+    /// Indique si la plage `[freq_start, freq_end]` est entièrement couverte par l’antenne.
+    pub fn covers(&self, freq_start: f64, freq_end: f64) -> bool {
+        freq_start >= self.freq_min && freq_end <= self.freq_max
+    }
+}
+
+/// Tâche incompatible avec son antenne.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AntennaViolation {
+    /// Indice de la tâche.
+    pub task_index: usize,
+    /// Description de l’incompatibilité.
+    pub message: String,
+}
+
+/// Occupation d’une antenne sur la durée de la mission.
+#[derive(Clone, Serialize)]
+pub struct AntennaLoad {
+    /// Nom de l’antenne.
+    pub antenna: String,
+    /// Nombre de tâches affectées à l’antenne.
+    pub task_count: usize,
+    /// Temps pendant lequel l’antenne émet, en ms.
+    pub busy_ms: f64,
+    /// Part du temps de mission occupée (entre 0 et 1).
+    pub utilization: f64,
+    /// Nombre maximal de tâches simultanées sur l’antenne.
+    pub peak_concurrency: usize,
+}
+
+/// Vérifie que l’antenne d’une tâche existe et couvre sa bande de fréquence.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si l’antenne est inconnue ou hors bande.
+pub fn check_antenna(task: &Task, antennas: &[Antenna]) -> Result<(), String> {
+    let Some(name) = &task.antenna else {
+        return Ok(());
+    };
+    let antenna = antennas
+        .iter()
+        .find(|a| a.name == *name)
+        .ok_or_else(|| format!("« {} » : antenne inconnue « {} »", task.name, name))?;
+    if !antenna.covers(task.freq_start, task.freq_end) {
+        return Err(format!(
+            "« {} » : {:.0}–{:.0} MHz hors de la plage de l’antenne {} ({:.0}–{:.0} MHz)",
+            task.name, task.freq_start, task.freq_end, antenna.name, antenna.freq_min, antenna.freq_max
+        ));
+    }
+    Ok(())
+}
+
+/// Recherche les tâches incompatibles avec leur antenne.
+pub fn antenna_violations(tasks: &[Task], antennas: &[Antenna]) -> Vec<AntennaViolation> {
+    tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(task_index, task)| {
+            check_antenna(task, antennas).err().map(|message| AntennaViolation { task_index, message })
+        })
+        .collect()
+}
+
+/// Calcule l’occupation de chaque antenne.
+pub fn antenna_loads(antennas: &[Antenna], tasks: &[Task]) -> Vec<AntennaLoad> {
+    antennas
+        .iter()
+        .map(|antenna| {
+            let intervals: Vec<(f64, f64)> = tasks
+                .iter()
+                .filter(|t| t.antenna.as_ref() == Some(&antenna.name))
+                .map(|t| (t.time_start, t.time_end))
+                .collect();
+            let task_count = intervals.len();
+            let peak_concurrency = peak_concurrency(&intervals);
+            let busy_ms = union_length(intervals);
+            AntennaLoad {
+                antenna: antenna.name.clone(),
+                task_count,
+                busy_ms,
+                utilization: busy_ms / MAX_TIME,
+                peak_concurrency,
+            }
+        })
+        .collect()
+}
+
+/// Liste triée des antennes citées par les tâches ou déclarées dans la configuration.
+pub fn antenna_names(antennas: &[Antenna], tasks: &[Task]) -> Vec<String> {
+    let mut names: Vec<String> = antennas.iter().map(|a| a.name.clone()).collect();
+    for name in tasks.iter().filter_map(|t| t.antenna.as_ref()) {
+        if !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    names.sort();
+    names
+}
+
+/// Charge les antennes depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_antennas(path: &Path) -> Result<Vec<Antenna>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let antennas: Vec<Antenna> =
+        serde_json::from_str(&content).map_err(|e| format!("Antennes {} invalides : {}", path.display(), e))?;
+    if let Some(a) = antennas.iter().find(|a| a.freq_min >= a.freq_max) {
+        return Err(format!("Antenne {} : freq_min doit être inférieure à freq_max", a.name));
+    }
+    Ok(antennas)
+}
+
+/// Charge la configuration des antennes, ou aucune antenne si le fichier est absent ou invalide.
+pub fn load_antenna_config() -> Vec<Antenna> {
+    let path = Path::new(ANTENNAS_PATH);
+    if !path.exists() {
+        return Vec::new();
+    }
+    load_antennas(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        Vec::new()
+    })
+}
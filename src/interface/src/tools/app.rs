@@ -2,112 +2,1109 @@
 //!
 //! Ce module contient la structure [`MyApp`] qui implémente [`eframe::App`].
 //! Il orchestre l'interface utilisateur (UI), les données affichées, le traitement
-//! des événements de zoom et d'échelle logarithmique, et l'affichage des tâches et des zones.
+//! des événements de zoom et d'échelle logarithmique ; le rendu des panneaux, des
+//! graphes et des info-bulles est délégué au module [`view`](crate::tools::view).
 
 use crate::tools::utils::*;
 use crate::tools::task::*;
 use crate::tools::background::*;
+use crate::tools::history::History;
+use crate::tools::repair::*;
+use crate::tools::csv_import::CsvImportDialog;
+use crate::tools::plan::{demo_plan, load_plan};
+use crate::tools::inspector::TaskInspector;
+use crate::tools::coloring::{compute_colors, ColorBy};
+use crate::tools::analysis::AnalysisWorker;
+use crate::tools::storage::TaskStore;
+use crate::tools::query::{Query, QueryContext};
+use crate::tools::system::{load_system_config, System};
+use crate::tools::antenna::{load_antenna_config, Antenna};
+use crate::tools::filter::BandFilter;
+use crate::tools::effect::Effect;
+use crate::tools::hud::show_hud;
+use crate::tools::refresh::RefreshSettings;
+use crate::tools::watchdog::FrameWatchdog;
+use crate::tools::hierarchy::TaskTree;
+use crate::tools::profile::{builtin_profiles, load_profiles, Profile, Theme};
+use crate::tools::config_watch::{ConfigFile, ConfigWatcher};
+use crate::tools::diagnostics::Diagnostics;
+use crate::tools::task_log::{TaskChangeLog, NO_CHANGES};
+use crate::tools::trash::{removed_tasks, Trash};
+use crate::tools::placement::TaskDrag;
+use crate::tools::headroom::Headroom;
+use crate::tools::amplifiers::{load_amplifiers, set_amplifiers, AmplifierRegistry};
+use crate::tools::compression::Compression;
+use crate::tools::transport::Subscribers;
+use crate::tools::mirror::{Mirror, MirrorRole, MirrorState, MirrorView};
+use crate::tools::wall::WallTile;
+use crate::tools::kiosk::Kiosk;
+use crate::tools::markup::Markups;
+use crate::tools::harmonics::HarmonicsRuler;
+use crate::tools::baseline::Baseline;
+use crate::tools::sandbox::Sandbox;
+use crate::tools::revisions::PlanRevisions;
+use crate::tools::git_import::GitImporter;
+use crate::tools::align::Selection;
+use crate::tools::split::{join_tasks, split_task, SplitAxis};
+use crate::tools::presets::{load_preset_config, BandPresets};
+use crate::tools::protocol::{parse_decompressed, parse_message, CommandMessage, IncomingMessage, ProtocolEvent, MAX_HIGHLIGHT_MS};
+use crate::tools::animation::{Highlights, PlanTransition, UpdateAnimations, BLINK_HALF_PERIOD};
+use crate::tools::dedup::Deduplicator;
+use crate::tools::plan_hash::plan_hash;
+use crate::tools::rate_limit::{load_rate_limit_config, Admission, RateLimiter, STDIN_SOURCE};
+use crate::tools::screenshot::ScreenshotRecorder;
+use crate::tools::phase::{load_phase_config, Phase};
+use crate::tools::platform::tint_by_platform;
+use crate::tools::range::{load_propagation_config, PropagationModel};
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::robustness::RobustnessPanel;
+use crate::tools::timeline::SessionTimeline;
+use crate::tools::tooltip::{load_tooltip_config, TooltipConfig};
+use crate::tools::pinned::PinnedCards;
+use crate::tools::hit_stack::HitStack;
+use crate::tools::marginal::MarginalMetric;
+use crate::tools::layers::{PlanLayer, PlanLayers};
+use crate::tools::detection::Detections;
+use crate::tools::alerts::{Alert, AlertContext, AlertEngine};
+use crate::tools::alarm::show_alarm_banner;
+use crate::tools::zone_editor::ZoneEditor;
+use crate::tools::events::{AppEvent, EventBus, PlanEvent, TransportEvent, UiEvent};
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::timefmt::TimeDisplay;
+use crate::tools::view::{show_plots, FrameView, PlotScene, PlotTools};
+use crate::tools::view::main_plot::MainPlot;
+use crate::tools::view::side_panel::SidePanel;
+use crate::tools::view::tooltips::show_pinned_cards;
 
 use eframe::egui;
-use egui::{Color32, Stroke, RichText};
-use egui_plot::{Plot, PlotPoints, Polygon, Line, PlotPoint, GridMark, log_grid_spacer, uniform_grid_spacer, Text};
-use std::sync::mpsc::{Receiver, Sender, channel};
-use std::thread;
-use std::time::Duration;
-
-use crossbeam_queue::SegQueue;
-use std::sync::Arc;
-use serde::Deserialize;
-
-#[derive(Deserialize)]
-struct IncomingTask {
-    name: String,
-    freq_start: f64,
-    freq_end: f64,
-    time_start: f64,
-    time_end: f64,
-    amplifier: String, // Amplifier représenté sous forme de String dans le JSON
+use egui::Color32;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Opacité des tâches provisoires.
+const TENTATIVE_OPACITY: f32 = 0.4;
+
+/// Intervalle de rafraîchissement de l'horloge temps réel (compte à rebours de la garde Rx).
+const CLOCK_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Charge la configuration des zones, ou les zones par défaut si le fichier est absent ou invalide.
+fn load_zone_config() -> Vec<ZoneDefinition> {
+    let path = Path::new(ZONES_PATH);
+    if !path.exists() {
+        return default_zone_definitions();
+    }
+    load_zones(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        default_zone_definitions()
+    })
+}
+
+/// Confirme les tâches provisoires satisfaisant `filter` et retourne leur nombre.
+pub fn confirm_tentative(tasks: &mut [Task], filter: impl Fn(&Task) -> bool) -> usize {
+    let mut confirmed = 0;
+    for task in tasks.iter_mut().filter(|t| t.tentative && filter(t)) {
+        task.tentative = false;
+        confirmed += 1;
+    }
+    confirmed
 }
 
 /// Application principale représentant un diagramme de Gantt fréquentiel et temporel.
 pub struct MyApp {
-    /// Queue partagée pour les messages provenant de stdin.
-    msg_queue: Arc<SegQueue<String>>,
+    /// Bus d'événements partagé avec les lecteurs d'entrée et les vues.
+    pub bus: EventBus,
+    /// Messages et plans surveillés reçus pendant que le bac à sable est ouvert,
+    /// appliqués à sa fermeture.
+    deferred: Vec<TransportEvent>,
     /// Liste des tâches à afficher dans le diagramme.
     pub tasks: Vec<Task>,
-    /// Limites actuelles de la vue en X (bande fréquentielle).
-    pub plot_bounds_x: Option<(f64, f64)>,
-    /// Dernière valeur connue des limites X (pour détection de changement).
-    pub last_bounds_x: Option<(f64, f64)>,
-    /// Émetteur pour transmettre la position du curseur sur le graphique.
-    pub label_tx: Sender<PlotPoint>,
-    /// Récepteur associé au canal d'envoi du curseur.
-    pub label_rx: Receiver<PlotPoint>,
+    /// Vue du graphe principal : bornes courantes et forcées, zooms par bande et
+    /// par système, menu contextuel.
+    pub main_plot: MainPlot,
     /// Indique si le mode logarithmique était actif précédemment.
     pub old_log_scale: bool,
     /// Indique si l'affichage utilise l'échelle logarithmique des fréquences.
     pub log_scale: bool,
-    /// Indice de la bande d'amplification actuellement zoomée (si zoom actif).
-    pub zoom_band: Option<usize>,
-    /// Si défini, force l'application de limites X spécifiques.
-    pub force_bounds_x: Option<(f64, f64)>,
+    /// Systèmes regroupant les amplificateurs (fichier de configuration des systèmes).
+    pub systems: Vec<System>,
+    /// Antennes d'émission (fichier de configuration des antennes).
+    pub antennas: Vec<Antenna>,
+    /// Modèle de propagation et positions des plateformes (estimation de la portée des tâches).
+    pub propagation: PropagationModel,
+    /// Mode miroir (instance principale ou suiveuse).
+    pub mirror: Mirror,
+    /// Plage de fréquences verrouillée (instance d'un mur d'images).
+    pub wall: Option<WallTile>,
+    /// Mode kiosque (panneau latéral escamotable, sans fenêtres de dialogue).
+    pub kiosk: Kiosk,
+    /// Annotations dessinées sur le graphe principal.
+    pub markups: Markups,
+    /// Règle des harmoniques et des fréquences image.
+    pub harmonics: HarmonicsRuler,
+    /// Analyse de robustesse du plan (Monte-Carlo).
+    pub robustness: RobustnessPanel,
+    /// Chronologie horodatée des événements et des actions de la session.
+    pub timeline: SessionTimeline,
+    /// Modèle de contenu des info-bulles des tâches.
+    pub tooltip: TooltipConfig,
+    /// Fiches d'information épinglées (Alt+clic sur une tâche).
+    pub pinned: PinnedCards,
+    /// Pile des tâches superposées sous le curseur.
+    pub hit_stack: HitStack,
+    /// Couches exécutée et observée, confrontées au plan prévu.
+    pub layers: PlanLayers,
+    /// Détections ESM reçues, estompées avec le temps.
+    pub detections: Detections,
+    /// Moteur de règles d'alerte évaluées en continu sur le plan.
+    pub alerts: AlertEngine,
+    /// Bandes de référence (GPS, Link 16…) affichées comme bandes protégées ou cibles.
+    pub presets: BandPresets,
+    /// Plan de référence figé pour le suivi des écarts.
+    pub baseline: Baseline,
+    /// Bac à sable : copie de travail du plan, sans diffusion des modifications.
+    pub sandbox: Sandbox,
+    /// Révisions nommées du plan et révision consultée en lecture seule.
+    pub revisions: PlanRevisions,
+    /// Import de plans depuis un dépôt Git et révision d'origine du plan.
+    pub git_import: GitImporter,
+    /// Historique des modifications pour annuler / rétablir.
+    pub history: History,
+    /// Historique des modifications de chaque tâche (journal de l'inspecteur).
+    pub task_log: TaskChangeLog,
+    /// Corbeille des tâches supprimées.
+    pub trash: Trash,
+    /// Corrections suggérées en cours de prévisualisation.
+    pub repair_preview: Option<Vec<Fix>>,
+    /// Message de résultat de la dernière action (export, import…).
+    pub status: Option<String>,
+    /// Fenêtre de dialogue d'import CSV.
+    pub csv_dialog: CsvImportDialog,
+    /// Indique si la fenêtre d'import CSV est ouverte.
+    pub csv_dialog_open: bool,
+    /// Chemin du fichier de plan (JSON, YAML ou TOML selon l'extension).
+    pub plan_path: String,
+    /// Heure H de la mission et fuseau d'affichage des temps absolus.
+    pub time_display: TimeDisplay,
+    /// Réglages de cadence de rafraîchissement et d'économie d'énergie.
+    pub refresh: RefreshSettings,
+    /// Surveillance du temps de rendu et mode dégradé.
+    pub watchdog: FrameWatchdog,
+    /// Rechargement à chaud des fichiers de configuration et journal des rechargements.
+    pub config_watcher: ConfigWatcher,
+    /// Table arborescente des tâches (parents repliés).
+    pub task_tree: TaskTree,
+    /// Transition animée lors du remplacement du plan.
+    pub transition: PlanTransition,
+    /// Tâches mises en évidence par un contrôleur externe (commande `highlight`).
+    pub highlights: Highlights,
+    /// Profils d'affichage disponibles.
+    pub profiles: Vec<Profile>,
+    /// Profil d'affichage actif (thème, panneaux, couches et droits).
+    pub profile: Profile,
+    /// Captures automatiques du graphe sur événement.
+    pub screenshots: ScreenshotRecorder,
+    /// Indique si le HUD temps réel (temps écoulé, prochaine tâche, garde Rx) est affiché.
+    pub show_hud: bool,
+    /// Saisie de l'heure H sous forme de DTG.
+    pub mission_start_text: String,
+    /// Inspecteur de la tâche sélectionnée.
+    pub inspector: Option<TaskInspector>,
+    /// Glisser-déposer d'une tâche en cours sur le graphe principal.
+    pub task_drag: TaskDrag,
+    /// Marge de garde aux bords de bande des amplificateurs.
+    pub headroom: Headroom,
+    /// Sélection multiple (Maj+clic) pour l'alignement et la répartition.
+    pub selection: Selection,
+    /// Attribut utilisé pour colorer les tâches.
+    pub color_by: ColorBy,
+    /// Filtres d'affichage par bande (Ctrl+clic / Maj+clic sur les boutons de bande).
+    pub band_filter: BandFilter,
+    /// Si défini, seules les tâches de cette plateforme sont affichées.
+    pub platform_filter: Option<String>,
+    /// Si défini, seules les tâches rayonnées par cette antenne sont affichées.
+    pub antenna_filter: Option<String>,
+    /// Si défini, seules les tâches recherchant cet effet sont affichées.
+    pub effect_filter: Option<Effect>,
+    /// Indique si la couleur des tâches est teintée selon leur plateforme.
+    pub platform_tint: bool,
+    /// Indique si l'axe des temps est découpé en un couloir par plateforme.
+    pub platform_lanes: bool,
+    /// Indique si le bandeau d'occupation temporelle est affiché sous le mini graphe.
+    pub time_strip: bool,
+    /// Indique si le bandeau d'occupation fréquentielle est affiché au-dessus du graphe principal.
+    pub freq_strip: bool,
+    /// Grandeur cumulée par les bandeaux d'occupation.
+    pub marginal_metric: MarginalMetric,
+    /// Saisie du champ de recherche (expression de filtrage).
+    pub search_text: String,
+    /// Filtre de recherche analysé, ou message d'erreur de l'expression saisie.
+    pub search: Result<Option<Query>, String>,
+    /// Nombre de messages reçus de l'émetteur depuis le lancement.
+    pub messages_received: usize,
+    /// Limiteur de débit des émetteurs.
+    pub rate_limiter: RateLimiter,
+    /// Filtre des messages de tâche répétés.
+    pub dedup: Deduplicator,
+    /// Animations des tâches mises à jour par l'émetteur.
+    pub update_animations: UpdateAnimations,
+    /// Résultat du dernier contrôle d'empreinte du plan : empreinte vérifiée, ou
+    /// description de la divergence.
+    pub plan_sync: Option<Result<String, String>>,
+    /// Compression négociée avec l'émetteur lors de la poignée de main.
+    pub compression: Option<Compression>,
+    /// Connexions distantes abonnées au flux d'événements.
+    pub subscribers: Subscribers,
+    /// Réponses à transmettre à la source du message qui les a provoquées.
+    replies: Vec<(String, ProtocolEvent)>,
+    /// Fenêtre de garde de réception en début de cycle.
+    pub rx_guard: RxGuard,
+    /// Phases de mission (affichées en bandes horizontales).
+    pub phases: Vec<Phase>,
+    /// Si défini, seules les tâches émettant pendant cette phase sont affichées.
+    pub phase_filter: Option<String>,
+    /// Masques d'exclusion fréquentielle (affichés hachurés).
+    pub exclusions: Vec<ExclusionMask>,
+    /// Définitions des zones de fond (fichier de configuration des zones).
+    pub zones: Vec<ZoneDefinition>,
+    /// Fenêtre d'édition des zones.
+    pub zone_editor: ZoneEditor,
+    /// Indique si l'éditeur de zones est ouvert.
+    pub zone_editor_open: bool,
+    /// Résultat des diagnostics de démarrage.
+    pub diagnostics: Diagnostics,
+    /// Indique si la fenêtre des diagnostics est ouverte.
+    pub diagnostics_open: bool,
+    /// Thread d'analyse du plan (conflits, cycles de service, couverture).
+    pub analysis: AnalysisWorker,
+    /// Stockage persistant du plan (mémoire ou base SQLite).
+    pub store: Box<dyn TaskStore>,
+    /// Révision du plan dernièrement enregistrée dans le stockage.
+    pub stored_revision: u64,
 }
 
 impl MyApp {
-    /// Crée une nouvelle instance de l'application `MyApp` et démarre un thread d'animation cyclique.
-    pub fn new(queue: Arc<SegQueue<String>>) -> Self {
-        let (label_tx, label_rx) = channel();
+    /// Crée une nouvelle instance de l'application `MyApp`, avec les tâches relues
+    /// depuis le stockage (reprise après redémarrage).
+    pub fn new(bus: EventBus, store: Box<dyn TaskStore>) -> Self {
+        let tasks = store.load().unwrap_or_else(|e| {
+            eprintln!("Reprise du plan impossible : {}", e);
+            vec![]
+        });
 
         Self {
-            msg_queue: queue,
-            tasks: vec![],
-            plot_bounds_x: Some(get_bounds(false)),
-            last_bounds_x: Some((0., 1.)),
-            label_tx,
-            label_rx,
+            bus,
+            deferred: Vec::new(),
+            tasks,
+            main_plot: MainPlot::default(),
             old_log_scale: false,
             log_scale: false,
-            zoom_band: None,
-            force_bounds_x: Some(get_bounds(false)),
+            systems: load_system_config(),
+            antennas: load_antenna_config(),
+            propagation: load_propagation_config(),
+            mirror: Mirror::default(),
+            wall: None,
+            kiosk: Kiosk::default(),
+            markups: Markups::default(),
+            harmonics: HarmonicsRuler::default(),
+            robustness: RobustnessPanel::default(),
+            timeline: SessionTimeline::default(),
+            tooltip: load_tooltip_config(),
+            pinned: PinnedCards::default(),
+            hit_stack: HitStack::default(),
+            layers: PlanLayers::default(),
+            detections: Detections::default(),
+            alerts: AlertEngine::default(),
+            presets: load_preset_config(),
+            baseline: Baseline::default(),
+            sandbox: Sandbox::default(),
+            revisions: PlanRevisions::default(),
+            git_import: GitImporter::default(),
+            history: History::default(),
+            task_log: TaskChangeLog::default(),
+            trash: Trash::default(),
+            repair_preview: None,
+            status: None,
+            csv_dialog: CsvImportDialog::default(),
+            csv_dialog_open: false,
+            plan_path: "plan.json".into(),
+            time_display: TimeDisplay::default(),
+            refresh: RefreshSettings::default(),
+            watchdog: FrameWatchdog::default(),
+            config_watcher: ConfigWatcher::default(),
+            task_tree: TaskTree::default(),
+            transition: PlanTransition::default(),
+            highlights: Highlights::default(),
+            profiles: builtin_profiles(),
+            profile: Profile::default(),
+            screenshots: ScreenshotRecorder::default(),
+            show_hud: true,
+            mission_start_text: String::new(),
+            inspector: None,
+            task_drag: TaskDrag::default(),
+            headroom: Headroom::default(),
+            selection: Selection::default(),
+            color_by: ColorBy::default(),
+            band_filter: BandFilter::default(),
+            platform_filter: None,
+            antenna_filter: None,
+            effect_filter: None,
+            platform_tint: false,
+            platform_lanes: false,
+            time_strip: false,
+            freq_strip: false,
+            marginal_metric: MarginalMetric::TaskCount,
+            search_text: String::new(),
+            search: Ok(None),
+            messages_received: 0,
+            rate_limiter: RateLimiter::new(load_rate_limit_config()),
+            dedup: Deduplicator::default(),
+            update_animations: UpdateAnimations::default(),
+            plan_sync: None,
+            compression: None,
+            subscribers: Subscribers::default(),
+            replies: Vec::new(),
+            rx_guard: RxGuard::default(),
+            phases: load_phase_config(),
+            phase_filter: None,
+            exclusions: Vec::new(),
+            zones: load_zone_config(),
+            zone_editor: ZoneEditor::default(),
+            zone_editor_open: false,
+            diagnostics: Diagnostics::default(),
+            diagnostics_open: false,
+            analysis: AnalysisWorker::default(),
+            store,
+            stored_revision: 0,
         }
     }
 
-    /// Renvoie les bandes de fréquence associées à chaque amplificateur.
-    pub fn bands(&self) -> Vec<(Amplifier, f64, f64)> {
-        vec![
-            (Amplifier::A20_500, 20.0, 500.0),
-            (Amplifier::A500_1000, 500.0, 1000.0),
-            (Amplifier::A960_1215, 960.0, 1215.0),
-            (Amplifier::A1000_2500, 1000.0, 2500.0),
-            (Amplifier::A2400_6000, 2400.0, 6000.0),
-        ]
+    /// Remplace les tâches par un plan chargé, de façon annulable.
+    fn replace_tasks(&mut self, tasks: Vec<Task>, source: &str) {
+        self.history.record(&self.tasks);
+        self.dedup.reset();
+        self.status = Some(format!("{} tâches chargées depuis {}", tasks.len(), source));
+        self.timeline.record_action("load_plan", format!("{} tâches chargées depuis {}", tasks.len(), source));
+        self.transition.start(&self.tasks, &tasks, Instant::now());
+        self.tasks = tasks;
+        self.repair_preview = None;
+        self.screenshots.request("chargement du plan");
     }
 
-    /// Gère les messages reçus de la queue partagée.
-    fn handle_message(&mut self, json: String) {
-        eprintln!("Réception depuis la queue : {}", json);
+    /// Charge le plan désigné par `plan_path`.
+    pub fn open_plan(&mut self) {
+        match load_plan(Path::new(&self.plan_path)) {
+            Ok(tasks) => self.replace_tasks(tasks, &self.plan_path.clone()),
+            Err(e) => self.status = Some(e),
+        }
+    }
 
-        // Désérialisation du JSON en liste de tâches
-        match serde_json::from_str::<IncomingTask>(&json) {
-            Ok(incoming) => {
-                // Reset de la liste des tâches
-                self.tasks.clear();
-
-                // Ajout de la tâche reçue
-                self.tasks.push(Task {
-                    name: incoming.name,
-                    freq_start: incoming.freq_start,
-                    freq_end: incoming.freq_end,
-                    time_start: incoming.time_start,
-                    time_end: incoming.time_end,
-                    amplifier: Amplifier::from_str(&incoming.amplifier)
-                        .unwrap_or(Amplifier::A20_500),
+    /// Affiche, à la place d'une grille vide, les instructions de connexion d'un
+    /// émetteur ainsi que les boutons de chargement d'un plan.
+    fn show_empty_state(&mut self, ctx: &egui::Context) {
+        egui::Area::new(egui::Id::new("empty_state"))
+            .anchor(egui::Align2::CENTER_CENTER, [0., 0.])
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).inner_margin(16.).show(ui, |ui| {
+                    ui.set_max_width(420.);
+                    ui.heading("Aucune tâche à afficher");
+                    ui.label("Aucun émetteur n'est connecté. Les tâches sont reçues sur l'entrée standard, \
+                              une tâche JSON par ligne, par exemple :");
+                    ui.code("cargo run --manifest-path src/interface/Cargo.toml");
+                    ui.code(r#"{"name":"T1","freq_start":100,"freq_end":300,"time_start":0,"time_end":300,"amplifier":"A20_500"}"#);
+                    ui.label("L'émetteur de démonstration (cargo run à la racine du dépôt) lance l'interface et lui envoie des tâches.");
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button("Charger le plan de démonstration").clicked() {
+                            self.replace_tasks(demo_plan(), "le plan de démonstration");
+                        }
+                        if ui.button(format!("Ouvrir {}", self.plan_path)).clicked() {
+                            self.open_plan();
+                        }
+                    });
+                    if let Some(status) = &self.status {
+                        ui.label(status);
+                    }
                 });
+            });
+    }
+
+    /// Répartit un événement du bus et retourne les événements du protocole émis.
+    ///
+    /// Les messages reçus pendant que le bac à sable est ouvert ou qu'une révision
+    /// est consultée sont conservés et appliqués à leur fermeture.
+    pub fn dispatch(&mut self, event: AppEvent, now: Instant) -> Vec<ProtocolEvent> {
+        match event {
+            AppEvent::Transport(event @ (TransportEvent::Message { .. } | TransportEvent::Plan(_))) if self.is_detached() => {
+                self.deferred.push(event)
+            }
+            AppEvent::Transport(TransportEvent::Plan(event)) => {
+                self.messages_received += 1;
+                self.apply_plan_change(event);
+            }
+            AppEvent::Transport(TransportEvent::Message { source, json }) => {
+                eprintln!("UI a reçu depuis la queue : {}", json);
+                return self.ingest(&source, json, now).into_iter().collect();
+            }
+            AppEvent::Transport(TransportEvent::Layer { layer, line }) => {
+                if let Err(e) = self.layers.apply(layer, &line) {
+                    eprintln!("{}", e);
+                }
+            }
+            AppEvent::Plan(event) => self.apply_plan_event(event),
+            AppEvent::Ui(UiEvent::Inspect(index)) => {
+                self.inspector = index.filter(|&i| i < self.tasks.len()).map(|i| TaskInspector::new(i, &self.tasks[i]));
+            }
+            AppEvent::Ui(UiEvent::FocusAlert(alert)) => self.focus_alert(&alert),
+            AppEvent::Ui(UiEvent::ToggleSelection(index)) => {
+                if let Some(task) = self.tasks.get(index) {
+                    self.selection.toggle(task);
+                }
+            }
+            AppEvent::Ui(UiEvent::Pin(index)) => {
+                if let Some(task) = self.tasks.get(index) {
+                    self.pinned.pin(task);
+                }
+            }
+            AppEvent::Ui(UiEvent::Split { index, axis, at }) => {
+                let result = self.split_at(index, axis, at);
+                self.record_context_action(result);
+            }
+            AppEvent::Ui(UiEvent::Join { index, other }) => {
+                let result = self.join(index, other);
+                self.record_context_action(result);
+            }
+            AppEvent::Ui(UiEvent::SelectProfile(name)) => {
+                if let Err(e) = self.select_profile(&name) {
+                    self.status = Some(e);
+                }
+            }
+            AppEvent::Ui(UiEvent::ViewRevision(_)) if self.sandbox.is_active() => {
+                self.status = Some("Fermer le bac à sable avant de consulter une révision".into());
+            }
+            AppEvent::Ui(UiEvent::ViewRevision(index)) => match self.revisions.view(index, &mut self.tasks) {
+                Ok(()) => {
+                    self.repair_preview = None;
+                    self.inspector = None;
+                    self.dedup.reset();
+                }
+                Err(e) => self.status = Some(e),
+            },
+            AppEvent::Ui(UiEvent::OpenPlan) => self.open_plan(),
+            AppEvent::Ui(UiEvent::Status(status)) => self.status = Some(status),
+        }
+        Vec::new()
+    }
+
+    /// Inscrit dans la chronologie de la session le résultat d'une opération du menu
+    /// contextuel (scission, fusion), ou affiche son erreur.
+    fn record_context_action(&mut self, result: Result<ProtocolEvent, String>) {
+        match result {
+            Ok(event) => self.timeline.record_action("context_menu", serde_json::to_string(&event).unwrap_or_default()),
+            Err(e) => self.status = Some(e),
+        }
+    }
+
+    /// Applique une modification du plan, de façon annulable, et l'inscrit dans la
+    /// chronologie de la session.
+    pub fn apply_plan_event(&mut self, event: PlanEvent) {
+        if !self.profile.permissions.edit {
+            self.status = Some(format!("Profil « {} » : modification du plan non autorisée", self.profile.name));
+            return;
+        }
+        if let Some(revision) = self.revisions.viewed() {
+            self.status = Some(format!("Révision « {} » consultée en lecture seule : revenir au plan courant pour le modifier", revision.label));
+            return;
+        }
+        self.apply_plan_change(event);
+    }
 
-                eprintln!("Réception : remplacement par {} tâches.", self.tasks.len());
+    /// Applique une modification du plan, de façon annulable, sans contrôle des
+    /// droits (plans surveillés), et l'inscrit dans la chronologie de la session.
+    fn apply_plan_change(&mut self, event: PlanEvent) {
+        match event {
+            PlanEvent::Undo => {
+                if !self.history.undo(&mut self.tasks) {
+                    return;
+                }
+                self.timeline.record_action("undo", "Annulation");
+                self.task_log.note("Annulation");
+            }
+            PlanEvent::Redo => {
+                if !self.history.redo(&mut self.tasks) {
+                    return;
+                }
+                self.timeline.record_action("redo", "Rétablissement");
+                self.task_log.note("Rétablissement");
             }
+            PlanEvent::Replace { tasks, action, description } => {
+                self.history.record(&self.tasks);
+                self.trash.put(removed_tasks(&self.tasks, &tasks), &description, Instant::now());
+                self.task_log.note(description.clone());
+                self.timeline.record_action(action, description);
+                self.tasks = tasks;
+                self.inspector = None;
+            }
+            PlanEvent::Patch { changed, removed, action, description } => {
+                if changed.is_empty() && removed.is_empty() {
+                    return;
+                }
+                self.history.record(&self.tasks);
+                let (deleted, kept): (Vec<Task>, Vec<Task>) = std::mem::take(&mut self.tasks).into_iter().partition(|t| removed.iter().any(|k| k == t.key()));
+                self.tasks = kept;
+                self.trash.put(deleted, &description, Instant::now());
+                for task in changed {
+                    match self.tasks.iter().position(|t| t.key() == task.key()) {
+                        Some(i) => self.tasks[i] = task,
+                        None => self.tasks.push(task),
+                    }
+                }
+                self.task_log.note(description.clone());
+                self.timeline.record_action(action, description);
+                if self.inspector.as_ref().is_some_and(|i| removed.contains(&i.key)) {
+                    self.inspector = None;
+                }
+            }
+            PlanEvent::Extend { tasks, action, description } => {
+                self.history.record(&self.tasks);
+                self.task_log.note(description.clone());
+                self.timeline.record_action(action, description);
+                self.tasks.extend(tasks);
+            }
+            PlanEvent::Edit { key, task } => {
+                let Some(index) = self.tasks.iter().position(|t| t.key() == key) else {
+                    self.status = Some(format!("Tâche « {} » introuvable : modification ignorée", key));
+                    return;
+                };
+                self.history.record(&self.tasks);
+                self.task_log.note("inspecteur");
+                self.timeline.record_action("edit_task", format!("{} modifiée dans l'inspecteur", task.name));
+                self.tasks[index] = *task;
+            }
+        }
+        self.repair_preview = None;
+        self.dedup.reset();
+    }
+
+    /// Soumet un message de `source` au filtre des répétitions et au limiteur de
+    /// débit, puis l’applique.
+    ///
+    /// Retourne l’événement émis, ou `None` si le message est mis en attente.
+    pub fn ingest(&mut self, source: &str, message: String, now: Instant) -> Option<ProtocolEvent> {
+        if self.dedup.is_duplicate(source, &message) {
+            eprintln!("Réception : tâche répétée ignorée.");
+            return Some(ProtocolEvent::DuplicateIgnored);
+        }
+        match self.rate_limiter.admit(source, message, now) {
+            Admission::Apply(message) => Some(self.handle_from(source, message)),
+            Admission::Held => None,
+            Admission::Rejected(event) => {
+                eprintln!("Message refusé : {:?}", event);
+                Some(event)
+            }
+        }
+    }
+
+    /// Applique les messages en attente que le débit autorise (tous si `force`).
+    pub fn release_held(&mut self, now: Instant, force: bool) -> Vec<ProtocolEvent> {
+        let released = self.rate_limiter.release(now, force);
+        released.into_iter().map(|(source, message)| self.handle_from(&source, message)).collect()
+    }
+
+    /// Applique un message de `source` ; une réponse est retenue pour être
+    /// transmise à cette source.
+    fn handle_from(&mut self, source: &str, message: String) -> ProtocolEvent {
+        let event = self.handle_message(message);
+        if event.is_reply() {
+            self.replies.push((source.to_string(), event.clone()));
+        }
+        event
+    }
+
+    /// Retire les réponses en attente, avec leur source destinataire.
+    pub fn take_replies(&mut self) -> Vec<(String, ProtocolEvent)> {
+        std::mem::take(&mut self.replies)
+    }
+
+    /// Applique un message reçu sur stdin et retourne l’événement correspondant.
+    pub fn handle_message(&mut self, json: String) -> ProtocolEvent {
+        eprintln!("Réception depuis la queue : {}", json);
+        self.messages_received += 1;
+
+        // Désérialisation du JSON en tâche ou en message de configuration (tâches validées)
+        match parse_message(&json) {
+            Ok(message) => self.apply_message(message),
             Err(e) => {
-                eprintln!("Erreur JSON : {:?}", e);
+                eprintln!("Message refusé : {}", e);
+                ProtocolEvent::Error { message: e }
+            }
+        }
+    }
+
+    /// Active le profil d'affichage `name` : thème, bandeaux, HUD et couches
+    /// affichées ; le panneau latéral et les droits sont lus du profil actif.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d'erreur si le profil est inconnu.
+    pub fn select_profile(&mut self, name: &str) -> Result<(), String> {
+        let profile = self.profiles.iter().find(|p| p.name == name).cloned()
+            .ok_or_else(|| format!("Profil « {} » inconnu", name))?;
+        self.time_strip = profile.time_strip;
+        self.freq_strip = profile.freq_strip;
+        self.show_hud = profile.hud;
+        for layer in PlanLayer::ALL {
+            self.layers.set_visible(layer, profile.shows_layer(layer));
+        }
+        if !profile.permissions.dialogs {
+            self.inspector = None;
+            self.csv_dialog_open = false;
+            self.zone_editor_open = false;
+        }
+        eprintln!("Profil d'affichage : {}", profile.name);
+        self.profile = profile;
+        Ok(())
+    }
+
+    /// Indique si les fenêtres de dialogue peuvent être ouvertes (mode kiosque et
+    /// droits du profil).
+    pub fn allows_dialogs(&self) -> bool {
+        self.kiosk.allows_dialogs() && self.profile.permissions.dialogs
+    }
+
+    /// Indique si le plan affiché est détaché du plan diffusé (bac à sable ou
+    /// consultation d'une révision) : rien n'est alors diffusé ni enregistré.
+    pub fn is_detached(&self) -> bool {
+        self.sandbox.is_active() || self.revisions.is_viewing()
+    }
+
+    /// Recharge les fichiers de configuration modifiés depuis leur dernier
+    /// chargement et inscrit le résultat au journal. Un fichier invalide laisse
+    /// en place la dernière configuration valide ; un fichier supprimé rétablit
+    /// la configuration par défaut.
+    pub fn reload_config(&mut self) {
+        for file in self.config_watcher.changed() {
+            let path = file.path();
+            let exists = path.exists();
+            let result = match file {
+                ConfigFile::Amplifiers => {
+                    let amplifiers = if exists { load_amplifiers(path) } else { Ok(AmplifierRegistry::default()) };
+                    amplifiers.map(|amplifiers| {
+                        set_amplifiers(amplifiers);
+                        // Cycles de service modifiés : le plan doit être réanalysé
+                        self.analysis.invalidate();
+                        "couleurs et cycles de service des amplificateurs appliqués".to_string()
+                    })
+                }
+                ConfigFile::Zones => {
+                    let zones = if exists { load_zones(path) } else { Ok(default_zone_definitions()) };
+                    zones.map(|zones| {
+                        self.zones = zones;
+                        format!("{} zone(s) de fond", self.zones.len())
+                    })
+                }
+                ConfigFile::Profiles => {
+                    let profiles = if exists { load_profiles(path) } else { Ok(builtin_profiles()) };
+                    profiles.map(|profiles| {
+                        self.profiles = profiles;
+                        // Le profil actif est réappliqué s'il existe toujours
+                        let name = self.profile.name.clone();
+                        match self.select_profile(&name) {
+                            Ok(()) => format!("{} profil(s), profil « {} » réappliqué", self.profiles.len(), name),
+                            Err(_) => format!("{} profil(s), profil « {} » conservé", self.profiles.len(), name),
+                        }
+                    })
+                }
+                ConfigFile::Alerts => self.alerts.reload(),
+            };
+            self.config_watcher.record(file, result);
+        }
+    }
+
+    /// Amène le graphe sur les tâches ou, à défaut, la région en cause de l'alerte,
+    /// et ouvre l'inspecteur sur la première tâche en cause.
+    pub fn focus_alert(&mut self, alert: &Alert) {
+        let indices: Vec<usize> = alert.tasks.iter().filter_map(|key| self.tasks.iter().position(|t| t.key() == key)).collect();
+        let region = if indices.is_empty() {
+            match alert.region {
+                Some(region) => region,
+                None => return,
+            }
+        } else {
+            indices.iter().map(|&i| &self.tasks[i]).fold(
+                [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY],
+                |r, t| [r[0].min(t.freq_start), r[1].min(t.time_start), r[2].max(t.freq_end), r[3].max(t.time_end)],
+            )
+        };
+        self.focus_region(region);
+        self.inspector = indices.first().map(|&i| TaskInspector::new(i, &self.tasks[i]));
+    }
+
+    /// Amène le graphe sur la région `[freq_start, time_start, freq_end, time_end]`,
+    /// avec une marge temporelle.
+    pub fn focus_region(&mut self, region: [f64; 4]) {
+        self.main_plot.focus_region(region, self.log_scale);
+    }
+
+    /// Scinde la tâche `index` à la position `at` de l'axe `axis` (opération annulable).
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d'erreur si la scission est impossible ou si la clé de la
+    /// seconde partie est déjà utilisée.
+    pub fn split_at(&mut self, index: usize, axis: SplitAxis, at: f64) -> Result<ProtocolEvent, String> {
+        let task = self.tasks.get(index).ok_or("Tâche inconnue")?;
+        let (mut first, mut second) = split_task(task, axis, at)?;
+        first.quantize_times(self.time_display.resolution);
+        second.quantize_times(self.time_display.resolution);
+        let new_key = second.key().to_string();
+        if self.tasks.iter().any(|t| t.key() == new_key) {
+            return Err(format!("Clé « {} » déjà utilisée", new_key));
+        }
+        self.history.record(&self.tasks);
+        let key = first.key().to_string();
+        self.tasks[index] = first;
+        self.tasks.insert(index + 1, second);
+        self.repair_preview = None;
+        self.inspector = None;
+        self.dedup.reset();
+        Ok(ProtocolEvent::TaskSplit { key, new_key })
+    }
+
+    /// Fusionne la tâche `other` dans la tâche `index` (opération annulable).
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d'erreur si les tâches ne sont pas adjacentes et compatibles.
+    pub fn join(&mut self, index: usize, other: usize) -> Result<ProtocolEvent, String> {
+        let (Some(first), Some(second)) = (self.tasks.get(index), self.tasks.get(other)) else {
+            return Err("Tâche inconnue".into());
+        };
+        if index == other {
+            return Err("Une tâche ne peut pas être fusionnée avec elle-même".into());
+        }
+        let joined = join_tasks(first, second)?;
+        let removed_key = second.key().to_string();
+        self.history.record(&self.tasks);
+        let key = joined.key().to_string();
+        self.tasks[index] = joined;
+        self.tasks.remove(other);
+        self.repair_preview = None;
+        self.inspector = None;
+        self.dedup.reset();
+        Ok(ProtocolEvent::TasksJoined { key, removed_key })
+    }
+
+    /// Affiche le bandeau de la révision consultée, avec le retour au plan courant
+    /// et la restauration de la révision (publiée comme une modification du plan).
+    fn show_revision_banner(&mut self, ctx: &egui::Context) {
+        let Some(revision) = self.revisions.viewed() else {
+            return;
+        };
+        let (label, time, tasks) = (revision.label.clone(), revision.time.clone(), revision.tasks.clone());
+        egui::TopBottomPanel::top("revision_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::from_rgb(200, 160, 255), format!("Révision « {} » du {} – lecture seule", label, time));
+                let back = ui.button("Revenir au plan courant").clicked();
+                let restore = ui.add_enabled(self.profile.permissions.edit, egui::Button::new("Restaurer cette révision")).clicked();
+                if back || restore {
+                    if let Some(current) = self.revisions.leave() {
+                        self.tasks = current;
+                    }
+                    self.repair_preview = None;
+                    self.inspector = None;
+                    self.dedup.reset();
+                }
+                if restore {
+                    let description = format!("Révision « {} » restaurée", label);
+                    self.bus.publish(PlanEvent::Replace { tasks, action: "restore_revision", description });
+                }
+            });
+        });
+    }
+
+    /// Affiche le bandeau du bac à sable et retourne le lot de mutations s'il est validé.
+    fn show_sandbox_banner(&mut self, ctx: &egui::Context) -> Vec<ProtocolEvent> {
+        if !self.sandbox.is_active() {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        egui::TopBottomPanel::top("sandbox_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    Color32::from_rgb(120, 200, 255),
+                    format!("Bac à sable : {} modification(s), non diffusées", self.sandbox.change_count(&self.tasks)),
+                );
+                if ui.button("Valider").clicked() {
+                    self.timeline.record_action("sandbox_commit", "Bac à sable validé");
+                    events = self.sandbox.commit(&self.tasks);
+                    self.dedup.reset();
+                }
+                if ui.button("Abandonner").clicked() {
+                    self.timeline.record_action("sandbox_discard", "Bac à sable abandonné");
+                    if let Some(base) = self.sandbox.discard() {
+                        self.tasks = base;
+                    }
+                    self.repair_preview = None;
+                    self.inspector = None;
+                    self.dedup.reset();
+                }
+            });
+        });
+        events
+    }
+
+    /// Affiche le dernier état reçu de l'instance principale (instance suiveuse).
+    fn apply_mirror_state(&mut self) {
+        let state = match self.mirror.take_state() {
+            Some(Ok(state)) => state,
+            Some(Err(e)) => {
+                self.status = Some(e);
+                return;
+            }
+            None => return,
+        };
+        if self.tasks != state.tasks {
+            self.repair_preview = None;
+            self.inspector = None;
+            self.tasks = state.tasks;
+        }
+        self.rx_guard.duration = state.rx_guard_ms;
+        self.exclusions = state.exclusions;
+        self.phases = state.phases;
+        if let (true, Some(view)) = (self.mirror.sync_view, state.view) {
+            self.log_scale = view.log_scale;
+            self.main_plot.force_view = Some(view.bounds);
+        }
+    }
+
+    /// Applique un message analysé et retourne l’événement correspondant.
+    pub fn apply_message(&mut self, message: IncomingMessage) -> ProtocolEvent {
+        match message {
+            IncomingMessage::RxGuard(config) => {
+                self.rx_guard.duration = config.rx_guard_ms.clamp(0., MAX_TIME);
+                eprintln!("Réception : garde Rx fixée à {} ms.", self.rx_guard.duration);
+                ProtocolEvent::RxGuardSet { duration_ms: self.rx_guard.duration }
+            }
+            IncomingMessage::Exclusion(message) => {
+                let mask = message.exclusion;
+                eprintln!("Réception : masque d'exclusion {}.", mask.name);
+                self.exclusions.retain(|m| m.name != mask.name);
+                let name = mask.name.clone();
+                self.exclusions.push(mask);
+                ProtocolEvent::ExclusionSet { name }
+            }
+            IncomingMessage::Phase(message) => {
+                let phase = message.phase;
+                eprintln!("Réception : phase {}.", phase.name);
+                self.phases.retain(|p| p.name != phase.name);
+                let name = phase.name.clone();
+                self.phases.push(phase);
+                self.phases.sort_by(|a, b| a.t_start.total_cmp(&b.t_start));
+                ProtocolEvent::PhaseSet { name }
+            }
+            IncomingMessage::RemoveWhere(message) => match Query::parse(&message.remove_where) {
+                Ok(query) => {
+                    let ctx = QueryContext { rx_guard: self.rx_guard };
+                    let (removed, kept): (Vec<Task>, Vec<Task>) =
+                        std::mem::take(&mut self.tasks).into_iter().partition(|t| query.matches(t, &ctx));
+                    self.tasks = kept;
+                    self.repair_preview = None;
+                    self.inspector = None;
+                    self.dedup.reset();
+                    let count = self.trash.put(removed, "protocole (remove_where)", Instant::now());
+                    eprintln!("Réception : {} tâches supprimées.", count);
+                    ProtocolEvent::TasksRemoved { count }
+                }
+                Err(e) => {
+                    eprintln!("Expression remove_where invalide : {}", e);
+                    ProtocolEvent::Error { message: e }
+                }
+            },
+            IncomingMessage::ConfirmWhere(message) => match Query::parse(&message.confirm_where) {
+                Ok(query) => {
+                    let ctx = QueryContext { rx_guard: self.rx_guard };
+                    let count = confirm_tentative(&mut self.tasks, |t| query.matches(t, &ctx));
+                    self.dedup.reset();
+                    eprintln!("Réception : {} tâches confirmées.", count);
+                    ProtocolEvent::TasksConfirmed { count }
+                }
+                Err(e) => {
+                    eprintln!("Expression confirm_where invalide : {}", e);
+                    ProtocolEvent::Error { message: e }
+                }
+            },
+            IncomingMessage::Split(message) => {
+                let command = message.split;
+                let result = match self.tasks.iter().position(|t| t.key() == command.key) {
+                    Some(i) => self.split_at(i, command.axis, command.at),
+                    None => Err(format!("Tâche « {} » inconnue", command.key)),
+                };
+                result.unwrap_or_else(|e| {
+                    eprintln!("Scission refusée : {}", e);
+                    ProtocolEvent::Error { message: e }
+                })
+            }
+            IncomingMessage::Detection(message) => {
+                let detection = message.detection;
+                eprintln!("Réception : détection {} à {} MHz.", detection.label, detection.freq);
+                let label = detection.label.clone();
+                self.detections.push(detection, Instant::now());
+                ProtocolEvent::DetectionReceived { label }
+            }
+            IncomingMessage::Command(CommandMessage::Highlight { id, duration_ms, zoom }) => {
+                let Some(task) = self.tasks.iter().find(|t| t.key() == id) else {
+                    let e = format!("Tâche « {} » inconnue", id);
+                    eprintln!("Mise en évidence refusée : {}", e);
+                    return ProtocolEvent::Error { message: e };
+                };
+                let region = [task.freq_start, task.time_start, task.freq_end, task.time_end];
+                let duration = Duration::from_millis(duration_ms.min(MAX_HIGHLIGHT_MS));
+                self.highlights.start(&id, duration, Instant::now());
+                if zoom {
+                    self.focus_region(region);
+                }
+                eprintln!("Réception : mise en évidence de « {} » pendant {} ms.", id, duration.as_millis());
+                ProtocolEvent::TaskHighlighted { key: id }
+            }
+            IncomingMessage::Join(message) => {
+                let [first, second] = message.join;
+                let position = |key: &str| self.tasks.iter().position(|t| t.key() == key);
+                let result = match (position(&first), position(&second)) {
+                    (Some(i), Some(j)) => self.join(i, j),
+                    (None, _) => Err(format!("Tâche « {} » inconnue", first)),
+                    (_, None) => Err(format!("Tâche « {} » inconnue", second)),
+                };
+                result.unwrap_or_else(|e| {
+                    eprintln!("Fusion refusée : {}", e);
+                    ProtocolEvent::Error { message: e }
+                })
+            }
+            IncomingMessage::Plan(mut message) => {
+                // Plan complet rediffusé : les tâches reçues remplacent les tâches affichées
+                let now = Instant::now();
+                if let Err(e) = self.rate_limiter.check_tasks(STDIN_SOURCE, message.plan.len(), now) {
+                    eprintln!("Message refusé : {}", e);
+                    return ProtocolEvent::Error { message: e };
+                }
+                let plan: Vec<Task> = message
+                    .plan
+                    .drain(..)
+                    .map(|incoming| {
+                        let mut task = incoming.into_task();
+                        task.quantize_times(self.time_display.resolution);
+                        task
+                    })
+                    .collect();
+                let current: HashMap<&str, &Task> = self.tasks.iter().map(|t| (t.key(), t)).collect();
+                let (mut added, mut updated) = (0, 0);
+                for task in &plan {
+                    match current.get(task.key()) {
+                        Some(&previous) if previous != task => {
+                            self.update_animations.record(previous.clone(), now);
+                            updated += 1;
+                        }
+                        Some(_) => {}
+                        None => added += 1,
+                    }
+                }
+                let removed = self.trash.put(removed_tasks(&self.tasks, &plan), "protocole (plan rediffusé)", now);
+                if added + updated + removed > 0 {
+                    self.repair_preview = None;
+                    self.inspector = None;
+                    self.dedup.reset();
+                    self.transition.start(&self.tasks, &plan, now);
+                }
+                self.tasks = plan;
+                eprintln!("Réception : plan complet ({} ajoutées, {} mises à jour, {} supprimées).", added, updated, removed);
+
+                // Contrôle de l'empreinte annoncée (conversions divergentes entre émetteur et interface)
+                let local_hash = plan_hash(&self.tasks);
+                self.plan_sync = Some(match message.hash {
+                    Some(remote) if remote != local_hash => {
+                        eprintln!(
+                            "Divergence du plan : empreinte locale {} ≠ empreinte annoncée {} ({} tâches).",
+                            local_hash, remote, self.tasks.len()
+                        );
+                        Err(format!("plan rediffusé {} ≠ local {}", remote, local_hash))
+                    }
+                    _ => Ok(local_hash),
+                });
+                let hash_mismatch = matches!(self.plan_sync, Some(Err(_)));
+                ProtocolEvent::PlanApplied { added, updated, removed, hash_mismatch }
+            }
+            IncomingMessage::Hello(message) => {
+                self.compression = Compression::negotiate(&message.hello.compression);
+                let compression = self.compression.map(|c| c.name().to_string());
+                eprintln!(
+                    "Réception : poignée de main de {} (compression : {}).",
+                    message.hello.client.as_deref().unwrap_or("l'émetteur"),
+                    compression.as_deref().unwrap_or("aucune")
+                );
+                ProtocolEvent::Welcome { compression }
+            }
+            IncomingMessage::Compressed(message) => {
+                let inner = match Compression::from_name(&message.compressed).filter(|c| Some(*c) == self.compression) {
+                    Some(compression) => compression.decompress(&message.data).and_then(|json| parse_decompressed(&json)),
+                    None => Err(format!("Compression « {} » non négociée", message.compressed)),
+                };
+                match inner {
+                    Ok(message) => self.apply_message(message),
+                    Err(e) => {
+                        eprintln!("Message refusé : {}", e);
+                        ProtocolEvent::Error { message: e }
+                    }
+                }
+            }
+            IncomingMessage::PlanHash(message) => {
+                let local_hash = plan_hash(&self.tasks);
+                if message.plan_hash == local_hash {
+                    self.plan_sync = Some(Ok(local_hash));
+                    ProtocolEvent::PlanVerified
+                } else {
+                    eprintln!(
+                        "Divergence du plan : empreinte locale {} ({} tâches) ≠ empreinte de l'émetteur {}, rediffusion demandée.",
+                        local_hash, self.tasks.len(), message.plan_hash
+                    );
+                    self.plan_sync = Some(Err(format!("émetteur {} ≠ local {}", message.plan_hash, local_hash)));
+                    ProtocolEvent::ResyncRequested { local_hash, remote_hash: message.plan_hash }
+                }
+            }
+            IncomingMessage::Task(incoming) => {
+                // Tâche reçue (bornes recalculées si elle est polygonale)
+                let mut task = incoming.into_task();
+                task.quantize_times(self.time_display.resolution);
+
+                // Mise à jour sur place d'une tâche déjà affichée, ajout sinon
+                // (les corrections prévisualisées deviennent caduques)
+                let now = Instant::now();
+                let key = task.key().to_string();
+                let existing = self.tasks.iter().position(|t| t.key() == key);
+                let count = self.tasks.len() + usize::from(existing.is_none());
+                if let Err(e) = self.rate_limiter.check_tasks(STDIN_SOURCE, count, now) {
+                    eprintln!("Message refusé : {}", e);
+                    return ProtocolEvent::Error { message: e };
+                }
+                self.repair_preview = None;
+                match existing {
+                    Some(i) => {
+                        let previous = std::mem::replace(&mut self.tasks[i], task);
+                        if previous != self.tasks[i] {
+                            self.update_animations.record(previous, now);
+                        }
+                        if self.inspector.as_ref().is_some_and(|inspector| inspector.index == i) {
+                            self.inspector = None;
+                        }
+                        eprintln!("Réception : tâche {} mise à jour.", key);
+                        ProtocolEvent::TaskUpdated { key }
+                    }
+                    None => {
+                        self.tasks.push(task);
+                        eprintln!("Réception : tâche {} ajoutée ({} tâches).", key, self.tasks.len());
+                        ProtocolEvent::TaskAdded { key }
+                    }
+                }
             }
         }
     }
@@ -120,221 +1117,386 @@ impl MyApp {
 /// ainsi que les interactions avec les utilisateurs.
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        /// Tant que réception des messages de la queue partagée
-        while let Some(msg) = self.msg_queue.pop() {
-            println!("UI a reçu depuis la queue : {}", msg);
-            self.handle_message(msg);
+        // Messages mis en attente par le limiteur de débit, pendant le bac à sable ou
+        // la consultation d'une révision,
+        // puis répartition des événements publiés sur le bus depuis l'image précédente
+        let now = Instant::now();
+        let mut events = Vec::new();
+        if !self.is_detached() {
+            events = self.release_held(now, false);
+            for event in std::mem::take(&mut self.deferred) {
+                events.extend(self.dispatch(event.into(), now));
+            }
+        }
+        for event in self.bus.drain() {
+            events.extend(self.dispatch(event, now));
+        }
+        events.extend(self.show_sandbox_banner(ctx));
+        self.show_revision_banner(ctx);
+        // Les événements sont diffusés aux connexions distantes abonnées ; les
+        // réponses (poignée de main, demandes de rediffusion) ne sont transmises
+        // qu'à leur source : l'émetteur sur stdout, ou la connexion distante
+        for event in &events {
+            self.timeline.record_event(event);
+            if !event.is_reply() {
+                self.subscribers.broadcast(event);
+            }
+        }
+        for (source, reply) in self.take_replies() {
+            if source == STDIN_SOURCE {
+                if let Ok(json) = serde_json::to_string(&reply) {
+                    println!("{}", json);
+                }
+            } else if !self.subscribers.send_to(&source, &reply) {
+                eprintln!("Réponse à « {} » impossible : connexion fermée", source);
+            }
+        }
+        if self.rate_limiter.has_held() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // Mode miroir : affichage de l'état de l'instance principale
+        if matches!(self.mirror.role, MirrorRole::Follower { .. }) && !self.is_detached() {
+            self.apply_mirror_state();
+        }
+        // Animations suspendues en mode dégradé : pas de rafraîchissement continu
+        self.update_animations.prune(now);
+        self.transition.prune(now);
+        if (self.update_animations.is_animating() || self.transition.is_animating()) && !self.watchdog.is_degraded() {
+            ctx.request_repaint();
+        }
+        self.highlights.prune(now);
+        self.trash.expire(now);
+        if self.highlights.is_animating() {
+            ctx.request_repaint_after(BLINK_HALF_PERIOD);
+        }
+        self.detections.prune(now);
+        if self.detections.is_fading() && !self.watchdog.is_degraded() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // Configuration et règles d'alerte : relecture des fichiers modifiés
+        self.reload_config();
+        // Plan récupéré depuis un dépôt Git
+        match self.git_import.poll() {
+            Some(Ok(plan)) => {
+                let description = format!("Plan importé depuis {} ({})", plan.source.label(), plan.short_commit());
+                eprintln!("{}", description);
+                self.bus.publish(PlanEvent::Replace { tasks: plan.tasks, action: "git_import", description });
+            }
+            Some(Err(e)) => self.status = Some(e),
+            None => {}
+        }
+        // Notifications d'alerte : rafraîchissement tant qu'elles sont affichées
+        if self.alerts.has_notifications() {
+            ctx.request_repaint_after(Duration::from_millis(500));
+        }
+        if let Some(alert) = show_alarm_banner(ctx, &self.alerts.active) {
+            self.bus.publish(UiEvent::FocusAlert(alert.clone()));
+        }
+
+        // Bandeau d'avertissement des émetteurs en dépassement de débit
+        let warnings = self.rate_limiter.warnings(now);
+        if !warnings.is_empty() {
+            egui::TopBottomPanel::top("rate_limit_banner").show(ctx, |ui| {
+                for warning in &warnings {
+                    ui.colored_label(Color32::from_rgb(255, 170, 0), format!("⚠ {}", warning));
+                }
+            });
+            ctx.request_repaint_after(Duration::from_secs(1));
         }
         
         // Mise à jour des limites X du graphe principal
         if self.log_scale != self.old_log_scale {
             self.old_log_scale = self.log_scale;
-            self.zoom_band = None;
-            self.force_bounds_x = Some(get_bounds(self.log_scale));
-        }
-
-        ctx.request_repaint(); // Demande de rafraîchissement de l'interface
-
-        // Affichage du panneau latéral avec les contrôles
-        egui::SidePanel::left("side_panel").show(ctx, |ui| {
-            ui.heading("Contrôles");
-            ui.label(format!("Nombre de tâches : {}", self.tasks.len()));
-            ui.separator();
-            ui.checkbox(&mut self.log_scale, "Échelle logarithmique");
-            ui.separator();
-            ui.label("Zoom bande :");
-            for (i, (amp, start, end)) in self.bands().iter().enumerate() {
-                if ui.selectable_label(self.zoom_band == Some(i), format!("{:?}", amp)).clicked() {
-                    self.zoom_band = Some(i);
-                    let (xmin, xmax) = if self.log_scale {
-                        (start.log10(), end.log10())
-                    } else {
-                        (*start, *end)
-                    };
-                    self.force_bounds_x = Some((xmin, xmax));
-                }
+            let invalid = self.tasks.iter().filter(|t| !t.is_log_safe()).count();
+            if self.log_scale && invalid > 0 {
+                eprintln!("Échelle logarithmique : {} tâche(s) de fréquence nulle ou négative ramenée(s) à {} MHz", invalid, MIN_FREQ);
             }
-            if ui.selectable_label(self.zoom_band.is_none(), "Tout").clicked() {
-                self.zoom_band = None;
-                self.force_bounds_x = Some(get_bounds(self.log_scale));
+            self.main_plot.reset_zoom(self.log_scale);
+        }
+
+        // Pas de rafraîchissement continu : egui redessine sur interaction, le lecteur
+        // de stdin à la réception des messages, et l'horloge temps réel est animée à
+        // cadence réduite, abaissée hors focus en mode économie d'énergie
+        let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        self.refresh.publish(focused);
+        if self.time_display.mission_start.is_some() {
+            ctx.request_repaint_after(CLOCK_REPAINT_INTERVAL.max(self.refresh.frame_interval(focused)));
+        }
+
+        // Raccourcis clavier d'annulation / rétablissement
+        let (undo, redo) = ctx.input(|i| {
+            (
+                i.modifiers.command && !i.modifiers.shift && i.key_pressed(egui::Key::Z),
+                i.modifiers.command && (i.key_pressed(egui::Key::Y) || (i.modifiers.shift && i.key_pressed(egui::Key::Z))),
+            )
+        });
+        if undo {
+            self.apply_plan_event(PlanEvent::Undo);
+        }
+        if redo {
+            self.apply_plan_event(PlanEvent::Redo);
+        }
+
+        // Masques d'exclusion reçus, complétés des bandes de référence protégées
+        let masks: Vec<ExclusionMask> = self.exclusions.iter().cloned().chain(self.presets.protected_masks()).collect();
+
+        // Cycles de service, conflits, garde Rx et occupation des amplificateurs,
+        // recalculés en arrière-plan à chaque modification du plan
+        self.analysis.update(&self.tasks, &self.rx_guard, &self.systems, &masks, &self.antennas, ctx);
+        let analysis = self.analysis.result();
+
+        // Règles d'alerte, réévaluées à chaque révision du plan
+        let alert_ctx = AlertContext { presets: &self.presets.presets, phases: &self.phases, rx_guard: self.rx_guard };
+        self.alerts.update(self.analysis.revision(), &self.tasks, &alert_ctx, now);
+
+        // Mode miroir : republication de l'état de l'instance principale à chaque
+        // révision du plan ou changement des phases ou de la vue
+        let view = self.main_plot.view_bounds.filter(|_| self.mirror.sync_view).map(|bounds| MirrorView { log_scale: self.log_scale, bounds });
+        let state = || MirrorState {
+            tasks: self.tasks.clone(),
+            rx_guard_ms: self.rx_guard.duration,
+            exclusions: self.exclusions.clone(),
+            phases: self.phases.clone(),
+            view,
+        };
+        if !self.is_detached() && self.mirror.publish(self.analysis.revision(), &self.phases, view, state, &self.subscribers, now) {
+            ctx.request_repaint_after(crate::tools::mirror::PUBLISH_INTERVAL);
+        }
+        if let Some(e) = self.mirror.take_error() {
+            self.status = Some(e);
+        }
+
+        // Enregistrement du plan modifié dans le stockage persistant et dans
+        // l'historique des tâches
+        if self.analysis.revision() != self.stored_revision && !self.is_detached() {
+            self.stored_revision = self.analysis.revision();
+            self.task_log.observe(&self.tasks);
+            if let Err(e) = self.store.replace_all(&self.tasks) {
+                self.status = Some(format!("Enregistrement du plan impossible : {}", e));
             }
+        }
+
+        // Captures sur événement : enregistrement des images reçues, détection des
+        // nouveaux conflits et des changements de phase en temps réel
+        for saved in self.screenshots.save_received(ctx, self.main_plot.rect) {
+            match saved {
+                Ok(path) => eprintln!("Capture enregistrée : {}", path.display()),
+                Err(e) => self.status = Some(e),
+            }
+        }
+        let current_phase = self.time_display.mission_start.and_then(|start| {
+            let elapsed = (chrono::Utc::now().timestamp_millis() - start) as f64;
+            self.phases.iter().find(|p| p.contains(elapsed)).map(|p| p.name.clone())
         });
+        if !self.analysis.pending() {
+            self.screenshots.observe(analysis.conflicts.len(), current_phase.as_deref());
+        }
 
-        // Affichage du panneau central avec le graphe principal et le mini graphe
-        egui::CentralPanel::default().show(ctx, |ui| {
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                let total_height = ui.available_height();
-                let main_height = total_height * 0.8;
-                let mini_height = total_height * 0.18;
-
-                // Si le mode logarithmique est activé, on utilise un espacement logarithmique pour les grilles
-                // Sinon, on utilise un espacement uniforme basé sur les bandes d'amplification
-                let spacer = if self.log_scale {
-                    log_grid_spacer(10)
-                } else {
-                    uniform_grid_spacer(|_input| [100.0, 500.0, 1000.0])
-                };
-                let formatter = |mark: GridMark, _range: &_| {
-                    if self.log_scale {
-                        format!("{:.1} MHz", 10f64.powf(mark.value))
-                    } else {
-                        format!("{:.0} MHz", mark.value)
-                    }
-                };
+        // Couleurs des tâches selon l'attribut choisi
+        let mut color_map = compute_colors(&self.tasks, self.color_by);
+        if self.platform_tint {
+            tint_by_platform(&mut color_map, &self.tasks);
+        }
+        // Tâches provisoires : semi-transparentes
+        for (color, task) in color_map.colors.iter_mut().zip(&self.tasks) {
+            if task.tentative {
+                *color = color.gamma_multiply(TENTATIVE_OPACITY);
+            }
+        }
 
-                // Graphe principal
-                ui.allocate_ui(egui::vec2(ui.available_width(), main_height), |ui| {
-                    let label_tx_main = self.label_tx.clone();
-                    let mut plot = Plot::new("main")
-                        .link_axis("shared_x", [true, false])
-                        .x_axis_formatter(formatter)
-                        .y_axis_formatter(|y, _| format!("{:.0} ms", y.value))
-                        .include_y(0.0)
-                        .include_y(MAX_TIME)
-                        .x_grid_spacer(spacer)
-                        .show_grid([false, false])
-                        .label_formatter(move |_name, pt| {
-                            let _ = label_tx_main.send(*pt);
-                            "".into()
-                        });
-
-                    // Si le mode logarithmique est activé, on utilise un espacement logarithmique pour l'axe X
-                    if let Some((xmin, xmax)) = self.force_bounds_x.take() {
-                        plot = plot.default_x_bounds(xmin, xmax);
-                    }
+        // Thème du profil d'affichage
+        let dark = self.profile.theme == Theme::Dark;
+        if ctx.style().visuals.dark_mode != dark {
+            ctx.set_visuals(self.profile.theme.visuals());
+        }
 
-                    // Affichage du graphe principal
-                    plot.show(ui, |plot_ui| {
-                        let bounds = plot_ui.plot_bounds();
-                        let new_bounds_x = (bounds.min()[0], bounds.max()[0]);
-                        if self.last_bounds_x != Some(new_bounds_x) {
-                            self.plot_bounds_x = Some(new_bounds_x);
-                            self.last_bounds_x = Some(new_bounds_x);
-                        }
+        // Panneau latéral des contrôles
+        let panel = SidePanel {
+            tasks: &self.tasks,
+            selected: self.inspector.as_ref().map(|inspector| inspector.index),
+            profile: &self.profile,
+            profiles: &self.profiles,
+            wall: self.wall,
+            alerts: &self.alerts,
+            diagnostics: &self.diagnostics,
+            systems: &self.systems,
+            antennas: &self.antennas,
+            propagation: &self.propagation,
+            phases: &self.phases,
+            history: &self.history,
+            plan_sync: self.plan_sync.as_ref(),
+            messages_received: self.messages_received,
+            revision: self.analysis.revision(),
+            pending: self.analysis.pending(),
+            kiosk: &mut self.kiosk,
+            log_scale: &mut self.log_scale,
+            time_display: &mut self.time_display,
+            mission_start_text: &mut self.mission_start_text,
+            show_hud: &mut self.show_hud,
+            status: &mut self.status,
+            refresh: &mut self.refresh,
+            transition: &mut self.transition,
+            watchdog: &mut self.watchdog,
+            config_watcher: &mut self.config_watcher,
+            diagnostics_open: &mut self.diagnostics_open,
+            markups: &mut self.markups,
+            task_drag: &mut self.task_drag,
+            task_tree: &mut self.task_tree,
+            trash: &mut self.trash,
+            sandbox: &mut self.sandbox,
+            timeline: &mut self.timeline,
+            selection: &mut self.selection,
+            revisions: &mut self.revisions,
+            baseline: &mut self.baseline,
+            harmonics: &mut self.harmonics,
+            presets: &mut self.presets,
+            tooltip: &mut self.tooltip,
+            pinned: &mut self.pinned,
+            layers: &mut self.layers,
+            detections: &mut self.detections,
+            robustness: &mut self.robustness,
+            color_by: &mut self.color_by,
+            platform_tint: &mut self.platform_tint,
+            time_strip: &mut self.time_strip,
+            freq_strip: &mut self.freq_strip,
+            marginal_metric: &mut self.marginal_metric,
+            effect_filter: &mut self.effect_filter,
+            platform_filter: &mut self.platform_filter,
+            antenna_filter: &mut self.antenna_filter,
+            phase_filter: &mut self.phase_filter,
+            platform_lanes: &mut self.platform_lanes,
+            search_text: &mut self.search_text,
+            search: &mut self.search,
+            main_plot: &mut self.main_plot,
+            band_filter: &mut self.band_filter,
+            rx_guard: &mut self.rx_guard,
+            headroom: &mut self.headroom,
+            exclusions: &mut self.exclusions,
+            dedup: &mut self.dedup,
+            repair_preview: &mut self.repair_preview,
+            plan_path: &mut self.plan_path,
+            git_import: &mut self.git_import,
+            screenshots: &mut self.screenshots,
+            zone_editor_open: &mut self.zone_editor_open,
+            csv_dialog_open: &mut self.csv_dialog_open,
+        };
+        for event in panel.show(ctx, &analysis, &masks, &color_map.legend) {
+            self.bus.publish(event);
+        }
 
-                        // Affichage des zones de fond
-                        for zone in get_background_zones() {
-                            let area = if self.log_scale {
-                                zone.area.iter().map(|[x, y]| [x.log10(), *y]).collect()
-                            } else {
-                                zone.area.clone()
-                            };
-
-                            plot_ui.polygon(Polygon::new("zone", PlotPoints::from(area))
-                                .fill_color(zone.fill)
-                                .stroke(zone.stroke));
-
-                            if let Some((text, pos, color)) = zone.label {
-                                let x = if self.log_scale { pos[0].log10() } else { pos[0] };
-                                plot_ui.text(Text::new(text.clone(), PlotPoint::new(x, pos[1]), RichText::new(text).color(color)));
-                            }
-                        }
+        // Fenêtre d'import CSV : insertion groupée en une seule opération annulable
+        if self.csv_dialog_open && self.allows_dialogs() {
+            let mut open = true;
+            if let Some(mut imported) = self.csv_dialog.show(ctx, self.time_display.mission_start, &mut open) {
+                imported.iter_mut().for_each(|t| t.quantize_times(self.time_display.resolution));
+                let description = format!("{} tâches importées depuis {}", imported.len(), self.csv_dialog.path);
+                self.status = Some(description.clone());
+                self.bus.publish(PlanEvent::Extend { tasks: imported, action: "csv_import", description });
+                open = false;
+            }
+            self.csv_dialog_open = open;
+        }
 
-                        // Affichage de la ligne horizontale pour la limite de temps
-                        let hline = if self.log_scale {
-                            vec![[MIN_FREQ.log10(), MAX_TIME], [MAX_FREQ.log10(), MAX_TIME]]
-                        } else {
-                            vec![[MIN_FREQ, MAX_TIME], [MAX_FREQ, MAX_TIME]]
-                        };
-                        plot_ui.line(Line::new("hline", PlotPoints::from(hline)).stroke(Stroke::new(1.0, Color32::GRAY)));
-
-                        // Affichage des tâches
-                        for task in &self.tasks {
-                            let poly = Polygon::new(&task.name, PlotPoints::from(task.rect(self.log_scale)))
-                                .fill_color(task.color())
-                                .stroke(Stroke::new(0., Color32::TRANSPARENT));
-                            plot_ui.polygon(poly);
-                        }
-                    });
-                });
+        // Éditeur des zones de fond
+        if self.zone_editor_open && self.allows_dialogs() {
+            self.zone_editor.show(ctx, &mut self.zones, &mut self.zone_editor_open);
+        }
 
-                // Mini graphe
-                ui.allocate_ui(egui::vec2(ui.available_width(), mini_height), |ui| {
-                    let label_tx_mini = self.label_tx.clone();
-                    Plot::new("mini")
-                        .link_axis("shared_x", [true, false])
-                        .show_axes([false, true])
-                        .y_axis_formatter(|y, _| format!("{:.0} ms", y.value))
-                        .include_y(0.0)
-                        .include_y(MAX_TIME)
-                        .include_x(get_bounds(self.log_scale).0)
-                        .include_x(get_bounds(self.log_scale).1)
-                        .show_grid([false, false])
-                        .label_formatter(move |_name, pt| {
-                            let _ = label_tx_mini.send(*pt);
-                            "".into()
-                        })
-                        .show(ui, |plot_ui| {
-                            for task in &self.tasks {
-                                let poly = Polygon::new(&task.name, PlotPoints::from(task.rect(self.log_scale)))
-                                    .fill_color(task.color())
-                                    .stroke(Stroke::new(0., Color32::TRANSPARENT));
-                                plot_ui.polygon(poly);
-                            }
-                        });
-                });
+        // Diagnostics de démarrage
+        if self.diagnostics_open && self.allows_dialogs() {
+            self.diagnostics.show(ctx, &mut self.diagnostics_open);
+        }
 
-                // Tooltips interactifs
-                if let Ok(data_pos) = self.label_rx.try_recv() {
-                    let hovered_freq = if self.log_scale {
-                        10f64.powf(data_pos.x)
-                    } else {
-                        data_pos.x
-                    };
-                    let mut task_hovered = false;
-
-                    // Tooltip pour les tâches
-                    for task in &self.tasks {
-                        if hovered_freq >= task.freq_start && hovered_freq <= task.freq_end
-                            && data_pos.y >= task.time_start && data_pos.y <= task.time_end {
-                            egui::show_tooltip_at_pointer(ctx, ui.layer_id(), ui.id().with("tooltip"), |ui| {
-                                ui.set_min_width(120.);
-                                ui.label(&task.name);
-                                ui.label(format!(
-                                    "Amplifier: {:?}\nΔf: {:.0}MHz\nΔt: {:.0}ms\ntmin: {:.0}ms\ntmax: {:.0}ms\nfmin: {:.0}MHz\nfmax: {:.0}MHz",
-                                    task.amplifier,
-                                    task.freq_end - task.freq_start,
-                                    task.time_end - task.time_start,
-                                    task.time_start, task.time_end,
-                                    task.freq_start, task.freq_end
-                                ));
-                            });
-                            task_hovered = true;
-                            break;
-                        }
-                    }
+        // Inspecteur de la tâche sélectionnée
+        let dialogs = self.allows_dialogs();
+        if let Some(inspector) = self.inspector.as_mut().filter(|_| dialogs) {
+            let mut open = inspector.index < self.tasks.len();
+            let changes = self.tasks.get(inspector.index).map_or(&NO_CHANGES, |t| self.task_log.changes(t.key()));
+            let range = self.tasks.get(inspector.index).and_then(|t| self.propagation.estimate(t, &self.antennas));
+            if let Some(task) = inspector.show(ctx, &self.time_display, &self.rx_guard, changes, range, &mut open) {
+                let key = std::mem::replace(&mut inspector.key, task.key().to_string());
+                self.bus.publish(PlanEvent::Edit { key, task: Box::new(task) });
+            }
+            if !open {
+                self.inspector = None;
+            }
+        }
 
-                    // Tooltip pour les zones de fond si aucune tâche n'est survolée
-                    if !task_hovered {
-                        let zones: Vec<String> = get_background_zones()
-                            .into_iter()
-                            .filter(|z| z.contains(hovered_freq, data_pos.y))
-                            .map(|z| z.name())
-                            .collect();
-
-                        // Affichage des zones de fond si elles sont survolées
-                        if !zones.is_empty() {
-                            egui::show_tooltip_at_pointer(ctx, ui.layer_id(), ui.id().with("tooltip"), |ui| {
-                                ui.set_min_width(80.);
-                                for label in zones {
-                                    ui.label(label);
-                                }
-                            });
-                        }
+        // Écran d'accueil tant qu'aucune tâche n'est disponible
+        if self.tasks.is_empty() && self.messages_received == 0 && self.allows_dialogs() {
+            self.show_empty_state(ctx);
+        }
 
-                        // Affichage des coordonnées du curseur dans tous les cas
-                        egui::show_tooltip_at_pointer(
-                            ui.ctx(),
-                            ui.layer_id(),
-                            ui.id().with("tooltip"),
-                            |ui| {
-                                ui.set_min_width(70.);
-                                ui.label(format!("{:.1} MHz\n{:.1} ms", data_pos.x, data_pos.y));
-                            },
-                        );
-                    }
-                }
-            });
-        });
+        // Panneau central avec le graphe principal et le mini graphe
+        let scene = PlotScene {
+            tasks: &self.tasks,
+            selected: self.inspector.as_ref().map(|inspector| inspector.index),
+            log_scale: self.log_scale,
+            time_display: self.time_display,
+            rx_guard: self.rx_guard,
+            zones: &self.zones,
+            phases: &self.phases,
+            presets: &self.presets,
+            headroom: &self.headroom,
+            exclusions: &self.exclusions,
+            band_filter: &self.band_filter,
+            platform_filter: self.platform_filter.as_deref(),
+            antenna_filter: self.antenna_filter.as_deref(),
+            effect_filter: self.effect_filter,
+            phase_filter: self.phase_filter.as_deref(),
+            search: self.search.as_ref().ok().and_then(Option::as_ref),
+            layers: &self.layers,
+            detections: &self.detections,
+            transition: &self.transition,
+            update_animations: &self.update_animations,
+            highlights: &self.highlights,
+            selection: &self.selection,
+            baseline: &self.baseline,
+            repair_preview: self.repair_preview.as_deref(),
+            propagation: &self.propagation,
+            antennas: &self.antennas,
+            tooltip: &self.tooltip,
+            wall: self.wall,
+            platform_lanes: self.platform_lanes,
+            time_strip: self.time_strip,
+            freq_strip: self.freq_strip,
+            marginal_metric: self.marginal_metric,
+            degraded: self.watchdog.is_degraded(),
+            editable: self.profile.permissions.edit,
+            dialogs,
+        };
+        let frame = FrameView::new(now, analysis, masks, color_map, &scene, self.main_plot.freq_span(self.log_scale));
+        let mut tools = PlotTools {
+            harmonics: &mut self.harmonics,
+            markups: &mut self.markups,
+            task_drag: &mut self.task_drag,
+            hit_stack: &mut self.hit_stack,
+        };
+        for event in show_plots(ctx, &frame, &scene, &mut self.main_plot, &mut tools) {
+            self.bus.publish(event);
+        }
+
+        self.screenshots.flush(ctx);
+
+        // Notifications d'alerte et fiches épinglées
+        self.alerts.show_notifications(ctx);
+        show_pinned_cards(ctx, &mut self.pinned, &scene, self.main_plot.time_span, frame.freq_span);
+
+        // HUD temps réel, relativement à l'heure H
+        if let (true, Some(start)) = (self.show_hud, self.time_display.mission_start) {
+            let elapsed = (chrono::Utc::now().timestamp_millis() - start) as f64;
+            show_hud(ctx, elapsed, &self.tasks, &self.rx_guard);
+        }
+
+        // Événements publiés par les vues : répartis dès l'image suivante
+        if self.bus.has_pending() {
+            ctx.request_repaint();
+        }
+
+        // Temps de construction de l'image, comparé au budget du chien de garde
+        self.watchdog.show_indicator(ctx);
+        self.watchdog.record(now.elapsed());
     }
 }
@@ -0,0 +1,128 @@
+//! Module des pièces jointes des tâches.
+//!
+//! Une tâche peut référencer des documents externes (ordre d’opération, rapport
+//! d’analyse…) par une URI (`https://…`, `file://…`) ou un chemin de fichier.
+//! Les références sont enregistrées avec le plan et listées dans l’inspecteur,
+//! qui les ouvre avec l’application associée du système.
+//!
+//! Les références proviennent du protocole (stdin ou réseau) : seules les URI
+//! `http`, `https` et `mailto` sont ouvertes directement ; les fichiers locaux
+//! (`file://` ou chemin) demandent une confirmation de l’opérateur et les
+//! exécutables sont refusés. Les références contenant des caractères de contrôle
+//! ou des métacaractères du shell sont rejetées, et aucune commande n’est passée
+//! par un interpréteur (`explorer` sous Windows, `open` sous macOS, `xdg-open`
+//! ailleurs).
+
+use std::path::Path;
+use std::process::Command;
+
+/// Nombre maximal de pièces jointes d’une tâche.
+pub const MAX_ATTACHMENTS: usize = 64;
+
+/// Métacaractères du shell refusés dans une référence.
+const SHELL_METACHARACTERS: &[char] = &['&', '|', ';', '<', '>', '^', '"', '`', '$', '%', '!', '\'', '(', ')', '{', '}', '*', '?'];
+
+/// Extensions de fichiers exécutables, jamais ouverts.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "app", "bat", "cmd", "com", "cpl", "desktop", "exe", "jar", "js", "lnk", "msi", "ps1", "scr", "sh", "vbs",
+];
+
+/// Nature d’une pièce jointe autorisée.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttachmentKind {
+    /// Page web (`http`, `https`).
+    Web,
+    /// Adresse électronique (`mailto`).
+    Mail,
+    /// Fichier local (`file://` ou chemin), ouvert après confirmation.
+    Local,
+}
+
+/// Schéma d’une URI (`https` pour `https://…`), absent pour un chemin (y compris
+/// un chemin Windows `C:\…`).
+fn scheme(uri: &str) -> Option<&str> {
+    let (scheme, _) = uri.split_once(':')?;
+    let valid = scheme.len() > 1 && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    valid.then_some(scheme)
+}
+
+/// Vérifie qu’une pièce jointe peut être ouverte et retourne sa nature.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la référence contient des caractères de
+/// contrôle ou des métacaractères du shell, si son schéma n’est pas autorisé ou
+/// si elle désigne un exécutable.
+pub fn classify_attachment(uri: &str) -> Result<AttachmentKind, String> {
+    if uri.trim().is_empty() {
+        return Err("Pièce jointe vide".into());
+    }
+    if uri.chars().any(|c| c.is_control() || SHELL_METACHARACTERS.contains(&c)) {
+        return Err(format!("Pièce jointe « {} » refusée : caractères non autorisés", uri));
+    }
+    let kind = match scheme(uri).map(str::to_ascii_lowercase).as_deref() {
+        Some("http") | Some("https") => return Ok(AttachmentKind::Web),
+        Some("mailto") => return Ok(AttachmentKind::Mail),
+        Some("file") | None => AttachmentKind::Local,
+        Some(other) => return Err(format!("Pièce jointe « {} » refusée : schéma « {} » non autorisé", uri, other)),
+    };
+    let path = Path::new(uri.strip_prefix("file://").unwrap_or(uri));
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    if extension.is_some_and(|e| EXECUTABLE_EXTENSIONS.contains(&e.as_str())) {
+        return Err(format!("Pièce jointe « {} » refusée : fichier exécutable", uri));
+    }
+    Ok(kind)
+}
+
+/// Libellé court d’une pièce jointe : son nom de fichier, ou l’URI complète.
+pub fn attachment_label(uri: &str) -> &str {
+    if uri.contains("://") && !uri.starts_with("file://") {
+        return uri;
+    }
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(uri)
+}
+
+/// Ouvre une pièce jointe avec l’application associée du système. Un fichier
+/// local n’est ouvert que si `local_confirmed` est vrai.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la pièce jointe est refusée
+/// ([`classify_attachment`]), si un fichier local n’a pas été confirmé ou si
+/// l’application ne peut pas être lancée.
+pub fn open_attachment(uri: &str, local_confirmed: bool) -> Result<(), String> {
+    if classify_attachment(uri)? == AttachmentKind::Local && !local_confirmed {
+        return Err(format!("Ouverture de {} : confirmation requise pour un fichier local", uri));
+    }
+    let mut command = if cfg!(target_os = "windows") {
+        Command::new("explorer")
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(uri)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Ouverture de {} impossible : {}", uri, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachments_are_restricted_to_safe_schemes() {
+        assert_eq!(classify_attachment("https://example.org/ordre.pdf"), Ok(AttachmentKind::Web));
+        assert_eq!(classify_attachment("mailto:ops@example.org"), Ok(AttachmentKind::Mail));
+        assert_eq!(classify_attachment("file:///srv/ordres/ordre.pdf"), Ok(AttachmentKind::Local));
+        assert_eq!(classify_attachment("C:\\ordres\\ordre.pdf"), Ok(AttachmentKind::Local));
+        assert!(classify_attachment("https://example.org/&calc.exe").is_err());
+        assert!(classify_attachment("ordre.pdf\" & calc").is_err());
+        assert!(classify_attachment("javascript:alert(1)").is_err());
+        assert!(classify_attachment("smb://serveur/partage").is_err());
+        assert!(classify_attachment("file:///tmp/outil.exe").is_err());
+    }
+}
@@ -2,8 +2,19 @@
 //!
 //! Ce module permet de définir et de gérer des zones visuelles dans le diagramme,
 //! telles que la zone de réception (RxZone) et les zones correspondant aux amplificateurs.
+//! Hormis la zone de réception, les zones sont décrites par des [`ZoneDefinition`]
+//! modifiables depuis l’éditeur de zones et enregistrées dans `zones.json`.
+
+use std::fs;
+use std::path::Path;
 
 use egui::{Color32, Stroke};
+use serde::{Deserialize, Serialize};
+
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::geometry::point_in_polygon;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Amplifier;
 
 /// Enumération des types de zones de fond.
 ///
@@ -12,8 +23,10 @@ use egui::{Color32, Stroke};
 pub enum BackgroundZoneKind {
     /// Zone de réception générique.
     RxZone,
-    /// Zone d’un amplificateur, identifiée par un label statique.
-    Amplifier(&'static str),
+    /// Zone d’un amplificateur.
+    Amplifier(Amplifier),
+    /// Zone libre définie par l’utilisateur.
+    Custom,
 }
 
 /// Représente une zone de fond à dessiner dans le diagramme.
@@ -53,77 +66,147 @@ impl BackgroundZone {
         Self { kind, area, stroke, fill, label }
     }
 
-    /// Indique si un point `(x, y)` se trouve dans la zone (algorithme du rayon).
+    /// Indique si un point `(x, y)` se trouve dans la zone.
     pub fn contains(&self, x: f64, y: f64) -> bool {
-        let mut inside = false;
-        let points = &self.area;
-        let n = points.len();
-        let mut j = n - 1;
-        for i in 0..n {
-            let (xi, yi) = (points[i][0], points[i][1]);
-            let (xj, yj) = (points[j][0], points[j][1]);
-            if (yi > y) != (yj > y)
-                && (x < (xj - xi) * (y - yi) / (yj - yi + f64::EPSILON) + xi)
-            {
-                inside = !inside;
-            }
-            j = i;
-        }
-        inside
+        point_in_polygon(&self.area, x, y)
     }
 
     /// Retourne le nom lisible de la zone.
     pub fn name(&self) -> String {
         match self.kind {
             BackgroundZoneKind::RxZone => "Zone de réception".into(),
-            BackgroundZoneKind::Amplifier(label) => label.into(),
+            BackgroundZoneKind::Amplifier(amp) => amp.label().into(),
+            BackgroundZoneKind::Custom => self
+                .label
+                .as_ref()
+                .map_or("Zone".into(), |(text, _, _)| text.replace('\n', " ")),
         }
     }
 }
 
-use crate::tools::task::Amplifier;
 use crate::tools::utils::{MIN_FREQ, MAX_FREQ};
 
+/// Chemin par défaut du fichier de configuration des zones.
+pub const ZONES_PATH: &str = "zones.json";
+
+/// Étiquette d’une zone telle qu’enregistrée dans le fichier de configuration.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ZoneLabel {
+    /// Texte de l’étiquette (`\n` pour passer à la ligne).
+    pub text: String,
+    /// Position `[fréquence, temps]` de l’étiquette.
+    pub position: [f64; 2],
+    /// Couleur RVBA (non prémultipliée) du texte.
+    pub color: [u8; 4],
+}
+
+/// Définition éditable d’une zone de fond, telle qu’enregistrée dans le fichier
+/// de configuration des zones.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ZoneDefinition {
+    /// Amplificateur associé, ou `None` pour une zone libre.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amplifier: Option<Amplifier>,
+    /// Sommets `[fréquence, temps]` du polygone.
+    pub points: Vec<[f64; 2]>,
+    /// Épaisseur du contour.
+    pub stroke_width: f32,
+    /// Couleur RVBA (non prémultipliée) du contour.
+    pub stroke_color: [u8; 4],
+    /// Couleur RVBA (non prémultipliée) du remplissage.
+    pub fill: [u8; 4],
+    /// Étiquette optionnelle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<ZoneLabel>,
+}
+
+impl ZoneDefinition {
+    /// Construit la zone de fond correspondant à la définition.
+    pub fn to_zone(&self) -> BackgroundZone {
+        let color = |[r, g, b, a]: [u8; 4]| Color32::from_rgba_unmultiplied(r, g, b, a);
+        BackgroundZone::new(
+            self.amplifier.map_or(BackgroundZoneKind::Custom, BackgroundZoneKind::Amplifier),
+            self.points.clone(),
+            Stroke::new(self.stroke_width, color(self.stroke_color)),
+            color(self.fill),
+            self.label.as_ref().map(|l| (l.text.clone(), l.position, color(l.color))),
+        )
+    }
+}
+
+/// Zones par défaut : une zone par bande d’amplification.
+pub fn default_zone_definitions() -> Vec<ZoneDefinition> {
+    Amplifier::ALL
+        .iter()
+        .map(|&amp| {
+            let (f_start, f_end) = amp.band();
+            let color = amplifier_color(amp).to_srgba_unmultiplied();
+            let height = 1100.;
+            let y_max = if amp == Amplifier::A960_1215 { height + 25. } else { height };
+            let label_y = if amp == Amplifier::A960_1215 { height + 50. } else { height - 50. };
+
+            ZoneDefinition {
+                amplifier: Some(amp),
+                points: vec![[f_start, 0.], [f_end, 0.], [f_end, y_max], [f_start, y_max]],
+                stroke_width: 1.,
+                stroke_color: color,
+                fill: [0, 0, 0, 0],
+                label: Some(ZoneLabel {
+                    text: amp.label().replace(" ", "\n"),
+                    position: [(f_start + f_end) / 2., label_y],
+                    color,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Charge les définitions de zones depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé, ou si
+/// une zone compte moins de 3 sommets.
+pub fn load_zones(path: &Path) -> Result<Vec<ZoneDefinition>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let zones: Vec<ZoneDefinition> =
+        serde_json::from_str(&content).map_err(|e| format!("Zones {} invalides : {}", path.display(), e))?;
+    if let Some(index) = zones.iter().position(|zone| zone.points.len() < 3) {
+        return Err(format!("Zones {} invalides : la zone {} compte moins de 3 sommets", path.display(), index + 1));
+    }
+    Ok(zones)
+}
+
+/// Enregistre les définitions de zones dans un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier ne peut pas être écrit.
+pub fn save_zones(path: &Path, zones: &[ZoneDefinition]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(zones).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))
+}
+
 /// Construit la liste des zones de fond à afficher dans le graphe.
 ///
-/// Inclut la zone de réception ainsi que les bandes d’amplification.
+/// Inclut la zone de réception, couvrant la fenêtre de garde `rx_guard`, ainsi que
+/// les zones définies dans la configuration (`definitions`).
 ///
 /// # Retour
 ///
 /// Un vecteur de [`BackgroundZone`] correspondant aux aires à dessiner.
-pub fn get_background_zones() -> Vec<BackgroundZone> {
+pub fn get_background_zones(definitions: &[ZoneDefinition], rx_guard: &RxGuard) -> Vec<BackgroundZone> {
+    let rx_end = rx_guard.duration;
     let mut zones = vec![
         BackgroundZone::new(
             BackgroundZoneKind::RxZone,
-            vec![[MIN_FREQ, 0.], [MAX_FREQ, 0.], [MAX_FREQ, 100.], [MIN_FREQ, 100.]],
+            vec![[MIN_FREQ, 0.], [MAX_FREQ, 0.], [MAX_FREQ, rx_end], [MIN_FREQ, rx_end]],
             Stroke::new(0.1, Color32::from_gray(100)),
             Color32::from_rgba_unmultiplied(200, 200, 200, 100),
             None,
         )
     ];
-
-    let amplifiers = vec![
-        ("Amplifier 20-500MHz", 20., 500., Amplifier::A20_500),
-        ("Amplifier 500-1000MHz", 500., 1000., Amplifier::A500_1000),
-        ("Amplifier 960-1215MHz", 960., 1215., Amplifier::A960_1215),
-        ("Amplifier 1000-2500MHz", 1000., 2500., Amplifier::A1000_2500),
-        ("Amplifier 2400-6000MHz", 2400., 6000., Amplifier::A2400_6000),
-    ];
-
-    for (label, f_start, f_end, amp) in amplifiers {
-        let color = amp.color();
-        let height = 1100.;
-        let y_max = if label == "Amplifier 960-1215MHz" { height + 25. } else { height };
-        let label_y = if label == "Amplifier 960-1215MHz" { height + 50. } else { height - 50. };
-
-        zones.push(BackgroundZone::new(
-            BackgroundZoneKind::Amplifier(label),
-            vec![[f_start, 0.], [f_end, 0.], [f_end, y_max], [f_start, y_max]],
-            Stroke::new(1., color),
-            Color32::TRANSPARENT,
-            Some((label.replace(" ", "\n"), [(f_start + f_end) / 2., label_y], color)),
-        ));
-    }
-
+    zones.extend(definitions.iter().filter(|d| d.points.len() >= 3).map(ZoneDefinition::to_zone));
     zones
 }
@@ -0,0 +1,141 @@
+//! Module de suivi des écarts par rapport à un plan de référence.
+//!
+//! « Définir comme référence » fige une copie du plan courant. Les tâches sont
+//! ensuite appariées à la référence par leur clé ([`Task::key`]) : les tâches
+//! déplacées sont accompagnées d’un contour fantôme à leur position d’origine, les
+//! nouvelles tâches d’un badge « + », et le panneau latéral liste tous les écarts.
+
+use crate::tools::task::Task;
+
+/// Nature d’un écart par rapport à la référence.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DeviationKind {
+    /// Tâche absente de la référence.
+    Added,
+    /// Tâche de la référence absente du plan.
+    Removed,
+    /// Tâche déplacée en fréquence ou en temps (ou changée d’amplificateur).
+    Moved,
+    /// Tâche modifiée sans changement de position (nom, attributs…).
+    Modified,
+}
+
+/// Écart d’une tâche par rapport à la référence.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Deviation {
+    /// Clé de la tâche.
+    pub key: String,
+    /// Nature de l’écart.
+    pub kind: DeviationKind,
+    /// Description de l’écart.
+    pub detail: String,
+}
+
+/// Indique si deux versions d’une tâche occupent une position différente.
+fn moved(original: &Task, task: &Task) -> bool {
+    original.freq_start != task.freq_start
+        || original.freq_end != task.freq_end
+        || original.time_start != task.time_start
+        || original.time_end != task.time_end
+        || original.amplifier != task.amplifier
+        || original.shape != task.shape
+}
+
+/// Liste les écarts du plan `tasks` par rapport au plan `baseline`.
+pub fn plan_deviations(baseline: &[Task], tasks: &[Task]) -> Vec<Deviation> {
+    let mut deviations = Vec::new();
+    for task in tasks {
+        let key = task.key().to_string();
+        match baseline.iter().find(|t| t.key() == task.key()) {
+            None => deviations.push(Deviation { key, kind: DeviationKind::Added, detail: format!("{} ajoutée", task.name) }),
+            Some(original) if moved(original, task) => deviations.push(Deviation {
+                key,
+                kind: DeviationKind::Moved,
+                detail: format!(
+                    "{} déplacée : {:.0}–{:.0} MHz, {:.0}–{:.0} ms → {:.0}–{:.0} MHz, {:.0}–{:.0} ms",
+                    task.name,
+                    original.freq_start,
+                    original.freq_end,
+                    original.time_start,
+                    original.time_end,
+                    task.freq_start,
+                    task.freq_end,
+                    task.time_start,
+                    task.time_end
+                ),
+            }),
+            Some(original) if original != task => {
+                deviations.push(Deviation { key, kind: DeviationKind::Modified, detail: format!("{} modifiée", task.name) })
+            }
+            Some(_) => {}
+        }
+    }
+    for original in baseline.iter().filter(|o| !tasks.iter().any(|t| t.key() == o.key())) {
+        deviations.push(Deviation {
+            key: original.key().to_string(),
+            kind: DeviationKind::Removed,
+            detail: format!("{} supprimée", original.name),
+        });
+    }
+    deviations
+}
+
+/// Plan de référence figé.
+#[derive(Default)]
+pub struct Baseline {
+    /// Tâches de la référence (aucune référence si `None`).
+    pub tasks: Option<Vec<Task>>,
+}
+
+impl Baseline {
+    /// Fige une copie du plan comme référence.
+    pub fn set(&mut self, tasks: &[Task]) {
+        self.tasks = Some(tasks.to_vec());
+    }
+
+    /// Supprime la référence.
+    pub fn clear(&mut self) {
+        self.tasks = None;
+    }
+
+    /// Indique si une référence est définie.
+    pub fn is_set(&self) -> bool {
+        self.tasks.is_some()
+    }
+
+    /// Version de référence d’une tâche, appariée par sa clé.
+    pub fn original(&self, task: &Task) -> Option<&Task> {
+        self.tasks.as_ref()?.iter().find(|t| t.key() == task.key())
+    }
+
+    /// Version de référence d’une tâche si elle a été déplacée depuis.
+    pub fn moved_from(&self, task: &Task) -> Option<&Task> {
+        self.original(task).filter(|original| moved(original, task))
+    }
+
+    /// Indique si la tâche a été ajoutée depuis la définition de la référence.
+    pub fn is_new(&self, task: &Task) -> bool {
+        self.is_set() && self.original(task).is_none()
+    }
+
+    /// Liste les écarts du plan par rapport à la référence.
+    pub fn deviations(&self, tasks: &[Task]) -> Vec<Deviation> {
+        match &self.tasks {
+            Some(baseline) => plan_deviations(baseline, tasks),
+            None => Vec::new(),
+        }
+    }
+
+    /// Met en forme le rapport des écarts en Markdown.
+    pub fn report_markdown(&self, tasks: &[Task]) -> String {
+        let deviations = self.deviations(tasks);
+        let mut md = String::from("# Écarts par rapport à la référence\n\n");
+        if deviations.is_empty() {
+            md.push_str("Aucun écart.\n");
+        }
+        for deviation in deviations {
+            md.push_str(&format!("- {}\n", deviation.detail));
+        }
+        md
+    }
+}
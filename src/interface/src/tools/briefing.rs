@@ -0,0 +1,334 @@
+//! Module d’export du briefing de mission au format PDF.
+//!
+//! Le document est entièrement généré en Rust (crate `pdf-writer`), sans outil
+//! externe. Il contient :
+//! - une vue d’ensemble du plan ;
+//! - une vue zoomée par amplificateur utilisé ;
+//! - sur ces vues, les annotations dessinées sur le graphe ;
+//! - le tableau des tâches ;
+//! - la liste des conflits et violations, ainsi que les statistiques du plan.
+
+use std::fs;
+use std::path::Path;
+
+use egui::Color32;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::constraints::{check_duty_cycles, find_conflicts};
+use crate::tools::markup::{Markup, MarkupShape};
+use crate::tools::report::plan_stats;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::timefmt::TimeDisplay;
+use crate::tools::utils::{freq_decimals, MAX_FREQ, MAX_TIME, MIN_FREQ};
+
+/// Largeur d’une page A4 en paysage (points).
+const PAGE_WIDTH: f32 = 842.;
+/// Hauteur d’une page A4 en paysage (points).
+const PAGE_HEIGHT: f32 = 595.;
+/// Marge autour du contenu des pages (points).
+const MARGIN: f32 = 40.;
+/// Nombre de lignes du tableau des tâches par page.
+const ROWS_PER_PAGE: usize = 30;
+
+/// Nom de ressource de la police normale.
+const FONT: Name = Name(b"F1");
+/// Nom de ressource de la police grasse.
+const FONT_BOLD: Name = Name(b"F2");
+
+/// Convertit une chaîne en octets WinAnsi pour les polices standard PDF.
+///
+/// Les caractères non représentables sont remplacés par `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '’' => 0x92,
+            '–' => 0x96,
+            '—' => 0x97,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Page en cours de construction.
+struct Page {
+    content: Content,
+}
+
+impl Page {
+    fn new() -> Self {
+        Self { content: Content::new() }
+    }
+
+    /// Écrit une ligne de texte à la position `(x, y)` (origine en bas à gauche).
+    fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, text: &str) {
+        self.content.begin_text();
+        self.content.set_font(if bold { FONT_BOLD } else { FONT }, size);
+        self.content.next_line(x, y);
+        self.content.show(Str(&win_ansi(text)));
+        self.content.end_text();
+    }
+
+    /// Écrit le titre de la page.
+    fn title(&mut self, text: &str) {
+        self.text(MARGIN, PAGE_HEIGHT - MARGIN, 18., true, text);
+    }
+
+    fn set_fill(&mut self, color: Color32) {
+        let [r, g, b, _] = color.to_normalized_gamma_f32();
+        self.content.set_fill_rgb(r, g, b);
+    }
+
+    fn set_stroke(&mut self, color: Color32) {
+        let [r, g, b, _] = color.to_normalized_gamma_f32();
+        self.content.set_stroke_rgb(r, g, b);
+    }
+
+    /// Dessine un graphe fréquence/temps des tâches et des annotations sur la plage `[f_min, f_max]`.
+    fn plot(&mut self, tasks: &[Task], markups: &[Markup], f_min: f64, f_max: f64, area: Rect, display: &TimeDisplay) {
+        let width = area.x2 - area.x1;
+        let height = area.y2 - area.y1;
+        let to_x = |f: f64| area.x1 + ((f.clamp(f_min, f_max) - f_min) / (f_max - f_min)) as f32 * width;
+        let to_y = |t: f64| area.y1 + (t.clamp(0., MAX_TIME) / MAX_TIME) as f32 * height;
+
+        // Zones des amplificateurs
+        self.content.set_line_width(0.8);
+        for amp in Amplifier::ALL {
+            let (start, end) = amp.band();
+            if end <= f_min || start >= f_max {
+                continue;
+            }
+            self.set_stroke(amplifier_color(amp));
+            self.content.rect(to_x(start), area.y1, to_x(end) - to_x(start), height);
+            self.content.stroke();
+        }
+
+        // Tâches
+        for task in tasks {
+            if task.freq_end <= f_min || task.freq_start >= f_max {
+                continue;
+            }
+            self.set_fill(task.color());
+            if let Some(shape) = task.polygon() {
+                for (i, [f, t]) in shape.iter().enumerate() {
+                    if i == 0 {
+                        self.content.move_to(to_x(*f), to_y(*t));
+                    } else {
+                        self.content.line_to(to_x(*f), to_y(*t));
+                    }
+                }
+                self.content.close_path();
+            } else {
+                let (x0, x1) = (to_x(task.freq_start), to_x(task.freq_end));
+                let (y0, y1) = (to_y(task.time_start), to_y(task.time_end));
+                self.content.rect(x0, y0, (x1 - x0).max(0.5), (y1 - y0).max(0.5));
+            }
+            self.content.fill_nonzero();
+        }
+
+        // Annotations, limitées à la zone de tracé
+        self.content.save_state();
+        self.content.rect(area.x1, area.y1, width, height);
+        self.content.clip_nonzero();
+        self.content.end_path();
+        self.content.set_line_width(1.5);
+        let to_page = |[f, t]: [f64; 2]| {
+            (area.x1 + ((f - f_min) / (f_max - f_min)) as f32 * width, area.y1 + (t / MAX_TIME) as f32 * height)
+        };
+        for markup in markups {
+            self.set_stroke(markup.color);
+            self.markup(&markup.shape, to_page);
+        }
+        self.content.restore_state();
+
+        // Cadre et graduations
+        self.set_stroke(Color32::BLACK);
+        self.content.rect(area.x1, area.y1, width, height);
+        self.content.stroke();
+        self.set_fill(Color32::BLACK);
+        for i in 0..=4 {
+            let ratio = i as f64 / 4.;
+            let f = f_min + (f_max - f_min) * ratio;
+            self.text(to_x(f) - 15., area.y1 - 14., 8., false, &format!("{:.0} MHz", f));
+            let t = MAX_TIME * ratio;
+            self.text(area.x1 - 34., to_y(t) - 3., 8., false, &display.format(t));
+        }
+    }
+
+    /// Trace une annotation, `to_page` convertissant un point `[fréquence, temps]` en coordonnées de la page.
+    fn markup(&mut self, shape: &MarkupShape, to_page: impl Fn([f64; 2]) -> (f32, f32)) {
+        match shape {
+            MarkupShape::Pen(points) => {
+                for (i, point) in points.iter().enumerate() {
+                    let (x, y) = to_page(*point);
+                    if i == 0 {
+                        self.content.move_to(x, y);
+                    } else {
+                        self.content.line_to(x, y);
+                    }
+                }
+            }
+            MarkupShape::Arrow { from, to } => {
+                let ((x0, y0), (x1, y1)) = (to_page(*from), to_page(*to));
+                self.content.move_to(x0, y0);
+                self.content.line_to(x1, y1);
+                // Pointe : deux traits de 8 points à ±25° de la direction opposée
+                let angle = (y0 - y1).atan2(x0 - x1);
+                for side in [-1., 1.] {
+                    let a = angle + side * 25f32.to_radians();
+                    self.content.move_to(x1, y1);
+                    self.content.line_to(x1 + 8. * a.cos(), y1 + 8. * a.sin());
+                }
+            }
+            MarkupShape::Circle { center, edge } => {
+                let ((cx, cy), (ex, ey)) = (to_page(*center), to_page(*edge));
+                let r = (ex - cx).hypot(ey - cy);
+                // Cercle approché par quatre courbes de Bézier
+                let k = 0.552_284_8 * r;
+                self.content.move_to(cx + r, cy);
+                self.content.cubic_to(cx + r, cy + k, cx + k, cy + r, cx, cy + r);
+                self.content.cubic_to(cx - k, cy + r, cx - r, cy + k, cx - r, cy);
+                self.content.cubic_to(cx - r, cy - k, cx - k, cy - r, cx, cy - r);
+                self.content.cubic_to(cx + k, cy - r, cx + r, cy - k, cx + r, cy);
+            }
+        }
+        self.content.stroke();
+    }
+}
+
+/// Zone de tracé standard d’une page de graphe.
+fn plot_area() -> Rect {
+    Rect::new(MARGIN + 30., MARGIN + 20., PAGE_WIDTH - MARGIN, PAGE_HEIGHT - MARGIN - 30.)
+}
+
+/// Construit les pages du briefing.
+fn build_pages(tasks: &[Task], markups: &[Markup], display: &TimeDisplay) -> Vec<Page> {
+    let mut pages = Vec::new();
+
+    // Vue d’ensemble
+    let mut overview = Page::new();
+    overview.title("Briefing - vue d’ensemble du plan de brouillage");
+    overview.plot(tasks, markups, MIN_FREQ, MAX_FREQ, plot_area(), display);
+    pages.push(overview);
+
+    // Vues zoomées par amplificateur utilisé
+    for amp in Amplifier::ALL {
+        if !tasks.iter().any(|t| t.amplifier == amp) {
+            continue;
+        }
+        let (start, end) = amp.band();
+        let mut page = Page::new();
+        page.title(&format!("Amplificateur {:?} ({:.0}–{:.0} MHz)", amp, start, end));
+        page.plot(tasks, markups, start, end, plot_area(), display);
+        pages.push(page);
+    }
+
+    // Tableau des tâches
+    let columns = [MARGIN, MARGIN + 220., MARGIN + 340., MARGIN + 440., MARGIN + 540., MARGIN + 640.];
+    let headers = ["Nom", "Amplificateur", "fmin (MHz)", "fmax (MHz)", "tmin", "tmax"];
+    for (chunk_index, chunk) in tasks.chunks(ROWS_PER_PAGE).enumerate() {
+        let mut page = Page::new();
+        page.title(&format!("Tâches ({}/{})", chunk_index + 1, tasks.len().div_ceil(ROWS_PER_PAGE)));
+        let mut y = PAGE_HEIGHT - MARGIN - 30.;
+        for (x, header) in columns.iter().zip(headers) {
+            page.text(*x, y, 10., true, header);
+        }
+        for task in chunk {
+            y -= 15.;
+            let cells = [
+                task.name.clone(),
+                format!("{:?}", task.amplifier),
+                format!("{:.*}", freq_decimals(task.freq_end - task.freq_start).max(1), task.freq_start),
+                format!("{:.*}", freq_decimals(task.freq_end - task.freq_start).max(1), task.freq_end),
+                display.format(task.time_start),
+                display.format(task.time_end),
+            ];
+            for (x, cell) in columns.iter().zip(cells) {
+                page.text(*x, y, 9., false, &cell);
+            }
+        }
+        pages.push(page);
+    }
+
+    // Conflits, violations et statistiques
+    let mut page = Page::new();
+    page.title("Conflits et statistiques");
+    let mut lines = Vec::new();
+    let conflicts = find_conflicts(tasks);
+    lines.push((true, format!("Conflits ({})", conflicts.len())));
+    for c in &conflicts {
+        lines.push((false, format!("{} / {}", tasks[c.first].name, tasks[c.second].name)));
+    }
+    let violations = check_duty_cycles(tasks);
+    lines.push((true, format!("Violations de cycle de service ({})", violations.len())));
+    for v in &violations {
+        lines.push((false, format!("{:?} - {} : {:.0} ms continus", v.amplifier, tasks[v.task_index].name, v.on_time)));
+    }
+    let stats = plan_stats(tasks);
+    lines.push((true, "Statistiques".into()));
+    lines.push((false, format!("Tâches : {}", stats.task_count)));
+    lines.push((false, format!("Couverture temporelle : {:.0} ms", stats.time_coverage_ms)));
+    lines.push((false, format!("Couverture fréquentielle : {:.0} MHz", stats.freq_coverage_mhz)));
+    for load in &stats.amplifiers {
+        lines.push((false, format!(
+            "{:?} : {} tâches, occupation {:.0} %",
+            load.amplifier, load.task_count, load.utilization * 100.
+        )));
+    }
+    let mut y = PAGE_HEIGHT - MARGIN - 30.;
+    for (bold, line) in lines {
+        if y < MARGIN {
+            pages.push(std::mem::replace(&mut page, Page::new()));
+            page.title("Conflits et statistiques (suite)");
+            y = PAGE_HEIGHT - MARGIN - 30.;
+        }
+        page.text(MARGIN, y, if bold { 12. } else { 10. }, bold, &line);
+        y -= if bold { 18. } else { 14. };
+    }
+    pages.push(page);
+
+    pages
+}
+
+/// Exporte le briefing du plan dans un fichier PDF.
+///
+/// Les temps sont affichés selon `display` (heure absolue dans le fuseau choisi
+/// si l’heure H est connue) ; les annotations `markups` sont dessinées sur les graphes.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier ne peut pas être écrit.
+pub fn export_briefing(path: &Path, tasks: &[Task], markups: &[Markup], display: &TimeDisplay) -> Result<(), String> {
+    let pages = build_pages(tasks, markups, display);
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let font_bold_id = Ref::new(4);
+    let page_ids: Vec<Ref> = (0..pages.len()).map(|i| Ref::new(5 + 2 * i as i32)).collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids(page_ids.iter().copied()).count(pages.len() as i32);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(font_bold_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    for (page, page_id) in pages.into_iter().zip(&page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut writer = pdf.page(*page_id);
+        writer.media_box(Rect::new(0., 0., PAGE_WIDTH, PAGE_HEIGHT));
+        writer.parent(page_tree_id);
+        writer.contents(content_id);
+        writer.resources().fonts().pair(FONT, font_id).pair(FONT_BOLD, font_bold_id);
+        drop(writer);
+        pdf.stream(content_id, &page.content.finish());
+    }
+
+    fs::write(path, pdf.finish())
+        .map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))
+}
@@ -0,0 +1,267 @@
+//! Module des sous-commandes en ligne de commande exécutées sans interface graphique.
+//!
+//! Ces sous-commandes sont destinées aux chaînes de traitement automatisées
+//! (revue de plans, intégration continue) et s’exécutent avant l’ouverture de la fenêtre.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::tools::diagnostics::{Diagnostics, TransportState};
+use crate::tools::exclusion::{load_exclusions, ExclusionMask};
+use crate::tools::headless::{run_session, self_test};
+use crate::tools::layers::PlanLayer;
+use crate::tools::mirror::{Mirror, MirrorRole};
+use crate::tools::plan::{format_plan, load_plan, PlanFormat};
+use crate::tools::presets::{load_preset_config, PresetRole};
+use crate::tools::protocol::schema_json;
+use crate::tools::query::{Query, QueryContext};
+use crate::tools::report::compare_plans;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::schedule::{schedule, DEFAULT_CAPACITY};
+use crate::tools::storage::{MemoryStore, SqliteStore, TaskStore};
+use crate::tools::transport::{load_transport_config, TransportConfig};
+use crate::tools::wall::WallTile;
+
+/// Exécute la sous-commande demandée sur la ligne de commande, s’il y en a une.
+///
+/// Retourne `Some(code)` avec le code de sortie du processus si une sous-commande
+/// a été traitée, ou `None` si l’application graphique doit être lancée.
+pub fn run_headless(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("compare") => Some(report_errors(compare(&args[1..]))),
+        Some("query") => Some(report_errors(query(&args[1..]))),
+        Some("schedule") => Some(report_errors(schedule_plan(&args[1..]))),
+        Some("--headless") => Some(report_errors(run_session(io::stdin().lock(), &mut io::stdout().lock()))),
+        Some("--self-test") => Some(report_errors(self_test())),
+        Some("--check") => Some(report_errors(check(&args[1..]))),
+        Some("--dump-schema") => {
+            println!("{}", schema_json());
+            Some(0)
+        }
+        _ => None,
+    }
+}
+
+/// Ouvre le stockage du plan : la base SQLite passée par `--db <fichier>`, ou un
+/// stockage en mémoire si l’option est absente.
+pub fn open_store(args: &[String]) -> Result<Box<dyn TaskStore>, String> {
+    match args.iter().position(|a| a == "--db") {
+        Some(i) => {
+            let path = args.get(i + 1).ok_or("usage : --db <fichier>")?;
+            Ok(Box::new(SqliteStore::open(Path::new(path))?))
+        }
+        None => Ok(Box::new(MemoryStore::default())),
+    }
+}
+
+/// Configuration du transport réseau : le fichier de configuration, dont
+/// l’adresse d’écoute est remplacée par `--listen <adresse>` si l’option est présente.
+pub fn transport_config(args: &[String]) -> Result<TransportConfig, String> {
+    let mut config = load_transport_config();
+    if let Some(i) = args.iter().position(|a| a == "--listen") {
+        config.listen = Some(args.get(i + 1).ok_or("usage : --listen <adresse>")?.clone());
+    }
+    Ok(config)
+}
+
+/// Mode miroir : instance principale (`--mirror`) ou suiveuse (`--follow <adresse>`
+/// [`--token <secret>`]), avec synchronisation de la vue si `--mirror-view` est présente.
+pub fn mirror(args: &[String]) -> Result<Mirror, String> {
+    let value = |flag: &str| -> Result<Option<String>, String> {
+        match args.iter().position(|a| a == flag) {
+            Some(i) => Ok(Some(args.get(i + 1).ok_or(format!("usage : {} <valeur>", flag))?.clone())),
+            None => Ok(None),
+        }
+    };
+    let primary = args.iter().any(|a| a == "--mirror");
+    let role = match (primary, value("--follow")?) {
+        (true, Some(_)) => return Err("--mirror et --follow sont incompatibles".into()),
+        (true, None) => MirrorRole::Primary,
+        (false, Some(address)) => MirrorRole::Follower { address, token: value("--token")? },
+        (false, None) => MirrorRole::Standalone,
+    };
+    Ok(Mirror::new(role, args.iter().any(|a| a == "--mirror-view")))
+}
+
+/// Plage de fréquences du mur d’images (`--band <amplificateur>|<fmin>:<fmax>`,
+/// `--hide-controls`), ou `None` si l’option est absente.
+pub fn wall_tile(args: &[String]) -> Result<Option<WallTile>, String> {
+    let hide_controls = args.iter().any(|a| a == "--hide-controls");
+    match args.iter().position(|a| a == "--band") {
+        Some(i) => {
+            let band = args.get(i + 1).ok_or("usage : --band <amplificateur>|<fmin>:<fmax>")?;
+            WallTile::parse(band, hide_controls).map(Some)
+        }
+        None if hide_controls => Err("--hide-controls nécessite --band <amplificateur>|<fmin>:<fmax>".into()),
+        None => Ok(None),
+    }
+}
+
+/// Profil d’affichage choisi au lancement (`--profile <nom>`).
+pub fn profile_name(args: &[String]) -> Result<Option<String>, String> {
+    match args.iter().position(|a| a == "--profile") {
+        Some(i) => args.get(i + 1).map(|name| Some(name.clone())).ok_or_else(|| "usage : --profile <nom>".into()),
+        None => Ok(None),
+    }
+}
+
+/// Fichier de plan à surveiller (`--watch <plan>`).
+pub fn watch_path(args: &[String]) -> Result<Option<PathBuf>, String> {
+    match args.iter().position(|a| a == "--watch") {
+        Some(i) => args.get(i + 1).map(|path| Some(PathBuf::from(path))).ok_or_else(|| "usage : --watch <plan>".into()),
+        None => Ok(None),
+    }
+}
+
+/// Tubes nommés des couches de plan (`--planned`, `--executed`, `--observed <tube>`).
+pub fn layer_pipes(args: &[String]) -> Result<Vec<(PlanLayer, PathBuf)>, String> {
+    let mut pipes = Vec::new();
+    for layer in PlanLayer::ALL {
+        if let Some(i) = args.iter().position(|a| a == layer.flag()) {
+            let path = args.get(i + 1).ok_or_else(|| format!("usage : {} <tube>", layer.flag()))?;
+            pipes.push((layer, PathBuf::from(path)));
+        }
+    }
+    Ok(pipes)
+}
+
+/// Affiche l’éventuelle erreur d’une sous-commande et retourne le code de sortie.
+fn report_errors(result: Result<(), String>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Erreur : {}", e);
+            1
+        }
+    }
+}
+
+/// Sous-commande `--check [--listen <adresse>]` : diagnostics de démarrage sans
+/// fenêtre, rapport sur la sortie standard.
+fn check(args: &[String]) -> Result<(), String> {
+    let diagnostics = Diagnostics::run(&transport_config(args)?, TransportState::NotStarted, None);
+    println!("{}", diagnostics.report());
+    if diagnostics.passed() {
+        Ok(())
+    } else {
+        Err("diagnostics en échec".into())
+    }
+}
+
+/// Sous-commande `compare <plan_a> <plan_b> [--json <fichier>] [--markdown <fichier>]`.
+///
+/// Les formats non redirigés vers un fichier sont écrits sur la sortie standard.
+fn compare(args: &[String]) -> Result<(), String> {
+    let usage = "usage : compare <plan_a> <plan_b> [--json <fichier>] [--markdown <fichier>]";
+    let (Some(path_a), Some(path_b)) = (args.first(), args.get(1)) else {
+        return Err(usage.into());
+    };
+
+    let mut json_out = None;
+    let mut markdown_out = None;
+    let mut rest = args[2..].iter();
+    while let Some(flag) = rest.next() {
+        match flag.as_str() {
+            "--json" => json_out = Some(rest.next().ok_or(usage)?),
+            "--markdown" => markdown_out = Some(rest.next().ok_or(usage)?),
+            _ => return Err(usage.into()),
+        }
+    }
+
+    let report = compare_plans(&load_plan(Path::new(path_a))?, &load_plan(Path::new(path_b))?);
+
+    for (content, out) in [(report.to_json(), json_out), (report.to_markdown(), markdown_out)] {
+        match out {
+            Some(path) => fs::write(path, content)
+                .map_err(|e| format!("Écriture de {} impossible : {}", path, e))?,
+            None => println!("{}", content),
+        }
+    }
+    Ok(())
+}
+
+/// Sous-commande `query <base> [--from <ms>] [--to <ms>] [--where <expression>]`.
+///
+/// Écrit en JSON sur la sortie standard les tâches de la base actives dans l’intervalle
+/// et satisfaisant l’expression de filtrage (garde Rx par défaut pour `overlaps(rx)`).
+fn query(args: &[String]) -> Result<(), String> {
+    let usage = "usage : query <base> [--from <ms>] [--to <ms>] [--where <expression>]";
+    let Some(path) = args.first() else {
+        return Err(usage.into());
+    };
+
+    let (mut start, mut end) = (f64::NEG_INFINITY, f64::INFINITY);
+    let mut filter = None;
+    let mut rest = args[1..].iter();
+    while let Some(flag) = rest.next() {
+        let value = rest.next().ok_or(usage)?;
+        let number = || value.parse::<f64>().map_err(|_| usage.to_string());
+        match flag.as_str() {
+            "--from" => start = number()?,
+            "--to" => end = number()?,
+            "--where" => filter = Some(Query::parse(value)?),
+            _ => return Err(usage.into()),
+        }
+    }
+
+    let mut tasks = SqliteStore::open(Path::new(path))?.tasks_between(start, end)?;
+    if let Some(filter) = filter {
+        let ctx = QueryContext { rx_guard: RxGuard::default() };
+        tasks.retain(|t| filter.matches(t, &ctx));
+    }
+    println!("{}", format_plan(&tasks, PlanFormat::Json)?);
+    Ok(())
+}
+
+/// Sous-commande `schedule <plan> [--rx-guard <ms>] [--capacity <n>]
+/// [--exclusions <fichier>] [--protect <bande>]… [--out <fichier>]`.
+///
+/// Place automatiquement les tâches du plan (seules leurs bandes et durées sont
+/// prises en compte) et écrit le plan obtenu en JSON, sur la sortie standard si
+/// `--out` est absente. Comme dans l’interface, les tâches évitent les masques
+/// d’exclusion (liste JSON de masques lue depuis `--exclusions`) et les bandes
+/// de référence protégées (`--protect <nom>`, répétable, parmi les bandes de
+/// [`crate::tools::presets::PRESETS_PATH`]).
+fn schedule_plan(args: &[String]) -> Result<(), String> {
+    let usage = "usage : schedule <plan> [--rx-guard <ms>] [--capacity <n>] [--exclusions <fichier>] [--protect <bande>]… [--out <fichier>]";
+    let Some(path) = args.first() else {
+        return Err(usage.into());
+    };
+
+    let mut rx_guard = RxGuard::default();
+    let mut capacity = DEFAULT_CAPACITY;
+    let mut out = None;
+    let mut masks: Vec<ExclusionMask> = Vec::new();
+    let mut presets = load_preset_config();
+    let mut rest = args[1..].iter();
+    while let Some(flag) = rest.next() {
+        let value = rest.next().ok_or(usage)?;
+        match flag.as_str() {
+            "--rx-guard" => rx_guard.duration = value.parse().map_err(|_| usage.to_string())?,
+            "--capacity" => capacity = value.parse().map_err(|_| usage.to_string())?,
+            "--out" => out = Some(value),
+            "--exclusions" => masks.extend(load_exclusions(Path::new(value))?),
+            "--protect" => {
+                let i = presets
+                    .presets
+                    .iter()
+                    .position(|p| p.name == *value)
+                    .ok_or_else(|| format!("Bande de référence « {} » inconnue", value))?;
+                presets.roles[i] = PresetRole::Protected;
+            }
+            _ => return Err(usage.into()),
+        }
+    }
+    masks.extend(presets.protected_masks());
+
+    let tasks = schedule(&load_plan(Path::new(path))?, &rx_guard, &masks, capacity)?;
+    let content = format_plan(&tasks, PlanFormat::Json)?;
+    match out {
+        Some(path) => fs::write(path, content).map_err(|e| format!("Écriture de {} impossible : {}", path, e)),
+        None => {
+            println!("{}", content);
+            Ok(())
+        }
+    }
+}
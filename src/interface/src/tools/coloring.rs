@@ -0,0 +1,147 @@
+//! Module de coloration des tâches selon un attribut choisi.
+//!
+//! Par défaut les tâches sont colorées selon leur amplificateur ; l’utilisateur
+//! peut choisir un autre attribut (groupe, plateforme, antenne, priorité, statut, puissance). Les
+//! attributs catégoriels reçoivent une palette de teintes distinctes, les
+//! attributs numériques un dégradé du bleu (minimum) au rouge (maximum).
+//! Une légende est générée automatiquement dans les deux cas.
+
+use egui::Color32;
+use egui::ecolor::Hsva;
+
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::task::{Amplifier, Task};
+
+/// Couleur des tâches dont l’attribut n’est pas renseigné.
+const UNDEFINED_COLOR: Color32 = Color32::from_gray(140);
+
+/// Attribut utilisé pour colorer les tâches.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorBy {
+    #[default]
+    Amplifier,
+    Group,
+    Platform,
+    Antenna,
+    Priority,
+    Status,
+    Power,
+}
+
+impl ColorBy {
+    /// Liste de tous les attributs de coloration.
+    pub const ALL: [ColorBy; 7] = [
+        ColorBy::Amplifier,
+        ColorBy::Group,
+        ColorBy::Platform,
+        ColorBy::Antenna,
+        ColorBy::Priority,
+        ColorBy::Status,
+        ColorBy::Power,
+    ];
+
+    /// Libellé affiché dans le sélecteur.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorBy::Amplifier => "Amplificateur",
+            ColorBy::Group => "Groupe",
+            ColorBy::Platform => "Plateforme",
+            ColorBy::Antenna => "Antenne",
+            ColorBy::Priority => "Priorité",
+            ColorBy::Status => "Statut",
+            ColorBy::Power => "Puissance",
+        }
+    }
+}
+
+/// Couleurs calculées pour chaque tâche, accompagnées de leur légende.
+#[derive(Default)]
+pub struct ColorMap {
+    /// Couleur de chaque tâche, dans l’ordre de la liste des tâches.
+    pub colors: Vec<Color32>,
+    /// Entrées de légende `(libellé, couleur)`.
+    pub legend: Vec<(String, Color32)>,
+}
+
+/// Génère `n` couleurs de teintes bien distinctes (angle d’or).
+pub fn palette(n: usize) -> Vec<Color32> {
+    (0..n)
+        .map(|i| Hsva::new((i as f32 * 0.618_034).fract(), 0.65, 0.85, 1.).into())
+        .collect()
+}
+
+/// Interpole linéairement du bleu (0) au rouge (1).
+fn gradient(ratio: f64) -> Color32 {
+    let r = ratio.clamp(0., 1.) as f32;
+    Color32::from_rgb((40. + 215. * r) as u8, 80, (255. - 215. * r) as u8)
+}
+
+/// Colore des valeurs catégorielles, dans l’ordre d’apparition.
+fn categorical(values: Vec<Option<String>>) -> ColorMap {
+    let mut categories: Vec<String> = Vec::new();
+    for value in values.iter().flatten() {
+        if !categories.contains(value) {
+            categories.push(value.clone());
+        }
+    }
+    let colors_by_category = palette(categories.len());
+
+    let colors = values
+        .iter()
+        .map(|v| match v {
+            Some(v) => colors_by_category[categories.iter().position(|c| c == v).unwrap_or(0)],
+            None => UNDEFINED_COLOR,
+        })
+        .collect();
+    let mut legend: Vec<(String, Color32)> = categories.into_iter().zip(colors_by_category).collect();
+    if values.iter().any(Option::is_none) {
+        legend.push(("Non défini".into(), UNDEFINED_COLOR));
+    }
+    ColorMap { colors, legend }
+}
+
+/// Colore des valeurs numériques selon un dégradé entre leur minimum et leur maximum.
+fn numeric(values: Vec<Option<f64>>, unit: &str) -> ColorMap {
+    let defined = values.iter().flatten();
+    let min = defined.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = defined.copied().fold(f64::NEG_INFINITY, f64::max);
+    let ratio = |v: f64| if max > min { (v - min) / (max - min) } else { 0.5 };
+
+    let colors = values
+        .iter()
+        .map(|v| v.map_or(UNDEFINED_COLOR, |v| gradient(ratio(v))))
+        .collect();
+    let mut legend = Vec::new();
+    if min.is_finite() {
+        legend.push((format!("{}{}", min, unit), gradient(ratio(min))));
+        if max > min {
+            let mid = (min + max) / 2.;
+            legend.push((format!("{}{}", mid, unit), gradient(ratio(mid))));
+            legend.push((format!("{}{}", max, unit), gradient(ratio(max))));
+        }
+    }
+    if values.iter().any(Option::is_none) {
+        legend.push(("Non défini".into(), UNDEFINED_COLOR));
+    }
+    ColorMap { colors, legend }
+}
+
+/// Calcule la couleur de chaque tâche selon l’attribut choisi, ainsi que la légende.
+pub fn compute_colors(tasks: &[Task], by: ColorBy) -> ColorMap {
+    match by {
+        ColorBy::Amplifier => ColorMap {
+            colors: tasks.iter().map(Task::color).collect(),
+            legend: Amplifier::ALL
+                .iter()
+                .filter(|a| tasks.iter().any(|t| t.amplifier == **a))
+                .map(|a| (format!("{:?}", a), amplifier_color(*a)))
+                .collect(),
+        },
+        ColorBy::Group => categorical(tasks.iter().map(|t| t.group.clone()).collect()),
+        ColorBy::Platform => categorical(tasks.iter().map(|t| t.platform.clone()).collect()),
+        ColorBy::Antenna => categorical(tasks.iter().map(|t| t.antenna.clone()).collect()),
+        ColorBy::Status => categorical(tasks.iter().map(|t| t.status.clone()).collect()),
+        ColorBy::Priority => numeric(tasks.iter().map(|t| t.priority.map(f64::from)).collect(), ""),
+        ColorBy::Power => numeric(tasks.iter().map(|t| t.power).collect(), " W"),
+    }
+}
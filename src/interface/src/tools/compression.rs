@@ -0,0 +1,95 @@
+//! Module de compression des messages volumineux.
+//!
+//! Un plan complet de plusieurs dizaines de milliers de tâches représente
+//! plusieurs mégaoctets de JSON. L’émetteur propose ses algorithmes lors de la
+//! poignée de main (`{"hello": {"compression": ["zstd", "gzip"]}}`) ; l’interface
+//! retient le premier qu’elle prend en charge et l’annonce par l’événement
+//! `welcome`. L’émetteur peut ensuite envoyer ses plans sous la forme
+//! `{"compressed": "gzip", "data": "<base64>"}`.
+//!
+//! gzip est toujours disponible ; zstd nécessite la fonctionnalité `zstd`.
+//! La taille décompressée est bornée par [`MAX_DECOMPRESSED_BYTES`].
+
+use std::io::Read;
+
+use base64::Engine;
+
+/// Taille maximale d’un message décompressé, en octets.
+pub const MAX_DECOMPRESSED_BYTES: usize = 64 << 20;
+
+/// Algorithme de compression.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Compression {
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// Algorithmes pris en charge, par ordre de préférence.
+    pub const SUPPORTED: &'static [Compression] = &[
+        #[cfg(feature = "zstd")]
+        Compression::Zstd,
+        Compression::Gzip,
+    ];
+
+    /// Nom de l’algorithme dans le protocole.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    /// Algorithme désigné par son nom, s’il est pris en charge.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::SUPPORTED.iter().copied().find(|c| c.name() == name)
+    }
+
+    /// Premier algorithme proposé par l’émetteur que l’interface prend en charge.
+    pub fn negotiate(offered: &[String]) -> Option<Self> {
+        offered.iter().find_map(|name| Self::from_name(name))
+    }
+
+    /// Décode (base64) et décompresse des données, en bornant la taille du résultat.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si les données sont invalides ou dépassent
+    /// [`MAX_DECOMPRESSED_BYTES`] une fois décompressées.
+    pub fn decompress(&self, data: &str) -> Result<String, String> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("Données {} invalides : {}", self.name(), e))?;
+        let mut output = Vec::new();
+        let limit = MAX_DECOMPRESSED_BYTES as u64 + 1;
+        let read = match self {
+            Compression::Gzip => flate2::read::GzDecoder::new(bytes.as_slice()).take(limit).read_to_end(&mut output),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::stream::read::Decoder::new(bytes.as_slice())
+                .and_then(|decoder| decoder.take(limit).read_to_end(&mut output)),
+        };
+        read.map_err(|e| format!("Décompression {} impossible : {}", self.name(), e))?;
+        if output.len() > MAX_DECOMPRESSED_BYTES {
+            return Err(format!("Message décompressé de plus de {} octets", MAX_DECOMPRESSED_BYTES));
+        }
+        String::from_utf8(output).map_err(|_| "Message décompressé : UTF-8 invalide".to_string())
+    }
+
+    /// Compresse un message et l’encode en base64.
+    pub fn compress(&self, json: &str) -> String {
+        let bytes = match self {
+            Compression::Gzip => {
+                let mut encoder = flate2::read::GzEncoder::new(json.as_bytes(), flate2::Compression::default());
+                let mut bytes = Vec::new();
+                // La lecture depuis une tranche en mémoire ne peut pas échouer
+                let _ = encoder.read_to_end(&mut bytes);
+                bytes
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::encode_all(json.as_bytes(), 0).unwrap_or_default(),
+        };
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+}
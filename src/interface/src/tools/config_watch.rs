@@ -0,0 +1,182 @@
+//! Module du rechargement à chaud de la configuration.
+//!
+//! Les fichiers de configuration des amplificateurs (couleurs et cycles de
+//! service), des zones de fond, des profils d’affichage et des règles d’alerte
+//! sont surveillés par le système de fichiers pendant l’exécution
+//! ([`spawn_file_watcher`]) : chaque modification demande un rafraîchissement de
+//! l’interface, et tout fichier modifié est alors validé puis appliqué sans
+//! redémarrer l’interface.
+//! Un fichier invalide est refusé et la dernière configuration valide reste en
+//! place ; un fichier supprimé rétablit la configuration par défaut.
+//!
+//! Chaque rechargement, réussi ou refusé, est inscrit au journal de la
+//! configuration affiché dans le panneau latéral.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::SystemTime;
+
+use egui::Color32;
+
+use crate::tools::alerts::ALERTS_PATH;
+use crate::tools::amplifiers::AMPLIFIERS_PATH;
+use crate::tools::background::ZONES_PATH;
+use crate::tools::profile::PROFILES_PATH;
+use crate::tools::watch::spawn_file_watcher;
+
+/// Nombre maximal d’entrées conservées dans le journal.
+const MAX_LOG_ENTRIES: usize = 50;
+
+/// Fichier de configuration rechargeable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigFile {
+    /// Couleurs et cycles de service des amplificateurs.
+    Amplifiers,
+    /// Zones de fond.
+    Zones,
+    /// Profils d’affichage (thèmes, couches, droits).
+    Profiles,
+    /// Règles d’alerte.
+    Alerts,
+}
+
+impl ConfigFile {
+    /// Fichiers surveillés par [`ConfigWatcher`].
+    pub const WATCHED: [ConfigFile; 4] = [ConfigFile::Amplifiers, ConfigFile::Zones, ConfigFile::Profiles, ConfigFile::Alerts];
+
+    /// Chemin du fichier.
+    pub fn path(self) -> &'static Path {
+        Path::new(match self {
+            ConfigFile::Amplifiers => AMPLIFIERS_PATH,
+            ConfigFile::Zones => ZONES_PATH,
+            ConfigFile::Profiles => PROFILES_PATH,
+            ConfigFile::Alerts => ALERTS_PATH,
+        })
+    }
+}
+
+/// Entrée du journal de la configuration.
+#[derive(Clone, Debug)]
+pub struct ReloadEntry {
+    /// Heure UTC du rechargement (`HH:MM:SS`).
+    pub time: String,
+    /// Fichier rechargé.
+    pub file: ConfigFile,
+    /// Description de la configuration appliquée, ou erreur de validation.
+    pub result: Result<String, String>,
+}
+
+/// Surveillance des fichiers de configuration et journal des rechargements.
+pub struct ConfigWatcher {
+    /// Indique si le rechargement automatique est actif.
+    pub enabled: bool,
+    /// Date de modification de chaque fichier surveillé lors du dernier chargement.
+    modified: Vec<(ConfigFile, Option<SystemTime>)>,
+    /// Fichiers signalés par la surveillance, une fois démarrée.
+    notified: Option<Receiver<ConfigFile>>,
+    /// Rechargements, du plus ancien au plus récent.
+    pub log: VecDeque<ReloadEntry>,
+}
+
+impl Default for ConfigWatcher {
+    /// Surveillance partant des fichiers tels que chargés au démarrage.
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            modified: ConfigFile::WATCHED.iter().map(|&file| (file, modification_date(file.path()))).collect(),
+            notified: None,
+            log: VecDeque::new(),
+        }
+    }
+}
+
+/// Date de modification du fichier, ou `None` s’il est absent.
+fn modification_date(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl ConfigWatcher {
+    /// Démarre la surveillance des fichiers ; chaque modification demande un
+    /// rafraîchissement de `ctx`.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si la surveillance ne peut pas être mise en place.
+    pub fn start(&mut self, ctx: &egui::Context) -> Result<(), String> {
+        let (tx, rx) = channel();
+        let ctx = ctx.clone();
+        let paths = ConfigFile::WATCHED.iter().map(|file| file.path().to_path_buf()).collect();
+        spawn_file_watcher(paths, move |path| {
+            if let Some(file) = ConfigFile::WATCHED.into_iter().find(|file| file.path() == path) {
+                let _ = tx.send(file);
+                ctx.request_repaint();
+            }
+        })?;
+        self.notified = Some(rx);
+        Ok(())
+    }
+
+    /// Retourne les fichiers modifiés, créés ou supprimés depuis leur dernier
+    /// chargement, parmi ceux signalés par la surveillance.
+    pub fn changed(&mut self) -> Vec<ConfigFile> {
+        let Some(notified) = self.notified.as_ref().filter(|_| self.enabled) else {
+            return vec![];
+        };
+        let notified: Vec<ConfigFile> = notified.try_iter().collect();
+        let mut changed = Vec::new();
+        for (file, modified) in self.modified.iter_mut().filter(|(file, _)| notified.contains(file)) {
+            let current = modification_date(file.path());
+            if current != *modified {
+                *modified = current;
+                changed.push(*file);
+            }
+        }
+        changed
+    }
+
+    /// Inscrit le résultat d’un rechargement au journal.
+    pub fn record(&mut self, file: ConfigFile, result: Result<String, String>) {
+        match &result {
+            Ok(description) => eprintln!("Configuration {} rechargée : {}", file.path().display(), description),
+            Err(e) => eprintln!("Configuration {} refusée, dernière configuration valide conservée : {}", file.path().display(), e),
+        }
+        if self.log.len() >= MAX_LOG_ENTRIES {
+            self.log.pop_front();
+        }
+        self.log.push_back(ReloadEntry { time: chrono::Utc::now().format("%H:%M:%S").to_string(), file, result });
+    }
+
+    /// Indique si le dernier rechargement de chaque fichier a réussi.
+    pub fn is_healthy(&self) -> bool {
+        let mut seen = Vec::new();
+        self.log.iter().rev().filter(|entry| {
+            let first = !seen.contains(&entry.file);
+            seen.push(entry.file);
+            first
+        }).all(|entry| entry.result.is_ok())
+    }
+
+    /// Affiche le réglage et le journal dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, "Rechargement automatique")
+            .on_hover_text("Applique sans redémarrer les modifications des fichiers de couleurs, de zones, de profils et d'alertes");
+        if self.log.is_empty() {
+            ui.weak("Aucun rechargement");
+            return;
+        }
+        egui::ScrollArea::vertical().max_height(160.).id_salt("config_log").stick_to_bottom(true).show(ui, |ui| {
+            for entry in &self.log {
+                let path = entry.file.path().display();
+                match &entry.result {
+                    Ok(description) => ui.label(format!("{} ✔ {} : {}", entry.time, path, description)),
+                    Err(e) => ui.colored_label(Color32::RED, format!("{} ✖ {} : {}", entry.time, path, e)),
+                };
+            }
+        });
+        if ui.small_button("Effacer le journal").clicked() {
+            self.log.clear();
+        }
+    }
+}
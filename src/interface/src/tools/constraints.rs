@@ -0,0 +1,201 @@
+//! Module de vérification des contraintes matérielles du plan de brouillage.
+//!
+//! Il définit la contrainte de cycle de service ([`DutyCycle`]) propre à chaque
+//! amplificateur, lue dans le registre des amplificateurs
+//! ([`amplifier_duty_cycle`]), et un vérificateur qui signale les tâches ne respectant pas le
+//! temps de refroidissement, avec le décalage minimal permettant de corriger la violation.
+//! Il détecte également les conflits entre tâches simultanées sur les mêmes fréquences.
+
+use serde::Deserialize;
+
+use crate::tools::amplifiers::amplifier_duty_cycle;
+use crate::tools::hierarchy::Hierarchy;
+use crate::tools::task::{Amplifier, Task};
+
+/// Contrainte de cycle de service d’un amplificateur.
+///
+/// Après `max_on` ms d’émission continue, l’amplificateur doit rester au repos
+/// pendant au moins `cooldown` ms.
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize)]
+pub struct DutyCycle {
+    /// Durée maximale d’émission continue en ms.
+    pub max_on: f64,
+    /// Durée minimale de refroidissement en ms.
+    pub cooldown: f64,
+}
+
+impl DutyCycle {
+    /// Indique si les durées sont finies, l’émission maximale strictement positive
+    /// et le refroidissement positif.
+    pub fn is_valid(&self) -> bool {
+        self.max_on.is_finite() && self.max_on > 0. && self.cooldown.is_finite() && self.cooldown >= 0.
+    }
+}
+
+/// Violation de cycle de service détectée sur une tâche.
+#[derive(Clone, Debug)]
+pub struct DutyCycleViolation {
+    /// Amplificateur concerné.
+    pub amplifier: Amplifier,
+    /// Indice de la tâche fautive dans la liste des tâches.
+    pub task_index: usize,
+    /// Durée d’émission continue atteinte avec la tâche fautive, en ms.
+    pub on_time: f64,
+    /// Décalage temporel minimal (en ms) à appliquer à la tâche pour respecter
+    /// le refroidissement, ou `None` si la tâche dépasse seule `max_on`.
+    pub suggested_shift: Option<f64>,
+}
+
+/// Vérifie le cycle de service de chaque amplificateur sur l’ensemble des tâches.
+///
+/// Les tâches d’un même amplificateur séparées par moins de `cooldown` ms sont
+/// considérées comme une émission continue. Dès que cette émission dépasse
+/// `max_on` ms, la tâche qui la prolonge est signalée avec le décalage qui la ferait
+/// démarrer après un refroidissement complet.
+pub fn check_duty_cycles(tasks: &[Task]) -> Vec<DutyCycleViolation> {
+    let mut violations = Vec::new();
+
+    let mut amplifiers: Vec<Amplifier> = Vec::new();
+    for task in tasks {
+        if !amplifiers.contains(&task.amplifier) {
+            amplifiers.push(task.amplifier);
+        }
+    }
+
+    for amp in amplifiers {
+        let Some(duty) = amplifier_duty_cycle(amp) else { continue };
+
+        // Tâches de l’amplificateur triées par temps de début
+        let mut indices: Vec<usize> = (0..tasks.len())
+            .filter(|&i| tasks[i].amplifier == amp)
+            .collect();
+        indices.sort_by(|&a, &b| tasks[a].time_start.total_cmp(&tasks[b].time_start));
+
+        // Début et fin de la rafale d’émission en cours
+        let mut burst: Option<(f64, f64)> = None;
+        for i in indices {
+            let task = &tasks[i];
+            let duration = task.time_end - task.time_start;
+
+            if duration > duty.max_on {
+                violations.push(DutyCycleViolation {
+                    amplifier: amp,
+                    task_index: i,
+                    on_time: duration,
+                    suggested_shift: None,
+                });
+                burst = Some((task.time_start, task.time_end));
+                continue;
+            }
+
+            burst = match burst {
+                Some((start, end)) if task.time_start - end < duty.cooldown => {
+                    let new_end = end.max(task.time_end);
+                    if new_end - start > duty.max_on {
+                        violations.push(DutyCycleViolation {
+                            amplifier: amp,
+                            task_index: i,
+                            on_time: new_end - start,
+                            suggested_shift: Some(end + duty.cooldown - task.time_start),
+                        });
+                        // La tâche est considérée décalée pour ne pas propager la violation
+                        Some((task.time_start, task.time_end))
+                    } else {
+                        Some((start, new_end))
+                    }
+                }
+                _ => Some((task.time_start, task.time_end)),
+            };
+        }
+    }
+
+    violations
+}
+
+/// Conflit entre deux tâches se chevauchant à la fois en temps et en fréquence.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Conflict {
+    /// Indice de la tâche qui démarre en premier.
+    pub first: usize,
+    /// Indice de la tâche qui démarre en second.
+    pub second: usize,
+}
+
+/// Recherche toutes les paires de tâches en conflit.
+///
+/// Deux tâches sont en conflit si leurs intervalles de temps et de fréquence
+/// se chevauchent strictement (des tâches simplement adjacentes ne le sont pas)
+/// et, pour les tâches non rectangulaires, si leurs contours se chevauchent.
+/// Les tâches parentes, qui regroupent leurs sous-tâches sans émettre, ne sont
+/// jamais en conflit.
+pub fn find_conflicts(tasks: &[Task]) -> Vec<Conflict> {
+    let hierarchy = Hierarchy::new(tasks);
+    let mut conflicts = Vec::new();
+    for i in (0..tasks.len()).filter(|&i| !hierarchy.is_parent(i)) {
+        for j in ((i + 1)..tasks.len()).filter(|&j| !hierarchy.is_parent(j)) {
+            let (a, b) = (&tasks[i], &tasks[j]);
+            if a.overlaps(b) {
+                let (first, second) = if a.time_start <= b.time_start { (i, j) } else { (j, i) };
+                conflicts.push(Conflict { first, second });
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(name: &str, amplifier: Amplifier, freq: (f64, f64), time: (f64, f64)) -> Task {
+        Task::builder(name, amplifier).freq(freq.0, freq.1).time(time.0, time.1).build().unwrap()
+    }
+
+    #[test]
+    fn task_longer_than_max_on_is_flagged_without_shift() {
+        let tasks = [task("Radar", Amplifier::A960_1215, (1000., 1100.), (0., 350.))];
+        let violations = check_duty_cycles(&tasks);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].task_index, 0);
+        assert_eq!(violations[0].on_time, 350.);
+        assert_eq!(violations[0].suggested_shift, None);
+    }
+
+    #[test]
+    fn burst_without_cooldown_suggests_a_shift() {
+        let tasks = [
+            task("Radar 1", Amplifier::A960_1215, (1000., 1100.), (0., 200.)),
+            task("Radar 2", Amplifier::A960_1215, (1000., 1100.), (220., 400.)),
+        ];
+        let violations = check_duty_cycles(&tasks);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].task_index, 1);
+        assert_eq!(violations[0].on_time, 400.);
+        assert_eq!(violations[0].suggested_shift, Some(30.));
+    }
+
+    #[test]
+    fn cooldown_respected_or_unconstrained_amplifier_is_accepted() {
+        let cooled = [
+            task("Radar 1", Amplifier::A960_1215, (1000., 1100.), (0., 200.)),
+            task("Radar 2", Amplifier::A960_1215, (1000., 1100.), (250., 450.)),
+        ];
+        let unconstrained = [task("Veille", Amplifier::A20_500, (100., 200.), (0., 5000.))];
+
+        assert!(check_duty_cycles(&cooled).is_empty());
+        assert!(check_duty_cycles(&unconstrained).is_empty());
+    }
+
+    #[test]
+    fn only_strict_overlaps_are_conflicts() {
+        let tasks = [
+            task("A", Amplifier::A960_1215, (1000., 1100.), (0., 100.)),
+            task("B", Amplifier::A960_1215, (1050., 1150.), (50., 150.)),
+            task("C", Amplifier::A960_1215, (1150., 1250.), (0., 150.)),
+        ];
+
+        assert_eq!(find_conflicts(&tasks), vec![Conflict { first: 0, second: 1 }]);
+    }
+}
@@ -0,0 +1,233 @@
+//! Module d’import de tâches depuis un fichier CSV.
+//!
+//! Les plannings sont souvent fournis sous forme de tableur. Ce module lit un
+//! fichier CSV, propose une correspondance entre ses colonnes et les champs
+//! d’une tâche (devinée à partir des en-têtes puis modifiable dans une fenêtre
+//! de dialogue), valide chaque ligne et rapporte les erreurs ligne par ligne.
+
+use std::path::Path;
+
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::timefmt::parse_time;
+
+/// Champs d’une tâche pouvant être associés à une colonne, avec leur libellé.
+pub const FIELDS: [&str; 6] = ["Nom", "fmin (MHz)", "fmax (MHz)", "tmin (ms)", "tmax (ms)", "Amplificateur"];
+
+/// Indices des champs dans [`FIELDS`].
+const NAME: usize = 0;
+const FREQ_START: usize = 1;
+const FREQ_END: usize = 2;
+const TIME_START: usize = 3;
+const TIME_END: usize = 4;
+const AMPLIFIER: usize = 5;
+
+/// Contenu brut d’un fichier CSV.
+pub struct CsvTable {
+    /// En-têtes des colonnes.
+    pub headers: Vec<String>,
+    /// Lignes de données (sans l’en-tête).
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Erreur de validation d’une ligne du fichier.
+pub struct RowError {
+    /// Numéro de ligne dans le fichier (l’en-tête étant la ligne 1).
+    pub line: usize,
+    /// Description de l’erreur.
+    pub message: String,
+}
+
+/// Lit un fichier CSV (séparateur `,` ou `;` détecté automatiquement).
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible.
+pub fn read_csv(path: &Path) -> Result<CsvTable, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("En-têtes CSV invalides : {}", e))?
+        .iter()
+        .map(String::from)
+        .collect();
+    let rows = reader
+        .records()
+        .map(|r| r.map(|r| r.iter().map(String::from).collect()))
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("CSV invalide : {}", e))?;
+
+    Ok(CsvTable { headers, rows })
+}
+
+/// Devine la colonne correspondant à chaque champ à partir des en-têtes.
+pub fn guess_mapping(headers: &[String]) -> [Option<usize>; 6] {
+    let keywords: [&[&str]; 6] = [
+        &["name", "nom", "task", "tâche"],
+        &["freq_start", "fmin", "f_start", "freq min"],
+        &["freq_end", "fmax", "f_end", "freq max"],
+        &["time_start", "tmin", "t_start", "start", "début"],
+        &["time_end", "tmax", "t_end", "end", "fin"],
+        &["amplifier", "ampli", "amp"],
+    ];
+    let mut mapping = [None; 6];
+    for (field, words) in keywords.iter().enumerate() {
+        mapping[field] = headers.iter().position(|h| {
+            let h = h.to_lowercase();
+            words.iter().any(|w| h == *w || h.starts_with(w))
+        });
+    }
+    mapping
+}
+
+/// Convertit les lignes du tableau en tâches selon la correspondance de colonnes.
+///
+/// Les temps acceptent les mêmes expressions que l’inspecteur (ms, `H+15`, DTG),
+/// les DTG étant convertis relativement à l’heure H `mission_start`.
+/// Les lignes invalides sont ignorées et rapportées dans la liste d’erreurs.
+/// Si aucune colonne d’amplificateur n’est associée (ou si la valeur est vide),
+/// le premier amplificateur couvrant la plage de fréquence est choisi.
+pub fn convert(
+    table: &CsvTable,
+    mapping: &[Option<usize>; 6],
+    mission_start: Option<i64>,
+) -> (Vec<Task>, Vec<RowError>) {
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, row) in table.rows.iter().enumerate() {
+        let line = i + 2;
+        let cell = |field: usize| mapping[field].and_then(|c| row.get(c)).map(String::as_str).unwrap_or("");
+        let number = |field: usize| -> Result<f64, String> {
+            let value = cell(field);
+            value
+                .replace(',', ".")
+                .parse::<f64>()
+                .map_err(|_| format!("{} invalide : « {} »", FIELDS[field], value))
+        };
+
+        let parsed = (|| {
+            let (freq_start, freq_end) = (number(FREQ_START)?, number(FREQ_END)?);
+            let time = |field: usize| {
+                parse_time(cell(field), mission_start).map_err(|e| format!("{} : {}", FIELDS[field], e))
+            };
+            let (time_start, time_end) = (time(TIME_START)?, time(TIME_END)?);
+            if freq_start > freq_end {
+                return Err("fmin ne doit pas dépasser fmax".to_string());
+            }
+            if time_start > time_end {
+                return Err("tmin ne doit pas dépasser tmax".to_string());
+            }
+            let amplifier = match cell(AMPLIFIER) {
+                "" => Amplifier::ALL
+                    .into_iter()
+                    .find(|a| a.covers(freq_start, freq_end))
+                    .ok_or("aucun amplificateur ne couvre cette plage")?,
+                name => Amplifier::from_str(name).ok_or(format!("amplificateur inconnu : « {} »", name))?,
+            };
+            let name = match cell(NAME) {
+                "" => format!("Tâche {}", line),
+                name => name.to_string(),
+            };
+            Ok(Task::block(name, amplifier, (freq_start, freq_end), (time_start, time_end)))
+        })();
+
+        match parsed {
+            Ok(task) => tasks.push(task),
+            Err(message) => errors.push(RowError { line, message }),
+        }
+    }
+
+    (tasks, errors)
+}
+
+/// État de la fenêtre de dialogue d’import CSV.
+#[derive(Default)]
+pub struct CsvImportDialog {
+    /// Chemin du fichier saisi par l’utilisateur.
+    pub path: String,
+    /// Tableau chargé, s’il y en a un.
+    table: Option<CsvTable>,
+    /// Correspondance colonne associée à chaque champ.
+    mapping: [Option<usize>; 6],
+    /// Erreur de lecture du fichier.
+    error: Option<String>,
+}
+
+impl CsvImportDialog {
+    /// Affiche la fenêtre de dialogue.
+    ///
+    /// Retourne les tâches à insérer lorsque l’utilisateur valide l’import.
+    /// `open` passe à `false` lorsque la fenêtre est fermée.
+    pub fn show(&mut self, ctx: &egui::Context, mission_start: Option<i64>, open: &mut bool) -> Option<Vec<Task>> {
+        let mut imported = None;
+        egui::Window::new("Import CSV").open(open).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Fichier :");
+                ui.text_edit_singleline(&mut self.path);
+                if ui.button("Charger").clicked() {
+                    match read_csv(Path::new(&self.path)) {
+                        Ok(table) => {
+                            self.mapping = guess_mapping(&table.headers);
+                            self.table = Some(table);
+                            self.error = None;
+                        }
+                        Err(e) => {
+                            self.table = None;
+                            self.error = Some(e);
+                        }
+                    }
+                }
+            });
+            if let Some(e) = &self.error {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+
+            let Some(table) = &self.table else { return };
+            ui.separator();
+            ui.label("Correspondance des colonnes :");
+            egui::Grid::new("csv_mapping").show(ui, |ui| {
+                for (field, label) in FIELDS.iter().enumerate() {
+                    ui.label(*label);
+                    let selected = self.mapping[field]
+                        .and_then(|c| table.headers.get(c))
+                        .map(String::as_str)
+                        .unwrap_or("—");
+                    egui::ComboBox::from_id_salt(("csv_column", field))
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.mapping[field], None, "—");
+                            for (c, header) in table.headers.iter().enumerate() {
+                                ui.selectable_value(&mut self.mapping[field], Some(c), header);
+                            }
+                        });
+                    ui.end_row();
+                }
+            });
+
+            let (tasks, errors) = convert(table, &self.mapping, mission_start);
+            ui.separator();
+            ui.label(format!("{} lignes valides, {} erreurs", tasks.len(), errors.len()));
+            egui::ScrollArea::vertical().max_height(150.).show(ui, |ui| {
+                for e in &errors {
+                    ui.colored_label(egui::Color32::RED, format!("Ligne {} : {}", e.line, e.message));
+                }
+            });
+            if ui
+                .add_enabled(!tasks.is_empty(), egui::Button::new(format!("Importer {} tâches", tasks.len())))
+                .clicked()
+            {
+                imported = Some(tasks);
+            }
+        });
+        imported
+    }
+}
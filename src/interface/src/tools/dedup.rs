@@ -0,0 +1,108 @@
+//! Module de suppression des messages de tâche répétés.
+//!
+//! Un émetteur qui rediffuse ses tâches en boucle renvoie sans cesse les mêmes
+//! messages. Chaque message de tâche est réduit à une empreinte de son contenu
+//! (indépendante de l’ordre des champs et de la mise en forme) : un message
+//! identique au dernier message reçu de la même source pour la même tâche
+//! (désignée par son `id`, ou à défaut son `name`) est ignoré et comptabilisé,
+//! même si d’autres tâches ont été reçues entre-temps.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::Value;
+
+/// Empreinte du contenu d’une valeur JSON, indépendante de l’ordre des champs.
+fn hash_value(value: &Value, state: &mut impl Hasher) {
+    match value {
+        Value::Null => 0u8.hash(state),
+        Value::Bool(b) => (1u8, b).hash(state),
+        Value::Number(n) => (2u8, n.to_string()).hash(state),
+        Value::String(s) => (3u8, s).hash(state),
+        Value::Array(items) => {
+            (4u8, items.len()).hash(state);
+            for item in items {
+                hash_value(item, state);
+            }
+        }
+        Value::Object(map) => {
+            (5u8, map.len()).hash(state);
+            let mut fields: Vec<_> = map.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in fields {
+                key.hash(state);
+                hash_value(value, state);
+            }
+        }
+    }
+}
+
+/// Clé (`id`, ou à défaut `name`) et empreinte d’un message de tâche, ou `None`
+/// pour les autres messages.
+pub fn task_hash(json: &str) -> Option<(String, u64)> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    let name = value.get("name")?.as_str()?;
+    let key = value.get("id").and_then(Value::as_str).unwrap_or(name).to_string();
+    let mut hasher = DefaultHasher::new();
+    hash_value(&value, &mut hasher);
+    Some((key, hasher.finish()))
+}
+
+/// Filtre des messages de tâche répétés.
+pub struct Deduplicator {
+    /// Indique si les messages répétés sont ignorés.
+    pub enabled: bool,
+    /// Nombre de messages ignorés.
+    pub suppressed: usize,
+    /// Empreinte du dernier message reçu par source et par clé de tâche.
+    last: HashMap<(String, String), u64>,
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self {
+        Self { enabled: true, suppressed: 0, last: HashMap::new() }
+    }
+}
+
+impl Deduplicator {
+    /// Indique si le message répète le dernier message de la source pour la même
+    /// tâche (et le comptabilise).
+    pub fn is_duplicate(&mut self, source: &str, json: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let Some((key, hash)) = task_hash(json) else {
+            return false;
+        };
+        if self.last.insert((source.to_string(), key), hash) == Some(hash) {
+            self.suppressed += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Oublie les derniers messages reçus (ex. après une modification locale du plan).
+    pub fn reset(&mut self) {
+        self.last.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resend_after_another_task_is_suppressed() {
+        let a = r#"{"name":"A","freq_start":100,"freq_end":200,"time_start":0,"time_end":100,"amplifier":"A20_500"}"#;
+        let b = r#"{"name":"B","freq_start":300,"freq_end":400,"time_start":0,"time_end":100,"amplifier":"A20_500"}"#;
+        let mut dedup = Deduplicator::default();
+
+        assert!(!dedup.is_duplicate("stdin", a));
+        assert!(!dedup.is_duplicate("stdin", b));
+        assert!(!dedup.is_duplicate("stdin", r#"{"plan_hash":"0123"}"#));
+        assert!(dedup.is_duplicate("stdin", a));
+        assert!(!dedup.is_duplicate("tcp:10.0.0.2:4000", a));
+        assert_eq!(dedup.suppressed, 1);
+    }
+}
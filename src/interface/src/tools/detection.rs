@@ -0,0 +1,151 @@
+//! Module des détections ESM.
+//!
+//! Les comptes rendus de détection des capteurs ESM sont reçus sur stdin sous la
+//! forme `{"detection": {"time": 420, "freq": 2950, "bearing": 135, "label": "Radar SA"}}`.
+//! Chaque détection instantanée est dessinée sur le graphe par une croix au point
+//! (fréquence, temps), prolongée d’un trait indiquant son gisement ; le symbole
+//! s’estompe progressivement puis disparaît, afin que les détections récentes
+//! puissent être corrélées au plan de brouillage en temps réel.
+
+use std::time::{Duration, Instant};
+
+use egui::{Color32, Painter, Pos2, Stroke};
+use egui_plot::{PlotPoint, PlotTransform};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::utils::freq_to_x;
+
+/// Durée d’estompage par défaut d’une détection, en secondes.
+pub const DEFAULT_FADE_S: f32 = 30.;
+
+/// Demi-taille des croix de détection en points.
+const CROSS_SIZE: f32 = 5.;
+
+/// Longueur du trait de gisement en points.
+const BEARING_LENGTH: f32 = 14.;
+
+/// Couleur des détections.
+const DETECTION_COLOR: Color32 = Color32::from_rgb(120, 255, 120);
+
+/// Détection instantanée d’un émetteur.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Detection {
+    /// Temps de la détection en ms.
+    pub time: f64,
+    /// Fréquence détectée en MHz.
+    pub freq: f64,
+    /// Gisement de l’émetteur en degrés (0 = nord, sens horaire).
+    #[serde(default)]
+    pub bearing: Option<f64>,
+    /// Libellé de la détection (type d’émetteur, piste…).
+    #[serde(default)]
+    pub label: String,
+}
+
+/// Message de détection reçu sur stdin.
+#[derive(Deserialize, JsonSchema)]
+pub struct DetectionMessage {
+    /// Détection reçue.
+    pub detection: Detection,
+}
+
+impl Detection {
+    /// Vérifie la cohérence de la détection.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le temps, la fréquence ou le gisement ne
+    /// sont pas des nombres finis, ou si la fréquence n’est pas positive.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.time.is_finite() || !self.freq.is_finite() || !self.bearing.is_none_or(f64::is_finite) {
+            return Err("Détection invalide : valeurs non finies".into());
+        }
+        if self.freq <= 0. {
+            return Err(format!("Détection invalide : fréquence {} MHz", self.freq));
+        }
+        Ok(())
+    }
+}
+
+/// Détections reçues et réglages de leur affichage.
+pub struct Detections {
+    /// Détections et instant de leur réception.
+    entries: Vec<(Detection, Instant)>,
+    /// Indique si les détections sont affichées.
+    pub enabled: bool,
+    /// Durée d’estompage en secondes.
+    pub fade_s: f32,
+}
+
+impl Default for Detections {
+    fn default() -> Self {
+        Self { entries: vec![], enabled: true, fade_s: DEFAULT_FADE_S }
+    }
+}
+
+impl Detections {
+    /// Ajoute une détection reçue à l’instant `now`.
+    pub fn push(&mut self, detection: Detection, now: Instant) {
+        self.entries.push((detection, now));
+    }
+
+    /// Supprime les détections entièrement estompées.
+    pub fn prune(&mut self, now: Instant) {
+        let fade = Duration::from_secs_f32(self.fade_s);
+        self.entries.retain(|(_, received)| now.duration_since(*received) < fade);
+    }
+
+    /// Indique si des détections sont en cours d’estompage (rafraîchissement nécessaire).
+    pub fn is_fading(&self) -> bool {
+        self.enabled && !self.entries.is_empty()
+    }
+
+    /// Détections encore visibles et leur opacité (1 à la réception, 0 à la fin de l’estompage).
+    pub fn active(&self, now: Instant) -> impl Iterator<Item = (&Detection, f32)> {
+        let fade = self.fade_s.max(f32::EPSILON);
+        self.entries
+            .iter()
+            .map(move |(detection, received)| (detection, 1. - now.duration_since(*received).as_secs_f32() / fade))
+            .filter(|(_, alpha)| *alpha > 0.)
+    }
+
+    /// Dessine les détections pour chaque couloir de décalage `lane_offsets` (ms).
+    pub fn paint(&self, painter: &Painter, transform: &PlotTransform, log: bool, lane_offsets: &[f64], now: Instant) {
+        if !self.enabled {
+            return;
+        }
+        let frame = *transform.frame();
+        for (detection, alpha) in self.active(now) {
+            let color = DETECTION_COLOR.gamma_multiply(alpha);
+            let stroke = Stroke::new(1.5, color);
+            let x = freq_to_x(detection.freq, log);
+            for offset in lane_offsets {
+                let center = transform.position_from_point(&PlotPoint::new(x, detection.time + offset));
+                if !frame.contains(center) {
+                    continue;
+                }
+                let d = CROSS_SIZE;
+                painter.line_segment([center + egui::vec2(-d, -d), center + egui::vec2(d, d)], stroke);
+                painter.line_segment([center + egui::vec2(-d, d), center + egui::vec2(d, -d)], stroke);
+                if let Some(bearing) = detection.bearing {
+                    let angle = bearing.to_radians() as f32;
+                    let tip = Pos2::new(center.x + BEARING_LENGTH * angle.sin(), center.y - BEARING_LENGTH * angle.cos());
+                    painter.line_segment([center, tip], stroke);
+                }
+                if !detection.label.is_empty() {
+                    painter.text(center + egui::vec2(d + 2., -d), egui::Align2::LEFT_BOTTOM, &detection.label, egui::FontId::proportional(11.), color);
+                }
+            }
+        }
+    }
+
+    /// Affiche les réglages des détections.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, format!("Détections ESM ({})", self.entries.len()));
+        ui.add(egui::Slider::new(&mut self.fade_s, 1.0..=300.0).text("Estompage (s)"));
+        if !self.entries.is_empty() && ui.small_button("Effacer les détections").clicked() {
+            self.entries.clear();
+        }
+    }
+}
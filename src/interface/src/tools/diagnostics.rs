@@ -0,0 +1,264 @@
+//! Module des diagnostics de démarrage.
+//!
+//! Avant que l’opérateur ne se fie à l’affichage, une série de vérifications
+//! est exécutée : validité de chaque fichier de configuration présent,
+//! disponibilité du transport réseau, moteur de rendu et version du schéma du
+//! protocole. Le résultat est résumé en réussite ou échec :
+//!
+//! - `--check` exécute les vérifications sans ouvrir de fenêtre, affiche le
+//!   rapport et retourne un code de sortie non nul en cas d’échec ;
+//! - au lancement de l’interface, la fenêtre « Diagnostics » s’ouvre d’elle-même
+//!   si une vérification échoue, et reste accessible depuis le panneau latéral.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::net::TcpListener;
+use std::path::Path;
+
+use egui::Color32;
+
+use crate::tools::alerts::{load_alert_rules, ALERTS_PATH};
+use crate::tools::amplifiers::{load_amplifiers, AMPLIFIERS_PATH};
+use crate::tools::antenna::{load_antennas, ANTENNAS_PATH};
+use crate::tools::background::{load_zones, ZONES_PATH};
+use crate::tools::phase::{load_phases, PHASES_PATH};
+use crate::tools::presets::{load_presets, PRESETS_PATH};
+use crate::tools::profile::{load_profiles, PROFILES_PATH};
+use crate::tools::protocol::{schema_json, PROTOCOL_VERSION, SCHEMA_PATH};
+use crate::tools::rate_limit::{load_rate_limits, RATE_LIMITS_PATH};
+use crate::tools::system::{load_systems, SYSTEMS_PATH};
+use crate::tools::tooltip::{load_tooltip, TOOLTIP_PATH};
+use crate::tools::transport::{load_transport, tls_server_config, TransportConfig, TRANSPORT_PATH};
+
+/// Issue d’une vérification.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CheckStatus {
+    /// Vérification réussie.
+    Pass,
+    /// Vérification impossible ou point d’attention, sans empêcher l’exploitation.
+    Warn,
+    /// Vérification en échec.
+    Fail,
+}
+
+impl CheckStatus {
+    /// Libellé de l’issue.
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "OK",
+            CheckStatus::Warn => "AVERTISSEMENT",
+            CheckStatus::Fail => "ÉCHEC",
+        }
+    }
+
+    /// Couleur de l’issue dans la fenêtre des diagnostics.
+    pub fn color(self) -> Color32 {
+        match self {
+            CheckStatus::Pass => Color32::from_rgb(80, 200, 120),
+            CheckStatus::Warn => Color32::from_rgb(255, 170, 0),
+            CheckStatus::Fail => Color32::RED,
+        }
+    }
+}
+
+/// Résultat d’une vérification.
+#[derive(Clone, Debug)]
+pub struct CheckResult {
+    /// Élément vérifié.
+    pub name: String,
+    /// Issue.
+    pub status: CheckStatus,
+    /// Détail du résultat.
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status, detail: detail.into() }
+    }
+}
+
+/// État du transport réseau au moment des diagnostics.
+pub enum TransportState<'a> {
+    /// Transport non démarré (`--check`) : l’adresse d’écoute est essayée.
+    NotStarted,
+    /// Résultat du démarrage du transport par l’interface.
+    Started(&'a Result<(), String>),
+}
+
+/// Vérifie un fichier de configuration : absent (configuration par défaut) ou valide.
+fn check_config<T>(path: &str, load: fn(&Path) -> Result<T, String>) -> CheckResult {
+    let name = format!("Configuration {}", path);
+    let path = Path::new(path);
+    if !path.exists() {
+        return CheckResult::new(name, CheckStatus::Pass, "absente, configuration par défaut");
+    }
+    match load(path) {
+        Ok(_) => CheckResult::new(name, CheckStatus::Pass, "valide"),
+        Err(e) => CheckResult::new(name, CheckStatus::Fail, e),
+    }
+}
+
+/// Vérifie la configuration du transport réseau et la disponibilité de l’écoute.
+fn check_transport(config: &TransportConfig, state: TransportState) -> CheckResult {
+    let name = "Transport réseau";
+    let Some(address) = &config.listen else {
+        return CheckResult::new(name, CheckStatus::Pass, "aucune écoute configurée");
+    };
+    if let Some(Err(e)) = config.tls.as_ref().map(tls_server_config) {
+        return CheckResult::new(name, CheckStatus::Fail, e);
+    }
+    let tls = if config.tls.is_some() { " (TLS)" } else { "" };
+    match state {
+        TransportState::NotStarted => match TcpListener::bind(address) {
+            Ok(_) => CheckResult::new(name, CheckStatus::Pass, format!("{}{} disponible", address, tls)),
+            Err(e) => CheckResult::new(name, CheckStatus::Fail, format!("écoute sur {} impossible : {}", address, e)),
+        },
+        TransportState::Started(Ok(())) => CheckResult::new(name, CheckStatus::Pass, format!("écoute sur {}{}", address, tls)),
+        TransportState::Started(Err(e)) => CheckResult::new(name, CheckStatus::Fail, e.clone()),
+    }
+}
+
+/// Empreinte courte du schéma du protocole.
+fn schema_fingerprint(schema: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    schema.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Vérifie la version du protocole et, si un schéma de référence est présent
+/// ([`SCHEMA_PATH`], produit par `--dump-schema`), sa concordance avec le schéma
+/// de l’interface.
+fn check_protocol() -> CheckResult {
+    let name = "Schéma du protocole";
+    let schema = schema_json();
+    if serde_json::from_str::<serde_json::Value>(&schema).is_err() {
+        return CheckResult::new(name, CheckStatus::Fail, "schéma JSON invalide");
+    }
+    let detail = format!("version {}, empreinte {}", PROTOCOL_VERSION, schema_fingerprint(&schema));
+    let path = Path::new(SCHEMA_PATH);
+    if !path.exists() {
+        return CheckResult::new(name, CheckStatus::Pass, detail);
+    }
+    match fs::read_to_string(path) {
+        Ok(reference) if reference.trim() == schema.trim() => {
+            CheckResult::new(name, CheckStatus::Pass, format!("{}, conforme à {}", detail, SCHEMA_PATH))
+        }
+        Ok(reference) => CheckResult::new(
+            name,
+            CheckStatus::Fail,
+            format!("{}, différent de {} (empreinte {})", detail, SCHEMA_PATH, schema_fingerprint(&reference)),
+        ),
+        Err(e) => CheckResult::new(name, CheckStatus::Fail, format!("lecture de {} impossible : {}", SCHEMA_PATH, e)),
+    }
+}
+
+/// Vérifie le moteur de rendu à partir de sa description, ou de l’erreur
+/// rencontrée pour l’obtenir.
+fn check_renderer(renderer: Option<Result<String, String>>) -> CheckResult {
+    let name = "Moteur de rendu";
+    match renderer {
+        Some(Ok(info)) => CheckResult::new(name, CheckStatus::Pass, info),
+        Some(Err(e)) => CheckResult::new(name, CheckStatus::Fail, e),
+        None => CheckResult::new(name, CheckStatus::Warn, "non vérifié sans fenêtre"),
+    }
+}
+
+/// Description du moteur de rendu OpenGL (fabricant, carte et version).
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si aucun contexte OpenGL n’est disponible.
+pub fn renderer_info(gl: Option<&eframe::glow::Context>) -> Result<String, String> {
+    use eframe::glow::HasContext;
+    let gl = gl.ok_or("aucun contexte OpenGL")?;
+    // SAFETY : lecture de chaînes constantes du pilote sur le contexte courant
+    let (vendor, renderer, version) = unsafe {
+        (
+            gl.get_parameter_string(eframe::glow::VENDOR),
+            gl.get_parameter_string(eframe::glow::RENDERER),
+            gl.get_parameter_string(eframe::glow::VERSION),
+        )
+    };
+    Ok(format!("{} {} (OpenGL {})", vendor, renderer, version))
+}
+
+/// Résultat des diagnostics de démarrage.
+#[derive(Clone, Default, Debug)]
+pub struct Diagnostics {
+    /// Vérifications, dans l’ordre d’exécution.
+    pub checks: Vec<CheckResult>,
+}
+
+impl Diagnostics {
+    /// Exécute l’ensemble des vérifications. `renderer` vaut `None` sans fenêtre.
+    pub fn run(transport: &TransportConfig, state: TransportState, renderer: Option<Result<String, String>>) -> Self {
+        let checks = vec![
+            check_config(AMPLIFIERS_PATH, load_amplifiers),
+            check_config(ZONES_PATH, load_zones),
+            check_config(PROFILES_PATH, load_profiles),
+            check_config(ALERTS_PATH, load_alert_rules),
+            check_config(TOOLTIP_PATH, load_tooltip),
+            check_config(PRESETS_PATH, load_presets),
+            check_config(PHASES_PATH, load_phases),
+            check_config(SYSTEMS_PATH, load_systems),
+            check_config(ANTENNAS_PATH, load_antennas),
+            check_config(RATE_LIMITS_PATH, load_rate_limits),
+            check_config(TRANSPORT_PATH, load_transport),
+            check_transport(transport, state),
+            check_renderer(renderer),
+            check_protocol(),
+        ];
+        Self { checks }
+    }
+
+    /// Indique si aucune vérification n’est en échec.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+
+    /// Nombre de vérifications de l’issue donnée.
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Résumé des diagnostics.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} : {} réussie(s), {} avertissement(s), {} échec(s)",
+            if self.passed() { "RÉUSSITE" } else { "ÉCHEC" },
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        )
+    }
+
+    /// Rapport texte : une ligne par vérification, puis le résumé.
+    pub fn report(&self) -> String {
+        let mut report: String = self
+            .checks
+            .iter()
+            .map(|c| format!("[{}] {} : {}\n", c.status.label(), c.name, c.detail))
+            .collect();
+        report.push_str(&self.summary());
+        report
+    }
+
+    /// Affiche la fenêtre des diagnostics.
+    pub fn show(&self, ctx: &egui::Context, open: &mut bool) {
+        egui::Window::new("Diagnostics").open(open).show(ctx, |ui| {
+            let color = if self.passed() { CheckStatus::Pass.color() } else { CheckStatus::Fail.color() };
+            ui.colored_label(color, self.summary());
+            ui.separator();
+            egui::Grid::new("diagnostics").striped(true).show(ui, |ui| {
+                for check in &self.checks {
+                    ui.colored_label(check.status.color(), check.status.label());
+                    ui.label(&check.name);
+                    ui.label(&check.detail);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,63 @@
+//! Module des effets recherchés par les tâches de brouillage.
+//!
+//! Les planificateurs distinguent en permanence le déni, la dégradation, la
+//! déception et la simple surveillance. Chaque effet est rendu par un style de
+//! bordure distinct (plein, tirets, pointillés, tirets espacés), repris dans la
+//! légende du panneau latéral, et peut servir de filtre d’affichage.
+
+use egui::{Color32, Pos2, Shape, Stroke};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Épaisseur des bordures d’effet, en pixels.
+const BORDER_WIDTH: f32 = 1.5;
+
+/// Couleur des bordures d’effet, contrastant avec les couleurs de remplissage.
+pub const BORDER_COLOR: Color32 = Color32::from_gray(230);
+
+/// Effet recherché par une tâche.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema)]
+pub enum Effect {
+    /// Déni : empêcher toute exploitation du signal.
+    #[serde(alias = "deny")]
+    Deny,
+    /// Dégradation : réduire la qualité ou la portée du signal.
+    #[serde(alias = "degrade")]
+    Degrade,
+    /// Déception : injecter de fausses informations.
+    #[serde(alias = "deceive")]
+    Deceive,
+    /// Surveillance : écoute sans émission offensive.
+    #[serde(alias = "monitor")]
+    Monitor,
+}
+
+impl Effect {
+    /// Liste de tous les effets.
+    pub const ALL: [Effect; 4] = [Effect::Deny, Effect::Degrade, Effect::Deceive, Effect::Monitor];
+
+    /// Libellé affiché dans l’interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Effect::Deny => "Déni",
+            Effect::Degrade => "Dégradation",
+            Effect::Deceive => "Déception",
+            Effect::Monitor => "Surveillance",
+        }
+    }
+
+    /// Bordure du contour écran `outline` (polygone fermé) dans le style de l’effet.
+    pub fn border(&self, outline: &[Pos2], color: Color32) -> Vec<Shape> {
+        let stroke = Stroke::new(BORDER_WIDTH, color);
+        let mut path = outline.to_vec();
+        if let Some(&first) = outline.first() {
+            path.push(first);
+        }
+        match self {
+            Effect::Deny => vec![Shape::closed_line(outline.to_vec(), stroke)],
+            Effect::Degrade => Shape::dashed_line(&path, stroke, 6., 3.),
+            Effect::Deceive => Shape::dotted_line(&path, color, 4., BORDER_WIDTH / 2. + 0.25),
+            Effect::Monitor => Shape::dashed_line(&path, stroke, 2., 6.),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Module du bus d’événements interne.
+//!
+//! Les composants de l’interface ne modifient plus l’état de l’application par
+//! des canaux et des queues propres à chacun : lecteurs de stdin, de tubes et du
+//! réseau, surveillance du fichier de plan et vues publient des événements sur un
+//! [`EventBus`] unique, que `MyApp::update` répartit au début de chaque image
+//! (`MyApp::dispatch`). Les événements sont de trois familles :
+//!
+//! - [`TransportEvent`] : messages reçus des flux d’entrée ;
+//! - [`PlanEvent`] : modifications annulables du plan demandées par les vues,
+//!   adressées par clé de tâche ([`Task::key`]) et publiées sous forme d’écarts
+//!   ([`PlanEvent::Patch`]) plutôt que de copies du plan ;
+//! - [`UiEvent`] : demandes d’affichage et actions des vues (inspecteur, vue sur
+//!   une alerte, sélection, menu contextuel, profil…), que les vues retournent au
+//!   lieu de modifier l’application.
+//!
+//! Le bus se clone à moindre coût et peut être partagé entre threads.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crossbeam_queue::SegQueue;
+
+use crate::tools::alerts::Alert;
+use crate::tools::layers::PlanLayer;
+use crate::tools::rate_limit::STDIN_SOURCE;
+use crate::tools::split::SplitAxis;
+use crate::tools::task::Task;
+
+/// Message reçu d’un flux d’entrée.
+#[derive(Clone, Debug)]
+pub enum TransportEvent {
+    /// Message du protocole reçu d’une source (soumis au limiteur de débit de
+    /// cette source).
+    Message { source: String, json: String },
+    /// Ligne reçue du tube d’une couche exécutée ou observée.
+    Layer { layer: PlanLayer, line: String },
+    /// Modification du plan lue d’un fichier surveillé (`--watch`) ; comme les
+    /// messages, elle ne dépend pas des droits d’édition du profil.
+    Plan(PlanEvent),
+}
+
+impl TransportEvent {
+    /// Message reçu du flux principal (stdin, tube du plan, plan surveillé) ; les
+    /// connexions réseau publient leurs messages sous leur propre source.
+    pub fn message(json: String) -> Self {
+        Self::Message { source: STDIN_SOURCE.into(), json }
+    }
+}
+
+/// Modification annulable du plan.
+#[derive(Clone, Debug)]
+pub enum PlanEvent {
+    /// Annule la dernière modification.
+    Undo,
+    /// Rétablit la dernière modification annulée.
+    Redo,
+    /// Remplace toutes les tâches (import Git, restauration d’une révision).
+    Replace {
+        /// Nouvelles tâches.
+        tasks: Vec<Task>,
+        /// Type d’action, inscrit dans la chronologie de la session.
+        action: &'static str,
+        /// Description de l’action.
+        description: String,
+    },
+    /// Ajoute des tâches à la fin du plan (import).
+    Extend {
+        /// Tâches ajoutées.
+        tasks: Vec<Task>,
+        /// Type d’action, inscrit dans la chronologie de la session.
+        action: &'static str,
+        /// Description de l’action.
+        description: String,
+    },
+    /// Applique un écart au plan (corrections, planification, alignement,
+    /// suppression…) : les tâches de clé `removed` sont retirées, chaque tâche de
+    /// `changed` remplace la tâche de même clé (ou est ajoutée si elle est absente).
+    Patch {
+        /// Tâches modifiées ou ajoutées.
+        changed: Vec<Task>,
+        /// Clés des tâches supprimées.
+        removed: Vec<String>,
+        /// Type d’action, inscrit dans la chronologie de la session.
+        action: &'static str,
+        /// Description de l’action.
+        description: String,
+    },
+    /// Remplace la tâche de clé `key` (inspecteur, glisser sur le graphe) ; la
+    /// nouvelle tâche peut changer de clé.
+    Edit { key: String, task: Box<Task> },
+}
+
+impl PlanEvent {
+    /// Écart entre le plan `before` et le plan `after`, apparié par clé : seules
+    /// les tâches modifiées, ajoutées ou supprimées sont transmises.
+    pub fn diff(before: &[Task], after: &[Task], action: &'static str, description: String) -> Self {
+        let previous: HashMap<&str, &Task> = before.iter().map(|t| (t.key(), t)).collect();
+        let kept: HashMap<&str, &Task> = after.iter().map(|t| (t.key(), t)).collect();
+        let changed = after.iter().filter(|t| previous.get(t.key()) != Some(t)).cloned().collect();
+        let removed = before.iter().filter(|t| !kept.contains_key(t.key())).map(|t| t.key().to_string()).collect();
+        Self::Patch { changed, removed, action, description }
+    }
+}
+
+/// Demande d’affichage ou action d’une vue.
+#[derive(Clone, Debug)]
+pub enum UiEvent {
+    /// Ouvre l’inspecteur de la tâche d’indice donné, ou le ferme.
+    Inspect(Option<usize>),
+    /// Amène la vue sur les tâches ou la région d’une alerte.
+    FocusAlert(Alert),
+    /// Ajoute la tâche d’indice donné à la sélection multiple, ou l’en retire.
+    ToggleSelection(usize),
+    /// Épingle la fiche de la tâche d’indice donné.
+    Pin(usize),
+    /// Scinde la tâche `index` à la position `at` de l’axe `axis`.
+    Split { index: usize, axis: SplitAxis, at: f64 },
+    /// Fusionne la tâche `other` dans la tâche `index`.
+    Join { index: usize, other: usize },
+    /// Active le profil d’affichage nommé.
+    SelectProfile(String),
+    /// Consulte en lecture seule la révision d’indice donné.
+    ViewRevision(usize),
+    /// Charge le plan du fichier de plan saisi.
+    OpenPlan,
+    /// Affiche le message de résultat d’une action.
+    Status(String),
+}
+
+/// Événement interne.
+#[derive(Clone, Debug)]
+pub enum AppEvent {
+    /// Modification du plan.
+    Plan(PlanEvent),
+    /// Demande d’affichage.
+    Ui(UiEvent),
+    /// Message reçu d’un flux d’entrée.
+    Transport(TransportEvent),
+}
+
+impl From<PlanEvent> for AppEvent {
+    fn from(event: PlanEvent) -> Self {
+        Self::Plan(event)
+    }
+}
+
+impl From<UiEvent> for AppEvent {
+    fn from(event: UiEvent) -> Self {
+        Self::Ui(event)
+    }
+}
+
+impl From<TransportEvent> for AppEvent {
+    fn from(event: TransportEvent) -> Self {
+        Self::Transport(event)
+    }
+}
+
+/// Bus d’événements partagé entre les composants et le cœur de l’application.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    /// Événements publiés, dans l’ordre de publication.
+    queue: Arc<SegQueue<AppEvent>>,
+}
+
+impl EventBus {
+    /// Publie un événement.
+    pub fn publish(&self, event: impl Into<AppEvent>) {
+        self.queue.push(event.into());
+    }
+
+    /// Indique si des événements attendent d’être répartis.
+    pub fn has_pending(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Retire les événements publiés jusqu’ici, dans l’ordre de publication ; les
+    /// événements publiés pendant leur traitement attendent l’appel suivant.
+    pub fn drain(&self) -> Vec<AppEvent> {
+        let mut events = Vec::with_capacity(self.queue.len());
+        for _ in 0..self.queue.len() {
+            match self.queue.pop() {
+                Some(event) => events.push(event),
+                None => break,
+            }
+        }
+        events
+    }
+}
@@ -0,0 +1,108 @@
+//! Module des masques d’exclusion fréquentielle.
+//!
+//! Un masque d’exclusion est une région fréquence/temps dans laquelle aucune
+//! émission n’est permise. Il est dessiné avec des hachures diagonales afin de se
+//! distinguer des zones d’amplificateur (contours) et de la zone de réception
+//! (aplat gris). Les polygones `egui_plot` n’offrant que des remplissages unis,
+//! les hachures sont construites sous forme de maillage en coordonnées écran,
+//! ce qui garde un espacement constant quel que soit le zoom.
+//!
+//! Les masques sont reçus sur stdin sous la forme
+//! `{"exclusion": {"name": "GSM", "freq_start": 880, "freq_end": 960}}`, ou lus
+//! depuis un fichier JSON (liste de masques) par la sous-commande `schedule`.
+
+use std::fs;
+use std::path::Path;
+
+use egui::{Color32, Mesh, Pos2, Rect};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::utils::MAX_TIME;
+
+/// Couleur des hachures et du contour des masques.
+pub const EXCLUSION_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+/// Région fréquence/temps interdite à l’émission.
+#[derive(Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExclusionMask {
+    /// Nom du masque (affiché dans les info-bulles).
+    pub name: String,
+    /// Fréquence de début en MHz.
+    pub freq_start: f64,
+    /// Fréquence de fin en MHz.
+    pub freq_end: f64,
+    /// Temps de début en ms (début de mission par défaut).
+    #[serde(default)]
+    pub time_start: f64,
+    /// Temps de fin en ms (fin de mission par défaut).
+    #[serde(default = "default_time_end")]
+    pub time_end: f64,
+}
+
+fn default_time_end() -> f64 {
+    MAX_TIME
+}
+
+/// Message d’ajout d’un masque d’exclusion reçu sur stdin.
+#[derive(Deserialize, JsonSchema)]
+pub struct ExclusionMessage {
+    /// Masque à ajouter (remplace un masque de même nom).
+    pub exclusion: ExclusionMask,
+}
+
+impl ExclusionMask {
+    /// Indique si le point `(freq, time)` se trouve dans le masque.
+    pub fn contains(&self, freq: f64, time: f64) -> bool {
+        (self.freq_start..=self.freq_end).contains(&freq) && (self.time_start..=self.time_end).contains(&time)
+    }
+}
+
+/// Charge une liste de masques d’exclusion depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible, mal formé ou si un
+/// masque a des bornes inversées.
+pub fn load_exclusions(path: &Path) -> Result<Vec<ExclusionMask>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let masks: Vec<ExclusionMask> =
+        serde_json::from_str(&content).map_err(|e| format!("Masques d'exclusion {} invalides : {}", path.display(), e))?;
+    if let Some(m) = masks.iter().find(|m| m.freq_start >= m.freq_end || m.time_start > m.time_end) {
+        return Err(format!("Masque {} : bornes inversées", m.name));
+    }
+    Ok(masks)
+}
+
+/// Construit le maillage de hachures diagonales couvrant `rect` (coordonnées écran).
+///
+/// Les hachures sont espacées de `spacing` points et épaisses de `width` points ;
+/// seule la partie de `rect` visible dans `clip` est hachurée.
+pub fn hatch_mesh(rect: Rect, clip: Rect, spacing: f32, width: f32, color: Color32) -> Mesh {
+    let mut mesh = Mesh::default();
+    let rect = rect.intersect(clip);
+    if !rect.is_positive() {
+        return mesh;
+    }
+
+    // Droites x + y = k, qui apparaissent comme des « / » à l’écran
+    let half = width / std::f32::consts::SQRT_2 / 2.;
+    let mut k = ((rect.left() + rect.top()) / spacing).ceil() * spacing;
+    while k < rect.right() + rect.bottom() {
+        let x0 = rect.left().max(k - rect.bottom());
+        let x1 = rect.right().min(k - rect.top());
+        if x1 > x0 {
+            let (a, b) = (Pos2::new(x0, k - x0), Pos2::new(x1, k - x1));
+            let base = mesh.vertices.len() as u32;
+            mesh.colored_vertex(Pos2::new(a.x - half, a.y - half), color);
+            mesh.colored_vertex(Pos2::new(a.x + half, a.y + half), color);
+            mesh.colored_vertex(Pos2::new(b.x + half, b.y + half), color);
+            mesh.colored_vertex(Pos2::new(b.x - half, b.y - half), color);
+            mesh.add_triangle(base, base + 1, base + 2);
+            mesh.add_triangle(base, base + 2, base + 3);
+        }
+        k += spacing;
+    }
+    mesh
+}
@@ -0,0 +1,78 @@
+//! Module de filtrage de l’affichage par bande d’amplification.
+//!
+//! Les boutons de bande du panneau latéral acceptent des modificateurs :
+//! - clic simple : zoom sur la bande ;
+//! - Ctrl+clic : zoom et masquage des tâches n’utilisant pas l’amplificateur ;
+//! - Maj+clic : zoom et affichage de la seule zone de fond de la bande.
+//!
+//! Les boutons de système se comportent de même : clic simple pour zoomer sur la
+//! bande couverte par le système, Ctrl+clic pour n’afficher que ses tâches.
+
+use crate::tools::background::{BackgroundZone, BackgroundZoneKind};
+use crate::tools::system::System;
+use crate::tools::task::{Amplifier, Task};
+
+/// État des filtres d’affichage par bande.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BandFilter {
+    /// Si défini, seules les tâches de cet amplificateur sont affichées.
+    pub tasks_of: Option<Amplifier>,
+    /// Si défini, seules les tâches des amplificateurs de ce système sont affichées.
+    pub tasks_of_system: Option<System>,
+    /// Si défini, seule la zone de fond de cet amplificateur est affichée
+    /// (la zone de réception reste visible).
+    pub solo_zone: Option<Amplifier>,
+}
+
+impl BandFilter {
+    /// Indique si aucun filtre n’est actif.
+    pub fn is_empty(&self) -> bool {
+        self.tasks_of.is_none() && self.tasks_of_system.is_none() && self.solo_zone.is_none()
+    }
+
+    /// Indique si la tâche doit être affichée.
+    pub fn shows_task(&self, task: &Task) -> bool {
+        self.tasks_of.is_none_or(|amp| task.amplifier == amp)
+            && self.tasks_of_system.as_ref().is_none_or(|system| system.contains(task.amplifier))
+    }
+
+    /// Indique si la zone de fond doit être affichée.
+    pub fn shows_zone(&self, zone: &BackgroundZone) -> bool {
+        match (self.solo_zone, zone.kind) {
+            (Some(solo), BackgroundZoneKind::Amplifier(amp)) => amp == solo,
+            _ => true,
+        }
+    }
+
+    /// Applique un clic sur le bouton de bande de `amp` selon les modificateurs.
+    pub fn on_band_click(&mut self, amp: Amplifier, modifiers: egui::Modifiers) {
+        if modifiers.command {
+            self.tasks_of = Some(amp);
+        }
+        if modifiers.shift {
+            self.solo_zone = Some(amp);
+        }
+    }
+
+    /// Applique un clic sur le bouton du système selon les modificateurs.
+    pub fn on_system_click(&mut self, system: &System, modifiers: egui::Modifiers) {
+        if modifiers.command {
+            self.tasks_of_system = Some(system.clone());
+        }
+    }
+
+    /// Description textuelle des filtres actifs.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(amp) = self.tasks_of {
+            parts.push(format!("tâches {:?}", amp));
+        }
+        if let Some(system) = &self.tasks_of_system {
+            parts.push(format!("système {}", system.name));
+        }
+        if let Some(amp) = self.solo_zone {
+            parts.push(format!("zone {:?} seule", amp));
+        }
+        parts.join(", ")
+    }
+}
@@ -0,0 +1,146 @@
+//! Module d’export du plan vers des formats de diagramme de Gantt standards.
+//!
+//! Ces exports permettent d’intégrer le plan fréquence/temps dans les outils de
+//! documentation de projet existants :
+//! - Mermaid (`gantt`), avec des dates exprimées en millisecondes ;
+//! - MS Project XML, avec des dates relatives à une origine de mission fixe.
+//!
+//! Les tâches sont regroupées par amplificateur et leur plage de fréquence est
+//! ajoutée au nom, ces formats n’ayant pas d’axe fréquentiel. Les tâches
+//! polygonales sont exportées selon leur boîte englobante.
+
+use std::fs;
+use std::path::Path;
+
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::timefmt::format_iso;
+use crate::tools::utils::freq_decimals;
+
+/// Origine temporelle arbitraire utilisée pour MS Project (t = 0 ms).
+const MSPROJECT_ORIGIN: &str = "2000-01-01T00:00:00";
+/// Origine MS Project en ms depuis l’époque Unix.
+const MSPROJECT_ORIGIN_UNIX_MS: i64 = 946_684_800_000;
+
+/// Formats d’export de Gantt supportés.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GanttFormat {
+    Mermaid,
+    MsProject,
+}
+
+impl GanttFormat {
+    /// Extension de fichier conventionnelle du format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GanttFormat::Mermaid => "mmd",
+            GanttFormat::MsProject => "xml",
+        }
+    }
+}
+
+/// Libellé d’une tâche incluant sa plage de fréquence (signalée comme profil si
+/// la tâche est polygonale).
+fn label(task: &Task) -> String {
+    let profile = if task.shape.is_some() { ", profil" } else { "" };
+    let decimals = freq_decimals(task.freq_end - task.freq_start);
+    format!("{} ({:.*}-{:.*} MHz{})", task.name, decimals, task.freq_start, decimals, task.freq_end, profile)
+}
+
+/// Génère un diagramme Mermaid `gantt` avec une section par amplificateur.
+pub fn to_mermaid(tasks: &[Task]) -> String {
+    let mut out = String::from("gantt\n    title Plan de brouillage\n    dateFormat x\n    axisFormat %S.%L s\n");
+    for amp in Amplifier::ALL {
+        let mut section = tasks.iter().filter(|t| t.amplifier == amp).peekable();
+        if section.peek().is_none() {
+            continue;
+        }
+        out.push_str(&format!("    section {:?}\n", amp));
+        for task in section {
+            // `:` et `#` ont une signification dans la syntaxe Mermaid
+            let name = label(task).replace([':', '#'], " ");
+            out.push_str(&format!("    {} :{:.0}, {:.0}\n", name, task.time_start, task.time_end));
+        }
+    }
+    out
+}
+
+/// Formate une date MS Project à partir d’un temps de mission en ms.
+fn msproject_date(time_ms: f64) -> String {
+    // Les durées de plus de 24 h reportent les jours sur la date
+    let iso = format_iso(MSPROJECT_ORIGIN_UNIX_MS + time_ms.max(0.).round() as i64);
+    iso.trim_end_matches('Z').to_string()
+}
+
+/// Échappe les caractères spéciaux XML.
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Génère un document MS Project XML avec une tâche récapitulative par amplificateur.
+pub fn to_msproject_xml(tasks: &[Task]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<Project xmlns=\"http://schemas.microsoft.com/project\">\n");
+    out.push_str("  <Name>Plan de brouillage</Name>\n");
+    out.push_str(&format!("  <StartDate>{}</StartDate>\n", MSPROJECT_ORIGIN));
+    out.push_str("  <Tasks>\n");
+
+    let mut uid = 0;
+    let mut push_task = |out: &mut String, name: &str, start: f64, end: f64, level: u32, summary: bool| {
+        uid += 1;
+        out.push_str("    <Task>\n");
+        out.push_str(&format!("      <UID>{}</UID>\n      <ID>{}</ID>\n", uid, uid));
+        out.push_str(&format!("      <Name>{}</Name>\n", xml_escape(name)));
+        out.push_str(&format!("      <OutlineLevel>{}</OutlineLevel>\n", level));
+        out.push_str(&format!("      <Summary>{}</Summary>\n", summary as u8));
+        out.push_str(&format!("      <Start>{}</Start>\n", msproject_date(start)));
+        out.push_str(&format!("      <Finish>{}</Finish>\n", msproject_date(end)));
+        out.push_str(&format!("      <Duration>PT0H0M{:.3}S</Duration>\n", (end - start) / 1000.));
+        out.push_str("    </Task>\n");
+    };
+
+    for amp in Amplifier::ALL {
+        let section: Vec<&Task> = tasks.iter().filter(|t| t.amplifier == amp).collect();
+        if section.is_empty() {
+            continue;
+        }
+        let start = section.iter().map(|t| t.time_start).fold(f64::INFINITY, f64::min);
+        let end = section.iter().map(|t| t.time_end).fold(f64::NEG_INFINITY, f64::max);
+        push_task(&mut out, &format!("{:?}", amp), start, end, 1, true);
+        for task in section {
+            push_task(&mut out, &label(task), task.time_start, task.time_end, 2, false);
+        }
+    }
+
+    out.push_str("  </Tasks>\n</Project>\n");
+    out
+}
+
+/// Exporte le plan dans un fichier au format de Gantt demandé.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier ne peut pas être écrit.
+pub fn export_gantt(path: &Path, tasks: &[Task], format: GanttFormat) -> Result<(), String> {
+    let content = match format {
+        GanttFormat::Mermaid => to_mermaid(tasks),
+        GanttFormat::MsProject => to_msproject_xml(tasks),
+    };
+    fs::write(path, content)
+        .map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn msproject_dates_carry_hours_into_days() {
+        let long = Task::builder("Veille", Amplifier::A20_500).freq(100., 200.).time(3_600_000., 90_000_000.).build().unwrap();
+        let xml = to_msproject_xml(&[long]);
+        assert!(xml.contains("2000-01-01T01:00:00.000"));
+        assert!(xml.contains("2000-01-02T01:00:00.000"));
+    }
+}
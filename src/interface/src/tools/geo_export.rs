@@ -0,0 +1,133 @@
+//! Module d’export géographique du plan (KML et GeoJSON).
+//!
+//! Les tâches localisées (champ `location`) correspondent à des zones
+//! d’engagement : elles sont exportées sous forme de points accompagnés de leur
+//! fenêtre temporelle et de leur plage de fréquence, pour être superposées au
+//! plan dans les outils cartographiques. Lorsque l’heure H est connue, la fenêtre
+//! est aussi exprimée en temps absolu (ISO 8601), ce qui permet l’animation
+//! temporelle dans les outils qui la gèrent. Les tâches sans position sont ignorées.
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::tools::gantt_export::xml_escape;
+use crate::tools::task::{Location, Task};
+use crate::tools::timefmt::{format_iso, TimeDisplay};
+use crate::tools::utils::format_freq;
+
+/// Formats d’export géographique supportés.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeoFormat {
+    Kml,
+    GeoJson,
+}
+
+impl GeoFormat {
+    /// Extension de fichier conventionnelle du format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GeoFormat::Kml => "kml",
+            GeoFormat::GeoJson => "geojson",
+        }
+    }
+}
+
+/// Tâches localisées du plan, avec leur position.
+fn located(tasks: &[Task]) -> impl Iterator<Item = (&Task, Location)> {
+    tasks.iter().filter_map(|t| t.location.map(|l| (t, l)))
+}
+
+/// Instants absolus ISO 8601 de début et de fin de la tâche, si l’heure H est connue.
+fn absolute_window(task: &Task, display: &TimeDisplay) -> Option<(String, String)> {
+    let start = display.mission_start?;
+    Some((
+        format_iso(start + task.time_start.round() as i64),
+        format_iso(start + task.time_end.round() as i64),
+    ))
+}
+
+/// Description lisible de la tâche : fenêtre temporelle, plage de fréquence et amplificateur.
+fn description(task: &Task, display: &TimeDisplay) -> String {
+    let span = task.freq_end - task.freq_start;
+    let mut text = format!(
+        "Fenêtre : {} – {}\nFréquences : {} – {}\nAmplificateur : {:?}",
+        display.format(task.time_start),
+        display.format(task.time_end),
+        format_freq(task.freq_start, span),
+        format_freq(task.freq_end, span),
+        task.amplifier
+    );
+    if let Some(platform) = &task.platform {
+        text.push_str(&format!("\nPlateforme : {}", platform));
+    }
+    text
+}
+
+/// Génère un document KML avec un repère par tâche localisée.
+pub fn to_kml(tasks: &[Task], display: &TimeDisplay) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n");
+    out.push_str("  <Document>\n    <name>Plan de brouillage</name>\n");
+    for (task, location) in located(tasks) {
+        out.push_str("    <Placemark>\n");
+        out.push_str(&format!("      <name>{}</name>\n", xml_escape(&task.name)));
+        out.push_str(&format!("      <description>{}</description>\n", xml_escape(&description(task, display))));
+        if let Some((begin, end)) = absolute_window(task, display) {
+            out.push_str(&format!("      <TimeSpan><begin>{}</begin><end>{}</end></TimeSpan>\n", begin, end));
+        }
+        // KML ordonne les coordonnées en longitude, latitude
+        out.push_str(&format!("      <Point><coordinates>{},{}</coordinates></Point>\n", location.lon, location.lat));
+        out.push_str("    </Placemark>\n");
+    }
+    out.push_str("  </Document>\n</kml>\n");
+    out
+}
+
+/// Génère une collection GeoJSON avec un point par tâche localisée.
+pub fn to_geojson(tasks: &[Task], display: &TimeDisplay) -> String {
+    let features: Vec<Value> = located(tasks)
+        .map(|(task, location)| {
+            let mut properties = json!({
+                "name": task.name,
+                "amplifier": format!("{:?}", task.amplifier),
+                "freq_start_mhz": task.freq_start,
+                "freq_end_mhz": task.freq_end,
+                "time_start_ms": task.time_start,
+                "time_end_ms": task.time_end,
+                "description": description(task, display),
+            });
+            if let Some(platform) = &task.platform {
+                properties["platform"] = json!(platform);
+            }
+            if let Some((begin, end)) = absolute_window(task, display) {
+                properties["begin"] = json!(begin);
+                properties["end"] = json!(end);
+            }
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [location.lon, location.lat] },
+                "properties": properties,
+            })
+        })
+        .collect();
+    let collection = json!({ "type": "FeatureCollection", "features": features });
+    serde_json::to_string_pretty(&collection).unwrap_or_default()
+}
+
+/// Exporte les tâches localisées dans un fichier au format géographique demandé.
+///
+/// Retourne le nombre de tâches exportées.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier ne peut pas être écrit.
+pub fn export_geo(path: &Path, tasks: &[Task], display: &TimeDisplay, format: GeoFormat) -> Result<usize, String> {
+    let content = match format {
+        GeoFormat::Kml => to_kml(tasks, display),
+        GeoFormat::GeoJson => to_geojson(tasks, display),
+    };
+    fs::write(path, content).map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))?;
+    Ok(located(tasks).count())
+}
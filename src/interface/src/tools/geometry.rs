@@ -0,0 +1,148 @@
+//! Module de géométrie plane sur des polygones fréquence/temps.
+//!
+//! Les zones de fond et les tâches non rectangulaires sont décrites par des
+//! polygones quelconques (`[fréquence, temps]`). Ce module fournit le test
+//! d’appartenance d’un point, la détection de chevauchement entre polygones et
+//! la triangulation nécessaire à leur remplissage (les polygones `egui_plot` ne
+//! sont remplis correctement que s’ils sont convexes).
+
+/// Indique si un point `(x, y)` se trouve dans le polygone (algorithme du rayon).
+pub fn point_in_polygon(points: &[[f64; 2]], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let n = points.len();
+    if n < 3 {
+        return false;
+    }
+    let mut j = n - 1;
+    for i in 0..n {
+        let (xi, yi) = (points[i][0], points[i][1]);
+        let (xj, yj) = (points[j][0], points[j][1]);
+        if (yi > y) != (yj > y)
+            && (x < (xj - xi) * (y - yi) / (yj - yi + f64::EPSILON) + xi)
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Produit vectoriel `(b - a) × (c - a)`.
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Aire signée du polygone (positive si les sommets sont dans le sens trigonométrique).
+pub fn signed_area(points: &[[f64; 2]]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        a[0] * b[1] - b[0] * a[1]
+    }).sum::<f64>() / 2.
+}
+
+/// Indique si les segments `[a, b]` et `[c, d]` se croisent strictement
+/// (un simple contact ne compte pas).
+fn segments_cross(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> bool {
+    let (d1, d2) = (cross(c, d, a), cross(c, d, b));
+    let (d3, d4) = (cross(a, b, c), cross(a, b, d));
+    d1 * d2 < 0. && d3 * d4 < 0.
+}
+
+/// Découpe un polygone simple en triangles (méthode des oreilles).
+pub fn triangulate(points: &[[f64; 2]]) -> Vec<[[f64; 2]; 3]> {
+    let mut triangles = Vec::new();
+    if points.len() < 3 {
+        return triangles;
+    }
+    // Sommets parcourus dans le sens trigonométrique
+    let mut remaining: Vec<[f64; 2]> = points.to_vec();
+    if signed_area(&remaining) < 0. {
+        remaining.reverse();
+    }
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            cross(a, b, c) > 0.
+                && !remaining.iter().any(|&p| {
+                    p != a && p != b && p != c
+                        && cross(a, b, p) >= 0. && cross(b, c, p) >= 0. && cross(c, a, p) >= 0.
+                })
+        });
+        // Polygone dégénéré ou auto-intersecté : on termine en éventail
+        let i = ear.unwrap_or(0);
+        triangles.push([remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]]);
+        remaining.remove(i);
+    }
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+/// Indique si deux polygones se chevauchent sur une aire non nulle.
+///
+/// Des polygones simplement adjacents ne sont pas considérés en chevauchement.
+pub fn polygons_overlap(a: &[[f64; 2]], b: &[[f64; 2]]) -> bool {
+    let edges = |p: &[[f64; 2]]| (0..p.len()).map(|i| (p[i], p[(i + 1) % p.len()])).collect::<Vec<_>>();
+    let (edges_a, edges_b) = (edges(a), edges(b));
+    if edges_a.iter().any(|&(p, q)| edges_b.iter().any(|&(r, s)| segments_cross(p, q, r, s))) {
+        return true;
+    }
+    // Sans croisement d’arêtes, l’un des polygones contient l’autre ou ils sont disjoints
+    let centroids = |p: &[[f64; 2]]| {
+        triangulate(p)
+            .into_iter()
+            .map(|[u, v, w]| [(u[0] + v[0] + w[0]) / 3., (u[1] + v[1] + w[1]) / 3.])
+            .collect::<Vec<_>>()
+    };
+    centroids(a).iter().any(|c| point_in_polygon(b, c[0], c[1]))
+        || centroids(b).iter().any(|c| point_in_polygon(a, c[0], c[1]))
+}
+
+/// Indique si le polygone `a`, élargi de `margin` de part et d’autre selon
+/// l’axe des fréquences, chevauche le polygone `b` sur une aire non nulle.
+///
+/// Le polygone élargi est l’union de `a` translaté de `-margin` et de `+margin`
+/// et des quadrilatères balayés par chacune de ses arêtes.
+pub fn widened_polygons_overlap(a: &[[f64; 2]], b: &[[f64; 2]], margin: f64) -> bool {
+    if margin <= 0. {
+        return polygons_overlap(a, b);
+    }
+    let shifted = |delta: f64| a.iter().map(|p| [p[0] + delta, p[1]]).collect::<Vec<_>>();
+    polygons_overlap(&shifted(-margin), b)
+        || polygons_overlap(&shifted(margin), b)
+        || (0..a.len()).any(|i| {
+            let (p, q) = (a[i], a[(i + 1) % a.len()]);
+            let swept = [[p[0] - margin, p[1]], [q[0] - margin, q[1]], [q[0] + margin, q[1]], [p[0] + margin, p[1]]];
+            // Arête horizontale : le quadrilatère balayé est dégénéré
+            p[1] != q[1] && polygons_overlap(&swept, b)
+        })
+}
+
+/// Analyse une liste de sommets au format `f,t; f,t; …`.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si un sommet est mal formé ou s’il y a moins de 3 sommets.
+pub fn parse_points(text: &str) -> Result<Vec<[f64; 2]>, String> {
+    let points = text
+        .split(';')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (f, t) = p.split_once(',').ok_or(format!("Sommet invalide : « {} »", p))?;
+            let value = |v: &str| v.trim().parse::<f64>().map_err(|_| format!("Valeur invalide : « {} »", v));
+            Ok([value(f)?, value(t)?])
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if points.len() < 3 {
+        return Err("Un polygone nécessite au moins 3 sommets".into());
+    }
+    Ok(points)
+}
+
+/// Met en forme une liste de sommets au format `f,t; f,t; …`.
+pub fn format_points(points: &[[f64; 2]]) -> String {
+    points.iter().map(|[f, t]| format!("{},{}", f, t)).collect::<Vec<_>>().join("; ")
+}
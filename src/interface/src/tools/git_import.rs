@@ -0,0 +1,243 @@
+//! Module d’import d’un plan depuis un dépôt Git.
+//!
+//! Le panneau latéral permet de récupérer un fichier de plan (JSON, YAML ou
+//! TOML, voir [`crate::tools::plan`]) à une référence donnée (branche, étiquette
+//! ou commit) d’un dépôt Git distant ou local. La récupération appelle la
+//! commande `git` dans un thread dédié (`git fetch --depth 1` dans un dépôt
+//! temporaire, puis `git show`) et nécessite la fonctionnalité `git`.
+//!
+//! Le commit récupéré est inscrit dans les métadonnées de chaque tâche importée
+//! ([`COMMIT_METADATA_KEY`]) et conservé comme origine du plan : tant qu’il est
+//! connu, le panneau signale les écarts du plan local par rapport à la révision
+//! récupérée.
+
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+use crate::tools::baseline::{plan_deviations, Deviation};
+use crate::tools::plan::{parse_plan, PlanFormat};
+use crate::tools::task::Task;
+
+/// Clé de métadonnée portant le commit d’origine des tâches importées.
+pub const COMMIT_METADATA_KEY: &str = "git_commit";
+
+/// Emplacement d’un fichier de plan dans un dépôt Git.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GitSource {
+    /// URL (ou chemin) du dépôt.
+    pub url: String,
+    /// Branche, étiquette ou commit (`HEAD` si vide).
+    pub reference: String,
+    /// Chemin du fichier de plan dans le dépôt.
+    pub path: String,
+}
+
+impl Default for GitSource {
+    fn default() -> Self {
+        Self { url: String::new(), reference: "HEAD".into(), path: "plan.json".into() }
+    }
+}
+
+impl GitSource {
+    /// Référence à récupérer (`HEAD` si elle n’est pas renseignée).
+    fn reference(&self) -> &str {
+        match self.reference.trim() {
+            "" => "HEAD",
+            reference => reference,
+        }
+    }
+
+    /// Description lisible de l’emplacement : `url@référence:chemin`.
+    pub fn label(&self) -> String {
+        format!("{}@{}:{}", self.url.trim(), self.reference(), self.path.trim())
+    }
+}
+
+/// Plan récupéré depuis un dépôt Git.
+#[derive(Clone, Debug)]
+pub struct GitPlan {
+    /// Emplacement du fichier de plan.
+    pub source: GitSource,
+    /// Commit récupéré (empreinte complète).
+    pub commit: String,
+    /// Tâches du plan, annotées du commit d’origine.
+    pub tasks: Vec<Task>,
+}
+
+impl GitPlan {
+    /// Empreinte abrégée du commit.
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(7)]
+    }
+
+    /// Écarts du plan local `tasks` par rapport à la révision récupérée.
+    pub fn divergence(&self, tasks: &[Task]) -> Vec<Deviation> {
+        plan_deviations(&self.tasks, tasks)
+    }
+}
+
+/// Exécute une commande `git` et retourne sa sortie standard.
+#[cfg(feature = "git")]
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Exécution de git impossible : {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git {} : {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    String::from_utf8(output.stdout).map_err(|_| format!("git {} : sortie UTF-8 invalide", args[0]))
+}
+
+/// Récupère le contenu du fichier de plan et le commit correspondant, dans le
+/// dépôt temporaire `dir`.
+#[cfg(feature = "git")]
+fn fetch_file(source: &GitSource, dir: &Path) -> Result<(String, String), String> {
+    git(dir, &["init", "--quiet"])?;
+    git(dir, &["fetch", "--quiet", "--depth", "1", "--", source.url.trim(), source.reference()])?;
+    let commit = git(dir, &["rev-parse", "FETCH_HEAD"])?.trim().to_string();
+    let content = git(dir, &["show", &format!("FETCH_HEAD:{}", source.path.trim())])?;
+    Ok((content, commit))
+}
+
+/// Récupère le contenu du fichier de plan et le commit correspondant.
+#[cfg(feature = "git")]
+fn fetch_content(source: &GitSource) -> Result<(String, String), String> {
+    let dir = std::env::temp_dir().join(format!("gantt_git_{}_{}", std::process::id(), fastrand::u64(..)));
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Création de {} impossible : {}", dir.display(), e))?;
+    let result = fetch_file(source, &dir);
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        eprintln!("Suppression de {} impossible : {}", dir.display(), e);
+    }
+    result
+}
+
+/// Récupération indisponible sans la fonctionnalité `git`.
+#[cfg(not(feature = "git"))]
+fn fetch_content(_source: &GitSource) -> Result<(String, String), String> {
+    Err("Import Git indisponible : fonctionnalité `git` non compilée".into())
+}
+
+/// Récupère un plan depuis un dépôt Git.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si l’URL ou le chemin est vide, si la commande
+/// `git` échoue (dépôt, référence ou fichier introuvable) ou si le plan est mal formé.
+pub fn fetch_plan(source: &GitSource) -> Result<GitPlan, String> {
+    if source.url.trim().is_empty() || source.path.trim().is_empty() {
+        return Err("Import Git : URL du dépôt et chemin du plan requis".into());
+    }
+    // Une valeur commençant par « - » serait interprétée comme une option de git
+    if source.url.trim().starts_with('-') || source.reference().starts_with('-') {
+        return Err("Import Git : URL ou référence invalide".into());
+    }
+    let (content, commit) = fetch_content(source)?;
+    let path = Path::new(source.path.trim());
+    let mut tasks = parse_plan(&content, PlanFormat::from_path(path))
+        .map_err(|e| format!("Plan {} invalide : {}", source.label(), e))?;
+    for task in &mut tasks {
+        task.fit_to_shape();
+        task.metadata.insert(COMMIT_METADATA_KEY.into(), commit.clone());
+    }
+    Ok(GitPlan { source: source.clone(), commit, tasks })
+}
+
+/// Import en arrière-plan et origine du plan courant.
+#[derive(Default)]
+pub struct GitImporter {
+    /// Emplacement saisi dans le panneau latéral.
+    pub source: GitSource,
+    /// Révision dont provient le plan courant.
+    pub origin: Option<GitPlan>,
+    /// Récupération en cours.
+    pending: Option<Receiver<Result<GitPlan, String>>>,
+    /// Écarts du plan local par rapport à l’origine et révision du plan pour
+    /// laquelle ils ont été calculés.
+    divergence: Option<(u64, Vec<Deviation>)>,
+}
+
+impl GitImporter {
+    /// Lance la récupération du plan saisi dans un thread dédié.
+    pub fn start(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = channel();
+        let (source, ctx) = (self.source.clone(), ctx.clone());
+        thread::spawn(move || {
+            let _ = tx.send(fetch_plan(&source));
+            ctx.request_repaint();
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Indique si une récupération est en cours.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Résultat de la récupération en cours, s’il est disponible. Un plan
+    /// récupéré devient l’origine du plan courant.
+    pub fn poll(&mut self) -> Option<Result<GitPlan, String>> {
+        let result = match self.pending.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("Import Git interrompu".into()),
+        };
+        self.pending = None;
+        if let Ok(plan) = &result {
+            self.origin = Some(plan.clone());
+            self.divergence = None;
+        }
+        Some(result)
+    }
+
+    /// Affiche l’import dans le panneau latéral et indique si l’import est demandé.
+    /// L’import est désactivé si `editable` est faux. Les écarts du plan `tasks`
+    /// ne sont recalculés que lorsque sa révision `revision` change.
+    pub fn show(&mut self, ui: &mut egui::Ui, tasks: &[Task], revision: u64, editable: bool) -> bool {
+        egui::Grid::new("git_import").num_columns(2).show(ui, |ui| {
+            for (label, text, hint) in [
+                ("Dépôt", &mut self.source.url, "https://… ou chemin local"),
+                ("Référence", &mut self.source.reference, "HEAD, branche, étiquette ou commit"),
+                ("Fichier", &mut self.source.path, "plan.json"),
+            ] {
+                ui.label(label);
+                ui.add(egui::TextEdit::singleline(text).hint_text(hint));
+                ui.end_row();
+            }
+        });
+        let requested = ui
+            .add_enabled(editable && !self.is_pending(), egui::Button::new("Importer depuis Git"))
+            .on_hover_text("Remplace le plan courant par le fichier récupéré")
+            .clicked();
+        if self.is_pending() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Récupération en cours…");
+            });
+        }
+        if let Some(origin) = &self.origin {
+            ui.weak(format!("Origine : {} ({})", origin.source.label(), origin.short_commit()));
+            if self.divergence.as_ref().is_none_or(|(computed, _)| *computed != revision) {
+                self.divergence = Some((revision, origin.divergence(tasks)));
+            }
+            let divergence = self.divergence.as_ref().map_or(&[][..], |(_, divergence)| divergence);
+            if divergence.is_empty() {
+                ui.label("Plan local identique à la révision récupérée");
+            } else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 170, 0),
+                    format!("Plan local divergent de {} : {} écart(s)", origin.short_commit(), divergence.len()),
+                )
+                .on_hover_text(divergence.iter().map(|d| d.detail.as_str()).collect::<Vec<_>>().join("\n"));
+            }
+            if ui.small_button("Oublier l'origine").clicked() {
+                self.origin = None;
+                self.divergence = None;
+            }
+        }
+        requested
+    }
+}
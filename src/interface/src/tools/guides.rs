@@ -0,0 +1,200 @@
+//! Module des guides magnétiques d’aide au placement.
+//!
+//! Pendant le glisser d’une tâche ([`crate::tools::placement`]), des guides
+//! temporaires signalent les repères les plus proches de ses bords : bord de
+//! bande d’amplificateur le plus proche, bords des tâches voisines (chevauchant
+//! la tâche sur l’autre axe) et limite de la fenêtre de garde Rx. Chaque guide
+//! porte l’écart numérique entre le bord de la tâche et le repère. Lorsque
+//! l’écart est inférieur à la tolérance de saisie, la tâche est aimantée sur le
+//! repère ([`snap`]).
+
+use egui::{Align2, Color32, FontId, Pos2, Rect, Shape, Stroke};
+
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::{format_bandwidth, freq_to_x};
+
+/// Couleur des guides de bande d’amplificateur.
+const AMPLIFIER_GUIDE_COLOR: Color32 = Color32::from_rgb(90, 180, 255);
+
+/// Couleur des guides de tâches voisines.
+const NEIGHBOR_GUIDE_COLOR: Color32 = Color32::from_rgb(230, 110, 230);
+
+/// Couleur du guide de la garde Rx.
+const RX_GUIDE_COLOR: Color32 = Color32::from_rgb(200, 200, 200);
+
+/// Axe d’un guide.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuideAxis {
+    /// Guide vertical, à une fréquence donnée.
+    Frequency,
+    /// Guide horizontal, à un temps donné.
+    Time,
+}
+
+/// Nature du repère d’un guide.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuideKind {
+    /// Bord de bande d’un amplificateur.
+    Amplifier,
+    /// Bord d’une tâche voisine.
+    Neighbor,
+    /// Limite de la fenêtre de garde Rx.
+    RxGuard,
+}
+
+impl GuideKind {
+    /// Couleur du guide.
+    fn color(self) -> Color32 {
+        match self {
+            GuideKind::Amplifier => AMPLIFIER_GUIDE_COLOR,
+            GuideKind::Neighbor => NEIGHBOR_GUIDE_COLOR,
+            GuideKind::RxGuard => RX_GUIDE_COLOR,
+        }
+    }
+}
+
+/// Guide entre un bord de la tâche et le repère le plus proche.
+#[derive(Clone, Debug)]
+pub struct Guide {
+    /// Axe du guide.
+    pub axis: GuideAxis,
+    /// Nature du repère.
+    pub kind: GuideKind,
+    /// Position du repère (MHz ou ms).
+    pub position: f64,
+    /// Position du bord de la tâche le plus proche (MHz ou ms).
+    pub edge: f64,
+}
+
+impl Guide {
+    /// Écart signé entre le bord de la tâche et le repère, en unités du graphe.
+    fn offset(&self, log: bool) -> f64 {
+        match self.axis {
+            GuideAxis::Frequency => freq_to_x(self.position, log) - freq_to_x(self.edge, log),
+            GuideAxis::Time => self.position - self.edge,
+        }
+    }
+
+    /// Libellé de l’écart.
+    pub fn label(&self) -> String {
+        let gap = (self.position - self.edge).abs();
+        match self.axis {
+            GuideAxis::Frequency => format_bandwidth(gap),
+            GuideAxis::Time => format!("{:.1} ms", gap),
+        }
+    }
+}
+
+/// Repère le plus proche d’un des bords `edges`, parmi `candidates`, selon la
+/// distance mesurée par `distance`.
+fn nearest(
+    axis: GuideAxis,
+    kind: GuideKind,
+    edges: [f64; 2],
+    candidates: impl IntoIterator<Item = f64>,
+    distance: impl Fn(f64, f64) -> f64,
+) -> Option<Guide> {
+    candidates
+        .into_iter()
+        .flat_map(|position| edges.map(|edge| (position, edge)))
+        .min_by(|a, b| distance(a.0, a.1).total_cmp(&distance(b.0, b.1)))
+        .map(|(position, edge)| Guide { axis, kind, position, edge })
+}
+
+/// Guides de la tâche `task` : bord d’amplificateur le plus proche, bords des
+/// tâches voisines `others` les plus proches sur chaque axe, limite de la garde
+/// Rx de `rx_guard_ms` ms.
+pub fn find_guides<'a>(task: &Task, others: impl IntoIterator<Item = &'a Task> + Clone, rx_guard_ms: f64, log: bool) -> Vec<Guide> {
+    let freq_distance = |a: f64, b: f64| (freq_to_x(a, log) - freq_to_x(b, log)).abs();
+    let time_distance = |a: f64, b: f64| (a - b).abs();
+    let freq_edges = [task.freq_start, task.freq_end];
+    let time_edges = [task.time_start, task.time_end];
+    let bands = Amplifier::ALL.iter().flat_map(|a| {
+        let (start, end) = a.band();
+        [start, end]
+    });
+    // Voisines : tâches chevauchant la tâche sur l’autre axe
+    let freq_neighbors = others
+        .clone()
+        .into_iter()
+        .filter(|t| t.time_start < task.time_end && task.time_start < t.time_end)
+        .flat_map(|t| [t.freq_start, t.freq_end]);
+    let time_neighbors = others
+        .into_iter()
+        .filter(|t| t.freq_start < task.freq_end && task.freq_start < t.freq_end)
+        .flat_map(|t| [t.time_start, t.time_end]);
+    [
+        nearest(GuideAxis::Frequency, GuideKind::Amplifier, freq_edges, bands, freq_distance),
+        nearest(GuideAxis::Frequency, GuideKind::Neighbor, freq_edges, freq_neighbors, freq_distance),
+        nearest(GuideAxis::Time, GuideKind::Neighbor, time_edges, time_neighbors, time_distance),
+        nearest(GuideAxis::Time, GuideKind::RxGuard, time_edges, (rx_guard_ms > 0.).then_some(rx_guard_ms), time_distance),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Aimante la tâche sur le guide le plus proche de chaque axe dont l’écart est
+/// inférieur à `tolerance` (`[largeur, durée]` en unités du graphe).
+pub fn snap(task: &mut Task, guides: &[Guide], tolerance: [f64; 2], log: bool) {
+    for (axis, tolerance) in [(GuideAxis::Frequency, tolerance[0]), (GuideAxis::Time, tolerance[1])] {
+        let Some(guide) = guides
+            .iter()
+            .filter(|g| g.axis == axis && g.offset(log).abs() <= tolerance)
+            .min_by(|a, b| a.offset(log).abs().total_cmp(&b.offset(log).abs()))
+        else {
+            continue;
+        };
+        match axis {
+            GuideAxis::Frequency => {
+                let delta = guide.position - guide.edge;
+                if log && task.freq_start + delta <= 0. {
+                    continue;
+                }
+                task.freq_start += delta;
+                task.freq_end += delta;
+                if let Some(shape) = &mut task.shape {
+                    shape.iter_mut().for_each(|p| p[0] += delta);
+                }
+            }
+            GuideAxis::Time => task.shift_time(guide.position - guide.edge),
+        }
+    }
+}
+
+/// Formes des guides : ligne pointillée au repère sur toute la hauteur ou
+/// largeur de `frame`, trait de cote entre le bord de la tâche et le repère, et
+/// écart. `to_screen` convertit un point `[fréquence, temps]` en position écran
+/// et `center` est le centre de la tâche à l’écran.
+pub fn guide_shapes(
+    painter: &egui::Painter,
+    guides: &[Guide],
+    frame: Rect,
+    center: Pos2,
+    to_screen: impl Fn([f64; 2]) -> Pos2,
+) -> Vec<Shape> {
+    let mut shapes = Vec::new();
+    for guide in guides {
+        let color = guide.kind.color();
+        let stroke = Stroke::new(1., color);
+        let (line, from, to) = match guide.axis {
+            GuideAxis::Frequency => {
+                let x = to_screen([guide.position, 0.]).x;
+                let edge = to_screen([guide.edge, 0.]).x;
+                ([Pos2::new(x, frame.top()), Pos2::new(x, frame.bottom())], Pos2::new(edge, center.y), Pos2::new(x, center.y))
+            }
+            GuideAxis::Time => {
+                let y = to_screen([0., guide.position]).y;
+                let edge = to_screen([0., guide.edge]).y;
+                ([Pos2::new(frame.left(), y), Pos2::new(frame.right(), y)], Pos2::new(center.x, edge), Pos2::new(center.x, y))
+            }
+        };
+        shapes.extend(Shape::dashed_line(&line, stroke, 6., 4.));
+        shapes.push(Shape::line_segment([from, to], stroke));
+        let label = painter.layout_no_wrap(guide.label(), FontId::proportional(11.), color);
+        let anchor = if guide.axis == GuideAxis::Frequency { Align2::CENTER_BOTTOM } else { Align2::LEFT_CENTER };
+        let rect = anchor.anchor_size(from.lerp(to, 0.5) + egui::vec2(2., -2.), label.size());
+        shapes.push(Shape::galley(rect.min, label, color));
+    }
+    shapes
+}
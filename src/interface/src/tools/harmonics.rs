@@ -0,0 +1,94 @@
+//! Module de la règle des harmoniques.
+//!
+//! Une forme d’onde de brouillage émise sur une bande `[f1, f2]` produit aussi
+//! de l’énergie sur ses harmoniques (`[n·f1, n·f2]`), et un récepteur
+//! superhétérodyne de fréquence intermédiaire FI y est sensible sur la
+//! fréquence image (`f ± 2·FI`). La règle marque ces fréquences sur toute la
+//! hauteur du graphe, à partir de la tâche sélectionnée ou, à défaut, de la
+//! dernière fréquence cliquée, afin d’anticiper les brouillages involontaires.
+
+use egui::Color32;
+
+use crate::tools::utils::{MAX_FREQ, MIN_FREQ};
+
+/// Couleur des marqueurs d’harmoniques.
+pub const HARMONIC_COLOR: Color32 = Color32::from_rgb(255, 140, 0);
+
+/// Couleur des marqueurs de fréquence image.
+pub const IMAGE_COLOR: Color32 = Color32::from_rgb(200, 120, 255);
+
+/// Rang maximal des harmoniques proposées.
+pub const MAX_ORDER: usize = 5;
+
+/// Marqueur de la règle : bande `[début, fin]` en MHz (une seule fréquence si
+/// début = fin), libellé et couleur.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HarmonicMarker {
+    /// Début de la bande en MHz.
+    pub start: f64,
+    /// Fin de la bande en MHz.
+    pub end: f64,
+    /// Libellé affiché en haut du graphe.
+    pub label: String,
+    /// Couleur du marqueur.
+    pub color: Color32,
+}
+
+/// Réglages de la règle des harmoniques.
+pub struct HarmonicsRuler {
+    /// Règle affichée.
+    pub enabled: bool,
+    /// Rang maximal des harmoniques affichées (2 à [`MAX_ORDER`]).
+    pub max_order: usize,
+    /// Fréquence intermédiaire des récepteurs en MHz (0 : pas de fréquence image).
+    pub if_mhz: f64,
+    /// Dernière fréquence cliquée sur le graphe, en MHz.
+    pub clicked: Option<f64>,
+}
+
+impl Default for HarmonicsRuler {
+    fn default() -> Self {
+        Self { enabled: false, max_order: 3, if_mhz: 70., clicked: None }
+    }
+}
+
+impl HarmonicsRuler {
+    /// Marqueurs de la bande source `[start, end]` en MHz, limités au domaine affiché.
+    pub fn markers(&self, start: f64, end: f64) -> Vec<HarmonicMarker> {
+        let mut markers: Vec<HarmonicMarker> = (2..=self.max_order.clamp(2, MAX_ORDER))
+            .map(|n| HarmonicMarker {
+                start: start * n as f64,
+                end: end * n as f64,
+                label: format!("{}f", n),
+                color: HARMONIC_COLOR,
+            })
+            .collect();
+        if self.if_mhz > 0. {
+            let shift = 2. * self.if_mhz;
+            for (sign, label) in [(1., "image +2FI"), (-1., "image −2FI")] {
+                markers.push(HarmonicMarker {
+                    start: start + sign * shift,
+                    end: end + sign * shift,
+                    label: label.into(),
+                    color: IMAGE_COLOR,
+                });
+            }
+        }
+        markers.retain(|m| m.end >= MIN_FREQ && m.start <= MAX_FREQ);
+        markers
+    }
+
+    /// Affiche les réglages dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, "Afficher la règle")
+            .on_hover_text("Harmoniques et fréquences image de la tâche sélectionnée, ou de la dernière fréquence cliquée");
+        ui.horizontal(|ui| {
+            ui.label("Rang max :");
+            ui.add(egui::DragValue::new(&mut self.max_order).range(2..=MAX_ORDER));
+        });
+        ui.horizontal(|ui| {
+            ui.label("FI :");
+            ui.add(egui::DragValue::new(&mut self.if_mhz).range(0.0..=3000.0).suffix(" MHz"));
+        });
+    }
+}
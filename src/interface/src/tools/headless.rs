@@ -0,0 +1,234 @@
+//! Module du mode sans interface graphique (`--headless`) et de l’autotest (`--self-test`).
+//!
+//! En mode `--headless`, l’interface lit les messages sur stdin comme
+//! l’application graphique, les soumet au même limiteur de débit et les applique
+//! au même état ([`MyApp::ingest`]), puis écrit sur stdout, une ligne JSON par
+//! message, l’événement émis ([`ProtocolEvent`]). À la fermeture de stdin, l’état
+//! final ([`Snapshot`]) est écrit sur une dernière ligne.
+//!
+//! L’autotest lance l’interface en mode `--headless` dans un processus séparé,
+//! lui envoie un script de messages ([`SELF_TEST_SCRIPT`]) et vérifie les
+//! événements émis et l’état final : il garantit le contrat d’échange entre
+//! l’émetteur et l’interface.
+
+use std::fs;
+use std::io::{BufRead, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::app::MyApp;
+use crate::tools::events::EventBus;
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::phase::Phase;
+use crate::tools::protocol::{read_message, ProtocolEvent};
+use crate::tools::rate_limit::STDIN_SOURCE;
+use crate::tools::storage::MemoryStore;
+use crate::tools::task::Task;
+
+/// Messages envoyés par l’autotest, dans l’ordre.
+pub const SELF_TEST_SCRIPT: [&str; 7] = [
+    r#"{"rx_guard_ms": 120}"#,
+    r#"{"phase": {"name": "Autotest", "t_start": 100, "t_end": 400}}"#,
+    r#"{"exclusion": {"name": "Autotest", "freq_start": 1100, "freq_end": 1200}}"#,
+    r#"{"name": "Autotest A", "freq_start": 100, "freq_end": 300, "time_start": 0, "time_end": 200, "amplifier": "A20_500", "tentative": true}"#,
+    r#"{"confirm_where": "name == \"Autotest A\""}"#,
+    r#"{"remove_where": "fmin > 5000"}"#,
+    "pas du JSON",
+];
+
+/// État de l’interface à la fermeture de stdin.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Nombre de messages reçus.
+    pub messages_received: usize,
+    /// Durée de la garde Rx en ms.
+    pub rx_guard_ms: f64,
+    /// Tâches affichées.
+    pub tasks: Vec<Task>,
+    /// Masques d’exclusion.
+    pub exclusions: Vec<ExclusionMask>,
+    /// Phases de mission.
+    pub phases: Vec<Phase>,
+    /// Nombre de tâches dans la corbeille.
+    #[serde(default)]
+    pub trashed: usize,
+}
+
+impl Snapshot {
+    /// Capture l’état de l’application.
+    pub fn of(app: &MyApp) -> Self {
+        Self {
+            messages_received: app.messages_received,
+            rx_guard_ms: app.rx_guard.duration,
+            tasks: app.tasks.clone(),
+            exclusions: app.exclusions.clone(),
+            phases: app.phases.clone(),
+            trashed: app.trash.len(),
+        }
+    }
+}
+
+/// Applique les messages lus sur `input` et écrit les événements émis puis l’état
+/// final sur `output` (mode `--headless`).
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la lecture ou l’écriture échoue.
+pub fn run_session(mut input: impl BufRead, output: &mut impl Write) -> Result<(), String> {
+    let mut app = MyApp::new(EventBus::default(), Box::new(MemoryStore::default()));
+    while let Some(line) = read_message(&mut input).map_err(|e| format!("Lecture de stdin impossible : {}", e))? {
+        let events = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => {
+                let now = Instant::now();
+                let mut events = app.release_held(now, false);
+                events.extend(app.ingest(STDIN_SOURCE, line, now));
+                // Les réponses figurent déjà parmi les événements écrits
+                app.take_replies();
+                events
+            }
+            Err(message) => vec![ProtocolEvent::Error { message }],
+        };
+        write_events(output, &events)?;
+    }
+    // Fin du flux : les messages en attente sont appliqués
+    write_events(output, &app.release_held(Instant::now(), true))?;
+    let snapshot = serde_json::to_string(&Snapshot::of(&app)).map_err(|e| e.to_string())?;
+    writeln!(output, "{}", snapshot).map_err(write_error)
+}
+
+/// Écrit des événements sur `output`, un par ligne.
+fn write_events(output: &mut impl Write, events: &[ProtocolEvent]) -> Result<(), String> {
+    for event in events {
+        let json = serde_json::to_string(event).map_err(|e| e.to_string())?;
+        writeln!(output, "{}", json).map_err(write_error)?;
+    }
+    output.flush().map_err(write_error)
+}
+
+/// Message d’erreur d’écriture sur la sortie.
+fn write_error(e: std::io::Error) -> String {
+    format!("Écriture sur stdout impossible : {}", e)
+}
+
+/// Sépare la sortie d’une session `--headless` en événements et état final.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si une ligne ne correspond ni à un événement ni à l’état final.
+pub fn parse_session_output(text: &str) -> Result<(Vec<ProtocolEvent>, Snapshot), String> {
+    let mut lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let last = lines.pop().ok_or("Sortie vide : état final absent")?;
+    let snapshot = serde_json::from_str(last).map_err(|e| format!("État final invalide : {}", e))?;
+    let events = lines
+        .iter()
+        .map(|l| serde_json::from_str(l).map_err(|e| format!("Événement invalide « {} » : {}", l, e)))
+        .collect::<Result<_, String>>()?;
+    Ok((events, snapshot))
+}
+
+/// Lance `command` en mode `--headless` dans un dossier temporaire (sans fichier de
+/// configuration), lui envoie `script` puis ferme stdin.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le processus ne peut pas être lancé, échoue ou
+/// produit une sortie invalide.
+pub fn run_script(mut command: Command, script: &[&str]) -> Result<(Vec<ProtocolEvent>, Snapshot), String> {
+    static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+    let session = SESSIONS.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("gantt_headless_{}_{}", std::process::id(), session));
+    fs::create_dir_all(&dir).map_err(|e| format!("Création de {} impossible : {}", dir.display(), e))?;
+    let child = command
+        .arg("--headless")
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let result = child.map_err(|e| format!("Lancement de l'interface impossible : {}", e)).and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            for line in script {
+                writeln!(stdin, "{}", line).map_err(|e| format!("Envoi du message impossible : {}", e))?;
+            }
+        }
+        child.wait_with_output().map_err(|e| format!("Attente de l'interface impossible : {}", e))
+    });
+    let _ = fs::remove_dir_all(&dir);
+    let output = result?;
+    if !output.status.success() {
+        return Err(format!("L'interface s'est terminée en erreur ({})", output.status));
+    }
+    parse_session_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Vérifie les événements et l’état final produits par [`SELF_TEST_SCRIPT`].
+///
+/// # Erreurs
+///
+/// Retourne la liste des écarts constatés.
+pub fn check_self_test(events: &[ProtocolEvent], snapshot: &Snapshot) -> Result<(), String> {
+    let mut failures = Vec::new();
+    let expected = [
+        ProtocolEvent::RxGuardSet { duration_ms: 120. },
+        ProtocolEvent::PhaseSet { name: "Autotest".into() },
+        ProtocolEvent::ExclusionSet { name: "Autotest".into() },
+        ProtocolEvent::TaskAdded { key: "Autotest A".into() },
+        ProtocolEvent::TasksConfirmed { count: 1 },
+        ProtocolEvent::TasksRemoved { count: 0 },
+    ];
+    if events.len() != SELF_TEST_SCRIPT.len() {
+        failures.push(format!("{} événements reçus au lieu de {}", events.len(), SELF_TEST_SCRIPT.len()));
+    }
+    for (i, (event, expected)) in events.iter().zip(&expected).enumerate() {
+        if event != expected {
+            failures.push(format!("message {} : {:?} au lieu de {:?}", i + 1, event, expected));
+        }
+    }
+    if let Some(event) = events.get(expected.len()) {
+        if !matches!(event, ProtocolEvent::Error { .. }) {
+            failures.push(format!("message invalide accepté : {:?}", event));
+        }
+    }
+
+    if snapshot.messages_received != SELF_TEST_SCRIPT.len() {
+        failures.push(format!("{} messages comptés au lieu de {}", snapshot.messages_received, SELF_TEST_SCRIPT.len()));
+    }
+    if snapshot.rx_guard_ms != 120. {
+        failures.push(format!("garde Rx de {} ms au lieu de 120 ms", snapshot.rx_guard_ms));
+    }
+    match snapshot.tasks.as_slice() {
+        [task] if task.name == "Autotest A" && !task.tentative => {}
+        [task] => failures.push(format!("tâche « {} » inattendue ou restée provisoire", task.name)),
+        tasks => failures.push(format!("{} tâches au lieu de 1", tasks.len())),
+    }
+    if !snapshot.exclusions.iter().any(|m| m.name == "Autotest") {
+        failures.push("masque d'exclusion absent".into());
+    }
+    if !snapshot.phases.iter().any(|p| p.name == "Autotest") {
+        failures.push("phase de mission absente".into());
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("autotest en échec : {}", failures.join(" ; ")))
+    }
+}
+
+/// Autotest (`--self-test`) : lance l’exécutable courant en mode `--headless` et
+/// vérifie sa réponse à [`SELF_TEST_SCRIPT`].
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur décrivant les écarts constatés.
+pub fn self_test() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Exécutable introuvable : {}", e))?;
+    let (events, snapshot) = run_script(Command::new(exe), &SELF_TEST_SCRIPT)?;
+    check_self_test(&events, &snapshot)?;
+    println!("Autotest réussi : {} messages, {} événements.", snapshot.messages_received, events.len());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Module de la marge aux bords de bande des amplificateurs.
+//!
+//! Le gain d’un amplificateur chute à l’approche des bords de sa bande : une
+//! tâche collée à un bord émet avec une puissance réduite. Une marge de garde,
+//! exprimée en pourcentage de la largeur de bande et réglable dans le panneau
+//! latéral, est réservée à l’intérieur de chaque bord. Les tâches qui y
+//! empiètent sont signalées (contour orange et avertissement), et les marges
+//! sont dessinées en bandes estompées à l’intérieur des zones d’amplificateur.
+
+use crate::tools::task::{Amplifier, Task};
+
+/// Marge par défaut, en pourcentage de la largeur de bande.
+pub const DEFAULT_MARGIN_PCT: f64 = 2.;
+
+/// Tâche empiétant sur la marge d’un bord de bande.
+#[derive(Clone, Debug)]
+pub struct HeadroomWarning {
+    /// Indice de la tâche.
+    pub task_index: usize,
+    /// Bord de bande le plus proche, en MHz.
+    pub edge: f64,
+    /// Distance de la tâche à ce bord, en MHz.
+    pub distance: f64,
+    /// Marge requise, en MHz.
+    pub margin: f64,
+}
+
+/// Réglage de la marge aux bords de bande.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Headroom {
+    /// Indique si la marge est vérifiée et dessinée.
+    pub enabled: bool,
+    /// Marge en pourcentage de la largeur de bande.
+    pub margin_pct: f64,
+}
+
+impl Default for Headroom {
+    fn default() -> Self {
+        Self { enabled: true, margin_pct: DEFAULT_MARGIN_PCT }
+    }
+}
+
+impl Headroom {
+    /// Marge de l’amplificateur en MHz.
+    pub fn margin(&self, amp: Amplifier) -> f64 {
+        let (start, end) = amp.band();
+        (end - start) * self.margin_pct / 100.
+    }
+
+    /// Bandes de garde `(début, fin)` en MHz, à l’intérieur des deux bords de la
+    /// bande de l’amplificateur.
+    pub fn guard_bands(&self, amp: Amplifier) -> [(f64, f64); 2] {
+        let (start, end) = amp.band();
+        let margin = self.margin(amp);
+        [(start, start + margin), (end - margin, end)]
+    }
+
+    /// Vérifie la marge d’une tâche. Les tâches débordant de la bande de leur
+    /// amplificateur ne sont pas concernées (elles sont signalées par ailleurs).
+    pub fn check(&self, task_index: usize, task: &Task) -> Option<HeadroomWarning> {
+        let amp = task.amplifier;
+        if !self.enabled || !amp.covers(task.freq_start, task.freq_end) {
+            return None;
+        }
+        let (start, end) = amp.band();
+        let margin = self.margin(amp);
+        let (edge, distance) = if task.freq_start - start <= end - task.freq_end {
+            (start, task.freq_start - start)
+        } else {
+            (end, end - task.freq_end)
+        };
+        (distance < margin).then_some(HeadroomWarning { task_index, edge, distance, margin })
+    }
+
+    /// Tâches empiétant sur la marge d’un bord de bande.
+    pub fn violations(&self, tasks: &[Task]) -> Vec<HeadroomWarning> {
+        tasks.iter().enumerate().filter_map(|(i, t)| self.check(i, t)).collect()
+    }
+
+    /// Affiche le réglage dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Marge aux bords de bande :")
+                .on_hover_text("Signale les tâches trop proches d'un bord de bande de leur amplificateur (chute de gain)");
+            ui.add_enabled(self.enabled, egui::DragValue::new(&mut self.margin_pct).range(0.0..=25.0).speed(0.1).suffix(" %"));
+        });
+    }
+}
@@ -0,0 +1,201 @@
+//! Module de la hiérarchie des tâches.
+//!
+//! Un plan est naturellement hiérarchique (mission → série → affectation) : une
+//! tâche peut désigner sa tâche parente par `parent_id` (identifiant ou, à
+//! défaut, nom de la parente). Les tâches parentes ne sont pas dessinées comme
+//! des blocs pleins mais comme de fins crochets englobant leurs descendantes ; la
+//! table des tâches les présente sous forme d’arbre repliable et leur info-bulle
+//! agrège leurs sous-tâches.
+//!
+//! Une référence à une tâche inconnue, ou formant un cycle, est ignorée : la
+//! tâche est alors traitée comme une racine.
+
+use std::collections::{HashMap, HashSet};
+
+use egui::{Color32, Pos2, Shape, Stroke};
+
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::{format_bandwidth, format_freq};
+
+/// Longueur des retours horizontaux des crochets, en pixels.
+const BRACKET_TICK_PX: f32 = 6.;
+
+/// Liens parent/enfants des tâches d’un plan.
+#[derive(Clone, Default, Debug)]
+pub struct Hierarchy {
+    /// Indice de la tâche parente de chaque tâche.
+    parents: Vec<Option<usize>>,
+    /// Indices des enfants directs de chaque tâche, dans l’ordre du plan.
+    children: Vec<Vec<usize>>,
+}
+
+impl Hierarchy {
+    /// Résout les liens `parent_id` des tâches.
+    pub fn new(tasks: &[Task]) -> Self {
+        let keys: HashMap<&str, usize> = tasks.iter().enumerate().map(|(i, t)| (t.key(), i)).collect();
+        let mut parents: Vec<Option<usize>> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| t.parent_id.as_deref().and_then(|p| keys.get(p).copied()).filter(|&p| p != i))
+            .collect();
+        // Rupture des cycles : la tâche dont le parent referme la chaîne devient racine
+        for i in 0..tasks.len() {
+            let mut seen = HashSet::from([i]);
+            let mut current = i;
+            while let Some(parent) = parents[current] {
+                if !seen.insert(parent) {
+                    parents[current] = None;
+                    break;
+                }
+                current = parent;
+            }
+        }
+        let mut children = vec![Vec::new(); tasks.len()];
+        for (i, parent) in parents.iter().enumerate() {
+            if let Some(p) = parent {
+                children[*p].push(i);
+            }
+        }
+        Self { parents, children }
+    }
+
+    /// Indice de la tâche parente de la tâche `i`.
+    pub fn parent(&self, i: usize) -> Option<usize> {
+        self.parents.get(i).copied().flatten()
+    }
+
+    /// Enfants directs de la tâche `i`.
+    pub fn children(&self, i: usize) -> &[usize] {
+        self.children.get(i).map_or(&[], Vec::as_slice)
+    }
+
+    /// Indique si la tâche `i` a des sous-tâches.
+    pub fn is_parent(&self, i: usize) -> bool {
+        !self.children(i).is_empty()
+    }
+
+    /// Tâches sans parente, dans l’ordre du plan.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parents.iter().enumerate().filter(|(_, p)| p.is_none()).map(|(i, _)| i)
+    }
+
+    /// Toutes les descendantes de la tâche `i`, en profondeur d’abord.
+    pub fn descendants(&self, i: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut stack: Vec<usize> = self.children(i).iter().rev().copied().collect();
+        while let Some(j) = stack.pop() {
+            result.push(j);
+            stack.extend(self.children(j).iter().rev());
+        }
+        result
+    }
+
+    /// Boîte `[freq_start, time_start, freq_end, time_end]` englobant la tâche `i`
+    /// et ses descendantes.
+    pub fn span(&self, tasks: &[Task], i: usize) -> [f64; 4] {
+        let task = &tasks[i];
+        self.descendants(i).into_iter().map(|j| &tasks[j]).fold(
+            [task.freq_start, task.time_start, task.freq_end, task.time_end],
+            |r, t| [r[0].min(t.freq_start), r[1].min(t.time_start), r[2].max(t.freq_end), r[3].max(t.time_end)],
+        )
+    }
+}
+
+/// Crochets `[ ]` encadrant le rectangle écran `rect` d’une tâche parente.
+pub fn bracket_shapes(rect: egui::Rect, color: Color32) -> Vec<Shape> {
+    let stroke = Stroke::new(1., color);
+    let tick = BRACKET_TICK_PX.min(rect.width() / 3.);
+    let bracket = |x: f32, dir: f32| {
+        Shape::line(
+            vec![
+                Pos2::new(x + dir * tick, rect.top()),
+                Pos2::new(x, rect.top()),
+                Pos2::new(x, rect.bottom()),
+                Pos2::new(x + dir * tick, rect.bottom()),
+            ],
+            stroke,
+        )
+    };
+    vec![bracket(rect.left(), 1.), bracket(rect.right(), -1.)]
+}
+
+/// Affiche la synthèse des sous-tâches de la tâche parente `i` (info-bulle).
+pub fn show_parent_summary(ui: &mut egui::Ui, tasks: &[Task], hierarchy: &Hierarchy, i: usize, freq_span: f64) {
+    let descendants = hierarchy.descendants(i);
+    if descendants.is_empty() {
+        return;
+    }
+    let [freq_start, time_start, freq_end, time_end] = hierarchy.span(tasks, i);
+    let busy: f64 = descendants.iter().map(|&j| tasks[j].time_end - tasks[j].time_start).sum();
+    let amplifiers: Vec<String> = Amplifier::ALL
+        .iter()
+        .filter(|amp| descendants.iter().any(|&j| tasks[j].amplifier == **amp))
+        .map(|amp| format!("{:?}", amp))
+        .collect();
+    let width = freq_end - freq_start;
+    ui.separator();
+    ui.label(format!(
+        "Sous-tâches : {} directes, {} au total\nBande : {} – {} ({})\nPériode : {:.0} – {:.0} ms\nDurée cumulée : {:.0} ms\nAmplificateurs : {}",
+        hierarchy.children(i).len(),
+        descendants.len(),
+        format_freq(freq_start, freq_span.min(width)),
+        format_freq(freq_end, freq_span.min(width)),
+        format_bandwidth(width),
+        time_start,
+        time_end,
+        busy,
+        amplifiers.join(", ")
+    ));
+}
+
+/// Table des tâches présentée sous forme d’arbre repliable.
+#[derive(Default)]
+pub struct TaskTree {
+    /// Clés des tâches parentes repliées.
+    collapsed: HashSet<String>,
+}
+
+impl TaskTree {
+    /// Affiche la table et retourne l’indice de la tâche cliquée.
+    pub fn show(&mut self, ui: &mut egui::Ui, tasks: &[Task], hierarchy: &Hierarchy, selected: Option<usize>) -> Option<usize> {
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            if ui.small_button("Tout déplier").clicked() {
+                self.collapsed.clear();
+            }
+            if ui.small_button("Tout replier").clicked() {
+                self.collapsed = (0..tasks.len()).filter(|&i| hierarchy.is_parent(i)).map(|i| tasks[i].key().to_string()).collect();
+            }
+        });
+        egui::ScrollArea::vertical().max_height(240.).id_salt("task_tree").show(ui, |ui| {
+            // Parcours en profondeur : (indice, profondeur)
+            let mut stack: Vec<(usize, usize)> = hierarchy.roots().collect::<Vec<_>>().into_iter().rev().map(|i| (i, 0)).collect();
+            while let Some((i, depth)) = stack.pop() {
+                let task = &tasks[i];
+                let expanded = !self.collapsed.contains(task.key());
+                ui.horizontal(|ui| {
+                    ui.add_space(depth as f32 * 12.);
+                    if hierarchy.is_parent(i) {
+                        if ui.small_button(if expanded { "▼" } else { "▶" }).clicked() {
+                            if expanded {
+                                self.collapsed.insert(task.key().to_string());
+                            } else {
+                                self.collapsed.remove(task.key());
+                            }
+                        }
+                    } else {
+                        ui.add_space(18.);
+                    }
+                    if ui.selectable_label(selected == Some(i), &task.name).clicked() {
+                        clicked = Some(i);
+                    }
+                    ui.weak(format!("{:.0} – {:.0} ms", task.time_start, task.time_end));
+                });
+                if expanded {
+                    stack.extend(hierarchy.children(i).iter().rev().map(|&j| (j, depth + 1)));
+                }
+            }
+        });
+        clicked
+    }
+}
@@ -0,0 +1,67 @@
+//! Module de gestion de l’historique des modifications du plan (annuler / rétablir).
+//!
+//! Chaque opération utilisateur enregistre un instantané de la liste des tâches
+//! avant modification, ce qui permet d’annuler une opération groupée en une seule fois.
+
+use crate::tools::task::Task;
+
+/// Nombre maximal d’instantanés conservés dans la pile d’annulation.
+const MAX_HISTORY: usize = 100;
+
+/// Historique des états successifs de la liste des tâches.
+#[derive(Default)]
+pub struct History {
+    /// États précédents, du plus ancien au plus récent.
+    undo_stack: Vec<Vec<Task>>,
+    /// États annulés pouvant être rétablis.
+    redo_stack: Vec<Vec<Task>>,
+}
+
+impl History {
+    /// Enregistre l’état courant avant une modification.
+    ///
+    /// Toute nouvelle modification invalide les états pouvant être rétablis.
+    pub fn record(&mut self, tasks: &[Task]) {
+        self.undo_stack.push(tasks.to_vec());
+        if self.undo_stack.len() > MAX_HISTORY {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    /// Annule la dernière opération en restaurant l’état précédent.
+    ///
+    /// Retourne `true` si une opération a été annulée.
+    pub fn undo(&mut self, tasks: &mut Vec<Task>) -> bool {
+        match self.undo_stack.pop() {
+            Some(previous) => {
+                self.redo_stack.push(std::mem::replace(tasks, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rétablit la dernière opération annulée.
+    ///
+    /// Retourne `true` si une opération a été rétablie.
+    pub fn redo(&mut self, tasks: &mut Vec<Task>) -> bool {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                self.undo_stack.push(std::mem::replace(tasks, next));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Indique si une opération peut être annulée.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Indique si une opération peut être rétablie.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
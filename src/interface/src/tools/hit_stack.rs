@@ -0,0 +1,69 @@
+//! Module de désambiguïsation des tâches superposées sous le curseur.
+//!
+//! Plusieurs tâches peuvent contenir le point survolé. Elles forment alors une
+//! pile, de la tâche dessinée au-dessus à celle dessinée en dessous : l’info-bulle
+//! les liste toutes et détaille la tâche courante de la pile, que des clics
+//! répétés au même endroit ou Alt + molette font défiler. La tâche courante est
+//! celle ouverte dans l’inspecteur au clic.
+
+use crate::tools::task::Task;
+
+/// Pile des tâches sous le curseur et tâche courante.
+#[derive(Default)]
+pub struct HitStack {
+    /// Clés des tâches de la pile, de la plus haute à la plus basse.
+    keys: Vec<String>,
+    /// Position de la tâche courante dans la pile.
+    current: usize,
+    /// Un clic a déjà sélectionné une tâche de cette pile.
+    clicked: bool,
+}
+
+impl HitStack {
+    /// Met à jour la pile avec les indices des tâches sous le curseur ; une pile
+    /// différente de la précédente repart de sa tâche la plus haute.
+    pub fn observe(&mut self, tasks: &[Task], hits: &[usize]) {
+        let same = hits.len() == self.keys.len() && hits.iter().zip(&self.keys).all(|(&i, k)| tasks[i].key() == k);
+        if !same {
+            self.keys = hits.iter().map(|&i| tasks[i].key().to_string()).collect();
+            self.current = 0;
+            self.clicked = false;
+        }
+    }
+
+    /// Fait défiler la tâche courante de `step` positions (négatif : vers le haut).
+    pub fn cycle(&mut self, step: isize) {
+        if !self.keys.is_empty() {
+            self.current = (self.current as isize + step).rem_euclid(self.keys.len() as isize) as usize;
+        }
+    }
+
+    /// Tâche sélectionnée par un clic sur la pile `hits` : la tâche courante au
+    /// premier clic, puis la suivante à chaque clic répété.
+    pub fn click(&mut self, tasks: &[Task], hits: &[usize]) -> Option<usize> {
+        self.observe(tasks, hits);
+        if self.clicked {
+            self.cycle(1);
+        }
+        self.clicked = !hits.is_empty();
+        self.current(hits)
+    }
+
+    /// Indice de la tâche courante parmi `hits`.
+    pub fn current(&self, hits: &[usize]) -> Option<usize> {
+        hits.get(self.current).copied()
+    }
+
+    /// Affiche la liste des tâches de la pile, la tâche courante en évidence.
+    pub fn show(&self, ui: &mut egui::Ui, tasks: &[Task], hits: &[usize]) {
+        for (position, &i) in hits.iter().enumerate() {
+            if position == self.current {
+                ui.strong(format!("▶ {}", tasks[i].name));
+            } else {
+                ui.weak(format!("   {}", tasks[i].name));
+            }
+        }
+        ui.weak(format!("{} tâches superposées · clic ou Alt + molette : suivante", hits.len()));
+        ui.separator();
+    }
+}
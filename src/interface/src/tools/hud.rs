@@ -0,0 +1,51 @@
+//! Module de l’affichage tête haute (HUD) du mode temps réel.
+//!
+//! Lorsque l’heure H est définie, un encart dans le coin du graphe indique le
+//! temps écoulé depuis le début de la mission, le délai avant le début de la
+//! prochaine tâche et l’état de la fenêtre de garde Rx. Il est animé par
+//! l’horloge temps réel de l’application et peut être masqué depuis le panneau
+//! latéral.
+
+use egui::{Align2, Color32, RichText};
+
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Task;
+
+/// Met en forme une durée en ms sous la forme `hh:mm:ss.mmm`, précédée de son signe.
+fn format_elapsed(ms: f64) -> String {
+    let sign = if ms < 0. { '-' } else { '+' };
+    let total = ms.abs().round() as i64;
+    let secs = total / 1000;
+    format!("{}{:02}:{:02}:{:02}.{:03}", sign, secs / 3600, (secs / 60) % 60, secs % 60, total % 1000)
+}
+
+/// Prochaine tâche à démarrer après l’instant `elapsed` (ms) : nom et délai en ms.
+fn next_task(tasks: &[Task], elapsed: f64) -> Option<(&str, f64)> {
+    tasks
+        .iter()
+        .filter(|t| t.time_start > elapsed)
+        .min_by(|a, b| a.time_start.total_cmp(&b.time_start))
+        .map(|t| (t.name.as_str(), t.time_start - elapsed))
+}
+
+/// Affiche le HUD dans le coin supérieur droit, `elapsed` ms après l’heure H.
+pub fn show_hud(ctx: &egui::Context, elapsed: f64, tasks: &[Task], rx_guard: &RxGuard) {
+    egui::Area::new(egui::Id::new("hud"))
+        .anchor(Align2::RIGHT_TOP, [-12., 12.])
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(RichText::new(format!("T{}", format_elapsed(elapsed))).monospace().size(18.));
+                match next_task(tasks, elapsed) {
+                    Some((name, delay)) => ui.label(format!("Prochaine tâche : {} dans {:.1} s", name, delay / 1000.)),
+                    None => ui.weak("Aucune tâche à venir"),
+                };
+                match rx_guard.remaining(elapsed) {
+                    Some(remaining) => ui.colored_label(Color32::YELLOW, format!("Garde Rx : encore {:.0} ms", remaining)),
+                    None if elapsed < 0. => ui.label(format!("Garde Rx dans {:.1} s", -elapsed / 1000.)),
+                    None => ui.weak("Garde Rx terminée"),
+                };
+            });
+        });
+}
@@ -0,0 +1,347 @@
+//! Module de l’inspecteur de tâche.
+//!
+//! L’inspecteur est une fenêtre affichant et permettant de modifier les champs de
+//! la tâche sélectionnée. Les temps peuvent être saisis en ms, relativement à
+//! l’heure H (`H+15`) ou sous forme de DTG (`271530ZMAY25`).
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::tools::effect::Effect;
+use crate::tools::geometry::{format_points, parse_points, signed_area};
+use crate::tools::attachment::{attachment_label, classify_attachment, open_attachment, AttachmentKind, MAX_ATTACHMENTS};
+use crate::tools::notes::show_notes;
+use crate::tools::range::RangeEstimate;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task_log::{show_change_log, TaskChange};
+use crate::tools::task::{Amplifier, Location, Task};
+use crate::tools::timefmt::{parse_time, TimeDisplay};
+
+/// Analyse les métadonnées saisies, une paire `clé = valeur` par ligne.
+fn parse_metadata(text: &str) -> Result<BTreeMap<String, String>, String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.trim().to_string())),
+            _ => Err(format!("Métadonnée invalide : « {} » (attendu : clé = valeur)", line.trim())),
+        })
+        .collect()
+}
+
+/// État de l’inspecteur pour la tâche sélectionnée.
+///
+/// Les champs sont conservés sous forme de texte tant que l’utilisateur les édite,
+/// puis analysés lors de la validation.
+pub struct TaskInspector {
+    /// Indice de la tâche inspectée.
+    pub index: usize,
+    /// Clé de la tâche inspectée, à laquelle les modifications sont adressées.
+    pub key: String,
+    id: Option<String>,
+    name: String,
+    freq_start: String,
+    freq_end: String,
+    time_start: String,
+    time_end: String,
+    amplifier: Amplifier,
+    group: String,
+    parent_id: String,
+    platform: String,
+    antenna: String,
+    location: String,
+    effect: Option<Effect>,
+    priority: String,
+    status: String,
+    notes: String,
+    attachments: Vec<String>,
+    new_attachment: String,
+    /// Fichier local dont l’ouverture attend la confirmation de l’opérateur.
+    confirm_open: Option<String>,
+    metadata: String,
+    power: String,
+    freq_tolerance: String,
+    rx_safe: bool,
+    tentative: bool,
+    shape: String,
+    error: Option<String>,
+}
+
+impl TaskInspector {
+    /// Crée un inspecteur initialisé avec les valeurs de la tâche.
+    pub fn new(index: usize, task: &Task) -> Self {
+        Self {
+            index,
+            key: task.key().to_string(),
+            id: task.id.clone(),
+            name: task.name.clone(),
+            freq_start: task.freq_start.to_string(),
+            freq_end: task.freq_end.to_string(),
+            time_start: task.time_start.to_string(),
+            time_end: task.time_end.to_string(),
+            amplifier: task.amplifier,
+            group: task.group.clone().unwrap_or_default(),
+            parent_id: task.parent_id.clone().unwrap_or_default(),
+            platform: task.platform.clone().unwrap_or_default(),
+            antenna: task.antenna.clone().unwrap_or_default(),
+            location: task.location.map(|l| l.to_string()).unwrap_or_default(),
+            effect: task.effect,
+            priority: task.priority.map(|p| p.to_string()).unwrap_or_default(),
+            status: task.status.clone().unwrap_or_default(),
+            notes: task.notes.clone().unwrap_or_default(),
+            attachments: task.attachments.clone(),
+            new_attachment: String::new(),
+            confirm_open: None,
+            metadata: task.metadata.iter().map(|(k, v)| format!("{} = {}\n", k, v)).collect(),
+            power: task.power.map(|p| p.to_string()).unwrap_or_default(),
+            freq_tolerance: task.freq_tolerance.map(|t| t.to_string()).unwrap_or_default(),
+            rx_safe: task.rx_safe,
+            tentative: task.tentative,
+            shape: task.shape.as_deref().map(format_points).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    /// Construit la tâche modifiée à partir des champs saisis.
+    ///
+    /// La tâche est refusée si elle émet pendant la garde Rx sans être rx-safe.
+    fn build(&self, display: &TimeDisplay, rx_guard: &RxGuard) -> Result<Task, String> {
+        let mission_start = display.mission_start;
+        let freq = |text: &str| text.trim().parse::<f64>().map_err(|_| format!("Fréquence invalide : « {} »", text));
+        let optional_text = |text: &str| Some(text.trim().to_string()).filter(|t| !t.is_empty());
+        let priority = match self.priority.trim() {
+            "" => None,
+            p => Some(p.parse::<u32>().map_err(|_| format!("Priorité invalide : « {} »", p))?),
+        };
+        let power = match self.power.trim() {
+            "" => None,
+            p => Some(p.parse::<f64>().map_err(|_| format!("Puissance invalide : « {} »", p))?),
+        };
+        let freq_tolerance = match self.freq_tolerance.trim() {
+            "" => None,
+            t => Some(t.parse::<f64>().map_err(|_| format!("Tolérance en fréquence invalide : « {} »", t))?),
+        };
+        let location = match self.location.trim() {
+            "" => None,
+            l => Some(Location::parse(l)?),
+        };
+        let mut task = Task {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            freq_start: freq(&self.freq_start)?,
+            freq_end: freq(&self.freq_end)?,
+            time_start: parse_time(&self.time_start, mission_start)?,
+            time_end: parse_time(&self.time_end, mission_start)?,
+            amplifier: self.amplifier,
+            group: optional_text(&self.group),
+            parent_id: optional_text(&self.parent_id),
+            platform: optional_text(&self.platform),
+            antenna: optional_text(&self.antenna),
+            location,
+            effect: self.effect,
+            priority,
+            status: optional_text(&self.status),
+            notes: Some(self.notes.trim_end().to_string()).filter(|n| !n.trim().is_empty()),
+            attachments: self.attachments.clone(),
+            metadata: parse_metadata(&self.metadata)?,
+            power,
+            rx_safe: self.rx_safe,
+            tentative: self.tentative,
+            shape: None,
+            freq_tolerance,
+        };
+        // Un contour polygonal remplace les bornes saisies
+        if !self.shape.trim().is_empty() {
+            let shape = parse_points(&self.shape)?;
+            if signed_area(&shape) == 0. {
+                return Err("Le contour doit délimiter une surface non nulle".into());
+            }
+            task.shape = Some(shape);
+            task.fit_to_shape();
+        }
+        task.quantize_times(display.resolution);
+        if task.freq_start > task.freq_end {
+            return Err("fmin ne doit pas dépasser fmax".into());
+        }
+        if task.time_start > task.time_end {
+            return Err("tmin ne doit pas dépasser tmax".into());
+        }
+        rx_guard.validate(&task)?;
+        Ok(task)
+    }
+
+    /// Affiche la fenêtre de l’inspecteur.
+    ///
+    /// Retourne la tâche modifiée lorsque l’utilisateur valide ses modifications.
+    /// `changes` est l’historique des modifications de la tâche. `open` passe à
+    /// `false` lorsque la fenêtre est fermée.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        display: &TimeDisplay,
+        rx_guard: &RxGuard,
+        changes: &VecDeque<TaskChange>,
+        range: Option<RangeEstimate>,
+        open: &mut bool,
+    ) -> Option<Task> {
+        let mission_start = display.mission_start;
+        let mut edited = None;
+        egui::Window::new("Inspecteur de tâche").open(open).show(ctx, |ui| {
+            egui::Grid::new("inspector_fields").num_columns(3).show(ui, |ui| {
+                ui.label("Nom");
+                ui.text_edit_singleline(&mut self.name);
+                ui.end_row();
+
+                ui.label("fmin (MHz)");
+                ui.text_edit_singleline(&mut self.freq_start);
+                ui.end_row();
+
+                ui.label("fmax (MHz)");
+                ui.text_edit_singleline(&mut self.freq_end);
+                ui.end_row();
+
+                for (label, text) in [("tmin", &mut self.time_start), ("tmax", &mut self.time_end)] {
+                    ui.label(label);
+                    ui.text_edit_singleline(text);
+                    match parse_time(text, mission_start) {
+                        Ok(ms) => ui.weak(format!("{:.*} ms – {}", display.resolution.decimals(), ms, display.format_long(ms))),
+                        Err(_) => ui.colored_label(egui::Color32::RED, "invalide"),
+                    };
+                    ui.end_row();
+                }
+
+                ui.label("Amplificateur");
+                egui::ComboBox::from_id_salt("inspector_amplifier")
+                    .selected_text(format!("{:?}", self.amplifier))
+                    .show_ui(ui, |ui| {
+                        for amp in Amplifier::ALL {
+                            ui.selectable_value(&mut self.amplifier, amp, format!("{:?}", amp));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Effet");
+                egui::ComboBox::from_id_salt("inspector_effect")
+                    .selected_text(self.effect.map_or("Non défini", |e| e.label()))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.effect, None, "Non défini");
+                        for effect in Effect::ALL {
+                            ui.selectable_value(&mut self.effect, Some(effect), effect.label());
+                        }
+                    });
+                ui.end_row();
+
+                for (label, text) in [
+                    ("Groupe", &mut self.group),
+                    ("Tâche parente", &mut self.parent_id),
+                    ("Plateforme", &mut self.platform),
+                    ("Antenne", &mut self.antenna),
+                    ("Position (lat, lon)", &mut self.location),
+                    ("Priorité", &mut self.priority),
+                    ("Statut", &mut self.status),
+                    ("Puissance (W)", &mut self.power),
+                    ("Tolérance fréq. (MHz)", &mut self.freq_tolerance),
+                ] {
+                    ui.label(label);
+                    ui.text_edit_singleline(text);
+                    ui.end_row();
+                }
+
+                ui.label("Notes");
+                ui.add(egui::TextEdit::multiline(&mut self.notes).desired_rows(3).hint_text("Markdown : **gras**, *italique*, - liste"));
+                ui.end_row();
+
+                ui.label("Métadonnées");
+                ui.add(egui::TextEdit::multiline(&mut self.metadata).desired_rows(2).hint_text("clé = valeur (une par ligne)"));
+                ui.end_row();
+
+                ui.label("Contour (f,t; …)");
+                ui.add(egui::TextEdit::multiline(&mut self.shape).desired_rows(2).hint_text("rectangle"));
+                ui.end_row();
+
+                ui.label("Garde Rx");
+                ui.checkbox(&mut self.rx_safe, "rx-safe (émission autorisée)");
+                ui.end_row();
+
+                ui.label("Confirmation");
+                ui.checkbox(&mut self.tentative, "provisoire");
+                ui.end_row();
+
+                if let Some(range) = range {
+                    ui.label("Portée estimée");
+                    if range.is_short() {
+                        ui.colored_label(egui::Color32::RED, range.label()).on_hover_text("Puissance insuffisante pour atteindre la cible");
+                    } else {
+                        ui.label(range.label());
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.weak("Temps : ms, H+15 (minutes), H+30s, H+250ms, H+40us ou DTG (271530ZMAY25)");
+            if !self.notes.trim().is_empty() {
+                egui::CollapsingHeader::new("Aperçu des notes").default_open(true).show(ui, |ui| show_notes(ui, &self.notes));
+            }
+            egui::CollapsingHeader::new(format!("Historique ({})", changes.len())).show(ui, |ui| show_change_log(ui, changes));
+            egui::CollapsingHeader::new(format!("Pièces jointes ({})", self.attachments.len())).show(ui, |ui| {
+                let mut removed = None;
+                for (i, uri) in self.attachments.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.button("Ouvrir").clicked() {
+                            match classify_attachment(uri) {
+                                Ok(AttachmentKind::Local) => self.confirm_open = Some(uri.clone()),
+                                Ok(_) => {
+                                    if let Err(e) = open_attachment(uri, false) {
+                                        self.error = Some(e);
+                                    }
+                                }
+                                Err(e) => self.error = Some(e),
+                            }
+                        }
+                        ui.label(attachment_label(uri)).on_hover_text(uri);
+                        if ui.small_button("✖").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    self.attachments.remove(i);
+                }
+                if let Some(uri) = self.confirm_open.clone() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::YELLOW, format!("Ouvrir le fichier local « {} » ?", uri));
+                        if ui.button("Ouvrir").clicked() {
+                            if let Err(e) = open_attachment(&uri, true) {
+                                self.error = Some(e);
+                            }
+                            self.confirm_open = None;
+                        }
+                        if ui.button("Annuler").clicked() {
+                            self.confirm_open = None;
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.new_attachment).hint_text("URI ou chemin de fichier"));
+                    let uri = self.new_attachment.trim();
+                    let addable = !uri.is_empty() && self.attachments.len() < MAX_ATTACHMENTS;
+                    if ui.add_enabled(addable, egui::Button::new("Ajouter")).clicked() {
+                        self.attachments.push(uri.to_string());
+                        self.new_attachment.clear();
+                    }
+                });
+            });
+
+            if let Some(e) = &self.error {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+            if ui.button("Appliquer").clicked() {
+                match self.build(display, rx_guard) {
+                    Ok(task) => {
+                        self.error = None;
+                        edited = Some(task);
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+            }
+        });
+        edited
+    }
+}
@@ -0,0 +1,87 @@
+//! Module d’analyse des produits d’intermodulation.
+//!
+//! Deux tâches émises simultanément par le même amplificateur, aux fréquences
+//! `f1` et `f2`, produisent des raies d’intermodulation d’ordre 3 en `2f1 − f2`
+//! et `2f2 − f1`, proches des bandes émises et donc difficiles à filtrer. Pour
+//! des bandes `[a1, a2]` et `[b1, b2]`, ces produits couvrent les bandes
+//! `[2a1 − b2, 2a2 − b1]` et `[2b1 − a2, 2b2 − a1]`.
+//!
+//! Un avertissement est émis lorsqu’un produit tombe, pendant l’émission
+//! simultanée, dans un masque d’exclusion (bande protégée) ou, pendant la fenêtre
+//! de garde Rx, dans une bande écoutée : celle d’une autre tâche du plan, que le
+//! récepteur surveille avant d’émettre. Le reste du spectre n’étant pas écouté,
+//! un produit qui n’y tombe pas ne gêne pas la réception.
+
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Task;
+
+/// Produit d’intermodulation d’une paire de tâches tombant dans une zone protégée.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IntermodWarning {
+    /// Indice de la tâche `f1` (le produit est `2f1 − f2`).
+    pub first: usize,
+    /// Indice de la tâche `f2`.
+    pub second: usize,
+    /// Bande du produit `[début, fin]` en MHz.
+    pub product: (f64, f64),
+    /// Intervalle d’émission simultanée `[début, fin]` en ms.
+    pub time: (f64, f64),
+    /// Zone protégée atteinte (nom du masque, ou bande écoutée pendant la garde Rx).
+    pub target: String,
+}
+
+impl IntermodWarning {
+    /// Décrit l’avertissement.
+    pub fn describe(&self, tasks: &[Task]) -> String {
+        format!(
+            "2·{} − {} : {:.1}–{:.1} MHz dans {} ({:.0}–{:.0} ms)",
+            tasks[self.first].name, tasks[self.second].name, self.product.0, self.product.1, self.target, self.time.0, self.time.1
+        )
+    }
+}
+
+/// Indique si les intervalles `[a0, a1]` et `[b0, b1]` se chevauchent strictement.
+fn overlaps(a: (f64, f64), b: (f64, f64)) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// Recherche les produits d’intermodulation d’ordre 3 des tâches simultanées d’un
+/// même amplificateur tombant dans un masque d’exclusion, ou dans la bande d’une
+/// autre tâche pendant la garde Rx.
+pub fn find_intermod(tasks: &[Task], exclusions: &[ExclusionMask], rx_guard: &RxGuard) -> Vec<IntermodWarning> {
+    let mut warnings = Vec::new();
+    for i in 0..tasks.len() {
+        for j in (i + 1)..tasks.len() {
+            let (a, b) = (&tasks[i], &tasks[j]);
+            if a.amplifier != b.amplifier {
+                continue;
+            }
+            let time = (a.time_start.max(b.time_start), a.time_end.min(b.time_end));
+            if time.0 >= time.1 {
+                continue;
+            }
+            for (first, second) in [(i, j), (j, i)] {
+                let (f1, f2) = (&tasks[first], &tasks[second]);
+                let product = (2. * f1.freq_start - f2.freq_end, 2. * f1.freq_end - f2.freq_start);
+                for mask in exclusions {
+                    let window = (time.0.max(mask.time_start), time.1.min(mask.time_end));
+                    if overlaps(product, (mask.freq_start, mask.freq_end)) && window.0 < window.1 {
+                        warnings.push(IntermodWarning { first, second, product, time: window, target: mask.name.clone() });
+                    }
+                }
+                let window = (time.0.max(0.), time.1.min(rx_guard.duration));
+                if window.0 >= window.1 {
+                    continue;
+                }
+                for (k, listened) in tasks.iter().enumerate() {
+                    if k != i && k != j && overlaps(product, (listened.freq_start, listened.freq_end)) {
+                        let target = format!("la bande de « {} » écoutée pendant la garde Rx", listened.name);
+                        warnings.push(IntermodWarning { first, second, product, time: window, target });
+                    }
+                }
+            }
+        }
+    }
+    warnings
+}
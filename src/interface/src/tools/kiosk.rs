@@ -0,0 +1,62 @@
+//! Module du mode kiosque (`--kiosk`) des écrans de salle d’opérations.
+//!
+//! L’interface démarre en plein écran, sans décorations de fenêtre. Le panneau
+//! latéral est masqué : il apparaît lorsque le pointeur approche du bord gauche
+//! de l’écran (et disparaît lorsqu’il le quitte), ou reste affiché après un appui
+//! sur [`REVEAL_KEY`]. Les fenêtres de dialogue (inspecteur, import CSV, éditeur
+//! de zones, écran d’accueil) ne sont pas ouvertes, afin qu’aucune fenêtre ne
+//! reste affichée sur un écran sans opérateur.
+
+/// Touche affichant ou masquant durablement le panneau latéral.
+pub const REVEAL_KEY: egui::Key = egui::Key::F2;
+
+/// Distance au bord gauche de l’écran, en points, à laquelle le pointeur fait apparaître le panneau.
+pub const REVEAL_MARGIN: f32 = 8.;
+
+/// État du mode kiosque.
+#[derive(Default)]
+pub struct Kiosk {
+    /// Mode kiosque actif.
+    pub enabled: bool,
+    /// Panneau latéral affiché durablement (raccourci [`REVEAL_KEY`]).
+    pub pinned: bool,
+    /// Panneau latéral révélé par le pointeur.
+    hovered: bool,
+    /// Bord droit du panneau latéral lors de son dernier affichage.
+    panel_right: f32,
+}
+
+impl Kiosk {
+    /// Crée l’état du mode kiosque.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, ..Self::default() }
+    }
+
+    /// Indique si le panneau latéral doit être affiché, d’après le pointeur et le raccourci.
+    pub fn show_controls(&mut self, ctx: &egui::Context) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let (toggle, pointer) = ctx.input(|i| (i.key_pressed(REVEAL_KEY), i.pointer.hover_pos()));
+        if toggle {
+            self.pinned = !self.pinned;
+        }
+        self.hovered = match pointer.map(|p| p.x) {
+            Some(x) if x <= REVEAL_MARGIN => true,
+            Some(x) if x > self.panel_right => false,
+            Some(_) => self.hovered,
+            None => false,
+        };
+        self.pinned || self.hovered
+    }
+
+    /// Enregistre le bord droit du panneau latéral affiché.
+    pub fn set_panel_right(&mut self, right: f32) {
+        self.panel_right = right;
+    }
+
+    /// Indique si les fenêtres de dialogue peuvent être ouvertes.
+    pub fn allows_dialogs(&self) -> bool {
+        !self.enabled
+    }
+}
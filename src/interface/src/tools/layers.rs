@@ -0,0 +1,205 @@
+//! Module des couches de plan (prévu / exécuté / observé).
+//!
+//! En plus du flux principal (stdin, transport réseau), l’interface peut lire des
+//! tubes nommés (`--planned`, `--executed`, `--observed <tube>`) dont chaque ligne
+//! est un message de tâche ou de plan du protocole. Le flux prévu alimente le plan
+//! affiché ; les tâches exécutées sont dessinées en contour et les détections
+//! observées en marqueurs, afin de confronter le plan à la réalité. Chaque couche
+//! peut être masquée indépendamment.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::thread;
+
+use egui::{Color32, Painter, Pos2, Shape, Stroke};
+use egui_plot::{PlotPoint, PlotTransform};
+
+use crate::tools::protocol::{parse_message, IncomingMessage};
+use crate::tools::task::Task;
+use crate::tools::task_mesh::screen_outline;
+use crate::tools::utils::freq_to_x;
+
+/// Rayon des marqueurs de détection en points.
+const MARKER_RADIUS: f32 = 4.;
+
+/// Couleur des marqueurs de détection.
+const OBSERVED_COLOR: Color32 = Color32::from_rgb(255, 60, 200);
+
+/// Couche de plan alimentée par un flux d’entrée.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlanLayer {
+    /// Plan prévu (remplissage), alimenté comme le flux principal.
+    Planned,
+    /// Tâches effectivement exécutées (contour).
+    Executed,
+    /// Détections observées (marqueurs).
+    Observed,
+}
+
+impl PlanLayer {
+    /// Liste de toutes les couches.
+    pub const ALL: [PlanLayer; 3] = [PlanLayer::Planned, PlanLayer::Executed, PlanLayer::Observed];
+
+    /// Libellé de la couche.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlanLayer::Planned => "Prévu",
+            PlanLayer::Executed => "Exécuté",
+            PlanLayer::Observed => "Observé",
+        }
+    }
+
+    /// Nom de la couche dans les fichiers de configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlanLayer::Planned => "planned",
+            PlanLayer::Executed => "executed",
+            PlanLayer::Observed => "observed",
+        }
+    }
+
+    /// Option de ligne de commande associée à la couche.
+    pub fn flag(&self) -> &'static str {
+        match self {
+            PlanLayer::Planned => "--planned",
+            PlanLayer::Executed => "--executed",
+            PlanLayer::Observed => "--observed",
+        }
+    }
+}
+
+/// Couches exécutée et observée, et visibilité des trois couches.
+pub struct PlanLayers {
+    /// Tâches exécutées.
+    pub executed: Vec<Task>,
+    /// Détections observées.
+    pub observed: Vec<Task>,
+    /// Visibilité des couches, dans l’ordre de [`PlanLayer::ALL`].
+    visible: [bool; 3],
+}
+
+impl Default for PlanLayers {
+    fn default() -> Self {
+        Self { executed: vec![], observed: vec![], visible: [true; 3] }
+    }
+}
+
+impl PlanLayers {
+    /// Indique si la couche est affichée.
+    pub fn is_visible(&self, layer: PlanLayer) -> bool {
+        self.visible[layer as usize]
+    }
+
+    /// Affiche ou masque la couche.
+    pub fn set_visible(&mut self, layer: PlanLayer, visible: bool) {
+        self.visible[layer as usize] = visible;
+    }
+
+    /// Tâches de la couche (vide pour la couche prévue, portée par le plan).
+    fn tasks_mut(&mut self, layer: PlanLayer) -> Option<&mut Vec<Task>> {
+        match layer {
+            PlanLayer::Planned => None,
+            PlanLayer::Executed => Some(&mut self.executed),
+            PlanLayer::Observed => Some(&mut self.observed),
+        }
+    }
+
+    /// Applique un message à la couche : une tâche est ajoutée ou mise à jour
+    /// (même clé), un plan remplace toutes les tâches de la couche.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le message est invalide ou n’est ni une
+    /// tâche ni un plan.
+    pub fn apply(&mut self, layer: PlanLayer, json: &str) -> Result<(), String> {
+        let tasks = self.tasks_mut(layer).ok_or("La couche prévue est alimentée par le flux principal")?;
+        match parse_message(json)? {
+            IncomingMessage::Task(task) => {
+                let task = task.into_task();
+                match tasks.iter_mut().find(|t| t.key() == task.key()) {
+                    Some(existing) => *existing = task,
+                    None => tasks.push(task),
+                }
+            }
+            IncomingMessage::Plan(plan) => *tasks = plan.plan.into_iter().map(|t| t.into_task()).collect(),
+            _ => return Err(format!("Couche {} : seuls les messages de tâche ou de plan sont acceptés", layer.label())),
+        }
+        Ok(())
+    }
+
+    /// Dessine les tâches exécutées en contour et les détections observées en
+    /// marqueurs (cercle au début de la détection, trait sur sa durée).
+    /// `layout` place les tâches dans leur couloir d’affichage.
+    pub fn paint(&self, painter: &Painter, transform: &PlotTransform, log: bool, layout: impl Fn(&[Task]) -> Vec<Task>) {
+        let frame = *transform.frame();
+        if self.is_visible(PlanLayer::Executed) {
+            for task in layout(&self.executed) {
+                let outline = screen_outline(&task, transform, log);
+                painter.add(Shape::closed_line(outline, Stroke::new(2., task.color().to_opaque())));
+            }
+        }
+        if self.is_visible(PlanLayer::Observed) {
+            for task in layout(&self.observed) {
+                let freq = freq_to_x((task.freq_start + task.freq_end) / 2., log);
+                let start = transform.position_from_point(&PlotPoint::new(freq, task.time_start));
+                let end = transform.position_from_point(&PlotPoint::new(freq, task.time_end));
+                if !frame.expand(MARKER_RADIUS).contains(start) && !frame.contains(end) {
+                    continue;
+                }
+                painter.line_segment([start, Pos2::new(start.x, end.y)], Stroke::new(1.5, OBSERVED_COLOR));
+                painter.circle(start, MARKER_RADIUS, OBSERVED_COLOR.gamma_multiply(0.5), Stroke::new(1.5, OBSERVED_COLOR));
+            }
+        }
+    }
+
+    /// Affiche la visibilité et le contenu des couches.
+    pub fn show(&mut self, ui: &mut egui::Ui, planned: usize) {
+        for layer in PlanLayer::ALL {
+            let count = match layer {
+                PlanLayer::Planned => planned,
+                PlanLayer::Executed => self.executed.len(),
+                PlanLayer::Observed => self.observed.len(),
+            };
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.visible[layer as usize], format!("{} ({})", layer.label(), count));
+                if let Some(tasks) = self.tasks_mut(layer).filter(|t| !t.is_empty()) {
+                    if ui.small_button("Vider").clicked() {
+                        tasks.clear();
+                    }
+                }
+            });
+        }
+        ui.weak("Tubes : --planned, --executed, --observed <tube>");
+    }
+}
+
+/// Lit le tube nommé ligne par ligne et transmet chaque ligne à `on_line`.
+///
+/// Le tube est rouvert à chaque fin de flux, un écrivain pouvant se déconnecter
+/// puis revenir ; la lecture s’arrête sur une erreur d’ouverture ou de lecture, ou
+/// à la fin d’un fichier ordinaire.
+pub fn spawn_pipe_reader(path: PathBuf, on_line: impl Fn(String) + Send + 'static) {
+    thread::spawn(move || loop {
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Ouverture du tube {} impossible : {}", path.display(), e);
+                return;
+            }
+        };
+        for line in BufReader::new(file).lines() {
+            match line {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => on_line(line),
+                Err(e) => {
+                    eprintln!("Erreur lecture du tube {} : {}", path.display(), e);
+                    return;
+                }
+            }
+        }
+        if path.is_file() {
+            return;
+        }
+    });
+}
@@ -0,0 +1,172 @@
+//! Module des bandeaux d’occupation marginale.
+//!
+//! Le bandeau temporel, affiché sous le mini graphe, résume la charge du plan
+//! sur toute la bande de fréquence : pour chaque intervalle de temps, le nombre
+//! de tâches actives ou la largeur de bande cumulée. Les pics de charge restent
+//! ainsi visibles même lorsque le graphe est zoomé sur une bande étroite.
+//!
+//! Symétriquement, le bandeau fréquentiel, affiché au-dessus du graphe principal
+//! et aligné sur son axe des fréquences, indique pour chaque intervalle de la
+//! bande visible le temps de brouillage cumulé sur la mission. Les parties du
+//! spectre non couvertes sont marquées en rouge, celles dont le temps cumulé
+//! dépasse la durée de la mission (tâches superposées) en orange.
+
+use egui::{Color32, Rangef, Rect, Sense, Stroke, Ui};
+
+use crate::tools::task::Task;
+use crate::tools::utils::{format_bandwidth, format_freq, freq_to_x, MAX_TIME};
+
+/// Nombre d’intervalles des bandeaux.
+pub const MARGINAL_BINS: usize = 200;
+
+/// Couleur des barres des bandeaux.
+const BAR_COLOR: Color32 = Color32::from_rgb(90, 160, 230);
+
+/// Couleur des intervalles de fréquence sur-servis.
+const OVER_COLOR: Color32 = Color32::from_rgb(255, 150, 0);
+
+/// Couleur des intervalles de fréquence non couverts.
+const UNSERVED_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+/// Grandeur cumulée par intervalle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarginalMetric {
+    /// Nombre de tâches actives.
+    TaskCount,
+    /// Largeur de bande cumulée des tâches actives, en MHz.
+    Bandwidth,
+}
+
+impl MarginalMetric {
+    /// Liste de toutes les grandeurs.
+    pub const ALL: [MarginalMetric; 2] = [MarginalMetric::TaskCount, MarginalMetric::Bandwidth];
+
+    /// Libellé de la grandeur.
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarginalMetric::TaskCount => "Nombre de tâches",
+            MarginalMetric::Bandwidth => "Largeur de bande cumulée",
+        }
+    }
+
+    /// Met en forme une valeur de la grandeur.
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            MarginalMetric::TaskCount => format!("{:.0} tâches", value),
+            MarginalMetric::Bandwidth => format_bandwidth(value),
+        }
+    }
+
+    /// Contribution d’une tâche à un intervalle.
+    fn weight(&self, task: &Task) -> f64 {
+        match self {
+            MarginalMetric::TaskCount => 1.,
+            MarginalMetric::Bandwidth => task.freq_end - task.freq_start,
+        }
+    }
+}
+
+/// Cumule la grandeur des tâches actives dans chacun des `bins` intervalles de
+/// `[0, time_max]` ms. Une tâche compte dans tous les intervalles qu’elle recoupe.
+pub fn time_histogram<'a>(tasks: impl Iterator<Item = &'a Task>, bins: usize, time_max: f64, metric: MarginalMetric) -> Vec<f64> {
+    let mut values = vec![0.; bins];
+    let width = time_max / bins as f64;
+    for task in tasks {
+        if task.time_end <= 0. || task.time_start >= time_max {
+            continue;
+        }
+        let first = (task.time_start / width).floor().max(0.) as usize;
+        let last = ((task.time_end / width).ceil() as usize).min(bins);
+        for value in &mut values[first..last] {
+            *value += metric.weight(task);
+        }
+    }
+    values
+}
+
+/// Cumule, pour chacun des `bins` intervalles de l’axe X `[x_min, x_max]` (log10
+/// des fréquences si `log`), le temps de brouillage des tâches pondéré par la part
+/// de l’intervalle qu’elles couvrent, en ms.
+pub fn freq_histogram<'a>(tasks: impl Iterator<Item = &'a Task>, bins: usize, (x_min, x_max): (f64, f64), log: bool) -> Vec<f64> {
+    let mut values = vec![0.; bins];
+    let width = (x_max - x_min) / bins as f64;
+    if width <= 0. {
+        return values;
+    }
+    let x = |f: f64| freq_to_x(f, log);
+    for task in tasks {
+        let (start, end) = (x(task.freq_start), x(task.freq_end));
+        if end <= x_min || start >= x_max {
+            continue;
+        }
+        let duration = task.time_end - task.time_start;
+        let first = ((start - x_min) / width).floor().max(0.) as usize;
+        let last = (((end - x_min) / width).ceil() as usize).min(bins);
+        for (bin, value) in values.iter_mut().enumerate().take(last).skip(first) {
+            let (bin_start, bin_end) = (x_min + bin as f64 * width, x_min + (bin + 1) as f64 * width);
+            let covered = (end.min(bin_end) - start.max(bin_start)).max(0.) / width;
+            *value += duration * covered;
+        }
+    }
+    values
+}
+
+/// Dessine un histogramme dans `rect`, les barres le long de l’axe horizontal
+/// colorées par `color`, et retourne l’indice de l’intervalle survolé.
+fn paint_bars(ui: &Ui, rect: Rect, values: &[f64], color: impl Fn(f64) -> Color32) -> Option<usize> {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0., ui.visuals().extreme_bg_color);
+    let max = values.iter().copied().fold(0., f64::max);
+    let bar_width = rect.width() / values.len().max(1) as f32;
+    for (i, &value) in values.iter().enumerate() {
+        let height = if max > 0. { (value / max) as f32 * rect.height() } else { 0. };
+        let x = rect.left() + i as f32 * bar_width;
+        // Hauteur minimale pour laisser voir les intervalles vides colorés
+        let top = (rect.bottom() - height).min(rect.bottom() - 2.);
+        painter.rect_filled(Rect::from_min_max(egui::pos2(x, top), egui::pos2(x + bar_width, rect.bottom())), 0., color(value));
+    }
+    painter.rect_stroke(rect, 0., Stroke::new(1., Color32::from_gray(90)), egui::StrokeKind::Inside);
+    let pointer = ui.ctx().pointer_hover_pos().filter(|p| rect.contains(*p))?;
+    Some((((pointer.x - rect.left()) / bar_width) as usize).min(values.len().saturating_sub(1)))
+}
+
+/// Affiche le bandeau temporel : le temps de mission de 0 à `time_max` ms en
+/// abscisse, la grandeur cumulée en hauteur de barre.
+pub fn show_time_strip<'a>(ui: &mut Ui, tasks: impl Iterator<Item = &'a Task>, time_max: f64, metric: MarginalMetric, height: f32) {
+    let values = time_histogram(tasks, MARGINAL_BINS, time_max, metric);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), Sense::hover());
+    if let Some(bin) = paint_bars(ui, rect, &values, |v| if v > 0. { BAR_COLOR } else { Color32::TRANSPARENT }) {
+        let width = time_max / MARGINAL_BINS as f64;
+        response.on_hover_text_at_pointer(format!(
+            "{:.0}–{:.0} ms : {}",
+            bin as f64 * width,
+            (bin + 1) as f64 * width,
+            metric.format(values[bin])
+        ));
+    }
+}
+
+/// Affiche le bandeau fréquentiel : les intervalles de l’axe X visible
+/// `[x_min, x_max]` (log10 des fréquences si `log`) répartis sur la plage
+/// horizontale `plot_x` de l’écran, alignée sur le graphe principal.
+pub fn show_freq_strip<'a>(ui: &mut Ui, tasks: impl Iterator<Item = &'a Task>, x_range: (f64, f64), log: bool, plot_x: Rangef, height: f32) {
+    let values = freq_histogram(tasks, MARGINAL_BINS, x_range, log);
+    let (full, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), Sense::hover());
+    let rect = Rect::from_x_y_ranges(plot_x.intersection(full.x_range()), full.y_range());
+    let color = |value: f64| match value {
+        v if v <= 0. => UNSERVED_COLOR,
+        v if v > MAX_TIME => OVER_COLOR,
+        _ => BAR_COLOR,
+    };
+    if let Some(bin) = paint_bars(ui, rect, &values, color) {
+        let width = (x_range.1 - x_range.0) / MARGINAL_BINS as f64;
+        let freq = |x: f64| if log { 10f64.powf(x) } else { x };
+        let (start, end) = (freq(x_range.0 + bin as f64 * width), freq(x_range.0 + (bin + 1) as f64 * width));
+        response.on_hover_text_at_pointer(format!(
+            "{} – {} : {:.0} ms de brouillage",
+            format_freq(start, end - start),
+            format_freq(end, end - start),
+            values[bin]
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Module des annotations dessinées sur le graphe pour les briefings.
+//!
+//! Les annotations (tracé à main levée, flèche, cercle) sont enregistrées en
+//! coordonnées du graphe — fréquence en MHz, temps en ms — et restent donc
+//! attachées aux fréquences et aux temps visés lors des zooms, des déplacements
+//! et du passage en échelle logarithmique. Elles figurent sur les captures
+//! d’écran et sont reprises dans le briefing PDF.
+
+use egui::{Color32, Pos2, Stroke};
+
+/// Couleurs proposées pour les annotations.
+pub const MARKUP_COLORS: [Color32; 5] = [
+    Color32::from_rgb(255, 60, 60),
+    Color32::from_rgb(255, 200, 0),
+    Color32::from_rgb(60, 200, 255),
+    Color32::from_rgb(80, 220, 80),
+    Color32::WHITE,
+];
+
+/// Épaisseur du trait des annotations, en points.
+pub const MARKUP_WIDTH: f32 = 2.5;
+
+/// Distance au pointeur, en points, à laquelle la gomme efface une annotation.
+const ERASE_RADIUS: f32 = 8.;
+
+/// Outil d’annotation sélectionné.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MarkupTool {
+    /// Aucun outil : le graphe se manipule normalement.
+    #[default]
+    None,
+    /// Tracé à main levée.
+    Pen,
+    /// Flèche.
+    Arrow,
+    /// Cercle (centre puis rayon).
+    Circle,
+    /// Gomme : efface les annotations touchées.
+    Eraser,
+}
+
+impl MarkupTool {
+    /// Libellé de l’outil.
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarkupTool::None => "Aucun",
+            MarkupTool::Pen => "Crayon",
+            MarkupTool::Arrow => "Flèche",
+            MarkupTool::Circle => "Cercle",
+            MarkupTool::Eraser => "Gomme",
+        }
+    }
+}
+
+/// Forme d’une annotation, en coordonnées `[fréquence MHz, temps ms]`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum MarkupShape {
+    /// Tracé à main levée.
+    Pen(Vec<[f64; 2]>),
+    /// Flèche de `from` vers `to`.
+    Arrow { from: [f64; 2], to: [f64; 2] },
+    /// Cercle de centre `center` passant par `edge` (rond à l’écran quel que soit le zoom).
+    Circle { center: [f64; 2], edge: [f64; 2] },
+}
+
+/// Annotation dessinée sur le graphe.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Markup {
+    /// Forme de l’annotation.
+    pub shape: MarkupShape,
+    /// Couleur du trait.
+    pub color: Color32,
+}
+
+impl Markup {
+    /// Dessine l’annotation, `to_screen` convertissant un point `[fréquence, temps]` en position.
+    pub fn paint(&self, painter: &egui::Painter, to_screen: impl Fn([f64; 2]) -> Pos2) {
+        let stroke = Stroke::new(MARKUP_WIDTH, self.color);
+        match &self.shape {
+            MarkupShape::Pen(points) => {
+                painter.add(egui::Shape::line(points.iter().map(|&p| to_screen(p)).collect(), stroke));
+            }
+            MarkupShape::Arrow { from, to } => {
+                let origin = to_screen(*from);
+                painter.arrow(origin, to_screen(*to) - origin, stroke);
+            }
+            MarkupShape::Circle { center, edge } => {
+                let center = to_screen(*center);
+                painter.circle_stroke(center, center.distance(to_screen(*edge)), stroke);
+            }
+        }
+    }
+
+    /// Indique si l’annotation passe à moins de [`ERASE_RADIUS`] de la position `pos`.
+    fn touches(&self, pos: Pos2, to_screen: impl Fn([f64; 2]) -> Pos2) -> bool {
+        let near_segment = |a: Pos2, b: Pos2| {
+            let ab = b - a;
+            let t = if ab.length_sq() > 0. { ((pos - a).dot(ab) / ab.length_sq()).clamp(0., 1.) } else { 0. };
+            pos.distance(a + ab * t) <= ERASE_RADIUS
+        };
+        match &self.shape {
+            MarkupShape::Pen(points) => {
+                let screen: Vec<Pos2> = points.iter().map(|&p| to_screen(p)).collect();
+                match screen.as_slice() {
+                    [single] => single.distance(pos) <= ERASE_RADIUS,
+                    _ => screen.windows(2).any(|w| near_segment(w[0], w[1])),
+                }
+            }
+            MarkupShape::Arrow { from, to } => near_segment(to_screen(*from), to_screen(*to)),
+            MarkupShape::Circle { center, edge } => {
+                let center = to_screen(*center);
+                (center.distance(pos) - center.distance(to_screen(*edge))).abs() <= ERASE_RADIUS
+            }
+        }
+    }
+}
+
+/// Annotations du graphe et outil sélectionné.
+pub struct Markups {
+    /// Outil sélectionné.
+    pub tool: MarkupTool,
+    /// Couleur des nouvelles annotations.
+    pub color: Color32,
+    /// Annotations terminées.
+    pub items: Vec<Markup>,
+    /// Annotation en cours de tracé.
+    pub drawing: Option<Markup>,
+}
+
+impl Default for Markups {
+    fn default() -> Self {
+        Self { tool: MarkupTool::None, color: MARKUP_COLORS[0], items: Vec::new(), drawing: None }
+    }
+}
+
+impl Markups {
+    /// Indique si un outil d’annotation est actif (le graphe ne se déplace plus à la souris).
+    pub fn is_active(&self) -> bool {
+        self.tool != MarkupTool::None
+    }
+
+    /// Applique le glisser de la souris sur le graphe : `point` est la position du
+    /// pointeur en coordonnées `[fréquence, temps]` et `pos` sa position à l’écran.
+    pub fn handle_drag(
+        &mut self,
+        response: &egui::Response,
+        point: Option<[f64; 2]>,
+        pos: Option<Pos2>,
+        to_screen: impl Fn([f64; 2]) -> Pos2,
+    ) {
+        if self.tool == MarkupTool::Eraser {
+            if let (true, Some(pos)) = (response.clicked() || response.dragged(), pos) {
+                self.items.retain(|m| !m.touches(pos, &to_screen));
+            }
+            return;
+        }
+        if let (true, Some(point)) = (response.drag_started(), point) {
+            let shape = match self.tool {
+                MarkupTool::Pen => MarkupShape::Pen(vec![point]),
+                MarkupTool::Arrow => MarkupShape::Arrow { from: point, to: point },
+                MarkupTool::Circle => MarkupShape::Circle { center: point, edge: point },
+                MarkupTool::None | MarkupTool::Eraser => return,
+            };
+            self.drawing = Some(Markup { shape, color: self.color });
+        }
+        if let (Some(drawing), Some(point)) = (&mut self.drawing, point) {
+            match &mut drawing.shape {
+                MarkupShape::Pen(points) => {
+                    if points.last() != Some(&point) {
+                        points.push(point);
+                    }
+                }
+                MarkupShape::Arrow { to, .. } => *to = point,
+                MarkupShape::Circle { edge, .. } => *edge = point,
+            }
+        }
+        if response.drag_stopped() {
+            self.items.extend(self.drawing.take());
+        }
+    }
+
+    /// Annotations à dessiner, y compris celle en cours de tracé.
+    pub fn all(&self) -> impl Iterator<Item = &Markup> {
+        self.items.iter().chain(&self.drawing)
+    }
+
+    /// Affiche les outils d’annotation dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for tool in [MarkupTool::None, MarkupTool::Pen, MarkupTool::Arrow, MarkupTool::Circle, MarkupTool::Eraser] {
+                ui.selectable_value(&mut self.tool, tool, tool.label());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Couleur :");
+            for color in MARKUP_COLORS {
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(16., 16.), egui::Sense::click());
+                ui.painter().rect_filled(rect, 3., color);
+                if self.color == color {
+                    ui.painter().rect_stroke(rect.expand(2.), 3., Stroke::new(1.5, ui.visuals().strong_text_color()), egui::StrokeKind::Outside);
+                }
+                if response.clicked() {
+                    self.color = color;
+                }
+            }
+        });
+        ui.add_enabled_ui(!self.items.is_empty(), |ui| {
+            if ui.button(format!("Effacer les annotations ({})", self.items.len())).clicked() {
+                self.items.clear();
+            }
+        });
+    }
+}
@@ -0,0 +1,67 @@
+//! Module du fond simplifié du mini graphe.
+//!
+//! Pour s’orienter dans le mini graphe sans en alourdir le rendu, les zones de
+//! fond (dont la zone de réception), les bandes de phase, la fin de la garde Rx et
+//! l’instant courant y sont dessinés en coordonnées écran par un chemin allégé :
+//! chaque zone est réduite à sa boîte englobante, sans bordure ni étiquette, et
+//! l’ensemble des aplats forme un maillage unique.
+
+use egui::{Color32, Mesh, Pos2, Rect, Shape, Stroke};
+use egui_plot::{PlotPoint, PlotTransform};
+
+use crate::tools::background::BackgroundZone;
+use crate::tools::phase::Phase;
+use crate::tools::utils::{freq_to_x, MAX_TIME};
+
+/// Couleur de la ligne de l’instant courant.
+pub const NOW_COLOR: Color32 = Color32::from_rgb(255, 80, 80);
+
+/// Ajoute au maillage la partie de `rect` visible dans `clip`.
+fn add_clipped_rect(mesh: &mut Mesh, rect: Rect, clip: Rect, color: Color32) {
+    let rect = rect.intersect(clip);
+    if rect.is_positive() {
+        mesh.add_colored_rect(rect, color);
+    }
+}
+
+/// Construit le fond simplifié du mini graphe pour chaque couloir de décalage
+/// `lane_offsets` (ms) : aplats des zones et des phases, fin de la garde Rx et,
+/// si `now` est défini (ms depuis l’heure H), ligne de l’instant courant.
+pub fn mini_background<'a>(
+    zones: impl Iterator<Item = &'a BackgroundZone> + Clone,
+    phases: &[Phase],
+    rx_guard_ms: f64,
+    now: Option<f64>,
+    lane_offsets: &[f64],
+    transform: &PlotTransform,
+    log: bool,
+) -> Vec<Shape> {
+    let frame = *transform.frame();
+    let y = |time: f64| transform.position_from_point(&PlotPoint::new(0., time)).y;
+    let x = |freq: f64| transform.position_from_point(&PlotPoint::new(freq_to_x(freq, log), 0.)).x;
+    let mut mesh = Mesh::default();
+    let mut lines = Vec::new();
+    for &offset in lane_offsets {
+        for phase in phases {
+            let rect = Rect::from_two_pos(Pos2::new(frame.left(), y(phase.t_start + offset)), Pos2::new(frame.right(), y(phase.t_end + offset)));
+            add_clipped_rect(&mut mesh, rect, frame, phase.fill());
+        }
+        for zone in zones.clone() {
+            let (mut f0, mut f1, mut t0, mut t1) = (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY);
+            for &[f, t] in &zone.area {
+                (f0, f1, t0, t1) = (f0.min(f), f1.max(f), t0.min(t), t1.max(t));
+            }
+            let rect = Rect::from_two_pos(Pos2::new(x(f0), y(t0 + offset)), Pos2::new(x(f1), y(t1 + offset)));
+            add_clipped_rect(&mut mesh, rect, frame, zone.fill);
+        }
+        let rx_end = y(rx_guard_ms + offset);
+        lines.push(Shape::line_segment([Pos2::new(frame.left(), rx_end), Pos2::new(frame.right(), rx_end)], Stroke::new(1., Color32::from_gray(160))));
+        if let Some(now) = now.filter(|t| (0. ..=MAX_TIME).contains(t)) {
+            let now_y = y(now + offset);
+            lines.push(Shape::line_segment([Pos2::new(frame.left(), now_y), Pos2::new(frame.right(), now_y)], Stroke::new(1.5, NOW_COLOR)));
+        }
+    }
+    let mut shapes = vec![Shape::mesh(mesh)];
+    shapes.extend(lines);
+    shapes
+}
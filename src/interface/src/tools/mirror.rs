@@ -0,0 +1,271 @@
+//! Module du mode miroir : instance principale et instances suiveuses.
+//!
+//! Une instance principale (`--mirror`, avec `--listen <adresse>`) republie son
+//! état sur le transport réseau : à chaque changement (révision du plan, phases
+//! ou vue), un message `{"mirror": {…}}` portant les tâches, la garde Rx, les
+//! masques d’exclusion, les phases et, avec `--mirror-view`, la vue du graphe
+//! principal est diffusé aux connexions abonnées. La sérialisation et la
+//! diffusion ont lieu dans un thread dédié ; un état dépassant la taille maximale
+//! d’un message ([`MAX_MESSAGE_BYTES`]) n’est pas diffusé et l’erreur est signalée,
+//! de même que côté suiveur.
+//!
+//! Une instance suiveuse (`--follow <adresse>`, `--token <secret>` si
+//! l’instance principale exige une authentification) s’y connecte et affiche le
+//! même état ; avec `--mirror-view`, elle reprend aussi le zoom et le
+//! déplacement de l’instance principale. La connexion suiveuse est en clair :
+//! elle est destinée aux écrans d’une même salle ou d’un réseau de confiance.
+
+use std::io::{BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::phase::Phase;
+use crate::tools::protocol::{read_message, MAX_MESSAGE_BYTES};
+use crate::tools::task::Task;
+use crate::tools::transport::Subscribers;
+
+/// Intervalle minimal entre deux publications de l’état.
+pub const PUBLISH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Délai avant une nouvelle tentative de connexion à l’instance principale.
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Vue du graphe principal.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MirrorView {
+    /// Axe des fréquences en échelle logarithmique.
+    pub log_scale: bool,
+    /// Bornes `[x_min, temps_min, x_max, temps_max]`, en coordonnées du graphe.
+    pub bounds: [f64; 4],
+}
+
+/// État republié par l’instance principale.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MirrorState {
+    /// Tâches affichées.
+    pub tasks: Vec<Task>,
+    /// Durée de la garde Rx en ms.
+    pub rx_guard_ms: f64,
+    /// Masques d’exclusion.
+    pub exclusions: Vec<ExclusionMask>,
+    /// Phases de mission.
+    pub phases: Vec<Phase>,
+    /// Vue du graphe principal, si sa synchronisation est activée.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view: Option<MirrorView>,
+}
+
+/// Message de republication de l’état, ex. `{"mirror": {"tasks": […], …}}`.
+#[derive(Serialize, Deserialize)]
+pub struct MirrorMessage {
+    /// État de l’instance principale.
+    pub mirror: MirrorState,
+}
+
+/// Rôle de l’instance dans le mode miroir.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum MirrorRole {
+    /// Instance autonome.
+    #[default]
+    Standalone,
+    /// Instance principale : l’état est republié sur le transport réseau.
+    Primary,
+    /// Instance suiveuse de l’instance principale à l’adresse donnée.
+    Follower { address: String, token: Option<String> },
+}
+
+/// Dernier état reçu de l’instance principale, ou erreur de réception.
+pub type MirrorInbox = Arc<Mutex<Option<Result<MirrorState, String>>>>;
+
+/// Demande adressée au thread de publication.
+enum PublishRequest {
+    /// Nouvel état à sérialiser et diffuser.
+    State(MirrorState),
+    /// Nouvelle diffusion du dernier état (instance suiveuse connectée).
+    Resend,
+}
+
+/// État du mode miroir de l’application.
+#[derive(Default)]
+pub struct Mirror {
+    /// Rôle de l’instance.
+    pub role: MirrorRole,
+    /// Synchronise aussi la vue (zoom et déplacement) du graphe principal.
+    pub sync_view: bool,
+    /// Dernier état reçu de l’instance principale, pas encore affiché.
+    pub inbox: MirrorInbox,
+    /// Révision du plan, phases et vue du dernier état publié.
+    published: Option<(u64, Vec<Phase>, Option<MirrorView>)>,
+    /// Instant de la dernière publication.
+    last_publish: Option<Instant>,
+    /// Nombre d’abonnements lors de la dernière publication.
+    subscriptions: usize,
+    /// Canal du thread de publication, démarré à la première publication.
+    publisher: Option<Sender<PublishRequest>>,
+    /// Dernière erreur de publication, pas encore signalée.
+    error: Arc<Mutex<Option<String>>>,
+}
+
+/// Démarre le thread de publication : chaque état reçu est sérialisé puis
+/// diffusé aux abonnés, sauf s’il dépasse la taille maximale d’un message.
+fn spawn_publisher(subscribers: Subscribers, error: Arc<Mutex<Option<String>>>) -> Sender<PublishRequest> {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        let mut last = None;
+        while let Ok(mut request) = rx.recv() {
+            // Seul l’état le plus récent mérite d’être diffusé
+            while let Ok(newer) = rx.try_recv() {
+                if matches!(newer, PublishRequest::State(_)) || matches!(request, PublishRequest::Resend) {
+                    request = newer;
+                }
+            }
+            if let PublishRequest::State(state) = request {
+                let failure = match serde_json::to_string(&MirrorMessage { mirror: state }) {
+                    Ok(json) if json.len() > MAX_MESSAGE_BYTES => Some(format!(
+                        "État miroir de {} octets au-delà de la limite de {} : non diffusé",
+                        json.len(),
+                        MAX_MESSAGE_BYTES
+                    )),
+                    Ok(json) => {
+                        last = Some(json);
+                        None
+                    }
+                    Err(e) => Some(format!("Sérialisation de l'état miroir impossible : {}", e)),
+                };
+                if let Some(message) = failure {
+                    eprintln!("{}", message);
+                    if let Ok(mut error) = error.lock() {
+                        *error = Some(message);
+                    }
+                    continue;
+                }
+            }
+            if let Some(json) = &last {
+                subscribers.broadcast_json(json);
+            }
+        }
+    });
+    tx
+}
+
+impl Mirror {
+    /// Crée l’état du mode miroir pour le rôle donné.
+    pub fn new(role: MirrorRole, sync_view: bool) -> Self {
+        Self { role, sync_view, ..Self::default() }
+    }
+
+    /// Publie l’état construit par `state` aux abonnés si la révision du plan
+    /// `revision`, les phases `phases` ou la vue `view` ont changé depuis la
+    /// dernière publication, ou si une instance suiveuse s’est connectée (au plus
+    /// une fois par [`PUBLISH_INTERVAL`]). L’état est construit sur le thread
+    /// appelant, puis sérialisé et diffusé par le thread de publication.
+    ///
+    /// Retourne `true` si la publication est différée : l’état doit être proposé
+    /// de nouveau après [`PUBLISH_INTERVAL`].
+    pub fn publish(
+        &mut self,
+        revision: u64,
+        phases: &[Phase],
+        view: Option<MirrorView>,
+        state: impl FnOnce() -> MirrorState,
+        subscribers: &Subscribers,
+        now: Instant,
+    ) -> bool {
+        if self.role != MirrorRole::Primary {
+            return false;
+        }
+        let subscriptions = subscribers.subscriptions();
+        let changed = !self
+            .published
+            .as_ref()
+            .is_some_and(|(published, known, shown)| *published == revision && known.as_slice() == phases && *shown == view);
+        if !changed && subscriptions == self.subscriptions {
+            return false;
+        }
+        if self.last_publish.is_some_and(|t| now.duration_since(t) < PUBLISH_INTERVAL) {
+            return true;
+        }
+        self.last_publish = Some(now);
+        self.subscriptions = subscriptions;
+        let request = if changed {
+            self.published = Some((revision, phases.to_vec(), view));
+            PublishRequest::State(state())
+        } else {
+            PublishRequest::Resend
+        };
+        let publisher = self.publisher.get_or_insert_with(|| spawn_publisher(subscribers.clone(), self.error.clone()));
+        if publisher.send(request).is_err() {
+            eprintln!("Thread de publication du miroir arrêté");
+        }
+        false
+    }
+
+    /// Dernière erreur de publication, si elle n’a pas encore été signalée.
+    pub fn take_error(&self) -> Option<String> {
+        self.error.lock().ok().and_then(|mut error| error.take())
+    }
+
+    /// Dernier état reçu de l’instance principale (ou erreur de réception), s’il
+    /// n’a pas encore été affiché.
+    pub fn take_state(&self) -> Option<Result<MirrorState, String>> {
+        self.inbox.lock().ok().and_then(|mut inbox| inbox.take())
+    }
+}
+
+/// Se connecte à l’instance principale (et s’y reconnecte après une coupure) :
+/// chaque état reçu (ou l’erreur d’un état trop volumineux) est déposé dans
+/// `inbox`, puis `notify` est appelé.
+pub fn spawn_follower(
+    address: String,
+    token: Option<String>,
+    inbox: MirrorInbox,
+    notify: impl Fn() + Send + 'static,
+) {
+    thread::spawn(move || loop {
+        match follow(&address, token.as_deref(), &inbox, &notify) {
+            Ok(()) => eprintln!("Instance principale {} déconnectée.", address),
+            Err(e) => eprintln!("{}", e),
+        }
+        thread::sleep(RECONNECT_DELAY);
+    });
+}
+
+/// Lit les états publiés par l’instance principale jusqu’à la fermeture de la connexion.
+fn follow(
+    address: &str,
+    token: Option<&str>,
+    inbox: &Mutex<Option<Result<MirrorState, String>>>,
+    notify: &impl Fn(),
+) -> Result<(), String> {
+    let mut stream =
+        TcpStream::connect(address).map_err(|e| format!("Connexion à l'instance principale {} impossible : {}", address, e))?;
+    let hello = serde_json::json!({"hello": {"client": "miroir", "token": token}});
+    writeln!(stream, "{}", hello).map_err(|e| format!("Poignée de main avec {} impossible : {}", address, e))?;
+    eprintln!("Suivi de l'instance principale {}.", address);
+    let mut reader = BufReader::new(stream);
+    while let Some(line) = read_message(&mut reader).map_err(|e| format!("Connexion à {} interrompue : {}", address, e))? {
+        let received = match line {
+            Ok(line) => match serde_json::from_str::<MirrorMessage>(&line) {
+                Ok(message) => Ok(message.mirror),
+                // Les autres lignes (événements du flux) ne concernent pas l’affichage
+                Err(_) => continue,
+            },
+            // Ligne trop longue ou illisible : l’état de l’instance principale est perdu
+            Err(e) => Err(format!("État miroir de {} refusé : {}", address, e)),
+        };
+        if let Err(e) = &received {
+            eprintln!("{}", e);
+        }
+        if let Ok(mut inbox) = inbox.lock() {
+            *inbox = Some(received);
+        }
+        notify();
+    }
+    Ok(())
+}
@@ -1,4 +1,86 @@
 pub mod app;
 pub mod task;
 pub mod background;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+pub mod constraints;
+pub mod history;
+pub mod repair;
+pub mod plan;
+pub mod report;
+pub mod cli;
+pub mod briefing;
+pub mod csv_import;
+pub mod gantt_export;
+pub mod timefmt;
+pub mod inspector;
+pub mod coloring;
+pub mod filter;
+pub mod rx_guard;
+pub mod exclusion;
+pub mod zone_editor;
+pub mod geometry;
+pub mod task_mesh;
+pub mod analysis;
+pub mod storage;
+pub mod query;
+pub mod system;
+pub mod platform;
+pub mod geo_export;
+pub mod effect;
+pub mod phase;
+pub mod hud;
+pub mod refresh;
+pub mod screenshot;
+pub mod protocol;
+pub mod headless;
+pub mod rate_limit;
+pub mod dedup;
+pub mod animation;
+pub mod plan_hash;
+pub mod compression;
+pub mod transport;
+pub mod mirror;
+pub mod wall;
+pub mod kiosk;
+pub mod markup;
+pub mod harmonics;
+pub mod intermod;
+pub mod antenna;
+pub mod presets;
+pub mod notes;
+pub mod attachment;
+pub mod baseline;
+pub mod sandbox;
+pub mod split;
+pub mod align;
+pub mod schedule;
+pub mod robustness;
+pub mod timeline;
+pub mod tooltip;
+pub mod pinned;
+pub mod hit_stack;
+pub mod mini_overlay;
+pub mod marginal;
+pub mod amp_timeline;
+pub mod watch;
+pub mod layers;
+pub mod detection;
+pub mod alerts;
+pub mod alarm;
+pub mod amplifiers;
+pub mod widget;
+pub mod view;
+pub mod events;
+pub mod watchdog;
+pub mod hierarchy;
+pub mod profile;
+pub mod config_watch;
+pub mod diagnostics;
+pub mod task_log;
+pub mod trash;
+pub mod placement;
+pub mod guides;
+pub mod headroom;
+pub mod range;
+pub mod revisions;
+pub mod git_import;
@@ -0,0 +1,93 @@
+//! Module d’affichage des notes des tâches.
+//!
+//! Les notes sont du texte libre sur plusieurs lignes, mis en forme avec un
+//! sous-ensemble de Markdown : titres (`#`, `##`, `###`), listes à puces (`-` ou
+//! `*`), listes numérotées (`1.`), **gras**, *italique* et `code`. Elles sont
+//! affichées dans l’info-bulle des tâches et dans l’aperçu de l’inspecteur.
+
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, TextFormat};
+
+/// Style d’un fragment de texte.
+#[derive(Clone, Copy, Default)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    code: bool,
+}
+
+/// Ajoute au `job` une ligne dont les fragments `**gras**`, `*italique*` et `` `code` `` sont mis en forme.
+fn append_inline(job: &mut LayoutJob, line: &str, font: &FontId, color: Color32, strong: Color32) {
+    let mut style = Style::default();
+    let mut text = String::new();
+    let flush = |job: &mut LayoutJob, text: &mut String, style: Style| {
+        if text.is_empty() {
+            return;
+        }
+        let font_id = if style.code { FontId::monospace(font.size) } else { font.clone() };
+        let format = TextFormat {
+            font_id,
+            color: if style.bold { strong } else { color },
+            italics: style.italic,
+            background: if style.code { Color32::from_gray(60) } else { Color32::TRANSPARENT },
+            ..Default::default()
+        };
+        job.append(text, 0., format);
+        text.clear();
+    };
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => {
+                flush(job, &mut text, style);
+                style.code = !style.code;
+            }
+            '*' | '_' if !style.code && chars.peek() == Some(&c) => {
+                chars.next();
+                flush(job, &mut text, style);
+                style.bold = !style.bold;
+            }
+            '*' | '_' if !style.code => {
+                flush(job, &mut text, style);
+                style.italic = !style.italic;
+            }
+            _ => text.push(c),
+        }
+    }
+    flush(job, &mut text, style);
+}
+
+/// Met en forme des notes Markdown.
+pub fn markdown_job(notes: &str, style: &egui::Style) -> LayoutJob {
+    let body = style.text_styles.get(&egui::TextStyle::Body).cloned().unwrap_or_default();
+    let color = style.visuals.text_color();
+    let strong = style.visuals.strong_text_color();
+    let mut job = LayoutJob::default();
+    for (i, line) in notes.lines().enumerate() {
+        if i > 0 {
+            job.append("\n", 0., TextFormat::simple(body.clone(), color));
+        }
+        let trimmed = line.trim_start();
+        let heading = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=3).contains(&heading) && trimmed[heading..].starts_with(' ') {
+            let font = FontId::proportional(body.size * (1.5 - 0.15 * heading as f32));
+            append_inline(&mut job, trimmed[heading..].trim(), &font, strong, strong);
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            job.append("• ", 8., TextFormat::simple(body.clone(), color));
+            append_inline(&mut job, item, &body, color, strong);
+        } else if let Some((number, item)) = trimmed.split_once(". ").filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) {
+            job.append(&format!("{}. ", number), 8., TextFormat::simple(body.clone(), color));
+            append_inline(&mut job, item, &body, color, strong);
+        } else {
+            append_inline(&mut job, line, &body, color, strong);
+        }
+    }
+    job
+}
+
+/// Affiche des notes Markdown.
+pub fn show_notes(ui: &mut egui::Ui, notes: &str) {
+    let mut job = markdown_job(notes, ui.style());
+    job.wrap.max_width = ui.available_width();
+    ui.label(job);
+}
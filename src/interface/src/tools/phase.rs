@@ -0,0 +1,96 @@
+//! Module des phases de mission.
+//!
+//! Une mission se découpe en phases (ex. transit, pénétration, retour) couvrant
+//! chacune une fenêtre temporelle. Les phases sont lues depuis un fichier de
+//! configuration JSON ([`PHASES_PATH`]) ou reçues sur stdin sous la forme
+//! `{"phase": {"name": "Pénétration", "t_start": 300, "t_end": 700, "color": [80, 160, 255]}}`.
+//! Elles sont dessinées en bandes horizontales discrètes sur toutes les
+//! fréquences, étiquetées sur le bord droit du graphe, et servent de filtre
+//! d’affichage des tâches.
+
+use std::fs;
+use std::path::Path;
+
+use egui::Color32;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::task::Task;
+
+/// Chemin du fichier de configuration des phases.
+pub const PHASES_PATH: &str = "phases.json";
+
+/// Opacité du fond des bandes de phase.
+const BAND_ALPHA: u8 = 28;
+
+/// Phase de mission.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Phase {
+    /// Nom de la phase.
+    pub name: String,
+    /// Temps de début en ms.
+    pub t_start: f64,
+    /// Temps de fin en ms.
+    pub t_end: f64,
+    /// Couleur RVB de la bande.
+    #[serde(default = "default_color")]
+    pub color: [u8; 3],
+}
+
+fn default_color() -> [u8; 3] {
+    [160, 160, 160]
+}
+
+/// Message d’ajout d’une phase reçu sur stdin.
+#[derive(Deserialize, JsonSchema)]
+pub struct PhaseMessage {
+    /// Phase à ajouter (remplace une phase de même nom).
+    pub phase: Phase,
+}
+
+impl Phase {
+    /// Couleur de l’étiquette de la phase.
+    pub fn label_color(&self) -> Color32 {
+        let [r, g, b] = self.color;
+        Color32::from_rgb(r, g, b)
+    }
+
+    /// Couleur de remplissage, très atténuée, de la bande de la phase.
+    pub fn fill(&self) -> Color32 {
+        let [r, g, b] = self.color;
+        Color32::from_rgba_unmultiplied(r, g, b, BAND_ALPHA)
+    }
+
+    /// Indique si l’instant `time` (ms) appartient à la phase.
+    pub fn contains(&self, time: f64) -> bool {
+        (self.t_start..=self.t_end).contains(&time)
+    }
+
+    /// Indique si la tâche émet pendant la phase.
+    pub fn overlaps(&self, task: &Task) -> bool {
+        task.time_start < self.t_end && task.time_end > self.t_start
+    }
+}
+
+/// Charge les phases depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_phases(path: &Path) -> Result<Vec<Phase>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Phases {} invalides : {}", path.display(), e))
+}
+
+/// Charge la configuration des phases, ou aucune phase si le fichier est absent ou invalide.
+pub fn load_phase_config() -> Vec<Phase> {
+    let path = Path::new(PHASES_PATH);
+    if !path.exists() {
+        return Vec::new();
+    }
+    load_phases(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        Vec::new()
+    })
+}
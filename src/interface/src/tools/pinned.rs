@@ -0,0 +1,73 @@
+//! Module des fiches épinglées.
+//!
+//! Alt+clic sur une tâche épingle le contenu de son info-bulle dans une fiche
+//! flottante, déplaçable, qui reste affichée quand le curseur s’éloigne. Plusieurs
+//! fiches peuvent être ouvertes à la fois ; chacune se ferme individuellement, ou
+//! toutes ensemble depuis le panneau latéral. Les fiches suivent la tâche par sa
+//! clé et reflètent donc ses modifications ultérieures.
+
+use crate::tools::task::Task;
+
+/// Fiche épinglée d’une tâche.
+struct PinnedCard {
+    /// Identifiant unique de la fiche (position de la fenêtre mémorisée par egui).
+    id: u64,
+    /// Clé de la tâche affichée.
+    key: String,
+}
+
+/// Ensemble des fiches épinglées.
+#[derive(Default)]
+pub struct PinnedCards {
+    /// Fiches ouvertes, dans l’ordre d’épinglage.
+    cards: Vec<PinnedCard>,
+    /// Identifiant de la prochaine fiche.
+    next_id: u64,
+}
+
+impl PinnedCards {
+    /// Épingle la tâche, sauf si elle l’est déjà.
+    pub fn pin(&mut self, task: &Task) {
+        if self.cards.iter().any(|c| c.key == task.key()) {
+            return;
+        }
+        self.cards.push(PinnedCard { id: self.next_id, key: task.key().to_string() });
+        self.next_id += 1;
+    }
+
+    /// Nombre de fiches ouvertes.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Indique si aucune fiche n’est ouverte.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Ferme toutes les fiches.
+    pub fn clear(&mut self) {
+        self.cards.clear();
+    }
+
+    /// Affiche les fiches, dont le contenu est dessiné par `content`.
+    pub fn show(&mut self, ctx: &egui::Context, tasks: &[Task], mut content: impl FnMut(&mut egui::Ui, &Task)) {
+        self.cards.retain(|card| {
+            let task = tasks.iter().find(|t| t.key() == card.key);
+            let mut open = true;
+            egui::Window::new(task.map_or(card.key.as_str(), |t| t.name.as_str()))
+                .id(egui::Id::new(("pinned_card", card.id)))
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .default_pos(egui::pos2(360. + 24. * (card.id % 10) as f32, 120. + 24. * (card.id % 10) as f32))
+                .show(ctx, |ui| match task {
+                    Some(task) => content(ui, task),
+                    None => {
+                        ui.weak("Tâche supprimée du plan");
+                    }
+                });
+            open
+        });
+    }
+}
@@ -0,0 +1,238 @@
+//! Module du placement des tâches par glisser-déposer.
+//!
+//! Sur le graphe principal, Ctrl + glisser déplace la tâche saisie, ou trace une
+//! nouvelle tâche si le glisser commence hors de toute tâche. Pendant le
+//! glisser, la géométrie provisoire est vérifiée en continu ([`check_placement`])
+//! et l’aperçu est teinté selon le résultat :
+//!
+//! - vert : placement valide ;
+//! - orange : placement possible avec réserves (bande de l’amplificateur
+//!   dépassée ou marge de bord de bande entamée, émission pendant la garde Rx) ;
+//! - rouge : placement refusé au dépôt (bornes incohérentes, conflit avec une
+//!   autre tâche, masque d’exclusion).
+//!
+//! Des guides magnétiques ([`crate::tools::guides`]) indiquent les repères
+//! voisins et y aimantent la tâche (sauf si Alt est enfoncée). Échap annule le
+//! glisser en cours.
+
+use egui::Color32;
+
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::guides::Guide;
+use crate::tools::headroom::Headroom;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::{Amplifier, Task};
+
+/// Couleur d’un placement valide.
+const VALID_COLOR: Color32 = Color32::from_rgb(80, 200, 120);
+
+/// Couleur d’un placement avec réserves.
+const WARNING_COLOR: Color32 = Color32::from_rgb(255, 170, 0);
+
+/// Couleur d’un placement refusé.
+const INVALID_COLOR: Color32 = Color32::from_rgb(230, 50, 50);
+
+/// Résultat de la vérification d’un placement.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Placement {
+    /// Placement valide.
+    Valid,
+    /// Placement possible, avec les réserves indiquées.
+    Warning(Vec<String>),
+    /// Placement refusé, pour les raisons indiquées.
+    Invalid(Vec<String>),
+}
+
+impl Placement {
+    /// Couleur de l’aperçu.
+    pub fn color(&self) -> Color32 {
+        match self {
+            Placement::Valid => VALID_COLOR,
+            Placement::Warning(_) => WARNING_COLOR,
+            Placement::Invalid(_) => INVALID_COLOR,
+        }
+    }
+
+    /// Raisons des réserves ou du refus.
+    pub fn reasons(&self) -> &[String] {
+        match self {
+            Placement::Valid => &[],
+            Placement::Warning(reasons) | Placement::Invalid(reasons) => reasons,
+        }
+    }
+}
+
+/// Vérifie le placement de `task` parmi les tâches `others` (hors tâche
+/// déplacée et tâches parentes), les masques d’exclusion, la garde Rx et la
+/// marge aux bords de bande.
+pub fn check_placement<'a>(
+    task: &Task,
+    others: impl IntoIterator<Item = &'a Task>,
+    masks: &[ExclusionMask],
+    rx_guard: &RxGuard,
+    headroom: &Headroom,
+) -> Placement {
+    if let Err(e) = task.validate() {
+        return Placement::Invalid(vec![e]);
+    }
+    let mut invalid: Vec<String> = others
+        .into_iter()
+        .filter(|other| task.overlaps(other))
+        .map(|other| format!("conflit avec « {} »", other.name))
+        .collect();
+    invalid.extend(
+        masks
+            .iter()
+            .filter(|m| {
+                task.freq_start < m.freq_end && m.freq_start < task.freq_end
+                    && task.time_start < m.time_end && m.time_start < task.time_end
+            })
+            .map(|m| format!("masque d'exclusion « {} »", m.name)),
+    );
+    if !invalid.is_empty() {
+        return Placement::Invalid(invalid);
+    }
+    let mut warnings = Vec::new();
+    if !task.amplifier.covers(task.freq_start, task.freq_end) {
+        warnings.push(format!("hors de la bande de {}", task.amplifier.label()));
+    } else if headroom.check(0, task).is_some() {
+        warnings.push(format!("dans la marge de bord de bande de {}", task.amplifier.label()));
+    }
+    if let Err(e) = rx_guard.validate(task) {
+        warnings.push(e);
+    }
+    if warnings.is_empty() {
+        Placement::Valid
+    } else {
+        Placement::Warning(warnings)
+    }
+}
+
+/// Nom inédit d’une tâche tracée : « Nouvelle tâche n ».
+pub fn new_task_name(tasks: &[Task]) -> String {
+    (tasks.len() + 1..)
+        .map(|n| format!("Nouvelle tâche {}", n))
+        .find(|name| tasks.iter().all(|t| t.key() != name))
+        .unwrap_or_default()
+}
+
+/// Nature du glisser en cours.
+#[derive(Clone, Debug)]
+enum DragMode {
+    /// Déplacement de la tâche d’indice `index`, saisie au point `grab`.
+    Move { index: usize, grab: [f64; 2], original: Box<Task> },
+    /// Tracé d’une nouvelle tâche depuis le coin `anchor`.
+    Create { anchor: [f64; 2] },
+}
+
+/// Glisser-déposer d’une tâche sur le graphe.
+pub struct TaskDrag {
+    /// Indique si la tâche est aimantée sur les guides.
+    pub magnetic: bool,
+    /// Glisser en cours.
+    mode: Option<DragMode>,
+    /// Géométrie provisoire de la tâche.
+    pub preview: Option<Task>,
+    /// Vérification de la géométrie provisoire.
+    pub placement: Option<Placement>,
+    /// Guides de la géométrie provisoire.
+    pub guides: Vec<Guide>,
+}
+
+impl Default for TaskDrag {
+    fn default() -> Self {
+        Self { magnetic: true, mode: None, preview: None, placement: None, guides: Vec::new() }
+    }
+}
+
+impl TaskDrag {
+    /// Indique si un glisser est en cours.
+    pub fn is_active(&self) -> bool {
+        self.mode.is_some()
+    }
+
+    /// Indice de la tâche déplacée.
+    pub fn moved_index(&self) -> Option<usize> {
+        match &self.mode {
+            Some(DragMode::Move { index, .. }) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Commence le déplacement de la tâche d’indice `index`, saisie au point
+    /// `[fréquence, temps]`.
+    pub fn start_move(&mut self, index: usize, task: &Task, point: [f64; 2]) {
+        self.mode = Some(DragMode::Move { index, grab: point, original: Box::new(task.clone()) });
+        self.preview = Some(task.clone());
+        self.placement = None;
+    }
+
+    /// Commence le tracé d’une nouvelle tâche au point `[fréquence, temps]`.
+    pub fn start_create(&mut self, point: [f64; 2]) {
+        self.mode = Some(DragMode::Create { anchor: point });
+        self.preview = None;
+        self.placement = None;
+    }
+
+    /// Met à jour la géométrie provisoire d’après la position du pointeur ;
+    /// `name` est le nom d’une tâche tracée.
+    pub fn update(&mut self, point: [f64; 2], name: impl FnOnce() -> String) {
+        let preview = match &self.mode {
+            None => return,
+            Some(DragMode::Move { grab, original, .. }) => {
+                let (df, dt) = (point[0] - grab[0], point[1] - grab[1]);
+                let mut task = Task::clone(original);
+                task.freq_start += df;
+                task.freq_end += df;
+                if let Some(shape) = &mut task.shape {
+                    shape.iter_mut().for_each(|p| p[0] += df);
+                }
+                task.shift_time(dt);
+                task
+            }
+            Some(DragMode::Create { anchor }) => {
+                let (f0, f1) = (anchor[0].min(point[0]), anchor[0].max(point[0]));
+                let (t0, t1) = (anchor[1].min(point[1]), anchor[1].max(point[1]));
+                let amplifier = Amplifier::ALL.into_iter().find(|a| a.covers(f0, f1)).unwrap_or(Amplifier::ALL[0]);
+                match self.preview.take() {
+                    Some(mut task) => {
+                        (task.freq_start, task.freq_end, task.time_start, task.time_end) = (f0, f1, t0, t1);
+                        task.amplifier = amplifier;
+                        task
+                    }
+                    None => Task::block(name(), amplifier, (f0, f1), (t0, t1)),
+                }
+            }
+        };
+        self.preview = Some(preview);
+    }
+
+    /// Annule le glisser en cours.
+    pub fn cancel(&mut self) {
+        self.mode = None;
+        self.preview = None;
+        self.placement = None;
+        self.guides.clear();
+    }
+
+    /// Termine le glisser et retourne la clé de la tâche déplacée (`None` pour
+    /// une tâche tracée) et sa nouvelle géométrie.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le placement est refusé.
+    pub fn finish(&mut self) -> Option<Result<(Option<String>, Task), String>> {
+        let key = match &self.mode {
+            Some(DragMode::Move { original, .. }) => Some(original.key().to_string()),
+            _ => None,
+        };
+        let (preview, placement) = (self.preview.take(), self.placement.take());
+        self.mode = None;
+        self.guides.clear();
+        let task = preview?;
+        Some(match placement {
+            Some(Placement::Invalid(reasons)) => Err(format!("Placement de « {} » refusé : {}", task.name, reasons.join(", "))),
+            _ => Ok((key, task)),
+        })
+    }
+}
@@ -0,0 +1,99 @@
+//! Module de lecture et d’écriture des fichiers de plan.
+//!
+//! Un plan est une liste de tâches ayant la même forme que les messages reçus
+//! sur stdin. Le format du fichier est choisi d’après son extension :
+//! - `.json` (par défaut) : liste JSON de tâches ;
+//! - `.yaml` / `.yml` : liste YAML de tâches ;
+//! - `.toml` : tableau `[[tasks]]`, TOML n’autorisant pas de liste à la racine.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::task::{Amplifier, Task};
+
+/// Formats de fichier de plan supportés.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlanFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl PlanFormat {
+    /// Détermine le format d’après l’extension du fichier (JSON par défaut).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("yaml") | Some("yml") => PlanFormat::Yaml,
+            Some("toml") => PlanFormat::Toml,
+            _ => PlanFormat::Json,
+        }
+    }
+}
+
+/// Enveloppe du plan pour le format TOML.
+#[derive(Serialize, Deserialize)]
+struct TomlPlan {
+    tasks: Vec<Task>,
+}
+
+/// Désérialise un plan depuis son contenu textuel.
+pub fn parse_plan(content: &str, format: PlanFormat) -> Result<Vec<Task>, String> {
+    match format {
+        PlanFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        PlanFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        PlanFormat::Toml => toml::from_str::<TomlPlan>(content)
+            .map(|plan| plan.tasks)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Sérialise un plan dans le format demandé.
+pub fn format_plan(tasks: &[Task], format: PlanFormat) -> Result<String, String> {
+    match format {
+        PlanFormat::Json => serde_json::to_string_pretty(tasks).map_err(|e| e.to_string()),
+        PlanFormat::Yaml => serde_yaml::to_string(tasks).map_err(|e| e.to_string()),
+        PlanFormat::Toml => toml::to_string_pretty(&TomlPlan { tasks: tasks.to_vec() }).map_err(|e| e.to_string()),
+    }
+}
+
+/// Charge un plan depuis un fichier JSON, YAML ou TOML.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_plan(path: &Path) -> Result<Vec<Task>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let mut tasks = parse_plan(&content, PlanFormat::from_path(path))
+        .map_err(|e| format!("Plan {} invalide : {}", path.display(), e))?;
+    tasks.iter_mut().for_each(Task::fit_to_shape);
+    Ok(tasks)
+}
+
+/// Enregistre un plan dans un fichier JSON, YAML ou TOML.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le plan ne peut pas être sérialisé ou écrit.
+pub fn save_plan(path: &Path, tasks: &[Task]) -> Result<(), String> {
+    let content = format_plan(tasks, PlanFormat::from_path(path))
+        .map_err(|e| format!("Sérialisation du plan impossible : {}", e))?;
+    fs::write(path, content)
+        .map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))
+}
+
+/// Plan de démonstration proposé lorsqu’aucun émetteur n’est connecté.
+///
+/// Il reprend les tâches envoyées par l’émetteur de démonstration, complétées
+/// de quelques tâches couvrant les autres amplificateurs.
+pub fn demo_plan() -> Vec<Task> {
+    vec![
+        Task::block("Init capteurs", Amplifier::A20_500, (100., 300.), (0., 300.)),
+        Task::block("Transmission", Amplifier::A1000_2500, (1000., 2500.), (300., 600.)),
+        Task::block("Sleep mode", Amplifier::A2400_6000, (5000., 5500.), (0., 1000.)),
+        Task::sweep("Balayage VHF", Amplifier::A20_500, (30., 90.), (600., 900.), 10.),
+        Task::block("Radar L", Amplifier::A960_1215, (1000., 1200.), (50., 250.)),
+    ]
+}
@@ -0,0 +1,51 @@
+//! Module de l’empreinte du plan.
+//!
+//! L’émetteur joint l’empreinte de son plan aux rediffusions (`{"plan": […],
+//! "hash": "…"}`) et l’envoie seule pour contrôle (`{"plan_hash": "…"}`).
+//! L’interface la compare à l’empreinte de ses propres tâches : un écart révèle
+//! une désynchronisation silencieuse (message perdu, affichage relancé) et
+//! déclenche une demande de rediffusion complète.
+//!
+//! L’empreinte est le FNV-1a 64 bits, en hexadécimal, des tâches sérialisées en
+//! JSON (clés triées, une tâche par ligne terminée par `\n`), triées par clé
+//! (identifiant ou nom). L’émetteur de démonstration implémente le même calcul.
+
+use serde_json::Value;
+
+use crate::tools::task::Task;
+
+/// Base de décalage de FNV-1a 64 bits.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Nombre premier de FNV-1a 64 bits.
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Trie récursivement les clés des objets JSON.
+fn canonical(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<(String, Value)> = map.into_iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(fields.into_iter().map(|(k, v)| (k, canonical(v))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonical).collect()),
+        other => other,
+    }
+}
+
+/// Empreinte d’un ensemble de tâches, indépendante de leur ordre.
+pub fn plan_hash(tasks: &[Task]) -> String {
+    let mut lines: Vec<(&str, String)> = tasks
+        .iter()
+        .map(|t| (t.key(), serde_json::to_value(t).map(canonical).unwrap_or_default().to_string()))
+        .collect();
+    lines.sort();
+    let mut hash = FNV_OFFSET;
+    for (_, line) in lines {
+        for byte in line.bytes().chain([b'\n']) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,101 @@
+//! Module des plateformes de brouillage.
+//!
+//! Une mission peut engager plusieurs plateformes (aéronefs, véhicules) portant
+//! chacune ses brouilleurs. Ce module fournit la liste des plateformes d’un plan,
+//! la teinte associée à chacune et la disposition en couloirs : l’axe des temps
+//! est découpé en un couloir par plateforme, chaque couloir couvrant la durée de
+//! la mission, empilés du bas vers le haut.
+
+use crate::tools::coloring::{palette, ColorMap};
+use crate::tools::task::Task;
+use crate::tools::utils::MAX_TIME;
+
+/// Hauteur d’un couloir sur l’axe des temps en ms (durée de la mission et espacement).
+pub const LANE_HEIGHT: f64 = MAX_TIME * 1.1;
+
+/// Part de la teinte de la plateforme mélangée à la couleur des tâches.
+const TINT_AMOUNT: f32 = 0.35;
+
+/// Libellé d’une plateforme, y compris pour les tâches sans plateforme.
+pub fn platform_label(platform: Option<&str>) -> &str {
+    platform.unwrap_or("Sans plateforme")
+}
+
+/// Plateformes d’un plan par ordre alphabétique, les tâches sans plateforme (`None`) en dernier.
+pub fn platforms(tasks: &[Task]) -> Vec<Option<String>> {
+    let mut platforms: Vec<Option<String>> = Vec::new();
+    for task in tasks {
+        if !platforms.contains(&task.platform) {
+            platforms.push(task.platform.clone());
+        }
+    }
+    platforms.sort_by_key(|p| (p.is_none(), p.clone()));
+    platforms
+}
+
+/// Teinte les couleurs des tâches selon leur plateforme et complète la légende.
+///
+/// Les tâches sans plateforme conservent leur couleur.
+pub fn tint_by_platform(map: &mut ColorMap, tasks: &[Task]) {
+    let platforms: Vec<String> = platforms(tasks).into_iter().flatten().collect();
+    let tints = palette(platforms.len());
+    for (color, task) in map.colors.iter_mut().zip(tasks) {
+        if let Some(i) = platforms.iter().position(|p| task.platform.as_ref() == Some(p)) {
+            *color = color.lerp_to_gamma(tints[i], TINT_AMOUNT);
+        }
+    }
+    map.legend.extend(platforms.into_iter().zip(tints).map(|(p, tint)| (format!("Plateforme {}", p), tint)));
+}
+
+/// Disposition de l’axe des temps en un couloir par plateforme.
+pub struct PlatformLanes {
+    /// Plateformes, dans l’ordre des couloirs (du bas vers le haut).
+    pub platforms: Vec<Option<String>>,
+}
+
+impl PlatformLanes {
+    /// Crée la disposition des plateformes du plan.
+    pub fn new(tasks: &[Task]) -> Self {
+        Self { platforms: platforms(tasks) }
+    }
+
+    /// Décalage temporel du couloir d’indice `lane`, en ms.
+    pub fn lane_offset(lane: usize) -> f64 {
+        lane as f64 * LANE_HEIGHT
+    }
+
+    /// Décalages et libellés des couloirs (au moins un couloir, même sans tâche).
+    pub fn lanes(&self) -> Vec<(f64, &str)> {
+        if self.platforms.is_empty() {
+            return vec![(0., platform_label(None))];
+        }
+        self.platforms
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (Self::lane_offset(i), platform_label(p.as_deref())))
+            .collect()
+    }
+
+    /// Hauteur totale de l’axe des temps, en ms.
+    pub fn height(&self) -> f64 {
+        self.platforms.len().max(1) as f64 * LANE_HEIGHT
+    }
+
+    /// Temps de mission correspondant à une ordonnée du graphe.
+    pub fn local_time(y: f64) -> f64 {
+        y.rem_euclid(LANE_HEIGHT)
+    }
+
+    /// Copie des tâches décalées dans le couloir de leur plateforme.
+    pub fn layout(&self, tasks: &[Task]) -> Vec<Task> {
+        tasks
+            .iter()
+            .map(|task| {
+                let mut task = task.clone();
+                let lane = self.platforms.iter().position(|p| *p == task.platform).unwrap_or(0);
+                task.shift_time(Self::lane_offset(lane));
+                task
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,159 @@
+//! Module des bandes de référence (navigation, liaisons de données…).
+//!
+//! Un jeu de bandes bien connues (GPS L1/L2/L5, Link 16, ADS-B…) est intégré à
+//! l’application ; il peut être remplacé par un fichier de configuration JSON
+//! ([`PRESETS_PATH`]). Depuis le panneau latéral, chaque bande peut être activée
+//! comme bande protégée — elle est alors traitée comme un masque d’exclusion sur
+//! toute la mission — ou comme bande cible, simplement surlignée sur le graphe.
+
+use std::fs;
+use std::path::Path;
+
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::utils::MAX_TIME;
+
+/// Chemin du fichier de configuration des bandes de référence.
+pub const PRESETS_PATH: &str = "band_presets.json";
+
+/// Couleur de remplissage des bandes cibles.
+pub const TARGET_COLOR: Color32 = Color32::from_rgba_premultiplied(13, 34, 13, 40);
+
+/// Bande de référence.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BandPreset {
+    /// Nom de la bande.
+    pub name: String,
+    /// Fréquence de début en MHz.
+    pub freq_start: f64,
+    /// Fréquence de fin en MHz.
+    pub freq_end: f64,
+}
+
+/// Rôle d’une bande de référence sur le graphe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PresetRole {
+    /// Bande masquée.
+    #[default]
+    Off,
+    /// Bande protégée : masque d’exclusion sur toute la mission.
+    Protected,
+    /// Bande cible, surlignée.
+    Target,
+}
+
+impl PresetRole {
+    /// Liste de tous les rôles.
+    pub const ALL: [PresetRole; 3] = [PresetRole::Off, PresetRole::Protected, PresetRole::Target];
+
+    /// Libellé du rôle.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PresetRole::Off => "–",
+            PresetRole::Protected => "Protégée",
+            PresetRole::Target => "Cible",
+        }
+    }
+}
+
+/// Bandes de référence intégrées.
+pub fn default_presets() -> Vec<BandPreset> {
+    let preset = |name: &str, freq_start, freq_end| BandPreset { name: name.into(), freq_start, freq_end };
+    vec![
+        preset("GPS L1", 1563.42, 1587.42),
+        preset("GPS L2", 1215.6, 1239.6),
+        preset("GPS L5", 1164.45, 1188.45),
+        preset("Galileo E1", 1559., 1591.),
+        preset("GLONASS L1", 1593., 1610.),
+        preset("Link 16", 969., 1206.),
+        preset("ADS-B 1090", 1089., 1091.),
+        preset("IFF/SSR 1030", 1029., 1031.),
+        preset("DME/TACAN", 962., 1213.),
+        preset("Aviation VHF", 118., 137.),
+        preset("Garde UHF 243", 242.9, 243.1),
+        preset("Garde VHF 121.5", 121.4, 121.6),
+        preset("Iridium", 1616., 1626.5),
+    ]
+}
+
+/// Bandes de référence et rôle de chacune.
+#[derive(Default)]
+pub struct BandPresets {
+    /// Bandes disponibles.
+    pub presets: Vec<BandPreset>,
+    /// Rôle de chaque bande, dans l’ordre de [`BandPresets::presets`].
+    pub roles: Vec<PresetRole>,
+}
+
+impl BandPresets {
+    /// Crée la liste de bandes, toutes masquées.
+    pub fn new(presets: Vec<BandPreset>) -> Self {
+        let roles = vec![PresetRole::Off; presets.len()];
+        Self { presets, roles }
+    }
+
+    /// Bandes ayant le rôle `role`.
+    pub fn with_role(&self, role: PresetRole) -> impl Iterator<Item = &BandPreset> {
+        self.presets.iter().zip(&self.roles).filter(move |(_, r)| **r == role).map(|(p, _)| p)
+    }
+
+    /// Masques d’exclusion des bandes protégées, sur toute la mission.
+    pub fn protected_masks(&self) -> Vec<ExclusionMask> {
+        self.with_role(PresetRole::Protected)
+            .map(|p| ExclusionMask {
+                name: p.name.clone(),
+                freq_start: p.freq_start,
+                freq_end: p.freq_end,
+                time_start: 0.,
+                time_end: MAX_TIME,
+            })
+            .collect()
+    }
+
+    /// Affiche la liste des bandes dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("band_presets").num_columns(2).show(ui, |ui| {
+            for (preset, role) in self.presets.iter().zip(&mut self.roles) {
+                ui.label(&preset.name)
+                    .on_hover_text(format!("{} – {} MHz", preset.freq_start, preset.freq_end));
+                ui.horizontal(|ui| {
+                    for r in PresetRole::ALL {
+                        ui.selectable_value(role, r, r.label());
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    }
+}
+
+/// Charge les bandes de référence depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_presets(path: &Path) -> Result<Vec<BandPreset>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let presets: Vec<BandPreset> =
+        serde_json::from_str(&content).map_err(|e| format!("Bandes de référence {} invalides : {}", path.display(), e))?;
+    if let Some(p) = presets.iter().find(|p| p.freq_start >= p.freq_end) {
+        return Err(format!("Bande {} : freq_start doit être inférieure à freq_end", p.name));
+    }
+    Ok(presets)
+}
+
+/// Charge la configuration des bandes de référence, ou les bandes intégrées si le
+/// fichier est absent ou invalide.
+pub fn load_preset_config() -> BandPresets {
+    let path = Path::new(PRESETS_PATH);
+    if !path.exists() {
+        return BandPresets::new(default_presets());
+    }
+    BandPresets::new(load_presets(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        default_presets()
+    }))
+}
@@ -0,0 +1,199 @@
+//! Module des profils d’affichage.
+//!
+//! Un profil regroupe, sous un nom, les réglages propres à un poste : thème,
+//! disposition des panneaux, couches affichées et droits de l’opérateur. Trois
+//! profils sont prédéfinis ([`builtin_profiles`]) : `planner` (poste de
+//! planification, tous droits), `operator` (consultation, plan non modifiable)
+//! et `wall-display` (écran mural, sans panneau ni fenêtre). Le fichier
+//! [`PROFILES_PATH`] peut en ajouter ou les redéfinir, par exemple :
+//!
+//! ```json
+//! {"profiles": [{"name": "nuit", "theme": "dark", "layers": ["planned"], "permissions": {"edit": false}}]}
+//! ```
+//!
+//! Le profil est choisi au lancement (`--profile <nom>`) et peut être changé en
+//! cours d’exécution depuis le panneau latéral.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::tools::layers::PlanLayer;
+
+/// Chemin du fichier de configuration des profils.
+pub const PROFILES_PATH: &str = "profiles.json";
+
+/// Thème de l’interface.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    /// Thème sombre.
+    #[default]
+    Dark,
+    /// Thème clair.
+    Light,
+}
+
+impl Theme {
+    /// Apparence egui du thème.
+    pub fn visuals(self) -> egui::Visuals {
+        match self {
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn all_layers() -> Vec<String> {
+    PlanLayer::ALL.iter().map(|layer| layer.name().to_string()).collect()
+}
+
+/// Droits de l’opérateur.
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize)]
+pub struct Permissions {
+    /// Modification du plan depuis l’interface (édition, corrections, planification,
+    /// import, annulation).
+    #[serde(default = "enabled")]
+    pub edit: bool,
+    /// Ouverture des fenêtres de dialogue (inspecteur, import CSV, éditeur de zones).
+    #[serde(default = "enabled")]
+    pub dialogs: bool,
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self { edit: true, dialogs: true }
+    }
+}
+
+/// Profil d’affichage.
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct Profile {
+    /// Nom du profil.
+    pub name: String,
+    /// Thème de l’interface.
+    #[serde(default)]
+    pub theme: Theme,
+    /// Affichage du panneau latéral (sinon révélé par F2).
+    #[serde(default = "enabled")]
+    pub side_panel: bool,
+    /// Affichage du bandeau d’occupation temporelle.
+    #[serde(default)]
+    pub time_strip: bool,
+    /// Affichage du bandeau d’occupation fréquentielle.
+    #[serde(default)]
+    pub freq_strip: bool,
+    /// Affichage du HUD temps réel.
+    #[serde(default = "enabled")]
+    pub hud: bool,
+    /// Couches affichées (`planned`, `executed`, `observed`).
+    #[serde(default = "all_layers")]
+    pub layers: Vec<String>,
+    /// Droits de l’opérateur.
+    #[serde(default)]
+    pub permissions: Permissions,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: "défaut".into(),
+            theme: Theme::Dark,
+            side_panel: true,
+            time_strip: false,
+            freq_strip: false,
+            hud: true,
+            layers: all_layers(),
+            permissions: Permissions::default(),
+        }
+    }
+}
+
+impl Profile {
+    /// Indique si la couche est affichée par le profil.
+    pub fn shows_layer(&self, layer: PlanLayer) -> bool {
+        self.layers.iter().any(|name| name == layer.name())
+    }
+
+    /// Vérifie la cohérence du profil.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le nom est vide ou si une couche est inconnue.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Profil sans nom".into());
+        }
+        match self.layers.iter().find(|name| PlanLayer::ALL.iter().all(|layer| layer.name() != name.as_str())) {
+            Some(name) => Err(format!("Profil « {} » : couche « {} » inconnue", self.name, name)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Profils prédéfinis : `planner`, `operator` et `wall-display`.
+pub fn builtin_profiles() -> Vec<Profile> {
+    vec![
+        Profile { name: "planner".into(), time_strip: true, freq_strip: true, ..Profile::default() },
+        Profile {
+            name: "operator".into(),
+            permissions: Permissions { edit: false, dialogs: true },
+            ..Profile::default()
+        },
+        Profile {
+            name: "wall-display".into(),
+            side_panel: false,
+            permissions: Permissions { edit: false, dialogs: false },
+            ..Profile::default()
+        },
+    ]
+}
+
+/// Configuration des profils.
+#[derive(Deserialize, Default)]
+pub struct ProfileConfig {
+    /// Profils ajoutés ou redéfinis (même nom qu’un profil prédéfini).
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+/// Charge les profils depuis un fichier JSON, complétés des profils prédéfinis
+/// qu’il ne redéfinit pas.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible, mal formé ou
+/// contient un profil incohérent.
+pub fn load_profiles(path: &Path) -> Result<Vec<Profile>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let config: ProfileConfig =
+        serde_json::from_str(&content).map_err(|e| format!("Profils {} invalides : {}", path.display(), e))?;
+    config.profiles.iter().try_for_each(Profile::validate).map_err(|e| format!("{} : {}", path.display(), e))?;
+    let mut profiles = builtin_profiles();
+    for profile in config.profiles {
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+    }
+    Ok(profiles)
+}
+
+/// Charge la configuration des profils, ou les profils prédéfinis si le fichier
+/// est absent ou invalide.
+pub fn load_profile_config() -> Vec<Profile> {
+    let path = Path::new(PROFILES_PATH);
+    if !path.exists() {
+        return builtin_profiles();
+    }
+    load_profiles(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        builtin_profiles()
+    })
+}
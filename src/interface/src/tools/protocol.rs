@@ -0,0 +1,452 @@
+//! Module du protocole de messages reçus sur stdin.
+//!
+//! Chaque ligne de l’entrée standard est un objet JSON : une tâche ou un message
+//! de configuration, distingués par leurs champs. Le schéma JSON de l’ensemble
+//! des messages est généré à partir de ces types ([`schema_json`]) et affiché par
+//! `--dump-schema` : il constitue le contrat de référence pour les émetteurs
+//! écrits dans d’autres langages.
+//!
+//! L’entrée standard peut provenir d’un émetteur défaillant ou hostile : les
+//! lignes sont lues avec une taille bornée ([`read_message`]), et l’analyse
+//! ([`parse_message`]) refuse les messages incohérents sans jamais paniquer, de
+//! sorte qu’aucune entrée ne puisse bloquer ou interrompre l’affichage. Le
+//! point d’entrée `fuzz/fuzz_targets/parse_message.rs` (cargo-fuzz) vérifie
+//! cette propriété.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+
+use schemars::{schema_for, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+use crate::tools::attachment::MAX_ATTACHMENTS;
+use crate::tools::compression::MAX_DECOMPRESSED_BYTES;
+use crate::tools::effect::Effect;
+use crate::tools::exclusion::ExclusionMessage;
+use crate::tools::detection::DetectionMessage;
+use crate::tools::phase::PhaseMessage;
+use crate::tools::rx_guard::RxGuardMessage;
+use crate::tools::split::{JoinMessage, SplitMessage};
+use crate::tools::task::{Amplifier, Location, Task};
+
+/// Version du protocole, incrémentée à chaque changement incompatible du schéma.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Chemin du schéma de référence des émetteurs (sortie de `--dump-schema`),
+/// comparé au schéma de l’interface par les diagnostics s’il est présent.
+pub const SCHEMA_PATH: &str = "protocol_schema.json";
+
+/// Taille maximale d’un message, en octets.
+pub const MAX_MESSAGE_BYTES: usize = 1 << 20;
+
+/// Nombre maximal de sommets du contour polygonal d’une tâche.
+pub const MAX_SHAPE_POINTS: usize = 4096;
+
+/// Tâche reçue de l’émetteur (ajoutée, ou mise à jour si elle est déjà affichée).
+#[derive(Deserialize, JsonSchema)]
+pub struct IncomingTask {
+    /// Identifiant de la tâche : une tâche reçue de nouveau avec le même
+    /// identifiant (ou, à défaut, le même nom) est mise à jour.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Nom de la tâche.
+    pub name: String,
+    /// Fréquence de début en MHz.
+    pub freq_start: f64,
+    /// Fréquence de fin en MHz.
+    pub freq_end: f64,
+    /// Temps de début en ms.
+    pub time_start: f64,
+    /// Temps de fin en ms.
+    pub time_end: f64,
+    /// Amplificateur (`A20_500`, `A500_1000`, `A960_1215`, `A1000_2500` ou `A2400_6000`).
+    pub amplifier: String,
+    /// Groupe (ou mission) auquel appartient la tâche.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Identifiant (ou nom) de la tâche parente.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Plateforme portant le brouilleur.
+    #[serde(default)]
+    pub platform: Option<String>,
+    /// Antenne rayonnant la tâche.
+    #[serde(default)]
+    pub antenna: Option<String>,
+    /// Position de la zone d’engagement.
+    #[serde(default)]
+    pub location: Option<Location>,
+    /// Effet recherché.
+    #[serde(default)]
+    pub effect: Option<Effect>,
+    /// Priorité de la tâche.
+    #[serde(default)]
+    pub priority: Option<u32>,
+    /// Statut de la tâche.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Notes libres, au format Markdown.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Références de documents externes (URI ou chemins de fichiers).
+    #[serde(default)]
+    pub attachments: Vec<String>,
+    /// Métadonnées libres (clé → valeur).
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    /// Puissance d’émission en W.
+    #[serde(default)]
+    pub power: Option<f64>,
+    /// Autorise la tâche à émettre pendant la garde Rx.
+    #[serde(default)]
+    pub rx_safe: bool,
+    /// Tâche planifiée à titre provisoire.
+    #[serde(default)]
+    pub tentative: bool,
+    /// Contour polygonal `[fréquence, temps]` de la tâche.
+    #[serde(default)]
+    pub shape: Option<Vec<[f64; 2]>>,
+    /// Tolérance en fréquence en MHz (incertitude d’accord, Doppler).
+    #[serde(default)]
+    pub freq_tolerance: Option<f64>,
+}
+
+impl IncomingTask {
+    /// Vérifie la cohérence de la tâche : valeurs finies, bornes ordonnées,
+    /// amplificateur connu et contour de 3 à [`MAX_SHAPE_POINTS`] sommets.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur décrivant la première incohérence trouvée.
+    pub fn validate(&self) -> Result<(), String> {
+        let bounds = [self.freq_start, self.freq_end, self.time_start, self.time_end];
+        if bounds.iter().any(|v| !v.is_finite()) {
+            return Err(format!("« {} » : bornes non finies", self.name));
+        }
+        // Une fréquence ponctuelle ou une tâche instantanée (bornes égales) est admise
+        if self.freq_start > self.freq_end {
+            return Err(format!("« {} » : freq_start ne doit pas dépasser freq_end", self.name));
+        }
+        if self.time_start > self.time_end {
+            return Err(format!("« {} » : time_start ne doit pas dépasser time_end", self.name));
+        }
+        if Amplifier::from_str(&self.amplifier).is_none() {
+            return Err(format!("« {} » : amplificateur inconnu « {} »", self.name, self.amplifier));
+        }
+        if let Some(shape) = &self.shape {
+            if shape.len() < 3 {
+                return Err(format!("« {} » : contour de moins de 3 sommets", self.name));
+            }
+            if shape.len() > MAX_SHAPE_POINTS {
+                return Err(format!("« {} » : contour de plus de {} sommets", self.name, MAX_SHAPE_POINTS));
+            }
+            if shape.iter().flatten().any(|v| !v.is_finite()) {
+                return Err(format!("« {} » : contour non fini", self.name));
+            }
+        }
+        if self.attachments.len() > MAX_ATTACHMENTS {
+            return Err(format!("« {} » : plus de {} pièces jointes", self.name, MAX_ATTACHMENTS));
+        }
+        if self.power.is_some_and(|p| !p.is_finite()) {
+            return Err(format!("« {} » : puissance non finie", self.name));
+        }
+        if self.freq_tolerance.is_some_and(|t| !t.is_finite() || t < 0.) {
+            return Err(format!("« {} » : tolérance en fréquence invalide", self.name));
+        }
+        Ok(())
+    }
+
+    /// Convertit la tâche reçue (bornes recalculées si elle est polygonale).
+    pub fn into_task(self) -> Task {
+        let mut task = Task {
+            id: self.id,
+            name: self.name,
+            freq_start: self.freq_start,
+            freq_end: self.freq_end,
+            time_start: self.time_start,
+            time_end: self.time_end,
+            amplifier: Amplifier::from_str(&self.amplifier).unwrap_or(Amplifier::A20_500),
+            group: self.group,
+            parent_id: self.parent_id,
+            platform: self.platform,
+            antenna: self.antenna,
+            location: self.location,
+            effect: self.effect,
+            priority: self.priority,
+            status: self.status,
+            notes: self.notes,
+            attachments: self.attachments,
+            metadata: self.metadata,
+            power: self.power,
+            rx_safe: self.rx_safe,
+            tentative: self.tentative,
+            shape: self.shape,
+            freq_tolerance: self.freq_tolerance,
+        };
+        task.fit_to_shape();
+        task
+    }
+}
+
+/// Plan complet rediffusé périodiquement par l’émetteur, ex.
+/// `{"plan": [{"name": "Init capteurs", …}], "hash": "9f3a…"}` : les tâches
+/// reçues remplacent les tâches affichées, ce qui permet à un affichage lancé ou
+/// relancé en cours de mission de converger vers le plan de l’émetteur.
+#[derive(Deserialize, JsonSchema)]
+pub struct PlanMessage {
+    /// Ensemble des tâches du plan.
+    pub plan: Vec<IncomingTask>,
+    /// Empreinte du plan calculée par l’émetteur.
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+/// Poignée de main de l’émetteur, ex. `{"hello": {"client": "planificateur", "compression": ["gzip"]}}`.
+#[derive(Deserialize, JsonSchema)]
+pub struct HelloMessage {
+    /// Capacités de l’émetteur.
+    pub hello: Hello,
+}
+
+/// Capacités annoncées par l’émetteur lors de la poignée de main.
+#[derive(Deserialize, JsonSchema)]
+pub struct Hello {
+    /// Nom de l’émetteur.
+    #[serde(default)]
+    pub client: Option<String>,
+    /// Algorithmes de compression proposés, par ordre de préférence.
+    #[serde(default)]
+    pub compression: Vec<String>,
+    /// Secret partagé exigé des émetteurs distants (transport réseau authentifié).
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Message compressé avec l’algorithme négocié, ex.
+/// `{"compressed": "gzip", "data": "<base64>"}`. Le message décompressé est un
+/// message quelconque du protocole, hormis un autre message compressé.
+#[derive(Deserialize, JsonSchema)]
+pub struct CompressedMessage {
+    /// Algorithme de compression.
+    pub compressed: String,
+    /// Message compressé, encodé en base64.
+    pub data: String,
+}
+
+/// Contrôle de synchronisation : empreinte du plan de l’émetteur, ex.
+/// `{"plan_hash": "9f3a…"}`. En cas d’écart avec le plan affiché, l’interface
+/// demande une rediffusion complète.
+#[derive(Deserialize, JsonSchema)]
+pub struct PlanHashMessage {
+    /// Empreinte du plan de l’émetteur.
+    pub plan_hash: String,
+}
+
+/// Suppression des tâches satisfaisant une expression de filtrage,
+/// ex. `{"remove_where": "status == \"annulé\""}`.
+#[derive(Deserialize, JsonSchema)]
+pub struct RemoveWhereMessage {
+    /// Expression de filtrage des tâches à supprimer.
+    pub remove_where: String,
+}
+
+/// Confirmation groupée des tâches provisoires satisfaisant une expression de filtrage,
+/// ex. `{"confirm_where": "platform == \"Rafale 1\""}`.
+#[derive(Deserialize, JsonSchema)]
+pub struct ConfirmWhereMessage {
+    /// Expression de filtrage des tâches à confirmer.
+    pub confirm_where: String,
+}
+
+/// Durée par défaut de la mise en évidence d’une tâche, en ms.
+pub const DEFAULT_HIGHLIGHT_MS: u64 = 3000;
+
+/// Durée maximale de la mise en évidence d’une tâche, en ms.
+pub const MAX_HIGHLIGHT_MS: u64 = 60_000;
+
+fn default_highlight_ms() -> u64 {
+    DEFAULT_HIGHLIGHT_MS
+}
+
+/// Commande de pilotage de l’affichage par un contrôleur externe, ex.
+/// `{"cmd": "highlight", "id": "T1", "duration_ms": 3000, "zoom": true}`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum CommandMessage {
+    /// Fait clignoter la tâche de clé `id` (identifiant ou nom) pendant
+    /// `duration_ms` ms (au plus [`MAX_HIGHLIGHT_MS`]) et, si `zoom`, amène la vue sur elle.
+    Highlight {
+        /// Clé de la tâche.
+        id: String,
+        /// Durée du clignotement en ms.
+        #[serde(default = "default_highlight_ms")]
+        duration_ms: u64,
+        /// Amène la vue sur la tâche.
+        #[serde(default)]
+        zoom: bool,
+    },
+}
+
+/// Messages acceptés sur stdin : une tâche ou un message de configuration.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum IncomingMessage {
+    Task(Box<IncomingTask>),
+    Plan(PlanMessage),
+    PlanHash(PlanHashMessage),
+    Hello(HelloMessage),
+    Compressed(CompressedMessage),
+    RxGuard(RxGuardMessage),
+    Exclusion(ExclusionMessage),
+    Phase(PhaseMessage),
+    RemoveWhere(RemoveWhereMessage),
+    ConfirmWhere(ConfirmWhereMessage),
+    Split(SplitMessage),
+    Join(JoinMessage),
+    Detection(DetectionMessage),
+    Command(CommandMessage),
+}
+
+/// Événement émis par l’interface après le traitement d’un message.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProtocolEvent {
+    /// Tâche ajoutée, désignée par sa clé (identifiant ou nom).
+    TaskAdded { key: String },
+    /// Tâche déjà affichée mise à jour.
+    TaskUpdated { key: String },
+    /// Plan complet appliqué : nombre de tâches ajoutées, mises à jour et supprimées,
+    /// et écart entre l’empreinte annoncée et celle du plan appliqué.
+    PlanApplied { added: usize, updated: usize, removed: usize, hash_mismatch: bool },
+    /// Empreinte de l’émetteur identique à celle du plan affiché.
+    PlanVerified,
+    /// Empreintes divergentes : l’émetteur est invité à rediffuser le plan complet.
+    /// Cet événement est aussi écrit sur stdout par l’application graphique.
+    ResyncRequested { local_hash: String, remote_hash: String },
+    /// Réponse à la poignée de main : algorithme de compression retenu.
+    /// Cet événement est aussi écrit sur stdout par l’application graphique.
+    Welcome { compression: Option<String> },
+    /// Émetteur distant authentifié par son secret partagé.
+    Authenticated,
+    /// Message d’un émetteur distant non authentifié refusé.
+    AuthError { message: String },
+    /// Garde Rx fixée (après bornage).
+    RxGuardSet { duration_ms: f64 },
+    /// Masque d’exclusion ajouté ou remplacé.
+    ExclusionSet { name: String },
+    /// Phase de mission ajoutée ou remplacée.
+    PhaseSet { name: String },
+    /// Tâches supprimées par `remove_where`.
+    TasksRemoved { count: usize },
+    /// Tâches provisoires confirmées par `confirm_where`.
+    TasksConfirmed { count: usize },
+    /// Tâche scindée : la seconde partie est ajoutée sous la clé `new_key`.
+    TaskSplit { key: String, new_key: String },
+    /// Tâches fusionnées : la tâche `removed_key` est absorbée par la tâche `key`.
+    TasksJoined { key: String, removed_key: String },
+    /// Détection ESM reçue, désignée par son libellé.
+    DetectionReceived { label: String },
+    /// Tâche mise en évidence par la commande `highlight`.
+    TaskHighlighted { key: String },
+    /// Message de tâche identique au précédent, ignoré.
+    DuplicateIgnored,
+    /// Message refusé.
+    Error { message: String },
+}
+
+impl ProtocolEvent {
+    /// Indique si l’événement est une réponse destinée à l’émetteur (écrite sur stdout).
+    pub fn is_reply(&self) -> bool {
+        matches!(self, ProtocolEvent::ResyncRequested { .. } | ProtocolEvent::Welcome { .. })
+    }
+}
+
+/// Schéma JSON de l’ensemble des messages du protocole.
+pub fn schema_json() -> String {
+    serde_json::to_string_pretty(&schema_for!(IncomingMessage)).unwrap_or_default()
+}
+
+/// Analyse un message et vérifie la cohérence des tâches qu’il contient.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le message est trop long, si le JSON ne
+/// correspond à aucun message du protocole ou si la tâche est incohérente.
+pub fn parse_message(json: &str) -> Result<IncomingMessage, String> {
+    parse_bounded(json, MAX_MESSAGE_BYTES)
+}
+
+/// Analyse un message décompressé, de taille bornée par [`MAX_DECOMPRESSED_BYTES`].
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur dans les mêmes cas que [`parse_message`], ou si
+/// le message est lui-même compressé.
+pub fn parse_decompressed(json: &str) -> Result<IncomingMessage, String> {
+    match parse_bounded(json, MAX_DECOMPRESSED_BYTES)? {
+        IncomingMessage::Compressed(_) => Err("Message invalide : compression imbriquée".into()),
+        message => Ok(message),
+    }
+}
+
+/// Analyse un message d’au plus `limit` octets.
+fn parse_bounded(json: &str, limit: usize) -> Result<IncomingMessage, String> {
+    if json.len() > limit {
+        return Err(format!("Message invalide : plus de {} octets", limit));
+    }
+    let message: IncomingMessage = serde_json::from_str(json).map_err(|e| format!("Message invalide : {}", e))?;
+    match &message {
+        IncomingMessage::Task(task) => task.validate()?,
+        IncomingMessage::Plan(plan) => plan.plan.iter().try_for_each(IncomingTask::validate)?,
+        IncomingMessage::Detection(message) => message.detection.validate()?,
+        _ => {}
+    }
+    Ok(message)
+}
+
+/// Lit le message suivant (une ligne) en bornant sa taille à [`MAX_MESSAGE_BYTES`].
+///
+/// Retourne `None` à la fin du flux, et `Some(Err(_))` pour une ligne trop longue
+/// (ignorée jusqu’à la fin de ligne) ou qui n’est pas en UTF-8 : la lecture peut
+/// se poursuivre avec la ligne suivante.
+///
+/// # Erreurs
+///
+/// Retourne l’erreur d’entrée/sortie du flux.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Result<String, String>>> {
+    let mut line = Vec::new();
+    let mut overflow = false;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if available.is_empty() {
+            if line.is_empty() && !overflow {
+                return Ok(None);
+            }
+            break;
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        if overflow || line.len() + chunk.len() > MAX_MESSAGE_BYTES {
+            overflow = true;
+            line.clear();
+        } else {
+            line.extend_from_slice(chunk);
+        }
+        let consumed = newline.map_or(available.len(), |i| i + 1);
+        reader.consume(consumed);
+        if newline.is_some() {
+            break;
+        }
+    }
+    if overflow {
+        return Ok(Some(Err(format!("Message ignoré : plus de {} octets", MAX_MESSAGE_BYTES))));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(Some(String::from_utf8(line).map_err(|_| "Message ignoré : UTF-8 invalide".to_string())))
+}
@@ -0,0 +1,402 @@
+//! Module du langage de filtrage des tâches.
+//!
+//! Une expression de filtrage combine des comparaisons sur les attributs des
+//! tâches, par exemple :
+//!
+//! ```text
+//! amp == "A20_500" && duration > 200 && overlaps(rx)
+//! !(status == "confirmé") || name ~ "radar"
+//! ```
+//!
+//! - attributs : `name`, `amp` (ou `amplifier`), `group`, `platform`, `antenna`, `effect`, `status`,
+//!   `priority`, `power`, `fmin`, `fmax`, `tmin`, `tmax`, `duration` (ms), `bandwidth` (MHz), `rx_safe`,
+//!   `tentative` ;
+//! - opérateurs : `==`, `!=`, `<`, `<=`, `>`, `>=` et `~` (contient, sans casse) ;
+//! - combinaisons : `&&`, `||`, `!` et parenthèses ;
+//! - fonctions : `overlaps(rx)` (la tâche émet pendant la garde Rx).
+//!
+//! Les valeurs textuelles s’écrivent entre guillemets ou sous forme de mot simple.
+//! Une comparaison portant sur un attribut absent de la tâche est fausse.
+//!
+//! Les expressions sont utilisées par le champ de recherche du panneau latéral, la
+//! sous-commande `query --where` et le message `{"remove_where": "…"}` sur stdin.
+
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Task;
+
+/// Nombre maximal d’éléments lexicaux d’une expression.
+pub const MAX_TOKENS: usize = 4096;
+
+/// Profondeur maximale d’imbrication (parenthèses et négations) d’une expression.
+pub const MAX_NESTING: usize = 64;
+
+/// Élément lexical d’une expression.
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Op(&'static str),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+/// Découpe une expression en éléments lexicaux.
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if c == '(' { Token::LParen } else { Token::RParen });
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => value.push(c),
+                        None => return Err("Guillemet non fermé".into()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next().map(|(_, n)| n) != Some(c) {
+                    return Err(format!("Opérateur invalide à la position {} (attendu {}{})", i, c, c));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                chars.next();
+                let double = chars.peek().map(|&(_, n)| n) == Some('=');
+                if double {
+                    chars.next();
+                }
+                tokens.push(match (c, double) {
+                    ('=', true) => Token::Op("=="),
+                    ('!', true) => Token::Op("!="),
+                    ('<', true) => Token::Op("<="),
+                    ('>', true) => Token::Op(">="),
+                    ('<', false) => Token::Op("<"),
+                    ('>', false) => Token::Op(">"),
+                    ('~', false) => Token::Op("~"),
+                    ('!', false) => Token::Not,
+                    _ => return Err(format!("Opérateur invalide à la position {}", i)),
+                });
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut number = String::new();
+                while let Some(&(_, n)) = chars.peek() {
+                    if n.is_ascii_digit() || n == '.' || (n == '-' && number.is_empty()) {
+                        number.push(n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let value = number.parse().map_err(|_| format!("Nombre invalide : « {} »", number))?;
+                tokens.push(Token::Num(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&(_, n)) = chars.peek() {
+                    if n.is_alphanumeric() || n == '_' {
+                        ident.push(n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            _ => return Err(format!("Caractère inattendu « {} » à la position {}", c, i)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Attribut d’une tâche accessible dans les expressions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Field {
+    Name,
+    Amplifier,
+    Group,
+    Platform,
+    Antenna,
+    Effect,
+    Status,
+    Priority,
+    Power,
+    FreqStart,
+    FreqEnd,
+    TimeStart,
+    TimeEnd,
+    Duration,
+    Bandwidth,
+    RxSafe,
+    Tentative,
+}
+
+impl Field {
+    /// Attribut désigné par un identifiant.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "name" => Field::Name,
+            "amp" | "amplifier" => Field::Amplifier,
+            "group" => Field::Group,
+            "platform" => Field::Platform,
+            "antenna" => Field::Antenna,
+            "effect" => Field::Effect,
+            "status" => Field::Status,
+            "priority" => Field::Priority,
+            "power" => Field::Power,
+            "fmin" | "freq_start" => Field::FreqStart,
+            "fmax" | "freq_end" => Field::FreqEnd,
+            "tmin" | "time_start" => Field::TimeStart,
+            "tmax" | "time_end" => Field::TimeEnd,
+            "duration" => Field::Duration,
+            "bandwidth" => Field::Bandwidth,
+            "rx_safe" => Field::RxSafe,
+            "tentative" => Field::Tentative,
+            _ => return None,
+        })
+    }
+
+    /// Valeur de l’attribut pour une tâche (`None` si l’attribut n’est pas renseigné).
+    fn value(&self, task: &Task) -> Option<Value> {
+        match self {
+            Field::Name => Some(Value::Str(task.name.clone())),
+            Field::Amplifier => Some(Value::Str(format!("{:?}", task.amplifier))),
+            Field::Group => task.group.clone().map(Value::Str),
+            Field::Platform => task.platform.clone().map(Value::Str),
+            Field::Antenna => task.antenna.clone().map(Value::Str),
+            Field::Effect => task.effect.map(|e| Value::Str(format!("{:?}", e))),
+            Field::Status => task.status.clone().map(Value::Str),
+            Field::Priority => task.priority.map(|p| Value::Num(p as f64)),
+            Field::Power => task.power.map(Value::Num),
+            Field::FreqStart => Some(Value::Num(task.freq_start)),
+            Field::FreqEnd => Some(Value::Num(task.freq_end)),
+            Field::TimeStart => Some(Value::Num(task.time_start)),
+            Field::TimeEnd => Some(Value::Num(task.time_end)),
+            Field::Duration => Some(Value::Num(task.time_end - task.time_start)),
+            Field::Bandwidth => Some(Value::Num(task.freq_end - task.freq_start)),
+            Field::RxSafe => Some(Value::Bool(task.rx_safe)),
+            Field::Tentative => Some(Value::Bool(task.tentative)),
+        }
+    }
+}
+
+/// Valeur littérale ou valeur d’attribut.
+#[derive(Clone, PartialEq, Debug)]
+enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+/// Expression de filtrage analysée.
+#[derive(Clone, PartialEq, Debug)]
+enum Expr {
+    Compare(Field, &'static str, Value),
+    /// Attribut booléen utilisé seul (ex. `rx_safe`).
+    Flag(Field),
+    OverlapsRx,
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// Analyseur syntaxique par descente récursive.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Profondeur d’imbrication courante.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            other => Err(format!("{:?} attendu, {:?} trouvé", expected, other)),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    /// Entre dans un niveau d’imbrication, en refusant les expressions trop profondes.
+    fn nest(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(format!("Expression trop imbriquée (plus de {} niveaux)", MAX_NESTING));
+        }
+        Ok(())
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            self.nest()?;
+            let expr = Expr::Not(Box::new(self.unary()?));
+            self.depth -= 1;
+            return Ok(expr);
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                self.nest()?;
+                let expr = self.or()?;
+                self.expect(Token::RParen)?;
+                self.depth -= 1;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                self.next();
+                let argument = match self.next() {
+                    Some(Token::Ident(argument)) => argument,
+                    other => return Err(format!("Argument attendu pour {}(), {:?} trouvé", name, other)),
+                };
+                self.expect(Token::RParen)?;
+                match (name.as_str(), argument.as_str()) {
+                    ("overlaps", "rx") => Ok(Expr::OverlapsRx),
+                    _ => Err(format!("Fonction inconnue : {}({})", name, argument)),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                let field = Field::from_name(&name).ok_or(format!("Attribut inconnu : « {} »", name))?;
+                let Some(Token::Op(op)) = self.peek().cloned() else {
+                    return Ok(Expr::Flag(field));
+                };
+                self.next();
+                let value = match self.next() {
+                    Some(Token::Num(n)) => Value::Num(n),
+                    Some(Token::Str(s)) => Value::Str(s),
+                    Some(Token::Ident(s)) if s == "true" || s == "false" => Value::Bool(s == "true"),
+                    Some(Token::Ident(s)) => Value::Str(s),
+                    other => return Err(format!("Valeur attendue après {}, {:?} trouvé", op, other)),
+                };
+                Ok(Expr::Compare(field, op, value))
+            }
+            other => Err(format!("Expression attendue, {:?} trouvé", other)),
+        }
+    }
+}
+
+/// Contexte d’évaluation des expressions.
+pub struct QueryContext {
+    /// Fenêtre de garde Rx utilisée par `overlaps(rx)`.
+    pub rx_guard: RxGuard,
+}
+
+/// Filtre de tâches compilé à partir d’une expression.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    /// Analyse une expression de filtrage.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si l’expression est mal formée ou désigne
+    /// un attribut ou une fonction inconnus.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text)?;
+        if tokens.len() > MAX_TOKENS {
+            return Err(format!("Expression trop longue (plus de {} éléments)", MAX_TOKENS));
+        }
+        let mut parser = Parser { tokens, pos: 0, depth: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Élément inattendu : {:?}", token));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Indique si la tâche satisfait l’expression.
+    pub fn matches(&self, task: &Task, ctx: &QueryContext) -> bool {
+        eval(&self.expr, task, ctx)
+    }
+}
+
+/// Évalue une expression sur une tâche.
+fn eval(expr: &Expr, task: &Task, ctx: &QueryContext) -> bool {
+    match expr {
+        Expr::Compare(field, op, expected) => match field.value(task) {
+            Some(actual) => compare(&actual, op, expected),
+            None => false,
+        },
+        Expr::Flag(field) => field.value(task) == Some(Value::Bool(true)),
+        Expr::OverlapsRx => ctx.rx_guard.overlaps(task),
+        Expr::Not(inner) => !eval(inner, task, ctx),
+        Expr::And(a, b) => eval(a, task, ctx) && eval(b, task, ctx),
+        Expr::Or(a, b) => eval(a, task, ctx) || eval(b, task, ctx),
+    }
+}
+
+/// Compare une valeur d’attribut à une valeur littérale.
+fn compare(actual: &Value, op: &str, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Num(a), Value::Num(b)) => match op {
+            "==" => a == b,
+            "!=" => a != b,
+            "<" => a < b,
+            "<=" => a <= b,
+            ">" => a > b,
+            ">=" => a >= b,
+            _ => false,
+        },
+        (Value::Str(a), Value::Str(b)) => match op {
+            "==" => a.eq_ignore_ascii_case(b),
+            "!=" => !a.eq_ignore_ascii_case(b),
+            "~" => a.to_lowercase().contains(&b.to_lowercase()),
+            _ => false,
+        },
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            "==" => a == b,
+            "!=" => a != b,
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,185 @@
+//! Module d’estimation de la portée efficace des tâches.
+//!
+//! À partir de la puissance d’émission d’une tâche (`power`), du gain de son
+//! antenne ([`crate::tools::antenna`]) et d’un modèle de propagation simple lu
+//! depuis [`PROPAGATION_PATH`], la portée efficace est la distance à laquelle la
+//! puissance reçue retombe au seuil requis sur la cible. Le modèle est un
+//! affaiblissement en espace libre généralisé (exposant de distance réglable),
+//! évalué à la fréquence la plus haute de la tâche (cas le plus défavorable) :
+//!
+//! `L(dB) = 32,44 + 20·log10(f MHz) + 10·n·log10(d km)`
+//!
+//! Lorsque la tâche est localisée (`location`) et que la position de sa
+//! plateforme est connue, la distance à la cible est comparée à la portée : les
+//! tâches trop faibles pour l’atteindre sont signalées.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::tools::antenna::Antenna;
+use crate::tools::task::{Location, Task};
+
+/// Chemin du fichier de configuration du modèle de propagation.
+pub const PROPAGATION_PATH: &str = "propagation.json";
+
+/// Rayon moyen de la Terre en km.
+const EARTH_RADIUS_KM: f64 = 6371.;
+
+/// Constante de l’affaiblissement en espace libre pour des MHz et des km.
+const FSPL_CONSTANT_DB: f64 = 32.44;
+
+/// Modèle de propagation et positions des plateformes.
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct PropagationModel {
+    /// Exposant de distance (2 : espace libre).
+    #[serde(default = "default_exponent")]
+    pub exponent: f64,
+    /// Puissance requise sur la cible, en dBm.
+    #[serde(default = "default_required_dbm")]
+    pub required_dbm: f64,
+    /// Pertes supplémentaires (câbles, polarisation, atmosphère), en dB.
+    #[serde(default)]
+    pub losses_db: f64,
+    /// Position par défaut des plateformes non listées.
+    #[serde(default)]
+    pub origin: Option<Location>,
+    /// Position de chaque plateforme, par nom.
+    #[serde(default)]
+    pub platforms: BTreeMap<String, Location>,
+}
+
+fn default_exponent() -> f64 {
+    2.
+}
+
+fn default_required_dbm() -> f64 {
+    -70.
+}
+
+impl Default for PropagationModel {
+    fn default() -> Self {
+        Self {
+            exponent: default_exponent(),
+            required_dbm: default_required_dbm(),
+            losses_db: 0.,
+            origin: None,
+            platforms: BTreeMap::new(),
+        }
+    }
+}
+
+/// Portée estimée d’une tâche.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RangeEstimate {
+    /// Portée efficace en km.
+    pub range_km: f64,
+    /// Distance de la plateforme à la cible en km, si les deux positions sont connues.
+    pub distance_km: Option<f64>,
+}
+
+impl RangeEstimate {
+    /// Indique si la puissance est insuffisante pour atteindre la cible.
+    pub fn is_short(&self) -> bool {
+        self.distance_km.is_some_and(|d| d > self.range_km)
+    }
+
+    /// Libellé de la portée, avec la distance à la cible si elle est connue.
+    pub fn label(&self) -> String {
+        match self.distance_km {
+            Some(distance) => format!("Portée : {:.1} km (cible à {:.1} km)", self.range_km, distance),
+            None => format!("Portée : {:.1} km", self.range_km),
+        }
+    }
+}
+
+/// Tâche trop faible pour atteindre sa cible.
+#[derive(Clone, Debug)]
+pub struct RangeShortfall {
+    /// Indice de la tâche.
+    pub task_index: usize,
+    /// Estimation de la portée.
+    pub estimate: RangeEstimate,
+}
+
+/// Distance orthodromique entre deux positions, en km (formule de haversine).
+pub fn distance_km(a: Location, b: Location) -> f64 {
+    let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.lon - a.lon).to_radians();
+    let h = (d_lat / 2.).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.).sin().powi(2);
+    2. * EARTH_RADIUS_KM * h.sqrt().min(1.).asin()
+}
+
+impl PropagationModel {
+    /// Position de la plateforme portant la tâche.
+    pub fn platform_position(&self, task: &Task) -> Option<Location> {
+        task.platform.as_ref().and_then(|p| self.platforms.get(p)).copied().or(self.origin)
+    }
+
+    /// Portée efficace de la tâche, en km, si sa puissance est renseignée.
+    pub fn range_km(&self, task: &Task, antennas: &[Antenna]) -> Option<f64> {
+        let power = task.power.filter(|p| *p > 0.)?;
+        let freq = task.freq_end.max(task.freq_start);
+        if freq <= 0. || self.exponent <= 0. {
+            return None;
+        }
+        let gain = task
+            .antenna
+            .as_ref()
+            .and_then(|name| antennas.iter().find(|a| a.name == *name))
+            .map_or(0., |a| a.gain_dbi);
+        let power_dbm = 10. * (power * 1000.).log10();
+        let budget = power_dbm + gain - self.losses_db - self.required_dbm - FSPL_CONSTANT_DB - 20. * freq.log10();
+        Some(10f64.powf(budget / (10. * self.exponent)))
+    }
+
+    /// Estimation de la portée de la tâche et distance à sa cible.
+    pub fn estimate(&self, task: &Task, antennas: &[Antenna]) -> Option<RangeEstimate> {
+        let range_km = self.range_km(task, antennas)?;
+        let distance_km = task.location.zip(self.platform_position(task)).map(|(target, origin)| distance_km(origin, target));
+        Some(RangeEstimate { range_km, distance_km })
+    }
+
+    /// Tâches dont la puissance est insuffisante pour atteindre leur cible.
+    pub fn shortfalls(&self, tasks: &[Task], antennas: &[Antenna]) -> Vec<RangeShortfall> {
+        tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(task_index, task)| {
+                self.estimate(task, antennas).filter(RangeEstimate::is_short).map(|estimate| RangeShortfall { task_index, estimate })
+            })
+            .collect()
+    }
+}
+
+/// Charge le modèle de propagation depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_propagation(path: &Path) -> Result<PropagationModel, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    let model: PropagationModel = serde_json::from_str(&content)
+        .map_err(|e| format!("Modèle de propagation {} invalide : {}", path.display(), e))?;
+    if !model.exponent.is_finite() || model.exponent <= 0. {
+        return Err(format!("Modèle de propagation {} : l’exposant doit être strictement positif", path.display()));
+    }
+    Ok(model)
+}
+
+/// Charge le modèle de propagation, ou le modèle par défaut (espace libre) si le
+/// fichier est absent ou invalide.
+pub fn load_propagation_config() -> PropagationModel {
+    let path = Path::new(PROPAGATION_PATH);
+    if !path.exists() {
+        return PropagationModel::default();
+    }
+    load_propagation(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        PropagationModel::default()
+    })
+}
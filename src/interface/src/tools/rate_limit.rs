@@ -0,0 +1,277 @@
+//! Module de limitation du débit des émetteurs.
+//!
+//! Un émetteur défaillant ou emballé peut saturer l’interface de messages. Les
+//! limites de chaque source (débit en messages/s, nombre maximal de tâches) sont
+//! lues depuis un fichier de configuration JSON ([`RATE_LIMITS_PATH`]), par
+//! exemple :
+//!
+//! ```json
+//! {"default": {"max_messages_per_sec": 200, "max_tasks": 100000, "overflow": "coalesce"},
+//!  "sources": {"stdin": {"max_messages_per_sec": 50, "overflow": "reject"}}}
+//! ```
+//!
+//! Au-delà du débit autorisé, les messages en excès sont soit mis en attente et
+//! fusionnés par tâche (un message de tâche remplace le message de tâche en
+//! attente portant sur la même tâche, identifiée par son `id` ou à défaut son
+//! `name` ; les autres messages, commandes comprises, sont conservés dans
+//! l’ordre de réception et appliqués dès que le débit le permet), soit rejetés avec un événement d’erreur. La file d’attente est
+//! bornée ([`MAX_HELD`]) : un message qui ne peut pas y entrer est rejeté avec un
+//! événement d’erreur. Un bandeau d’avertissement signale les sources en
+//! dépassement.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::protocol::{parse_message, IncomingMessage, ProtocolEvent};
+
+/// Chemin du fichier de configuration des limites de débit.
+pub const RATE_LIMITS_PATH: &str = "rate_limits.json";
+
+/// Nom de la source des messages reçus sur stdin.
+pub const STDIN_SOURCE: &str = "stdin";
+
+/// Durée d’affichage du bandeau d’avertissement après un dépassement.
+pub const WARNING_DURATION: Duration = Duration::from_secs(5);
+
+/// Fenêtre de comptage des messages.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Nombre maximal de messages en attente par source.
+pub const MAX_HELD: usize = 10_000;
+
+/// Traitement des messages au-delà du débit autorisé.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Les messages en excès sont mis en attente, fusionnés par tâche.
+    #[default]
+    Coalesce,
+    /// Les messages en excès sont rejetés.
+    Reject,
+}
+
+/// Limites d’une source de messages.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimit {
+    /// Nombre maximal de messages appliqués par seconde.
+    pub max_messages_per_sec: u32,
+    /// Nombre maximal de tâches affichées.
+    pub max_tasks: usize,
+    /// Traitement des messages en excès.
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self { max_messages_per_sec: 200, max_tasks: 100_000, overflow: OverflowPolicy::Coalesce }
+    }
+}
+
+/// Limites par défaut et limites propres à certaines sources.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Limites des sources non configurées.
+    pub default: RateLimit,
+    /// Limites par nom de source.
+    pub sources: HashMap<String, RateLimit>,
+}
+
+impl RateLimitConfig {
+    /// Limites applicables à une source.
+    pub fn limit(&self, source: &str) -> RateLimit {
+        self.sources.get(source).copied().unwrap_or(self.default)
+    }
+}
+
+/// Charge les limites de débit depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou invalide.
+pub fn load_rate_limits(path: &Path) -> Result<RateLimitConfig, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Limites de débit {} invalides : {}", path.display(), e))
+}
+
+/// Charge la configuration des limites, ou les limites par défaut si le fichier est absent ou invalide.
+pub fn load_rate_limit_config() -> RateLimitConfig {
+    let path = Path::new(RATE_LIMITS_PATH);
+    if !path.exists() {
+        return RateLimitConfig::default();
+    }
+    load_rate_limits(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        RateLimitConfig::default()
+    })
+}
+
+/// Sort d’un message soumis à la limitation.
+pub enum Admission {
+    /// Message à appliquer immédiatement.
+    Apply(String),
+    /// Message mis en attente (éventuellement fusionné) avant application.
+    Held,
+    /// Message rejeté.
+    Rejected(ProtocolEvent),
+}
+
+/// État de comptage d’une source.
+#[derive(Default)]
+struct SourceState {
+    /// Début de la fenêtre de comptage en cours.
+    window_start: Option<Instant>,
+    /// Messages appliqués dans la fenêtre en cours.
+    count: u32,
+    /// Messages en excès en attente, dans l’ordre de réception, avec la clé de
+    /// la tâche qu’ils concernent.
+    held: VecDeque<(Option<String>, String)>,
+    /// Nombre de messages rejetés.
+    rejected: usize,
+    /// Nombre de messages remplacés par un message plus récent sur la même tâche.
+    coalesced: usize,
+    /// Instant du dernier dépassement.
+    last_overflow: Option<Instant>,
+}
+
+impl SourceState {
+    /// Ouvre une nouvelle fenêtre de comptage si la précédente est écoulée.
+    fn roll(&mut self, now: Instant) {
+        if self.window_start.is_none_or(|start| now.duration_since(start) >= WINDOW) {
+            self.window_start = Some(now);
+            self.count = 0;
+        }
+    }
+}
+
+/// Clé de la tâche concernée par un message (`id`, ou à défaut `name`), s’il
+/// s’agit d’un message de tâche : les commandes et messages de configuration
+/// ne sont jamais fusionnés, même s’ils désignent une tâche.
+fn task_key(message: &str) -> Option<String> {
+    match parse_message(message).ok()? {
+        IncomingMessage::Task(task) => Some(task.id.unwrap_or(task.name)),
+        _ => None,
+    }
+}
+
+/// Limiteur de débit des sources de messages.
+#[derive(Default)]
+pub struct RateLimiter {
+    /// Limites configurées.
+    pub config: RateLimitConfig,
+    /// État de comptage par source.
+    sources: HashMap<String, SourceState>,
+}
+
+impl RateLimiter {
+    /// Crée un limiteur avec les limites données.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, sources: HashMap::new() }
+    }
+
+    /// Soumet un message de `source` reçu à l’instant `now`.
+    pub fn admit(&mut self, source: &str, message: String, now: Instant) -> Admission {
+        let limit = self.config.limit(source);
+        let state = self.sources.entry(source.to_string()).or_default();
+        state.roll(now);
+        // Les messages en attente sont plus anciens : le nouveau ne doit pas les doubler
+        if state.held.is_empty() && state.count < limit.max_messages_per_sec {
+            state.count += 1;
+            return Admission::Apply(message);
+        }
+        state.last_overflow = Some(now);
+        match limit.overflow {
+            OverflowPolicy::Coalesce => {
+                let key = task_key(&message);
+                if let Some(held) = key.as_ref().and_then(|k| state.held.iter_mut().find(|(held, _)| held.as_ref() == Some(k))) {
+                    held.1 = message;
+                    state.coalesced += 1;
+                    return Admission::Held;
+                }
+                if state.held.len() >= MAX_HELD {
+                    state.rejected += 1;
+                    return Admission::Rejected(ProtocolEvent::Error {
+                        message: format!("File d’attente de « {} » pleine ({} messages) : message rejeté", source, MAX_HELD),
+                    });
+                }
+                state.held.push_back((key, message));
+                Admission::Held
+            }
+            OverflowPolicy::Reject => {
+                state.rejected += 1;
+                Admission::Rejected(ProtocolEvent::Error {
+                    message: format!(
+                        "Débit de « {} » supérieur à {} messages/s : message rejeté",
+                        source, limit.max_messages_per_sec
+                    ),
+                })
+            }
+        }
+    }
+
+    /// Libère, dans l’ordre de réception, les messages en attente que le débit de
+    /// leur source permet à nouveau (tous si `force`, ex. à la fin du flux), avec
+    /// leur source.
+    pub fn release(&mut self, now: Instant, force: bool) -> Vec<(String, String)> {
+        let mut released = Vec::new();
+        for (source, state) in &mut self.sources {
+            state.roll(now);
+            let max = self.config.limit(source).max_messages_per_sec;
+            while force || state.count < max {
+                let Some((_, message)) = state.held.pop_front() else {
+                    break;
+                };
+                state.count += 1;
+                released.push((source.clone(), message));
+            }
+        }
+        released
+    }
+
+    /// Indique si des messages sont en attente.
+    pub fn has_held(&self) -> bool {
+        self.sources.values().any(|s| !s.held.is_empty())
+    }
+
+    /// Vérifie que `count` tâches respectent la limite de la source.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur (et signale un dépassement) si la limite est dépassée.
+    pub fn check_tasks(&mut self, source: &str, count: usize, now: Instant) -> Result<(), String> {
+        let max_tasks = self.config.limit(source).max_tasks;
+        if count <= max_tasks {
+            return Ok(());
+        }
+        let state = self.sources.entry(source.to_string()).or_default();
+        state.rejected += 1;
+        state.last_overflow = Some(now);
+        Err(format!("« {} » : {} tâches au-delà de la limite de {}", source, count, max_tasks))
+    }
+
+    /// Avertissements des sources en dépassement depuis moins de [`WARNING_DURATION`].
+    pub fn warnings(&self, now: Instant) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .sources
+            .iter()
+            .filter(|(_, s)| s.last_overflow.is_some_and(|t| now.duration_since(t) < WARNING_DURATION))
+            .map(|(source, s)| {
+                format!(
+                    "Émetteur « {} » : débit excessif (limite {} messages/s) — {} messages fusionnés, {} rejetés",
+                    source,
+                    self.config.limit(source).max_messages_per_sec,
+                    s.coalesced,
+                    s.rejected
+                )
+            })
+            .collect();
+        warnings.sort();
+        warnings
+    }
+}
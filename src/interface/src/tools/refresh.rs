@@ -0,0 +1,68 @@
+//! Module des réglages de cadence de rafraîchissement.
+//!
+//! L’affichage tourne souvent des heures sur un écran secondaire : l’interface
+//! ne se redessine que sur interaction, à la réception de messages et au rythme
+//! de l’horloge temps réel. Ces rafraîchissements autonomes sont bornés par une
+//! cadence maximale, les messages reçus sont regroupés selon un intervalle de
+//! prise en compte, et un mode économie d’énergie abaisse la cadence à
+//! [`POWER_SAVING_FPS`] lorsque la fenêtre n’a pas le focus.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Cadence des rafraîchissements autonomes en mode économie d’énergie, en images/s.
+pub const POWER_SAVING_FPS: f32 = 2.;
+
+/// Réglages de cadence de rafraîchissement.
+pub struct RefreshSettings {
+    /// Cadence maximale des rafraîchissements autonomes (horloge, réception), en images/s.
+    pub max_fps: f32,
+    /// Intervalle de prise en compte des messages reçus, en ms.
+    pub ingest_interval_ms: u64,
+    /// Indique si la cadence est abaissée lorsque la fenêtre n’a pas le focus.
+    pub power_saving: bool,
+    /// Délai effectif de réveil de l’interface après un message, en ms, partagé
+    /// avec le thread de lecture de stdin.
+    pub wake_interval_ms: Arc<AtomicU64>,
+}
+
+impl Default for RefreshSettings {
+    fn default() -> Self {
+        Self {
+            max_fps: 30.,
+            ingest_interval_ms: 50,
+            power_saving: true,
+            wake_interval_ms: Arc::new(AtomicU64::new(50)),
+        }
+    }
+}
+
+impl RefreshSettings {
+    /// Intervalle minimal entre deux rafraîchissements autonomes, selon le focus de la fenêtre.
+    pub fn frame_interval(&self, focused: bool) -> Duration {
+        let fps = if self.power_saving && !focused { POWER_SAVING_FPS } else { self.max_fps };
+        Duration::from_secs_f32(1. / fps.max(0.1))
+    }
+
+    /// Met à jour le délai de réveil partagé avec le thread de lecture de stdin :
+    /// l’intervalle de prise en compte, sans dépasser la cadence autorisée.
+    pub fn publish(&self, focused: bool) {
+        let wake = Duration::from_millis(self.ingest_interval_ms).max(self.frame_interval(focused));
+        self.wake_interval_ms.store(wake.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Affiche les réglages dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Cadence max :");
+            ui.add(egui::DragValue::new(&mut self.max_fps).range(1.0..=120.0).suffix(" i/s"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Prise en compte :");
+            ui.add(egui::DragValue::new(&mut self.ingest_interval_ms).range(0..=2000).suffix(" ms"))
+                .on_hover_text("Les messages reçus pendant cet intervalle sont affichés ensemble");
+        });
+        ui.checkbox(&mut self.power_saving, format!("Économie d'énergie hors focus ({} i/s)", POWER_SAVING_FPS));
+    }
+}
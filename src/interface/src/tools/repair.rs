@@ -0,0 +1,109 @@
+//! Module de suggestion de corrections automatiques du plan.
+//!
+//! À partir des conflits et des violations de cycle de service détectés par
+//! [`crate::tools::constraints`], une recherche gloutonne propose une liste minimale
+//! de décalages temporels et de réaffectations d’amplificateur, que l’utilisateur
+//! peut prévisualiser puis appliquer en une seule opération annulable.
+
+use crate::tools::constraints::{check_duty_cycles, find_conflicts};
+use crate::tools::task::{Amplifier, Task};
+
+/// Correction élémentaire proposée pour une tâche.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Fix {
+    /// Décale la tâche dans le temps de `delta` ms.
+    Shift { task_index: usize, delta: f64 },
+    /// Affecte la tâche à un autre amplificateur.
+    Reassign { task_index: usize, amplifier: Amplifier },
+}
+
+impl Fix {
+    /// Applique la correction à la liste de tâches.
+    pub fn apply(&self, tasks: &mut [Task]) {
+        match *self {
+            Fix::Shift { task_index, delta } => {
+                tasks[task_index].shift_time(delta);
+            }
+            Fix::Reassign { task_index, amplifier } => {
+                tasks[task_index].amplifier = amplifier;
+            }
+        }
+    }
+
+    /// Retourne une description lisible de la correction.
+    pub fn describe(&self, tasks: &[Task]) -> String {
+        match *self {
+            Fix::Shift { task_index, delta } => {
+                format!("{} : décaler de {:+.0} ms", tasks[task_index].name, delta)
+            }
+            Fix::Reassign { task_index, amplifier } => {
+                format!("{} : réaffecter à {:?}", tasks[task_index].name, amplifier)
+            }
+        }
+    }
+}
+
+/// Nombre maximal d’itérations de la recherche par tâche.
+const MAX_ITERATIONS_PER_TASK: usize = 4;
+
+/// Propose une liste de corrections résolvant les conflits et violations du plan.
+///
+/// La recherche est gloutonne :
+/// 1. chaque tâche hors de la bande de son amplificateur est réaffectée au premier
+///    amplificateur couvrant sa plage de fréquence ;
+/// 2. tant qu’un conflit subsiste, la tâche démarrant en second est décalée juste
+///    après la fin de la première ;
+/// 3. tant qu’une violation de cycle de service subsiste, la tâche fautive est
+///    décalée du décalage minimal suggéré.
+///
+/// Les décalages successifs d’une même tâche sont fusionnés en une seule correction.
+pub fn suggest_fixes(tasks: &[Task]) -> Vec<Fix> {
+    let mut work = tasks.to_vec();
+    let mut fixes: Vec<Fix> = Vec::new();
+
+    for i in 0..work.len() {
+        let task = &work[i];
+        if task.amplifier.covers(task.freq_start, task.freq_end) {
+            continue;
+        }
+        if let Some(amp) = Amplifier::ALL.iter().find(|a| a.covers(task.freq_start, task.freq_end)) {
+            let fix = Fix::Reassign { task_index: i, amplifier: *amp };
+            fix.apply(&mut work);
+            fixes.push(fix);
+        }
+    }
+
+    for _ in 0..work.len() * MAX_ITERATIONS_PER_TASK {
+        let shift = if let Some(c) = find_conflicts(&work).first() {
+            Some((c.second, work[c.first].time_end - work[c.second].time_start))
+        } else {
+            check_duty_cycles(&work)
+                .iter()
+                .find_map(|v| v.suggested_shift.map(|delta| (v.task_index, delta)))
+        };
+
+        let Some((task_index, delta)) = shift else { break };
+        let fix = Fix::Shift { task_index, delta };
+        fix.apply(&mut work);
+
+        let merged = fixes.iter_mut().find_map(|f| match f {
+            Fix::Shift { task_index: i, delta: d } if *i == task_index => Some(d),
+            _ => None,
+        });
+        match merged {
+            Some(d) => *d += delta,
+            None => fixes.push(fix),
+        }
+    }
+
+    fixes
+}
+
+/// Retourne une copie des tâches sur laquelle les corrections ont été appliquées.
+pub fn apply_fixes(tasks: &[Task], fixes: &[Fix]) -> Vec<Task> {
+    let mut repaired = tasks.to_vec();
+    for fix in fixes {
+        fix.apply(&mut repaired);
+    }
+    repaired
+}
@@ -0,0 +1,273 @@
+//! Module de génération de rapports de comparaison entre deux plans.
+//!
+//! Le rapport compare la couverture temporelle et fréquentielle, les conflits,
+//! les violations de cycle de service et la charge de chaque amplificateur.
+//! Il est produit au format JSON (pour les chaînes de revue automatisées)
+//! et au format Markdown (pour la lecture humaine).
+
+use serde::Serialize;
+
+use crate::tools::constraints::{check_duty_cycles, find_conflicts};
+use crate::tools::system::System;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::MAX_TIME;
+
+/// Charge d’un amplificateur sur la durée de la mission.
+#[derive(Clone, Serialize)]
+pub struct AmplifierLoad {
+    /// Amplificateur concerné.
+    pub amplifier: Amplifier,
+    /// Nombre de tâches affectées.
+    pub task_count: usize,
+    /// Temps pendant lequel au moins une tâche est active, en ms.
+    pub busy_ms: f64,
+    /// Part du temps de mission occupée (entre 0 et 1).
+    pub utilization: f64,
+    /// Nombre maximal de tâches simultanées.
+    pub peak_concurrency: usize,
+}
+
+/// Charge cumulée d’un système sur la durée de la mission.
+#[derive(Clone, Serialize)]
+pub struct SystemLoad {
+    /// Nom du système.
+    pub system: String,
+    /// Nombre de tâches affectées à l’un des amplificateurs du système.
+    pub task_count: usize,
+    /// Temps pendant lequel au moins un amplificateur du système est actif, en ms.
+    pub busy_ms: f64,
+    /// Part du temps de mission occupée (entre 0 et 1).
+    pub utilization: f64,
+    /// Nombre maximal de tâches simultanées sur le système.
+    pub peak_concurrency: usize,
+}
+
+/// Statistiques d’un plan.
+#[derive(Clone, Serialize)]
+pub struct PlanStats {
+    /// Nombre de tâches du plan.
+    pub task_count: usize,
+    /// Temps pendant lequel au moins une tâche est active, en ms.
+    pub time_coverage_ms: f64,
+    /// Largeur de spectre couverte par au moins une tâche, en MHz.
+    pub freq_coverage_mhz: f64,
+    /// Nombre de paires de tâches en conflit.
+    pub conflicts: usize,
+    /// Nombre de violations de cycle de service.
+    pub duty_cycle_violations: usize,
+    /// Charge par amplificateur.
+    pub amplifiers: Vec<AmplifierLoad>,
+}
+
+/// Rapport de comparaison entre deux plans.
+#[derive(Serialize)]
+pub struct ComparisonReport {
+    /// Statistiques du plan de référence.
+    pub plan_a: PlanStats,
+    /// Statistiques du plan comparé.
+    pub plan_b: PlanStats,
+    /// Noms des tâches présentes uniquement dans le plan comparé.
+    pub added: Vec<String>,
+    /// Noms des tâches présentes uniquement dans le plan de référence.
+    pub removed: Vec<String>,
+    /// Noms des tâches présentes dans les deux plans mais modifiées.
+    pub modified: Vec<String>,
+}
+
+/// Calcule la longueur de l’union d’une liste d’intervalles.
+pub fn union_length(mut intervals: Vec<(f64, f64)>) -> f64 {
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut total = 0.;
+    let mut current: Option<(f64, f64)> = None;
+    for (start, end) in intervals {
+        current = match current {
+            Some((s, e)) if start <= e => Some((s, e.max(end))),
+            Some((s, e)) => {
+                total += e - s;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((s, e)) = current {
+        total += e - s;
+    }
+    total
+}
+
+/// Calcule le nombre maximal d’intervalles simultanément actifs.
+///
+/// Des intervalles simplement adjacents ne sont pas considérés simultanés.
+pub fn peak_concurrency(intervals: &[(f64, f64)]) -> usize {
+    // Les fins (-1) sont traitées avant les débuts (+1) à instant égal
+    let mut events: Vec<(f64, i32)> = intervals.iter().flat_map(|&(s, e)| [(s, 1), (e, -1)]).collect();
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    let mut current = 0;
+    let mut peak = 0;
+    for (_, delta) in events {
+        current += delta;
+        peak = peak.max(current);
+    }
+    peak as usize
+}
+
+/// Calcule la charge de chaque amplificateur.
+pub fn amplifier_loads(tasks: &[Task]) -> Vec<AmplifierLoad> {
+    Amplifier::ALL
+        .iter()
+        .map(|amp| {
+            let intervals: Vec<(f64, f64)> = tasks
+                .iter()
+                .filter(|t| t.amplifier == *amp)
+                .map(|t| (t.time_start, t.time_end))
+                .collect();
+            let task_count = intervals.len();
+            let peak_concurrency = peak_concurrency(&intervals);
+            let busy_ms = union_length(intervals);
+            AmplifierLoad {
+                amplifier: *amp,
+                task_count,
+                busy_ms,
+                utilization: busy_ms / MAX_TIME,
+                peak_concurrency,
+            }
+        })
+        .collect()
+}
+
+/// Calcule la charge cumulée de chaque système.
+pub fn system_loads(systems: &[System], tasks: &[Task]) -> Vec<SystemLoad> {
+    systems
+        .iter()
+        .map(|system| {
+            let intervals: Vec<(f64, f64)> = tasks
+                .iter()
+                .filter(|t| system.contains(t.amplifier))
+                .map(|t| (t.time_start, t.time_end))
+                .collect();
+            let task_count = intervals.len();
+            let peak_concurrency = peak_concurrency(&intervals);
+            let busy_ms = union_length(intervals);
+            SystemLoad {
+                system: system.name.clone(),
+                task_count,
+                busy_ms,
+                utilization: busy_ms / MAX_TIME,
+                peak_concurrency,
+            }
+        })
+        .collect()
+}
+
+/// Calcule les statistiques d’un plan.
+pub fn plan_stats(tasks: &[Task]) -> PlanStats {
+    plan_stats_with(tasks, find_conflicts(tasks).len(), check_duty_cycles(tasks).len())
+}
+
+/// Calcule les statistiques d’un plan dont les conflits et les violations de cycle
+/// de service ont déjà été dénombrés.
+pub fn plan_stats_with(tasks: &[Task], conflicts: usize, duty_cycle_violations: usize) -> PlanStats {
+    let amplifiers = amplifier_loads(tasks);
+
+    PlanStats {
+        task_count: tasks.len(),
+        time_coverage_ms: union_length(tasks.iter().map(|t| (t.time_start, t.time_end)).collect()),
+        freq_coverage_mhz: union_length(tasks.iter().map(|t| (t.freq_start, t.freq_end)).collect()),
+        conflicts,
+        duty_cycle_violations,
+        amplifiers,
+    }
+}
+
+/// Compare deux plans, les tâches étant appariées par leur nom.
+pub fn compare_plans(plan_a: &[Task], plan_b: &[Task]) -> ComparisonReport {
+    let find = |plan: &[Task], name: &str| plan.iter().position(|t| t.name == name);
+
+    let added = plan_b
+        .iter()
+        .filter(|t| find(plan_a, &t.name).is_none())
+        .map(|t| t.name.clone())
+        .collect();
+    let removed = plan_a
+        .iter()
+        .filter(|t| find(plan_b, &t.name).is_none())
+        .map(|t| t.name.clone())
+        .collect();
+    let modified = plan_a
+        .iter()
+        .filter(|a| {
+            find(plan_b, &a.name).is_some_and(|i| {
+                let b = &plan_b[i];
+                a.freq_start != b.freq_start
+                    || a.freq_end != b.freq_end
+                    || a.time_start != b.time_start
+                    || a.time_end != b.time_end
+                    || a.amplifier != b.amplifier
+                    || a.shape != b.shape
+            })
+        })
+        .map(|t| t.name.clone())
+        .collect();
+
+    ComparisonReport {
+        plan_a: plan_stats(plan_a),
+        plan_b: plan_stats(plan_b),
+        added,
+        removed,
+        modified,
+    }
+}
+
+impl ComparisonReport {
+    /// Sérialise le rapport en JSON indenté.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Met en forme le rapport en Markdown.
+    pub fn to_markdown(&self) -> String {
+        let (a, b) = (&self.plan_a, &self.plan_b);
+        let mut md = String::from("# Comparaison de plans\n\n");
+
+        md.push_str("| Indicateur | Plan A | Plan B |\n|---|---:|---:|\n");
+        md.push_str(&format!("| Tâches | {} | {} |\n", a.task_count, b.task_count));
+        md.push_str(&format!(
+            "| Couverture temporelle (ms) | {:.0} | {:.0} |\n",
+            a.time_coverage_ms, b.time_coverage_ms
+        ));
+        md.push_str(&format!(
+            "| Couverture fréquentielle (MHz) | {:.0} | {:.0} |\n",
+            a.freq_coverage_mhz, b.freq_coverage_mhz
+        ));
+        md.push_str(&format!("| Conflits | {} | {} |\n", a.conflicts, b.conflicts));
+        md.push_str(&format!(
+            "| Violations de cycle de service | {} | {} |\n",
+            a.duty_cycle_violations, b.duty_cycle_violations
+        ));
+
+        md.push_str("\n## Charge des amplificateurs\n\n");
+        md.push_str("| Amplificateur | Plan A | Plan B |\n|---|---:|---:|\n");
+        for (la, lb) in a.amplifiers.iter().zip(&b.amplifiers) {
+            md.push_str(&format!(
+                "| {:?} | {:.0} % ({} tâches) | {:.0} % ({} tâches) |\n",
+                la.amplifier,
+                la.utilization * 100.,
+                la.task_count,
+                lb.utilization * 100.,
+                lb.task_count
+            ));
+        }
+
+        md.push_str("\n## Différences\n\n");
+        for (title, names) in [("Ajoutées", &self.added), ("Supprimées", &self.removed), ("Modifiées", &self.modified)] {
+            md.push_str(&format!("- {} : ", title));
+            if names.is_empty() {
+                md.push_str("aucune\n");
+            } else {
+                md.push_str(&format!("{}\n", names.join(", ")));
+            }
+        }
+
+        md
+    }
+}
@@ -0,0 +1,221 @@
+//! Module des révisions nommées du plan.
+//!
+//! L’opérateur fige l’état courant du plan sous un libellé (« Valider une
+//! révision »). Les révisions sont conservées à côté du fichier de plan, dans
+//! `<plan>.revisions.json` ([`revisions_path`]), et relues lorsque le chemin du
+//! plan change. Le panneau latéral liste les révisions : chacune peut être
+//! consultée en lecture seule (le plan courant est mis de côté, les messages reçus
+//! restent en file d’attente et toute modification est refusée jusqu’au retour au
+//! plan courant), et deux révisions quelconques peuvent être comparées tâche par
+//! tâche ([`crate::tools::baseline::plan_deviations`]).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::baseline::{plan_deviations, Deviation};
+use crate::tools::task::Task;
+
+/// Révision nommée du plan.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Revision {
+    /// Libellé de la révision.
+    pub label: String,
+    /// Date UTC de la révision (ISO 8601).
+    pub time: String,
+    /// Tâches du plan à la date de la révision.
+    pub tasks: Vec<Task>,
+}
+
+/// Action demandée depuis le panneau des révisions.
+#[derive(Clone, PartialEq, Debug)]
+pub enum RevisionAction {
+    /// Fige le plan courant sous le libellé indiqué.
+    Commit(String),
+    /// Consulte la révision d’indice indiqué en lecture seule.
+    View(usize),
+}
+
+/// Chemin du fichier des révisions d’un plan : `plan.json` → `plan.revisions.json`.
+pub fn revisions_path(plan_path: &Path) -> PathBuf {
+    let stem = plan_path.file_stem().and_then(|s| s.to_str()).unwrap_or("plan");
+    plan_path.with_file_name(format!("{}.revisions.json", stem))
+}
+
+/// Charge les révisions depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_revisions(path: &Path) -> Result<Vec<Revision>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Révisions {} invalides : {}", path.display(), e))
+}
+
+/// Enregistre les révisions dans un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si les révisions ne peuvent pas être sérialisées ou écrites.
+pub fn save_revisions(path: &Path, revisions: &[Revision]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(revisions).map_err(|e| format!("Sérialisation des révisions impossible : {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))
+}
+
+/// Révisions du plan et consultation en cours.
+#[derive(Default)]
+pub struct PlanRevisions {
+    /// Fichier des révisions chargé (`None` avant le premier chargement).
+    path: Option<PathBuf>,
+    /// Révisions, de la plus ancienne à la plus récente.
+    pub revisions: Vec<Revision>,
+    /// Libellé saisi pour la prochaine révision.
+    label: String,
+    /// Révision consultée et plan courant mis de côté.
+    viewing: Option<(usize, Vec<Task>)>,
+    /// Révisions comparées (indices « de » et « vers »).
+    compared: [usize; 2],
+}
+
+impl PlanRevisions {
+    /// Charge les révisions du plan `plan_path` si le chemin du plan a changé
+    /// (sauf pendant une consultation, dont la révision doit rester disponible).
+    pub fn open(&mut self, plan_path: &str) {
+        let path = revisions_path(Path::new(plan_path));
+        if self.path.as_ref() == Some(&path) || self.is_viewing() {
+            return;
+        }
+        self.revisions = if path.exists() {
+            load_revisions(&path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        self.compared = [self.revisions.len().saturating_sub(2), self.revisions.len().saturating_sub(1)];
+        self.path = Some(path);
+    }
+
+    /// Fige les tâches `tasks` sous le libellé `label` et enregistre les révisions.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le libellé est vide ou déjà utilisé, ou si
+    /// l’enregistrement échoue (la révision est alors abandonnée).
+    pub fn commit(&mut self, label: &str, tasks: &[Task]) -> Result<(), String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("Libellé de révision vide".into());
+        }
+        if self.revisions.iter().any(|r| r.label == label) {
+            return Err(format!("Une révision « {} » existe déjà", label));
+        }
+        let path = self.path.clone().ok_or("Fichier des révisions non ouvert")?;
+        let time = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        self.revisions.push(Revision { label: label.to_string(), time, tasks: tasks.to_vec() });
+        if let Err(e) = save_revisions(&path, &self.revisions) {
+            self.revisions.pop();
+            return Err(e);
+        }
+        self.compared = [self.revisions.len().saturating_sub(2), self.revisions.len() - 1];
+        self.label.clear();
+        Ok(())
+    }
+
+    /// Consulte la révision d’indice `index` : le plan courant `tasks` est mis de
+    /// côté et remplacé par la révision.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si l’indice est invalide.
+    pub fn view(&mut self, index: usize, tasks: &mut Vec<Task>) -> Result<(), String> {
+        let revision = self.revisions.get(index).ok_or("Révision inconnue")?;
+        let revision_tasks = revision.tasks.clone();
+        let current = match self.viewing.take() {
+            Some((_, current)) => current,
+            None => std::mem::take(tasks),
+        };
+        *tasks = revision_tasks;
+        self.viewing = Some((index, current));
+        Ok(())
+    }
+
+    /// Termine la consultation et retourne le plan courant mis de côté.
+    pub fn leave(&mut self) -> Option<Vec<Task>> {
+        self.viewing.take().map(|(_, current)| current)
+    }
+
+    /// Indique si une révision est consultée.
+    pub fn is_viewing(&self) -> bool {
+        self.viewing.is_some()
+    }
+
+    /// Révision consultée.
+    pub fn viewed(&self) -> Option<&Revision> {
+        self.viewing.as_ref().and_then(|(index, _)| self.revisions.get(*index))
+    }
+
+    /// Écarts de la révision `to` par rapport à la révision `from`.
+    pub fn diff(&self, from: usize, to: usize) -> Vec<Deviation> {
+        match (self.revisions.get(from), self.revisions.get(to)) {
+            (Some(from), Some(to)) => plan_deviations(&from.tasks, &to.tasks),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Affiche les révisions dans le panneau latéral et retourne l’action
+    /// demandée. La validation est désactivée si `editable` est faux.
+    pub fn show(&mut self, ui: &mut egui::Ui, editable: bool) -> Option<RevisionAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.label).hint_text("Libellé").desired_width(120.));
+            if ui.add_enabled(editable && !self.label.trim().is_empty(), egui::Button::new("Valider une révision")).clicked() {
+                action = Some(RevisionAction::Commit(self.label.clone()));
+            }
+        });
+        if let Some(path) = &self.path {
+            ui.weak(format!("Enregistrées dans {}", path.display()));
+        }
+        if self.revisions.is_empty() {
+            ui.weak("Aucune révision");
+            return action;
+        }
+        let viewed = self.viewing.as_ref().map(|(index, _)| *index);
+        egui::ScrollArea::vertical().max_height(160.).id_salt("revisions").show(ui, |ui| {
+            for (i, revision) in self.revisions.iter().enumerate().rev() {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(viewed != Some(i), egui::Button::new("👁").small()).on_hover_text("Consulter en lecture seule").clicked() {
+                        action = Some(RevisionAction::View(i));
+                    }
+                    ui.label(&revision.label);
+                    ui.weak(format!("{} – {} tâches", revision.time, revision.tasks.len()));
+                });
+            }
+        });
+        ui.label("Comparer :");
+        ui.horizontal(|ui| {
+            for (slot, salt) in [(0, "revision_from"), (1, "revision_to")] {
+                let selected = self.revisions.get(self.compared[slot]).map_or("—", |r| r.label.as_str());
+                egui::ComboBox::from_id_salt(salt).selected_text(selected).show_ui(ui, |ui| {
+                    for (i, revision) in self.revisions.iter().enumerate() {
+                        ui.selectable_value(&mut self.compared[slot], i, &revision.label);
+                    }
+                });
+                if slot == 0 {
+                    ui.label("→");
+                }
+            }
+        });
+        let deviations = self.diff(self.compared[0], self.compared[1]);
+        ui.label(format!("Écarts : {}", deviations.len()));
+        egui::ScrollArea::vertical().max_height(160.).id_salt("revision_diff").show(ui, |ui| {
+            for deviation in &deviations {
+                ui.label(&deviation.detail);
+            }
+        });
+        action
+    }
+}
@@ -0,0 +1,219 @@
+//! Module d’analyse de robustesse du plan par la méthode de Monte-Carlo.
+//!
+//! L’exécution réelle d’un plan glisse : chaque tâche peut démarrer un peu plus
+//! tôt ou plus tard que prévu. L’analyse décale aléatoirement chaque tâche selon
+//! une loi de gigue réglable (uniforme ou normale), sur un grand nombre de tirages,
+//! et estime la probabilité d’apparition de conflits et de violations de la garde
+//! Rx, globalement et par tâche. Un plan dont les tâches sont très rapprochées
+//! apparaît ainsi fragile même s’il est valide tel quel.
+//!
+//! Les tirages sont effectués dans un thread dédié pour ne pas figer
+//! l’interface ; un résultat portant sur une révision dépassée du plan est ignoré.
+
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+use crate::tools::constraints::find_conflicts;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Task;
+
+/// Loi de la gigue appliquée au début des tâches.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JitterDistribution {
+    /// Décalage uniforme dans `[-amplitude, +amplitude]`.
+    Uniform,
+    /// Décalage normal centré d’écart type `amplitude`.
+    Normal,
+}
+
+impl JitterDistribution {
+    /// Liste de toutes les lois.
+    pub const ALL: [JitterDistribution; 2] = [JitterDistribution::Uniform, JitterDistribution::Normal];
+
+    /// Libellé de la loi.
+    pub fn label(&self) -> &'static str {
+        match self {
+            JitterDistribution::Uniform => "Uniforme",
+            JitterDistribution::Normal => "Normale",
+        }
+    }
+
+    /// Tire un décalage en ms.
+    fn sample(&self, rng: &mut fastrand::Rng, amplitude: f64) -> f64 {
+        match self {
+            JitterDistribution::Uniform => (2. * rng.f64() - 1.) * amplitude,
+            JitterDistribution::Normal => {
+                // Méthode de Box-Muller
+                let u = 1. - rng.f64();
+                let v = rng.f64();
+                (-2. * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos() * amplitude
+            }
+        }
+    }
+}
+
+/// Paramètres de l’analyse.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct JitterConfig {
+    /// Loi de la gigue.
+    pub distribution: JitterDistribution,
+    /// Amplitude de la gigue en ms (demi-largeur ou écart type selon la loi).
+    pub amplitude: f64,
+    /// Nombre de tirages.
+    pub iterations: usize,
+    /// Graine du générateur pseudo-aléatoire (résultats reproductibles).
+    pub seed: u64,
+}
+
+impl Default for JitterConfig {
+    fn default() -> Self {
+        Self { distribution: JitterDistribution::Uniform, amplitude: 10., iterations: 500, seed: 1 }
+    }
+}
+
+/// Résultat de l’analyse de robustesse.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RobustnessReport {
+    /// Nombre de tirages effectués.
+    pub iterations: usize,
+    /// Probabilité qu’au moins un conflit apparaisse.
+    pub conflict_probability: f64,
+    /// Probabilité qu’au moins une tâche émette pendant la garde Rx.
+    pub rx_probability: f64,
+    /// Probabilité, pour chaque tâche, d’être impliquée dans un conflit ou une
+    /// violation de la garde Rx.
+    pub task_probabilities: Vec<f64>,
+}
+
+impl RobustnessReport {
+    /// Indices des tâches les plus fragiles, par probabilité décroissante (au plus `count`,
+    /// en excluant les tâches jamais en défaut).
+    pub fn most_fragile(&self, count: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.task_probabilities.len()).filter(|&i| self.task_probabilities[i] > 0.).collect();
+        indices.sort_by(|&a, &b| self.task_probabilities[b].total_cmp(&self.task_probabilities[a]));
+        indices.truncate(count);
+        indices
+    }
+}
+
+/// Estime la fragilité du plan en décalant aléatoirement les tâches.
+pub fn monte_carlo(tasks: &[Task], rx_guard: &RxGuard, config: &JitterConfig) -> RobustnessReport {
+    let mut rng = fastrand::Rng::with_seed(config.seed);
+    let iterations = config.iterations.max(1);
+    let (mut conflict_runs, mut rx_runs) = (0, 0);
+    let mut task_hits = vec![0usize; tasks.len()];
+    let mut jittered = tasks.to_vec();
+    for _ in 0..iterations {
+        for (task, original) in jittered.iter_mut().zip(tasks) {
+            *task = original.clone();
+            task.shift_time(config.distribution.sample(&mut rng, config.amplitude));
+        }
+        let mut faulty = vec![false; tasks.len()];
+        let conflicts = find_conflicts(&jittered);
+        for conflict in &conflicts {
+            faulty[conflict.first] = true;
+            faulty[conflict.second] = true;
+        }
+        let rx_violations = rx_guard.violations(&jittered);
+        for &i in &rx_violations {
+            faulty[i] = true;
+        }
+        conflict_runs += usize::from(!conflicts.is_empty());
+        rx_runs += usize::from(!rx_violations.is_empty());
+        for (hits, faulty) in task_hits.iter_mut().zip(faulty) {
+            *hits += usize::from(faulty);
+        }
+    }
+    let ratio = |count: usize| count as f64 / iterations as f64;
+    RobustnessReport {
+        iterations,
+        conflict_probability: ratio(conflict_runs),
+        rx_probability: ratio(rx_runs),
+        task_probabilities: task_hits.into_iter().map(ratio).collect(),
+    }
+}
+
+/// Panneau d’analyse de robustesse : paramètres et dernier résultat.
+#[derive(Default)]
+pub struct RobustnessPanel {
+    /// Paramètres de l’analyse.
+    pub config: JitterConfig,
+    /// Résultat de la dernière analyse (effacé à chaque modification du plan).
+    pub report: Option<RobustnessReport>,
+    /// Révision du plan analysé.
+    pub revision: u64,
+    /// Analyse en cours : révision du plan soumis et canal du résultat.
+    pending: Option<(u64, Receiver<RobustnessReport>)>,
+}
+
+impl RobustnessPanel {
+    /// Lance l’analyse du plan de révision `revision` dans un thread dédié.
+    fn start(&mut self, tasks: &[Task], rx_guard: &RxGuard, revision: u64, ctx: &egui::Context) {
+        let (tx, rx) = channel();
+        let (tasks, rx_guard, config, ctx) = (tasks.to_vec(), *rx_guard, self.config, ctx.clone());
+        thread::spawn(move || {
+            let _ = tx.send(monte_carlo(&tasks, &rx_guard, &config));
+            ctx.request_repaint();
+        });
+        self.pending = Some((revision, rx));
+    }
+
+    /// Récupère le résultat de l’analyse en cours s’il est disponible ; un
+    /// résultat portant sur une révision dépassée est abandonné.
+    fn poll(&mut self, revision: u64) {
+        let Some((submitted, rx)) = &self.pending else { return };
+        match rx.try_recv() {
+            Ok(report) => {
+                if *submitted == revision {
+                    self.report = Some(report);
+                    self.revision = revision;
+                }
+                self.pending = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.pending = None,
+        }
+    }
+
+    /// Affiche les paramètres et le résultat dans le panneau latéral ; `revision`
+    /// est la révision courante du plan.
+    pub fn show(&mut self, ui: &mut egui::Ui, tasks: &[Task], rx_guard: &RxGuard, revision: u64) {
+        self.poll(revision);
+        if revision != self.revision {
+            self.report = None;
+        }
+        egui::ComboBox::from_label("Loi de la gigue")
+            .selected_text(self.config.distribution.label())
+            .show_ui(ui, |ui| {
+                for distribution in JitterDistribution::ALL {
+                    ui.selectable_value(&mut self.config.distribution, distribution, distribution.label());
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.label("Amplitude :");
+            ui.add(egui::DragValue::new(&mut self.config.amplitude).range(0.0..=500.0).suffix(" ms"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Tirages :");
+            ui.add(egui::DragValue::new(&mut self.config.iterations).range(10..=10_000));
+        });
+        let running = self.pending.as_ref().is_some_and(|(submitted, _)| *submitted == revision);
+        if ui.add_enabled(!tasks.is_empty() && !running, egui::Button::new("Lancer l'analyse")).clicked() {
+            self.start(tasks, rx_guard, revision, ui.ctx());
+        }
+        if running {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Analyse en cours…");
+            });
+        }
+        let Some(report) = &self.report else { return };
+        ui.label(format!("Probabilité de conflit : {:.1} %", 100. * report.conflict_probability));
+        ui.label(format!("Probabilité de violation Rx : {:.1} %", 100. * report.rx_probability));
+        for i in report.most_fragile(5) {
+            if let Some(task) = tasks.get(i) {
+                ui.label(format!("{} : {:.1} %", task.name, 100. * report.task_probabilities[i]));
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Module de la fenêtre de garde de réception (Rx).
+//!
+//! Au début de chaque cycle, le système écoute pendant une fenêtre de garde
+//! durant laquelle aucune tâche ne doit émettre, sauf si elle est explicitement
+//! marquée « rx-safe ». La durée de la fenêtre est configurable depuis le panneau
+//! latéral ou par un message `{"rx_guard_ms": 150}` reçu sur stdin.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::tools::task::Task;
+
+/// Durée par défaut de la fenêtre de garde Rx, en ms.
+pub const DEFAULT_RX_GUARD_MS: f64 = 100.;
+
+/// Fenêtre de garde de réception, de 0 à `duration` ms.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RxGuard {
+    /// Durée de la fenêtre en ms.
+    pub duration: f64,
+}
+
+impl Default for RxGuard {
+    fn default() -> Self {
+        Self { duration: DEFAULT_RX_GUARD_MS }
+    }
+}
+
+/// Message de configuration de la garde Rx reçu sur stdin.
+#[derive(Deserialize, JsonSchema)]
+pub struct RxGuardMessage {
+    /// Nouvelle durée de la fenêtre en ms.
+    pub rx_guard_ms: f64,
+}
+
+impl RxGuard {
+    /// Indique si la tâche émet pendant la fenêtre de garde.
+    pub fn overlaps(&self, task: &Task) -> bool {
+        task.time_start < self.duration && task.time_end > 0.
+    }
+
+    /// Vérifie qu’une tâche n’émet pas pendant la fenêtre, sauf si elle est rx-safe.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si la tâche émet pendant la fenêtre.
+    pub fn validate(&self, task: &Task) -> Result<(), String> {
+        if self.overlaps(task) && !task.rx_safe {
+            return Err(format!(
+                "« {} » émet pendant la garde Rx (0–{:.0} ms) sans être marquée rx-safe",
+                task.name, self.duration
+            ));
+        }
+        Ok(())
+    }
+
+    /// Retourne les indices des tâches émettant pendant la fenêtre sans être rx-safe.
+    pub fn violations(&self, tasks: &[Task]) -> Vec<usize> {
+        (0..tasks.len()).filter(|&i| self.validate(&tasks[i]).is_err()).collect()
+    }
+
+    /// Temps restant avant la fin de la fenêtre, `elapsed` ms après le début du cycle.
+    ///
+    /// Retourne `None` si la fenêtre n’est pas en cours.
+    pub fn remaining(&self, elapsed: f64) -> Option<f64> {
+        (0. ..self.duration).contains(&elapsed).then_some(self.duration - elapsed)
+    }
+}
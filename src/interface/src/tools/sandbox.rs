@@ -0,0 +1,66 @@
+//! Module du bac à sable (« et si… »).
+//!
+//! Le bac à sable fige le plan réel et laisse l’opérateur modifier une copie de
+//! travail : les analyses portent sur cette copie, mais rien n’est diffusé (ni
+//! événements, ni republication miroir, ni enregistrement persistant) et les
+//! messages reçus restent en file d’attente. En sortie, la copie est soit
+//! abandonnée (le plan réel est restauré), soit validée : ses écarts avec le plan
+//! réel sont alors émis comme un lot de mutations.
+
+use crate::tools::protocol::ProtocolEvent;
+use crate::tools::task::Task;
+
+/// État du bac à sable.
+#[derive(Default)]
+pub struct Sandbox {
+    /// Plan réel figé à l’ouverture du bac à sable (`None` hors bac à sable).
+    base: Option<Vec<Task>>,
+}
+
+impl Sandbox {
+    /// Indique si le bac à sable est ouvert.
+    pub fn is_active(&self) -> bool {
+        self.base.is_some()
+    }
+
+    /// Ouvre le bac à sable en figeant le plan réel.
+    pub fn enter(&mut self, tasks: &[Task]) {
+        self.base = Some(tasks.to_vec());
+    }
+
+    /// Ferme le bac à sable et retourne le plan réel figé à son ouverture.
+    pub fn discard(&mut self) -> Option<Vec<Task>> {
+        self.base.take()
+    }
+
+    /// Ferme le bac à sable en validant la copie de travail `tasks` et retourne
+    /// le lot de mutations par rapport au plan réel.
+    pub fn commit(&mut self, tasks: &[Task]) -> Vec<ProtocolEvent> {
+        let Some(base) = self.base.take() else {
+            return Vec::new();
+        };
+        let mut events: Vec<ProtocolEvent> = tasks
+            .iter()
+            .filter_map(|task| match base.iter().find(|t| t.key() == task.key()) {
+                None => Some(ProtocolEvent::TaskAdded { key: task.key().to_string() }),
+                Some(original) if original != task => Some(ProtocolEvent::TaskUpdated { key: task.key().to_string() }),
+                Some(_) => None,
+            })
+            .collect();
+        let removed = base.iter().filter(|b| !tasks.iter().any(|t| t.key() == b.key())).count();
+        if removed > 0 {
+            events.push(ProtocolEvent::TasksRemoved { count: removed });
+        }
+        events
+    }
+
+    /// Nombre de tâches de la copie de travail ajoutées, modifiées ou supprimées.
+    pub fn change_count(&self, tasks: &[Task]) -> usize {
+        let Some(base) = &self.base else {
+            return 0;
+        };
+        let changed = tasks.iter().filter(|t| !base.contains(t)).count();
+        let removed = base.iter().filter(|b| !tasks.iter().any(|t| t.key() == b.key())).count();
+        changed + removed
+    }
+}
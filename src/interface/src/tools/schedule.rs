@@ -0,0 +1,127 @@
+//! Module de planification automatique des tâches.
+//!
+//! À partir de besoins exprimés en bande de fréquence et en durée seulement (les
+//! temps des tâches ne servent qu’à en déduire la durée), le planificateur place
+//! chaque tâche au plus tôt dans la fenêtre de mission sur un amplificateur couvrant
+//! sa bande, en respectant la garde Rx, les masques d’exclusion (dont les bandes
+//! protégées), la capacité de chaque amplificateur (nombre de tâches simultanées),
+//! le cycle de service et l’absence de conflit avec les tâches déjà placées.
+//!
+//! Les tâches sont placées par priorité décroissante puis par durée décroissante ;
+//! la planification est proposée dans le panneau latéral et par la sous-commande
+//! `schedule` de la ligne de commande.
+
+use crate::tools::amplifiers::amplifier_duty_cycle;
+use crate::tools::constraints::check_duty_cycles;
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::report::peak_concurrency;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::MAX_TIME;
+
+/// Capacité par défaut d’un amplificateur (nombre de tâches simultanées).
+pub const DEFAULT_CAPACITY: usize = 1;
+
+/// Indique si la tâche chevauche strictement le masque en temps et en fréquence.
+fn hits_mask(task: &Task, mask: &ExclusionMask) -> bool {
+    task.time_start < mask.time_end
+        && mask.time_start < task.time_end
+        && task.freq_start < mask.freq_end
+        && mask.freq_start < task.freq_end
+}
+
+/// Indique si la tâche `candidate` peut être ajoutée aux tâches déjà placées.
+fn fits(candidate: &Task, placed: &[Task], masks: &[ExclusionMask], capacity: usize) -> bool {
+    if candidate.time_end > MAX_TIME || masks.iter().any(|m| hits_mask(candidate, m)) {
+        return false;
+    }
+    if placed.iter().any(|t| t.overlaps(candidate)) {
+        return false;
+    }
+    let mut same_amp: Vec<Task> = placed.iter().filter(|t| t.amplifier == candidate.amplifier).cloned().collect();
+    same_amp.push(candidate.clone());
+    let intervals: Vec<(f64, f64)> = same_amp.iter().map(|t| (t.time_start, t.time_end)).collect();
+    peak_concurrency(&intervals) <= capacity && check_duty_cycles(&same_amp).is_empty()
+}
+
+/// Amplificateurs candidats pour la tâche : le sien en premier s’il couvre sa bande,
+/// puis les autres amplificateurs couvrant la bande.
+fn candidate_amplifiers(task: &Task) -> Vec<Amplifier> {
+    let mut amps = vec![task.amplifier];
+    amps.extend(Amplifier::ALL.iter().copied().filter(|&a| a != task.amplifier));
+    amps.retain(|a| a.covers(task.freq_start, task.freq_end));
+    amps
+}
+
+/// Instants de début à essayer pour une tâche : le plus tôt permis, puis la fin de
+/// chaque tâche placée (augmentée du refroidissement de l’amplificateur) et de
+/// chaque masque.
+fn candidate_starts(earliest: f64, amp: Amplifier, placed: &[Task], masks: &[ExclusionMask]) -> Vec<f64> {
+    let cooldown = amplifier_duty_cycle(amp).map_or(0., |d| d.cooldown);
+    let mut starts = vec![earliest];
+    for task in placed {
+        starts.push(task.time_end);
+        if task.amplifier == amp {
+            starts.push(task.time_end + cooldown);
+        }
+    }
+    starts.extend(masks.iter().map(|m| m.time_end));
+    starts.retain(|&s| s >= earliest);
+    starts.sort_by(f64::total_cmp);
+    starts.dedup();
+    starts
+}
+
+/// Place automatiquement les tâches dans la fenêtre de mission.
+///
+/// Chaque tâche conserve sa bande et sa durée ; son début et éventuellement son
+/// amplificateur sont choisis par le planificateur. Les tâches sont retournées dans
+/// leur ordre d’origine.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur listant les tâches qu’aucun amplificateur ne
+/// couvre ou qui ne trouvent pas de place avant la fin de la mission.
+pub fn schedule(tasks: &[Task], rx_guard: &RxGuard, masks: &[ExclusionMask], capacity: usize) -> Result<Vec<Task>, String> {
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    let duration = |t: &Task| t.time_end - t.time_start;
+    order.sort_by(|&a, &b| {
+        tasks[b].priority.unwrap_or(0).cmp(&tasks[a].priority.unwrap_or(0))
+            .then(duration(&tasks[b]).total_cmp(&duration(&tasks[a])))
+    });
+
+    let mut placed: Vec<Task> = Vec::new();
+    let mut slots: Vec<Option<usize>> = vec![None; tasks.len()];
+    let mut unplaced = Vec::new();
+    for i in order {
+        let task = &tasks[i];
+        let earliest = if task.rx_safe { 0. } else { rx_guard.duration };
+        let mut best: Option<Task> = None;
+        for amp in candidate_amplifiers(task) {
+            for start in candidate_starts(earliest, amp, &placed, masks) {
+                if best.as_ref().is_some_and(|b| b.time_start <= start) {
+                    break;
+                }
+                let mut candidate = task.clone();
+                candidate.amplifier = amp;
+                candidate.shift_time(start - task.time_start);
+                if fits(&candidate, &placed, masks, capacity) {
+                    best = Some(candidate);
+                    break;
+                }
+            }
+        }
+        match best {
+            Some(candidate) => {
+                slots[i] = Some(placed.len());
+                placed.push(candidate);
+            }
+            None => unplaced.push(task.name.as_str()),
+        }
+    }
+
+    if !unplaced.is_empty() {
+        return Err(format!("Aucune place trouvée pour : {}", unplaced.join(", ")));
+    }
+    Ok(slots.into_iter().flatten().map(|slot| placed[slot].clone()).collect())
+}
@@ -0,0 +1,119 @@
+//! Module de capture automatique du graphe sur événement.
+//!
+//! Lorsque l’option est active, une image PNG horodatée du graphe principal est
+//! enregistrée dans [`SCREENSHOT_DIR`] à chaque événement marquant (nouveau
+//! conflit, chargement d’un plan, changement de phase de mission), constituant
+//! un journal visuel de la mission.
+//!
+//! La capture est demandée à egui (`ViewportCommand::Screenshot`) à la fin de
+//! l’image en cours ; l’image est reçue à l’image suivante sous forme
+//! d’événement, puis découpée au cadre du graphe et encodée.
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use egui::{ColorImage, Rect};
+
+/// Dossier d’enregistrement des captures.
+pub const SCREENSHOT_DIR: &str = "captures";
+
+/// Déclencheur de captures sur événement.
+#[derive(Default)]
+pub struct ScreenshotRecorder {
+    /// Indique si les captures automatiques sont actives.
+    pub enabled: bool,
+    /// Motifs des événements en attente de capture.
+    pending: Vec<String>,
+    /// Nombre de conflits lors de la dernière observation.
+    conflicts: usize,
+    /// Phase de mission en cours lors de la dernière observation.
+    phase: Option<String>,
+}
+
+impl ScreenshotRecorder {
+    /// Signale un événement ; une capture sera demandée si l’option est active.
+    pub fn request(&mut self, reason: &str) {
+        if self.enabled {
+            self.pending.push(reason.to_string());
+        }
+    }
+
+    /// Détecte les nouveaux conflits et les changements de phase de mission.
+    pub fn observe(&mut self, conflicts: usize, phase: Option<&str>) {
+        if conflicts > self.conflicts {
+            self.request("conflit");
+        }
+        self.conflicts = conflicts;
+        if phase != self.phase.as_deref() {
+            if let Some(phase) = phase {
+                self.request(&format!("phase {}", phase));
+            }
+            self.phase = phase.map(str::to_string);
+        }
+    }
+
+    /// Demande à egui la capture des événements en attente.
+    pub fn flush(&mut self, ctx: &egui::Context) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let reason = self.pending.join(", ");
+        self.pending.clear();
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::new(reason)));
+        ctx.request_repaint();
+    }
+
+    /// Enregistre les captures reçues, découpées au cadre `region` du graphe.
+    ///
+    /// Retourne, pour chaque capture, le chemin du fichier ou un message d’erreur.
+    pub fn save_received(&self, ctx: &egui::Context, region: Option<Rect>) -> Vec<Result<PathBuf, String>> {
+        let received: Vec<(String, Arc<ColorImage>)> = ctx.input(|i| {
+            i.raw
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Screenshot { user_data, image, .. } => {
+                        let reason = user_data.data.as_ref()?.downcast_ref::<String>()?.clone();
+                        Some((reason, image.clone()))
+                    }
+                    _ => None,
+                })
+                .collect()
+        });
+        let pixels_per_point = ctx.pixels_per_point();
+        received
+            .into_iter()
+            .map(|(reason, image)| {
+                let image = match region {
+                    Some(rect) => image.region(&rect, Some(pixels_per_point)),
+                    None => (*image).clone(),
+                };
+                let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+                let name: String = reason.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+                let path = Path::new(SCREENSHOT_DIR).join(format!("{}_{}.png", stamp, name));
+                save_png(&path, &image).map(|()| path)
+            })
+            .collect()
+    }
+}
+
+/// Encode une image au format PNG.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier ne peut pas être écrit.
+fn save_png(path: &Path, image: &ColorImage) -> Result<(), String> {
+    let write_error = |e: &dyn std::fmt::Display| format!("Écriture de {} impossible : {}", path.display(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| write_error(&e))?;
+    }
+    let file = File::create(path).map_err(|e| write_error(&e))?;
+    let [width, height] = image.size;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| write_error(&e))?;
+    writer.write_image_data(image.as_raw()).map_err(|e| write_error(&e))
+}
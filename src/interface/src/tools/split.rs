@@ -0,0 +1,128 @@
+//! Module de scission et de fusion des tâches.
+//!
+//! Une tâche rectangulaire peut être scindée en deux à un temps (ou à une
+//! fréquence) donné ; la première partie conserve la clé de la tâche, la seconde
+//! reçoit une clé dérivée (« (2) »). Deux tâches adjacentes compatibles (même
+//! amplificateur, même bande et temps contigus, ou même intervalle de temps et
+//! bandes contiguës) peuvent être fusionnées en une seule, qui conserve les
+//! attributs de la première. Ces opérations sont proposées par le menu
+//! contextuel du graphe et par les messages `split` et `join` du protocole.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::tools::task::Task;
+
+/// Tolérance de contiguïté de deux tâches, en ms ou en MHz.
+const ADJACENCY_TOLERANCE: f64 = 1e-6;
+
+/// Axe de scission d’une tâche.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitAxis {
+    /// Scission à un temps donné, en ms.
+    #[default]
+    Time,
+    /// Scission à une fréquence donnée, en MHz.
+    Frequency,
+}
+
+/// Scission d’une tâche.
+#[derive(Deserialize, JsonSchema)]
+pub struct SplitCommand {
+    /// Clé (identifiant ou nom) de la tâche à scinder.
+    pub key: String,
+    /// Axe de scission (`time` par défaut).
+    #[serde(default)]
+    pub axis: SplitAxis,
+    /// Position de la scission, en ms ou en MHz selon l’axe.
+    pub at: f64,
+}
+
+/// Message de scission d’une tâche, ex. `{"split": {"key": "Balayage", "at": 450}}`.
+#[derive(Deserialize, JsonSchema)]
+pub struct SplitMessage {
+    /// Scission demandée.
+    pub split: SplitCommand,
+}
+
+/// Message de fusion de deux tâches adjacentes, ex. `{"join": ["Balayage", "Balayage (2)"]}`.
+#[derive(Deserialize, JsonSchema)]
+pub struct JoinMessage {
+    /// Clés des deux tâches à fusionner (la première conserve ses attributs).
+    pub join: [String; 2],
+}
+
+/// Clé dérivée de la seconde partie d’une tâche scindée.
+fn second_part(text: &str) -> String {
+    format!("{} (2)", text)
+}
+
+/// Scinde une tâche rectangulaire à la position `at` de l’axe `axis`.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la tâche est polygonale ou si la position
+/// n’est pas strictement à l’intérieur de la tâche.
+pub fn split_task(task: &Task, axis: SplitAxis, at: f64) -> Result<(Task, Task), String> {
+    if task.shape.is_some() {
+        return Err(format!("« {} » : une tâche polygonale ne peut pas être scindée", task.name));
+    }
+    let (start, end) = match axis {
+        SplitAxis::Time => (task.time_start, task.time_end),
+        SplitAxis::Frequency => (task.freq_start, task.freq_end),
+    };
+    if !(at.is_finite() && start < at && at < end) {
+        return Err(format!("« {} » : position de scission {} hors de [{}, {}]", task.name, at, start, end));
+    }
+    let (mut first, mut second) = (task.clone(), task.clone());
+    match axis {
+        SplitAxis::Time => {
+            first.time_end = at;
+            second.time_start = at;
+        }
+        SplitAxis::Frequency => {
+            first.freq_end = at;
+            second.freq_start = at;
+        }
+    }
+    second.name = second_part(&task.name);
+    second.id = task.id.as_deref().map(second_part);
+    Ok((first, second))
+}
+
+/// Fusionne deux tâches adjacentes compatibles ; la tâche fusionnée conserve les
+/// attributs de `first`.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si les tâches sont polygonales, d’amplificateurs
+/// différents ou non contiguës.
+pub fn join_tasks(first: &Task, second: &Task) -> Result<Task, String> {
+    let incompatible = |reason: &str| format!("« {} » et « {} » : {}", first.name, second.name, reason);
+    if first.shape.is_some() || second.shape.is_some() {
+        return Err(incompatible("les tâches polygonales ne peuvent pas être fusionnées"));
+    }
+    if first.amplifier != second.amplifier {
+        return Err(incompatible("amplificateurs différents"));
+    }
+    let close = |a: f64, b: f64| (a - b).abs() <= ADJACENCY_TOLERANCE;
+    let same_band = close(first.freq_start, second.freq_start) && close(first.freq_end, second.freq_end);
+    let same_time = close(first.time_start, second.time_start) && close(first.time_end, second.time_end);
+    let time_contiguous = close(first.time_end, second.time_start) || close(second.time_end, first.time_start);
+    let freq_contiguous = close(first.freq_end, second.freq_start) || close(second.freq_end, first.freq_start);
+    if !((same_band && time_contiguous) || (same_time && freq_contiguous)) {
+        return Err(incompatible("tâches non adjacentes"));
+    }
+    let mut joined = first.clone();
+    joined.freq_start = first.freq_start.min(second.freq_start);
+    joined.freq_end = first.freq_end.max(second.freq_end);
+    joined.time_start = first.time_start.min(second.time_start);
+    joined.time_end = first.time_end.max(second.time_end);
+    Ok(joined)
+}
+
+/// Indices des tâches pouvant être fusionnées avec la tâche `index`.
+pub fn join_candidates(tasks: &[Task], index: usize) -> Vec<usize> {
+    (0..tasks.len()).filter(|&i| i != index && join_tasks(&tasks[index], &tasks[i]).is_ok()).collect()
+}
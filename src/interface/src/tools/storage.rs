@@ -0,0 +1,229 @@
+//! Module de stockage persistant des tâches.
+//!
+//! Pour les missions longues, la référence du plan est conservée dans une base
+//! SQLite embarquée : l’application la relit au démarrage (reprise après
+//! redémarrage) et y répercute chaque modification du plan. La base peut ensuite
+//! être interrogée après la mission (sous-commande `query`).
+//!
+//! Le stockage est abstrait par le trait [`TaskStore`] ; sans base configurée,
+//! [`MemoryStore`] conserve le comportement historique (plan en mémoire seulement).
+//!
+//! Les écritures dans la base ont lieu dans un thread dédié, pour ne pas bloquer
+//! l’interface : seules les lignes dont la tâche a changé sont réécrites, et les
+//! lignes au-delà de la fin du plan sont supprimées.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use rusqlite::{params, Connection};
+
+use crate::tools::task::Task;
+
+/// Stockage de la liste des tâches du plan.
+pub trait TaskStore {
+    /// Relit l’ensemble des tâches, dans leur ordre d’enregistrement.
+    fn load(&self) -> Result<Vec<Task>, String>;
+
+    /// Remplace l’ensemble des tâches stockées. L’écriture peut être différée
+    /// ([`SqliteStore`]) : son éventuelle erreur est alors retournée par l’appel suivant.
+    fn replace_all(&mut self, tasks: &[Task]) -> Result<(), String>;
+
+    /// Retourne les tâches actives entre `start` et `end` ms.
+    fn tasks_between(&self, start: f64, end: f64) -> Result<Vec<Task>, String> {
+        Ok(self.load()?.into_iter().filter(|t| t.time_start < end && t.time_end > start).collect())
+    }
+}
+
+/// Stockage en mémoire, perdu à la fermeture de l’application.
+#[derive(Default)]
+pub struct MemoryStore {
+    tasks: Vec<Task>,
+}
+
+impl TaskStore for MemoryStore {
+    fn load(&self) -> Result<Vec<Task>, String> {
+        Ok(self.tasks.clone())
+    }
+
+    fn replace_all(&mut self, tasks: &[Task]) -> Result<(), String> {
+        self.tasks = tasks.to_vec();
+        Ok(())
+    }
+}
+
+/// Stockage dans une base SQLite.
+///
+/// Chaque tâche est enregistrée sous forme JSON, accompagnée de colonnes indexées
+/// (temps, fréquences, amplificateur) destinées aux requêtes après mission.
+pub struct SqliteStore {
+    /// Connexion des lectures.
+    conn: Connection,
+    /// Chemin de la base, ouverte à nouveau par le thread d’écriture.
+    path: PathBuf,
+    /// Thread d’écriture et son canal, démarré à la première écriture.
+    writer: Option<(Sender<Vec<Task>>, JoinHandle<()>)>,
+    /// Erreur de la dernière écriture, pas encore signalée.
+    error: Arc<Mutex<Option<String>>>,
+}
+
+/// Ligne de la table des tâches.
+struct Row {
+    name: String,
+    amplifier: String,
+    freq_start: f64,
+    freq_end: f64,
+    time_start: f64,
+    time_end: f64,
+    data: String,
+}
+
+impl Row {
+    /// Ligne d’une tâche.
+    fn of(task: &Task) -> Result<Self, String> {
+        Ok(Self {
+            name: task.name.clone(),
+            amplifier: format!("{:?}", task.amplifier),
+            freq_start: task.freq_start,
+            freq_end: task.freq_end,
+            time_start: task.time_start,
+            time_end: task.time_end,
+            data: serde_json::to_string(task).map_err(|e| e.to_string())?,
+        })
+    }
+}
+
+/// Répercute le plan `tasks` dans la base : seules les positions dont la tâche
+/// diffère de `stored` (données JSON déjà enregistrées) sont réécrites.
+fn write_diff(conn: &mut Connection, stored: &mut Vec<String>, tasks: &[Task]) -> Result<(), String> {
+    let rows = tasks.iter().map(Row::of).collect::<Result<Vec<_>, _>>()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    {
+        let mut upsert = tx
+            .prepare(
+                "INSERT OR REPLACE INTO tasks (position, name, amplifier, freq_start, freq_end, time_start, time_end, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )
+            .map_err(|e| e.to_string())?;
+        for (position, row) in rows.iter().enumerate() {
+            if stored.get(position) == Some(&row.data) {
+                continue;
+            }
+            upsert
+                .execute(params![
+                    position as i64,
+                    row.name,
+                    row.amplifier,
+                    row.freq_start,
+                    row.freq_end,
+                    row.time_start,
+                    row.time_end,
+                    row.data
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.execute("DELETE FROM tasks WHERE position >= ?1", params![rows.len() as i64]).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    *stored = rows.into_iter().map(|row| row.data).collect();
+    Ok(())
+}
+
+/// Démarre le thread d’écriture de la base `path` : chaque plan reçu (le plus
+/// récent seulement, en cas de rafale) y est répercuté.
+fn spawn_writer(path: PathBuf, error: Arc<Mutex<Option<String>>>) -> Result<(Sender<Vec<Task>>, JoinHandle<()>), String> {
+    let mut conn = Connection::open(&path).map_err(|e| format!("Ouverture de la base {} impossible : {}", path.display(), e))?;
+    let mut stored: Vec<String> = {
+        let mut stmt = conn.prepare("SELECT data FROM tasks ORDER BY position").map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0)).map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+    let (tx, rx) = channel::<Vec<Task>>();
+    let handle = thread::spawn(move || {
+        while let Ok(mut tasks) = rx.recv() {
+            while let Ok(newer) = rx.try_recv() {
+                tasks = newer;
+            }
+            if let Err(e) = write_diff(&mut conn, &mut stored, &tasks) {
+                eprintln!("Enregistrement dans {} impossible : {}", path.display(), e);
+                if let Ok(mut error) = error.lock() {
+                    *error = Some(e);
+                }
+            }
+        }
+    });
+    Ok((tx, handle))
+}
+
+impl SqliteStore {
+    /// Ouvre (ou crée) la base SQLite `path`.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Ouverture de la base {} impossible : {}", path.display(), e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tasks (
+                position INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                amplifier TEXT NOT NULL,
+                freq_start REAL NOT NULL,
+                freq_end REAL NOT NULL,
+                time_start REAL NOT NULL,
+                time_end REAL NOT NULL,
+                data TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS tasks_time ON tasks (time_start, time_end);",
+        )
+        .map_err(|e| format!("Initialisation de la base {} impossible : {}", path.display(), e))?;
+        Ok(Self { conn, path: path.to_path_buf(), writer: None, error: Arc::default() })
+    }
+
+    /// Exécute une requête retournant la colonne `data` et désérialise les tâches.
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<Task>, String> {
+        let mut stmt = self.conn.prepare(sql).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params, |row| row.get::<_, String>(0)).map_err(|e| e.to_string())?;
+        rows.map(|data| {
+            let data = data.map_err(|e| e.to_string())?;
+            serde_json::from_str(&data).map_err(|e| format!("Tâche invalide dans la base : {}", e))
+        })
+        .collect()
+    }
+}
+
+impl TaskStore for SqliteStore {
+    fn load(&self) -> Result<Vec<Task>, String> {
+        self.query("SELECT data FROM tasks ORDER BY position", [])
+    }
+
+    fn replace_all(&mut self, tasks: &[Task]) -> Result<(), String> {
+        if let Some(e) = self.error.lock().ok().and_then(|mut error| error.take()) {
+            return Err(e);
+        }
+        if self.writer.is_none() {
+            self.writer = Some(spawn_writer(self.path.clone(), self.error.clone())?);
+        }
+        match &self.writer {
+            Some((writer, _)) if writer.send(tasks.to_vec()).is_ok() => Ok(()),
+            _ => Err(format!("Thread d'écriture de la base {} arrêté", self.path.display())),
+        }
+    }
+
+    fn tasks_between(&self, start: f64, end: f64) -> Result<Vec<Task>, String> {
+        self.query(
+            "SELECT data FROM tasks WHERE time_start < ?2 AND time_end > ?1 ORDER BY position",
+            params![start, end],
+        )
+    }
+}
+
+impl Drop for SqliteStore {
+    /// Attend la fin des écritures en cours avant la fermeture.
+    fn drop(&mut self) {
+        if let Some((writer, handle)) = self.writer.take() {
+            drop(writer);
+            if handle.join().is_err() {
+                eprintln!("Thread d'écriture de la base {} interrompu", self.path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Module des systèmes de brouillage.
+//!
+//! Un système regroupe plusieurs amplificateurs montés dans un même équipement
+//! (ex. un pod VHF/UHF et un pod bandes L/S). Les systèmes sont lus depuis un
+//! fichier de configuration JSON ([`SYSTEMS_PATH`]) ; le panneau latéral permet de
+//! zoomer sur la bande couverte par un système ou d’en filtrer les tâches, et
+//! affiche l’occupation cumulée de chaque système.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::task::Amplifier;
+
+/// Chemin du fichier de configuration des systèmes.
+pub const SYSTEMS_PATH: &str = "systems.json";
+
+/// Système regroupant plusieurs amplificateurs.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct System {
+    /// Nom du système.
+    pub name: String,
+    /// Amplificateurs du système.
+    pub amplifiers: Vec<Amplifier>,
+}
+
+impl System {
+    /// Bande de fréquence `(début, fin)` couverte par les amplificateurs du système, en MHz.
+    pub fn band(&self) -> Option<(f64, f64)> {
+        let bands = self.amplifiers.iter().map(Amplifier::band);
+        let start = bands.clone().map(|b| b.0).reduce(f64::min)?;
+        let end = bands.map(|b| b.1).reduce(f64::max)?;
+        Some((start, end))
+    }
+
+    /// Indique si l’amplificateur appartient au système.
+    pub fn contains(&self, amplifier: Amplifier) -> bool {
+        self.amplifiers.contains(&amplifier)
+    }
+}
+
+/// Systèmes par défaut : un système basse fréquence et un système hautes fréquences.
+pub fn default_systems() -> Vec<System> {
+    vec![
+        System { name: "VHF/UHF".into(), amplifiers: vec![Amplifier::A20_500, Amplifier::A500_1000] },
+        System {
+            name: "Bandes L/S".into(),
+            amplifiers: vec![Amplifier::A960_1215, Amplifier::A1000_2500, Amplifier::A2400_6000],
+        },
+    ]
+}
+
+/// Charge les systèmes depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou mal formé.
+pub fn load_systems(path: &Path) -> Result<Vec<System>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Systèmes {} invalides : {}", path.display(), e))
+}
+
+/// Charge la configuration des systèmes, ou les systèmes par défaut si le fichier est absent ou invalide.
+pub fn load_system_config() -> Vec<System> {
+    let path = Path::new(SYSTEMS_PATH);
+    if !path.exists() {
+        return default_systems();
+    }
+    load_systems(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        default_systems()
+    })
+}
@@ -4,13 +4,23 @@
 //! disponibles avec leur plage de fréquences, ainsi que la structure [`task::Task`] qui modélise
 //! une tâche à afficher dans le diagramme de Gantt fréquence/temps.
 
+use std::collections::BTreeMap;
+
 use egui::Color32;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::effect::Effect;
+use crate::tools::geometry::{point_in_polygon, widened_polygons_overlap};
+use crate::tools::timefmt::TimeResolution;
+use crate::tools::utils::is_log_safe;
 
 /// Enumération des amplificateurs disponibles avec leur plage de fréquence spécifique.
 ///
 /// Chaque variante est associée à une plage fréquentielle unique.
 /// Cette énumération est utilisée pour colorer les tâches et déterminer leur zone de validité.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Amplifier {
     /// Amplificateur pour la bande 20–500 MHz
     A20_500,
@@ -25,19 +35,46 @@ pub enum Amplifier {
 }
 
 impl Amplifier {
-    /// Retourne la couleur associée à l’amplificateur pour l’affichage graphique.
-    pub fn color(&self) -> Color32 {
+    /// Liste de tous les amplificateurs disponibles.
+    pub const ALL: [Amplifier; 5] = [
+        Amplifier::A20_500,
+        Amplifier::A500_1000,
+        Amplifier::A960_1215,
+        Amplifier::A1000_2500,
+        Amplifier::A2400_6000,
+    ];
+
+    /// Retourne la bande de fréquence `(début, fin)` de l’amplificateur en MHz.
+    pub fn band(&self) -> (f64, f64) {
+        match self {
+            Amplifier::A20_500 => (20.0, 500.0),
+            Amplifier::A500_1000 => (500.0, 1000.0),
+            Amplifier::A960_1215 => (960.0, 1215.0),
+            Amplifier::A1000_2500 => (1000.0, 2500.0),
+            Amplifier::A2400_6000 => (2400.0, 6000.0),
+        }
+    }
+
+    /// Retourne le libellé lisible de l’amplificateur, ex. « Amplifier 20-500MHz ».
+    pub fn label(&self) -> &'static str {
         match self {
-            Amplifier::A20_500 => Color32::from_rgb(0, 187, 221),
-            Amplifier::A500_1000 => Color32::from_rgb(255, 163, 0),
-            Amplifier::A960_1215 => Color32::from_rgb(124, 127, 171),
-            Amplifier::A1000_2500 => Color32::from_rgb(0, 171, 142),
-            Amplifier::A2400_6000 => Color32::from_rgb(174, 37, 115),
+            Amplifier::A20_500 => "Amplifier 20-500MHz",
+            Amplifier::A500_1000 => "Amplifier 500-1000MHz",
+            Amplifier::A960_1215 => "Amplifier 960-1215MHz",
+            Amplifier::A1000_2500 => "Amplifier 1000-2500MHz",
+            Amplifier::A2400_6000 => "Amplifier 2400-6000MHz",
         }
     }
 
+    /// Indique si la plage `[freq_start, freq_end]` est entièrement couverte par l’amplificateur.
+    pub fn covers(&self, freq_start: f64, freq_end: f64) -> bool {
+        let (start, end) = self.band();
+        freq_start >= start && freq_end <= end
+    }
+
     /// Conversion Amplifier depuis une chaîne de caractères.
     /// Si la chaîne ne correspond à aucun amplificateur, retourne `None`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "A20_500" => Some(Amplifier::A20_500),
@@ -50,11 +87,48 @@ impl Amplifier {
     }
 }
 
+/// Position géographique de la zone d’engagement d’une tâche, en degrés décimaux (WGS 84).
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Location {
+    /// Latitude en degrés (positive au nord).
+    pub lat: f64,
+    /// Longitude en degrés (positive à l’est).
+    pub lon: f64,
+}
+
+impl Location {
+    /// Analyse une position saisie sous la forme `lat, lon` (ex. `48.85, 2.35`).
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le texte est mal formé ou hors des plages valides.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Position invalide : « {} » (attendu : lat, lon)", text);
+        let (lat, lon) = text.split_once(',').ok_or_else(invalid)?;
+        let lat: f64 = lat.trim().parse().map_err(|_| invalid())?;
+        let lon: f64 = lon.trim().parse().map_err(|_| invalid())?;
+        if !(-90. ..=90.).contains(&lat) || !(-180. ..=180.).contains(&lon) {
+            return Err(format!("Position hors limites : « {} »", text));
+        }
+        Ok(Self { lat, lon })
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.5}, {:.5}", self.lat, self.lon)
+    }
+}
+
 /// Structure représentant une tâche dans le diagramme fréquence/temps.
 ///
 /// Chaque tâche est caractérisée par un nom, une plage de fréquence, une durée
 /// et un amplificateur associé.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Task {
+    /// Identifiant de la tâche chez l’émetteur (le nom sert d’identifiant s’il est absent).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     /// Nom de la tâche (affiché dans les info-bulles).
     pub name: String,
     /// Fréquence de début en MHz.
@@ -67,28 +141,388 @@ pub struct Task {
     pub time_end: f64,
     /// Amplificateur utilisé pour cette tâche.
     pub amplifier: Amplifier,
+    /// Groupe (ou mission) auquel appartient la tâche.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Identifiant (ou nom) de la tâche parente (mission → série → affectation).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// Plateforme (aéronef, véhicule…) portant le brouilleur.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// Antenne (ou faisceau) rayonnant la tâche.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub antenna: Option<String>,
+    /// Position de la zone d’engagement visée par la tâche.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    /// Effet recherché (déni, dégradation, déception, surveillance).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect: Option<Effect>,
+    /// Priorité de la tâche (plus la valeur est élevée, plus la tâche est prioritaire).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    /// Statut de la tâche (ex. « planifiée », « en cours », « terminée »).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Notes libres sur plusieurs lignes, au format Markdown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Références de documents externes (URI ou chemins de fichiers).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    /// Métadonnées libres (clé → valeur), ex. l’opérateur responsable.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+    /// Puissance d’émission en W.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<f64>,
+    /// Autorise la tâche à émettre pendant la fenêtre de garde Rx.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rx_safe: bool,
+    /// Tâche planifiée à titre provisoire, en attente de confirmation.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tentative: bool,
+    /// Contour polygonal `[fréquence, temps]` de la tâche, si elle n’est pas rectangulaire
+    /// (ex. profil de bande à rampe). Les bornes ci-dessus forment alors sa boîte englobante.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<Vec<[f64; 2]>>,
+    /// Tolérance en fréquence, en MHz, de part et d’autre de la bande : incertitude
+    /// d’accord et Doppler de la cible. L’empreinte spectrale de la tâche, prise en
+    /// compte dans les chevauchements, est élargie d’autant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freq_tolerance: Option<f64>,
 }
 
 impl Task {
+    /// Tâche rectangulaire couvrant la bande `freq` (MHz) pendant `time` (ms),
+    /// sans attribut facultatif.
+    pub fn block(name: impl Into<String>, amplifier: Amplifier, freq: (f64, f64), time: (f64, f64)) -> Self {
+        Self {
+            id: None,
+            name: name.into(),
+            freq_start: freq.0,
+            freq_end: freq.1,
+            time_start: time.0,
+            time_end: time.1,
+            amplifier,
+            group: None,
+            parent_id: None,
+            platform: None,
+            antenna: None,
+            location: None,
+            effect: None,
+            priority: None,
+            status: None,
+            notes: None,
+            attachments: Vec::new(),
+            metadata: BTreeMap::new(),
+            power: None,
+            rx_safe: false,
+            tentative: false,
+            shape: None,
+            freq_tolerance: None,
+        }
+    }
+
+    /// Tâche de balayage : une bande instantanée de `width` MHz parcourant `freq`
+    /// (MHz) du début à la fin de `time` (ms), sous forme de parallélogramme.
+    pub fn sweep(name: impl Into<String>, amplifier: Amplifier, freq: (f64, f64), time: (f64, f64), width: f64) -> Self {
+        let mut task = Self::block(name, amplifier, freq, time);
+        task.shape = Some(vec![
+            [freq.0, time.0],
+            [freq.0 + width, time.0],
+            [freq.1, time.1],
+            [freq.1 - width, time.1],
+        ]);
+        task.fit_to_shape();
+        task
+    }
+
+    /// Constructeur de tâche pas à pas, validée par [`TaskBuilder::build`].
+    pub fn builder(name: impl Into<String>, amplifier: Amplifier) -> TaskBuilder {
+        TaskBuilder { task: Self::block(name, amplifier, (0., 0.), (0., 0.)) }
+    }
+
+    /// Vérifie la cohérence de la tâche.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si le nom est vide, si une borne, un sommet du
+    /// contour ou la puissance n’est pas un nombre fini, ou si les bornes sont inversées.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Nom de tâche vide".into());
+        }
+        let bounds = [self.freq_start, self.freq_end, self.time_start, self.time_end];
+        if bounds.iter().any(|v| !v.is_finite()) {
+            return Err(format!("« {} » : bornes non finies", self.name));
+        }
+        // Une fréquence ponctuelle ou une tâche instantanée (bornes égales) est admise
+        if self.freq_start > self.freq_end {
+            return Err(format!("« {} » : freq_start ne doit pas dépasser freq_end", self.name));
+        }
+        if self.time_start > self.time_end {
+            return Err(format!("« {} » : time_start ne doit pas dépasser time_end", self.name));
+        }
+        if self.shape.as_ref().is_some_and(|shape| shape.len() < 3) {
+            return Err(format!("« {} » : contour de moins de 3 sommets", self.name));
+        }
+        if self.shape.iter().flatten().flatten().any(|v| !v.is_finite()) {
+            return Err(format!("« {} » : contour non fini", self.name));
+        }
+        if self.power.is_some_and(|p| !p.is_finite()) {
+            return Err(format!("« {} » : puissance non finie", self.name));
+        }
+        if self.freq_tolerance.is_some_and(|t| !t.is_finite() || t < 0.) {
+            return Err(format!("« {} » : tolérance en fréquence invalide", self.name));
+        }
+        Ok(())
+    }
+
+    /// Indique si les fréquences de la tâche (bornes et sommets du contour) sont
+    /// représentables en échelle logarithmique.
+    pub fn is_log_safe(&self) -> bool {
+        is_log_safe(self.freq_start)
+            && is_log_safe(self.freq_end)
+            && self.shape.iter().flatten().all(|&[freq, _]| is_log_safe(freq))
+    }
+
+    /// Clé d’identification de la tâche : son identifiant, ou à défaut son nom.
+    pub fn key(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.name)
+    }
+
     /// Retourne la couleur associée à la tâche, déléguée à son amplificateur.
     pub fn color(&self) -> Color32 {
-        self.amplifier.color()
+        amplifier_color(self.amplifier)
+    }
+
+    /// Contour polygonal de la tâche, s’il est défini et compte au moins 3 sommets.
+    /// Un contour dégénéré est ignoré : la tâche est alors traitée comme un rectangle.
+    pub fn polygon(&self) -> Option<&[[f64; 2]]> {
+        self.shape.as_deref().filter(|shape| shape.len() >= 3)
+    }
+
+    /// Retourne le contour `[fréquence, temps]` de la tâche : son polygone s’il est
+    /// défini, sinon le rectangle formé par ses bornes.
+    pub fn outline(&self) -> Vec<[f64; 2]> {
+        match self.polygon() {
+            Some(shape) => shape.to_vec(),
+            None => vec![
+                [self.freq_start, self.time_start],
+                [self.freq_end, self.time_start],
+                [self.freq_end, self.time_end],
+                [self.freq_start, self.time_end],
+            ],
+        }
     }
 
-    /// Retourne les coordonnées de la tâche sous forme de rectangle `[x, y]` pour l’affichage.
+    /// Indique si le point `(freq, time)` appartient à la tâche.
+    pub fn contains(&self, freq: f64, time: f64) -> bool {
+        let in_bounds = freq >= self.freq_start && freq <= self.freq_end
+            && time >= self.time_start && time <= self.time_end;
+        match self.polygon() {
+            Some(shape) => in_bounds && point_in_polygon(shape, freq, time),
+            None => in_bounds,
+        }
+    }
+
+    /// Tolérance en fréquence de la tâche, en MHz (0 si non renseignée ou invalide).
+    pub fn tolerance(&self) -> f64 {
+        self.freq_tolerance.filter(|t| t.is_finite() && *t > 0.).unwrap_or(0.)
+    }
+
+    /// Bornes `(début, fin)` en MHz de l’empreinte spectrale de la tâche, c’est-à-dire
+    /// de sa bande élargie de sa tolérance en fréquence.
+    pub fn footprint(&self) -> (f64, f64) {
+        (self.freq_start - self.tolerance(), self.freq_end + self.tolerance())
+    }
+
+    /// Indique si deux tâches se chevauchent strictement en temps et en fréquence,
+    /// tolérances en fréquence comprises.
+    pub fn overlaps(&self, other: &Task) -> bool {
+        let margin = self.tolerance() + other.tolerance();
+        let time_overlap = self.time_start < other.time_end && other.time_start < self.time_end;
+        let freq_overlap = self.freq_start - margin < other.freq_end && other.freq_start < self.freq_end + margin;
+        time_overlap && freq_overlap
+            && (self.polygon().is_none() && other.polygon().is_none()
+                || widened_polygons_overlap(&self.outline(), &other.outline(), margin))
+    }
+
+    /// Décale la tâche (et son contour) de `delta` ms.
+    pub fn shift_time(&mut self, delta: f64) {
+        self.time_start += delta;
+        self.time_end += delta;
+        if let Some(shape) = &mut self.shape {
+            for point in shape {
+                point[1] += delta;
+            }
+        }
+    }
+
+    /// Arrondit les temps de la tâche (et de son contour) à la résolution donnée.
+    pub fn quantize_times(&mut self, resolution: TimeResolution) {
+        self.time_start = resolution.quantize(self.time_start);
+        self.time_end = resolution.quantize(self.time_end);
+        if let Some(shape) = &mut self.shape {
+            for point in shape {
+                point[1] = resolution.quantize(point[1]);
+            }
+        }
+    }
+
+    /// Recalcule les bornes de la tâche à partir de son contour polygonal, s’il existe.
+    pub fn fit_to_shape(&mut self) {
+        let Some(shape) = self.polygon() else { return };
+        let (freqs, times) = (shape.iter().map(|p| p[0]), shape.iter().map(|p| p[1]));
+        let (freq_start, freq_end) = (freqs.clone().fold(f64::INFINITY, f64::min), freqs.fold(f64::NEG_INFINITY, f64::max));
+        let (time_start, time_end) = (times.clone().fold(f64::INFINITY, f64::min), times.fold(f64::NEG_INFINITY, f64::max));
+        self.freq_start = freq_start;
+        self.freq_end = freq_end;
+        self.time_start = time_start;
+        self.time_end = time_end;
+    }
+}
+
+/// Constructeur de [`Task`] : les attributs sont renseignés un à un, puis la
+/// tâche est validée par [`TaskBuilder::build`].
+///
+/// ```ignore
+/// let task = Task::builder("Radar L", Amplifier::A960_1215)
+///     .freq(1000., 1200.)
+///     .time(50., 250.)
+///     .priority(3)
+///     .build()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaskBuilder {
+    task: Task,
+}
+
+impl TaskBuilder {
+    /// Identifiant de la tâche chez l’émetteur.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.task.id = Some(id.into());
+        self
+    }
+
+    /// Bande de fréquence en MHz.
+    pub fn freq(mut self, start: f64, end: f64) -> Self {
+        (self.task.freq_start, self.task.freq_end) = (start, end);
+        self
+    }
+
+    /// Fenêtre temporelle en ms.
+    pub fn time(mut self, start: f64, end: f64) -> Self {
+        (self.task.time_start, self.task.time_end) = (start, end);
+        self
+    }
+
+    /// Groupe (ou mission).
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.task.group = Some(group.into());
+        self
+    }
+
+    /// Tâche parente, désignée par son identifiant ou son nom.
+    pub fn parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.task.parent_id = Some(parent_id.into());
+        self
+    }
+
+    /// Plateforme portant le brouilleur.
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.task.platform = Some(platform.into());
+        self
+    }
+
+    /// Antenne rayonnant la tâche.
+    pub fn antenna(mut self, antenna: impl Into<String>) -> Self {
+        self.task.antenna = Some(antenna.into());
+        self
+    }
+
+    /// Position de la zone d’engagement.
+    pub fn location(mut self, location: Location) -> Self {
+        self.task.location = Some(location);
+        self
+    }
+
+    /// Effet recherché.
+    pub fn effect(mut self, effect: Effect) -> Self {
+        self.task.effect = Some(effect);
+        self
+    }
+
+    /// Priorité de la tâche.
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.task.priority = Some(priority);
+        self
+    }
+
+    /// Statut de la tâche.
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.task.status = Some(status.into());
+        self
+    }
+
+    /// Notes libres, au format Markdown.
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.task.notes = Some(notes.into());
+        self
+    }
+
+    /// Ajoute une pièce jointe.
+    pub fn attachment(mut self, reference: impl Into<String>) -> Self {
+        self.task.attachments.push(reference.into());
+        self
+    }
+
+    /// Ajoute une métadonnée.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.task.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Puissance d’émission en W.
+    pub fn power(mut self, power: f64) -> Self {
+        self.task.power = Some(power);
+        self
+    }
+
+    /// Autorise la tâche à émettre pendant la garde Rx.
+    pub fn rx_safe(mut self, rx_safe: bool) -> Self {
+        self.task.rx_safe = rx_safe;
+        self
+    }
+
+    /// Marque la tâche comme provisoire.
+    pub fn tentative(mut self, tentative: bool) -> Self {
+        self.task.tentative = tentative;
+        self
+    }
+
+    /// Tolérance en fréquence en MHz (incertitude d’accord, Doppler).
+    pub fn freq_tolerance(mut self, tolerance: f64) -> Self {
+        self.task.freq_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Contour polygonal `[fréquence, temps]` ; les bornes en sont déduites.
+    pub fn shape(mut self, shape: Vec<[f64; 2]>) -> Self {
+        self.task.shape = Some(shape);
+        self.task.fit_to_shape();
+        self
+    }
+
+    /// Construit la tâche.
+    ///
+    /// # Erreurs
     ///
-    /// Si `log` est `true`, applique le logarithme base 10 aux coordonnées X (fréquences).
-    pub fn rect(&self, log: bool) -> Vec<[f64; 2]> {
-        let (x0, x1) = if log {
-            (self.freq_start.log10(), self.freq_end.log10())
-        } else {
-            (self.freq_start, self.freq_end)
-        };
-        vec![
-            [x0, self.time_start],
-            [x1, self.time_start],
-            [x1, self.time_end],
-            [x0, self.time_end],
-        ]
+    /// Retourne un message d’erreur si la tâche est incohérente (voir [`Task::validate`]).
+    pub fn build(self) -> Result<Task, String> {
+        self.task.validate()?;
+        Ok(self.task)
     }
 }
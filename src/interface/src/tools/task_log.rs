@@ -0,0 +1,166 @@
+//! Module de l’historique des modifications de chaque tâche.
+//!
+//! Chaque fois que le plan change, les tâches sont comparées, champ par champ, à
+//! leur dernier état connu : création, modification (valeurs précédente et
+//! nouvelle de chaque champ) et suppression sont horodatées et rattachées à la
+//! tâche par sa clé. L’origine de la modification est l’action de l’opérateur
+//! signalée par [`TaskChangeLog::note`] (édition, alignement, annulation…) ou, à
+//! défaut, le protocole. L’inspecteur présente ce journal pour retracer
+//! l’évolution d’une affectation pendant la planification.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::Value;
+
+use crate::tools::task::Task;
+
+/// Nombre maximal de modifications conservées par tâche (les plus anciennes sont oubliées).
+const MAX_CHANGES_PER_TASK: usize = 50;
+
+/// Origine des modifications non attribuées à une action de l’opérateur.
+const PROTOCOL_SOURCE: &str = "protocole";
+
+/// Modification d’un champ.
+#[derive(Clone, Debug)]
+pub struct FieldChange {
+    /// Nom du champ.
+    pub field: String,
+    /// Valeur précédente.
+    pub old: String,
+    /// Nouvelle valeur.
+    pub new: String,
+}
+
+/// Nature d’une modification.
+#[derive(Clone, Debug)]
+pub enum ChangeKind {
+    /// Tâche créée.
+    Created,
+    /// Champs modifiés.
+    Modified(Vec<FieldChange>),
+    /// Tâche supprimée.
+    Removed,
+}
+
+/// Modification horodatée d’une tâche.
+#[derive(Clone, Debug)]
+pub struct TaskChange {
+    /// Heure UTC de la modification (`HH:MM:SS`).
+    pub time: String,
+    /// Origine de la modification.
+    pub source: String,
+    /// Nature de la modification.
+    pub kind: ChangeKind,
+}
+
+/// Historique des modifications, par clé de tâche.
+#[derive(Default)]
+pub struct TaskChangeLog {
+    /// Dernier état connu de chaque tâche, sérialisé champ par champ.
+    known: HashMap<String, serde_json::Map<String, Value>>,
+    /// Modifications de chaque tâche, de la plus ancienne à la plus récente.
+    changes: HashMap<String, VecDeque<TaskChange>>,
+    /// Actions de l’opérateur signalées depuis la dernière comparaison.
+    pending: Vec<String>,
+}
+
+/// Champs sérialisés d’une tâche.
+fn fields(task: &Task) -> serde_json::Map<String, Value> {
+    match serde_json::to_value(task) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Valeur d’un champ telle qu’affichée dans le journal.
+fn format_value(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "—".into(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+impl TaskChangeLog {
+    /// Signale l’action de l’opérateur à l’origine des prochaines modifications.
+    pub fn note(&mut self, action: impl Into<String>) {
+        self.pending.push(action.into());
+    }
+
+    /// Compare les tâches à leur dernier état connu et enregistre les créations,
+    /// modifications et suppressions.
+    pub fn observe(&mut self, tasks: &[Task]) {
+        let source = if self.pending.is_empty() { PROTOCOL_SOURCE.to_string() } else { self.pending.join(" ; ") };
+        self.pending.clear();
+        let time = chrono::Utc::now().format("%H:%M:%S").to_string();
+        let mut previous = std::mem::take(&mut self.known);
+        for task in tasks {
+            let current = fields(task);
+            let kind = match previous.remove(task.key()) {
+                None => Some(ChangeKind::Created),
+                Some(old) => {
+                    let mut names: Vec<&String> = old.keys().chain(current.keys()).collect();
+                    names.sort();
+                    names.dedup();
+                    let changed: Vec<FieldChange> = names
+                        .into_iter()
+                        .filter(|name| old.get(*name) != current.get(*name))
+                        .map(|name| FieldChange {
+                            field: name.clone(),
+                            old: format_value(old.get(name)),
+                            new: format_value(current.get(name)),
+                        })
+                        .collect();
+                    (!changed.is_empty()).then_some(ChangeKind::Modified(changed))
+                }
+            };
+            if let Some(kind) = kind {
+                self.push(task.key(), TaskChange { time: time.clone(), source: source.clone(), kind });
+            }
+            self.known.insert(task.key().to_string(), current);
+        }
+        for key in previous.into_keys() {
+            self.push(&key, TaskChange { time: time.clone(), source: source.clone(), kind: ChangeKind::Removed });
+        }
+    }
+
+    /// Ajoute une modification au journal de la tâche `key`.
+    fn push(&mut self, key: &str, change: TaskChange) {
+        let changes = self.changes.entry(key.to_string()).or_default();
+        if changes.len() >= MAX_CHANGES_PER_TASK {
+            changes.pop_front();
+        }
+        changes.push_back(change);
+    }
+
+    /// Modifications de la tâche `key`, de la plus ancienne à la plus récente.
+    pub fn changes(&self, key: &str) -> &VecDeque<TaskChange> {
+        self.changes.get(key).unwrap_or(&NO_CHANGES)
+    }
+}
+
+/// Historique vide, pour les tâches sans modification enregistrée.
+pub static NO_CHANGES: VecDeque<TaskChange> = VecDeque::new();
+
+/// Affiche le journal des modifications d’une tâche, de la plus récente à la plus ancienne.
+pub fn show_change_log(ui: &mut egui::Ui, changes: &VecDeque<TaskChange>) {
+    if changes.is_empty() {
+        ui.weak("Aucune modification enregistrée");
+        return;
+    }
+    egui::ScrollArea::vertical().max_height(160.).id_salt("task_change_log").show(ui, |ui| {
+        for change in changes.iter().rev() {
+            let title = match &change.kind {
+                ChangeKind::Created => "créée",
+                ChangeKind::Modified(_) => "modifiée",
+                ChangeKind::Removed => "supprimée",
+            };
+            ui.label(format!("{} – {} ({})", change.time, title, change.source));
+            if let ChangeKind::Modified(fields) = &change.kind {
+                for field in fields {
+                    ui.weak(format!("    {} : {} → {}", field.field, field.old, field.new));
+                }
+            }
+        }
+    });
+}
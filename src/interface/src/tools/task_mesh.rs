@@ -0,0 +1,147 @@
+//! Module de rendu groupé des tâches.
+//!
+//! Dessiner chaque tâche sous forme de `Polygon` `egui_plot` crée un élément de
+//! graphe par tâche (tessellation, légende, test de survol…), ce qui devient
+//! prohibitif au-delà de quelques milliers de tâches. Les tâches visibles sont ici
+//! regroupées dans un maillage unique en coordonnées écran (couleur portée par les
+//! sommets), dessiné en une seule opération par-dessus le graphe.
+//!
+//! Les tâches plus étroites que [`MIN_TASK_WIDTH_PX`] (ex. canaux de 25 kHz en vue
+//! globale) sont élargies à cette largeur pour rester visibles ; de même, les
+//! tâches plus courtes que [`MIN_TASK_HEIGHT_PX`] sont épaissies. Une tâche de
+//! largeur nulle (fréquence ponctuelle) est ainsi dessinée comme un trait vertical
+//! et une tâche de durée nulle (événement instantané) comme un trait horizontal.
+//!
+//! En mode dégradé, [`lod_mesh`] réduit encore le coût du rendu : les tâches sont
+//! dessinées par leur rectangle englobant et les tâches plus petites qu’une cellule
+//! de [`LOD_CELL_PX`] pixels sont agrégées en un rectangle par cellule.
+
+use std::collections::HashMap;
+
+use egui::{vec2, Color32, Mesh, Pos2, Rect};
+use egui_plot::{PlotBounds, PlotPoint, PlotTransform};
+
+use crate::tools::geometry::triangulate;
+use crate::tools::task::Task;
+use crate::tools::utils::freq_to_x;
+
+/// Largeur minimale d’affichage d’une tâche en pixels.
+pub const MIN_TASK_WIDTH_PX: f32 = 3.;
+
+/// Hauteur minimale d’affichage d’une tâche en pixels.
+pub const MIN_TASK_HEIGHT_PX: f32 = 2.;
+
+/// Étendue minimale de détection d’une tâche au survol et au clic, en pixels.
+pub const HIT_TOLERANCE_PX: f32 = 6.;
+
+/// Côté d’une cellule d’agrégation du niveau de détail réduit, en pixels.
+pub const LOD_CELL_PX: f32 = 6.;
+
+/// Convertit un point `[fréquence, temps]` en coordonnées écran.
+fn to_screen(transform: &PlotTransform, log: bool, [freq, time]: [f64; 2]) -> Pos2 {
+    let x = freq_to_x(freq, log);
+    transform.position_from_point(&PlotPoint::new(x, time))
+}
+
+/// Rectangle écran englobant la tâche et indicateur d’élargissement à la taille minimale.
+fn screen_rect(task: &Task, transform: &PlotTransform, log: bool) -> (Rect, bool) {
+    let bbox = Rect::from_two_pos(
+        to_screen(transform, log, [task.freq_start, task.time_start]),
+        to_screen(transform, log, [task.freq_end, task.time_end]),
+    );
+    if bbox.width() < MIN_TASK_WIDTH_PX || bbox.height() < MIN_TASK_HEIGHT_PX {
+        let size = vec2(bbox.width().max(MIN_TASK_WIDTH_PX), bbox.height().max(MIN_TASK_HEIGHT_PX));
+        (Rect::from_center_size(bbox.center(), size), true)
+    } else {
+        (bbox, false)
+    }
+}
+
+/// Étendue minimale de détection `[largeur, durée]` d’une tâche en unités du
+/// graphe, pour un graphe de limites `bounds` dessiné dans `frame`.
+pub fn hit_extent(bounds: &PlotBounds, frame: Rect) -> [f64; 2] {
+    let (width, height) = (frame.width().max(1.) as f64, frame.height().max(1.) as f64);
+    [
+        HIT_TOLERANCE_PX as f64 * bounds.width() / width,
+        HIT_TOLERANCE_PX as f64 * bounds.height() / height,
+    ]
+}
+
+/// Contour de la tâche en coordonnées écran (rectangle élargi si la tâche est trop étroite ou trop courte).
+pub fn screen_outline(task: &Task, transform: &PlotTransform, log: bool) -> Vec<Pos2> {
+    let (rect, widened) = screen_rect(task, transform, log);
+    match task.polygon() {
+        Some(shape) if !widened => shape.iter().map(|p| to_screen(transform, log, *p)).collect(),
+        _ => vec![rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()],
+    }
+}
+
+/// Construit le maillage de toutes les tâches `(tâche, couleur)` visibles dans le cadre du graphe.
+///
+/// Les tâches entièrement hors du cadre sont ignorées ; les tâches polygonales
+/// sont triangulées, les autres ajoutées comme de simples rectangles.
+pub fn task_mesh<'a>(tasks: impl Iterator<Item = (&'a Task, Color32)>, transform: &PlotTransform, log: bool) -> Mesh {
+    let frame = *transform.frame();
+    let mut mesh = Mesh::default();
+    for (task, color) in tasks {
+        let (rect, widened) = screen_rect(task, transform, log);
+        if !rect.intersects(frame) {
+            continue;
+        }
+        match task.polygon() {
+            Some(shape) if !widened => {
+                let points: Vec<[f64; 2]> = shape
+                    .iter()
+                    .map(|p| to_screen(transform, log, *p))
+                    .map(|p| [p.x as f64, p.y as f64])
+                    .collect();
+                for triangle in triangulate(&points) {
+                    let base = mesh.vertices.len() as u32;
+                    for [x, y] in triangle {
+                        mesh.colored_vertex(Pos2::new(x as f32, y as f32), color);
+                    }
+                    mesh.add_triangle(base, base + 1, base + 2);
+                }
+            }
+            _ => mesh.add_colored_rect(rect, color),
+        }
+    }
+    mesh
+}
+
+/// Construit un maillage à niveau de détail réduit des tâches `(tâche, couleur)`
+/// visibles dans le cadre du graphe.
+///
+/// Aucune tâche n’est triangulée : les tâches tenant dans une cellule de
+/// [`LOD_CELL_PX`] pixels sont agrégées en un rectangle par cellule, de couleur
+/// moyenne, les autres dessinées par leur rectangle englobant.
+pub fn lod_mesh<'a>(tasks: impl Iterator<Item = (&'a Task, Color32)>, transform: &PlotTransform, log: bool) -> Mesh {
+    let frame = *transform.frame();
+    let mut mesh = Mesh::default();
+    // Somme des composantes RVBA et nombre de tâches par cellule
+    let mut cells: HashMap<(i32, i32), ([u32; 4], u32)> = HashMap::new();
+    for (task, color) in tasks {
+        let (rect, _) = screen_rect(task, transform, log);
+        if !rect.intersects(frame) {
+            continue;
+        }
+        if rect.width() > LOD_CELL_PX || rect.height() > LOD_CELL_PX {
+            mesh.add_colored_rect(rect, color);
+            continue;
+        }
+        let center = rect.center() - frame.min;
+        let cell = ((center.x / LOD_CELL_PX).floor() as i32, (center.y / LOD_CELL_PX).floor() as i32);
+        let (sum, count) = cells.entry(cell).or_default();
+        for (s, c) in sum.iter_mut().zip(color.to_array()) {
+            *s += c as u32;
+        }
+        *count += 1;
+    }
+    for ((cx, cy), (sum, count)) in cells {
+        let [r, g, b, a] = sum.map(|s| (s / count) as u8);
+        let min = frame.min + vec2(cx as f32, cy as f32) * LOD_CELL_PX;
+        let rect = Rect::from_min_size(min, vec2(LOD_CELL_PX, LOD_CELL_PX));
+        mesh.add_colored_rect(rect, Color32::from_rgba_premultiplied(r, g, b, a));
+    }
+    mesh
+}
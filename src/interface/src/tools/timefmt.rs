@@ -0,0 +1,393 @@
+//! Module d’analyse et de mise en forme des expressions temporelles militaires.
+//!
+//! Les ordres expriment les instants sous forme de groupe date-heure (DTG, ex.
+//! `271530ZMAY25` : le 27 mai 2025 à 15h30 UTC) ou relativement à l’heure H de la
+//! mission (ex. `H+15`, en minutes par défaut). Ce module convertit ces expressions
+//! vers le modèle temporel interne (ms depuis le début de la mission) et inversement.
+//!
+//! Lorsque l’heure H est connue, les instants absolus sont affichés dans le fuseau
+//! choisi par l’utilisateur ([`TimeZoneSetting`]) avec un suffixe explicite
+//! (`Z` pour UTC, `L` pour l’heure locale, `±hh:mm` pour un décalage personnalisé)
+//! afin d’éviter toute ambiguïté en opérations interalliées.
+//!
+//! Les temps sont stockés en ms flottantes ; la résolution ([`TimeResolution`])
+//! borne la précision retenue à la saisie et affichée, jusqu’à la microseconde.
+//! Sur les axes et dans les info-bulles, le nombre de décimales s’adapte à
+//! l’étendue visible (niveau de zoom).
+
+/// Abréviations des mois utilisées dans les DTG.
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+/// Millisecondes par minute.
+const MS_PER_MINUTE: i64 = 60_000;
+/// Microsecondes par milliseconde.
+const US_PER_MS: i64 = 1000;
+
+/// Nombre de jours depuis le 1er janvier 1970 pour une date du calendrier grégorien.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Date du calendrier grégorien `(année, mois, jour)` à partir d’un nombre de jours depuis 1970.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Décalage en heures d’un fuseau militaire (A–M à l’est, N–Y à l’ouest, Z = UTC).
+///
+/// Le fuseau `J` (heure locale de l’observateur) n’a pas de décalage fixe et est refusé.
+fn zone_offset_hours(zone: char) -> Option<i64> {
+    match zone {
+        'Z' => Some(0),
+        'A'..='I' => Some(zone as i64 - 'A' as i64 + 1),
+        'K'..='M' => Some(zone as i64 - 'K' as i64 + 10),
+        'N'..='Y' => Some(-(zone as i64 - 'N' as i64 + 1)),
+        _ => None,
+    }
+}
+
+/// Nombre de jours du mois `month` (1 à 12) de l’année `year`.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Analyse un DTG (`DDHHMMZMONYY`, espaces tolérés, année sur 2 ou 4 chiffres).
+///
+/// Retourne l’instant correspondant en millisecondes depuis l’époque Unix (UTC).
+pub fn parse_dtg(text: &str) -> Result<i64, String> {
+    let s: String = text.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let invalid = || format!("DTG invalide : « {} » (attendu DDHHMMZMONYY)", text);
+    if !s.is_ascii() || s.len() < 12 {
+        return Err(invalid());
+    }
+
+    let number = |range: std::ops::Range<usize>| s[range].parse::<u32>().map_err(|_| invalid());
+    let (day, hour, minute) = (number(0..2)?, number(2..4)?, number(4..6)?);
+    let zone = s.as_bytes()[6] as char;
+    let offset = zone_offset_hours(zone).ok_or_else(invalid)?;
+    let month = MONTHS.iter().position(|m| *m == &s[7..10]).ok_or_else(invalid)? as u32 + 1;
+    let year = match &s[10..] {
+        y if y.len() == 2 => 2000 + y.parse::<i64>().map_err(|_| invalid())?,
+        y if y.len() == 4 => y.parse::<i64>().map_err(|_| invalid())?,
+        _ => return Err(invalid()),
+    };
+    if !(1..=days_in_month(year, month)).contains(&day) || hour > 23 || minute > 59 {
+        return Err(invalid());
+    }
+
+    let local_minutes = (days_from_civil(year, month, day) * 24 + hour as i64) * 60 + minute as i64;
+    Ok((local_minutes - offset * 60) * MS_PER_MINUTE)
+}
+
+/// Lettre militaire du fuseau correspondant à un décalage en minutes, s’il est entier en heures.
+fn military_zone(offset_minutes: i64) -> Option<char> {
+    if offset_minutes % 60 != 0 {
+        return None;
+    }
+    match offset_minutes / 60 {
+        0 => Some('Z'),
+        h @ 1..=9 => Some((b'A' + h as u8 - 1) as char),
+        h @ 10..=12 => Some((b'K' + h as u8 - 10) as char),
+        h @ -12..=-1 => Some((b'N' + (-h) as u8 - 1) as char),
+        _ => None,
+    }
+}
+
+/// Met en forme un instant (ms depuis l’époque Unix) en DTG UTC, ex. `271530ZMAY25`.
+pub fn format_dtg(unix_ms: i64) -> String {
+    format_dtg_in(unix_ms, TimeZoneSetting::Utc)
+}
+
+/// Met en forme un instant en DTG dans le fuseau demandé.
+///
+/// La lettre de fuseau militaire est utilisée lorsque le décalage est un nombre
+/// entier d’heures, sinon le décalage `±hh:mm` est inséré à sa place.
+pub fn format_dtg_in(unix_ms: i64, zone: TimeZoneSetting) -> String {
+    let offset = zone.offset_minutes();
+    let minutes = unix_ms.div_euclid(MS_PER_MINUTE) + offset;
+    let (days, minute_of_day) = (minutes.div_euclid(24 * 60), minutes.rem_euclid(24 * 60));
+    let (year, month, day) = civil_from_days(days);
+    let zone_text = match military_zone(offset) {
+        Some(letter) => letter.to_string(),
+        None => format_offset(offset),
+    };
+    format!(
+        "{:02}{:02}{:02}{}{}{:02}",
+        day,
+        minute_of_day / 60,
+        minute_of_day % 60,
+        zone_text,
+        MONTHS[month as usize - 1],
+        year.rem_euclid(100)
+    )
+}
+
+/// Met en forme un instant (ms depuis l’époque Unix) au format ISO 8601 UTC,
+/// ex. `2025-05-27T15:30:00.250Z` (échanges avec les outils cartographiques).
+pub fn format_iso(unix_ms: i64) -> String {
+    let ms_per_day = 24 * 60 * MS_PER_MINUTE;
+    let (days, ms_of_day) = (unix_ms.div_euclid(ms_per_day), unix_ms.rem_euclid(ms_per_day));
+    let (year, month, day) = civil_from_days(days);
+    let secs = ms_of_day / 1000;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        ms_of_day % 1000
+    )
+}
+
+/// Met en forme un décalage horaire en minutes sous la forme `±hh:mm`.
+fn format_offset(offset_minutes: i64) -> String {
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    format!("{}{:02}:{:02}", sign, offset_minutes.abs() / 60, offset_minutes.abs() % 60)
+}
+
+/// Met en forme l’heure d’un instant exprimé en µs depuis l’époque Unix, suffixée
+/// par le fuseau, avec `ms_decimals` décimales au-delà de la milliseconde (0 à 3),
+/// ex. `15:30:00.250Z` ou `15:30:00.250125Z`.
+pub fn format_clock(unix_us: i64, zone: TimeZoneSetting, ms_decimals: usize) -> String {
+    let us_per_day = 24 * 60 * MS_PER_MINUTE * US_PER_MS;
+    let local_us = unix_us + zone.offset_minutes() * MS_PER_MINUTE * US_PER_MS;
+    let us_of_day = local_us.rem_euclid(us_per_day);
+    let secs = us_of_day / 1_000_000;
+    let digits = 3 + ms_decimals.min(3);
+    let fraction = (us_of_day % 1_000_000) / 10i64.pow(6 - digits as u32);
+    format!(
+        "{:02}:{:02}:{:02}.{:0width$}{}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        fraction,
+        zone.suffix(),
+        width = digits
+    )
+}
+
+/// Analyse une expression relative à l’heure H (`H+15`, `H-5m`, `H+30s`, `H+250ms`, `H+40us`, `H+1h`).
+///
+/// Sans unité, la valeur est exprimée en minutes. Retourne le décalage en ms.
+pub fn parse_relative(text: &str) -> Result<f64, String> {
+    let s = text.trim().to_lowercase();
+    let invalid = || format!("Expression relative invalide : « {} » (ex. H+15)", text);
+    let rest = s.strip_prefix('h').ok_or_else(invalid)?.trim_start();
+    let (sign, rest) = match rest.chars().next() {
+        Some('+') => (1., &rest[1..]),
+        Some('-') => (-1., &rest[1..]),
+        _ => return Err(invalid()),
+    };
+    let rest = rest.trim();
+    let split = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+    let value: f64 = rest[..split].parse().map_err(|_| invalid())?;
+    let factor = match rest[split..].trim() {
+        "us" | "µs" => 1. / US_PER_MS as f64,
+        "ms" => 1.,
+        "s" => 1000.,
+        "" | "m" | "min" => MS_PER_MINUTE as f64,
+        "h" => 60. * MS_PER_MINUTE as f64,
+        _ => return Err(invalid()),
+    };
+    Ok(sign * value * factor)
+}
+
+/// Analyse une expression temporelle et la convertit en ms depuis le début de mission.
+///
+/// Sont acceptés : un nombre (ms), une expression relative (`H+15`) ou un DTG.
+/// Un DTG nécessite que l’heure H de la mission (`mission_start`, ms Unix) soit connue.
+pub fn parse_time(text: &str, mission_start: Option<i64>) -> Result<f64, String> {
+    let s = text.trim();
+    if let Ok(ms) = s.replace(',', ".").parse::<f64>() {
+        return Ok(ms);
+    }
+    if s.starts_with(['H', 'h']) {
+        return parse_relative(s);
+    }
+    let instant = parse_dtg(s)?;
+    let start = mission_start.ok_or("Un DTG nécessite de définir l’heure H de la mission")?;
+    Ok((instant - start) as f64)
+}
+
+/// Met en forme un temps de mission (ms) en expression relative à l’heure H.
+pub fn format_relative(time_ms: f64) -> String {
+    let sign = if time_ms < 0. { '-' } else { '+' };
+    let abs = time_ms.abs();
+    if abs >= MS_PER_MINUTE as f64 && abs % MS_PER_MINUTE as f64 == 0. {
+        format!("H{}{:.0}", sign, abs / MS_PER_MINUTE as f64)
+    } else if abs >= 1000. && abs % 1000. == 0. {
+        format!("H{}{:.0}s", sign, abs / 1000.)
+    } else {
+        format!("H{}{}ms", sign, abs)
+    }
+}
+
+/// Fuseau horaire d’affichage des instants absolus.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TimeZoneSetting {
+    /// Temps universel (suffixe `Z`).
+    #[default]
+    Utc,
+    /// Heure locale du poste (suffixe `L`).
+    Local,
+    /// Décalage personnalisé par rapport à UTC, en minutes.
+    Offset(i32),
+}
+
+impl TimeZoneSetting {
+    /// Décalage par rapport à UTC en minutes.
+    pub fn offset_minutes(&self) -> i64 {
+        match self {
+            TimeZoneSetting::Utc => 0,
+            TimeZoneSetting::Local => chrono::Local::now().offset().local_minus_utc() as i64 / 60,
+            TimeZoneSetting::Offset(minutes) => *minutes as i64,
+        }
+    }
+
+    /// Suffixe ajouté aux heures affichées.
+    pub fn suffix(&self) -> String {
+        match self {
+            TimeZoneSetting::Utc => "Z".into(),
+            TimeZoneSetting::Local => "L".into(),
+            TimeZoneSetting::Offset(minutes) => format_offset(*minutes as i64),
+        }
+    }
+}
+
+/// Résolution temporelle des tâches.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TimeResolution {
+    /// Milliseconde (comportement historique).
+    #[default]
+    Millisecond,
+    /// Dixième de milliseconde.
+    TenthMillisecond,
+    /// Microseconde.
+    Microsecond,
+}
+
+impl TimeResolution {
+    /// Liste de toutes les résolutions disponibles.
+    pub const ALL: [TimeResolution; 3] = [
+        TimeResolution::Millisecond,
+        TimeResolution::TenthMillisecond,
+        TimeResolution::Microsecond,
+    ];
+
+    /// Libellé affiché dans le sélecteur.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeResolution::Millisecond => "1 ms",
+            TimeResolution::TenthMillisecond => "100 µs",
+            TimeResolution::Microsecond => "1 µs",
+        }
+    }
+
+    /// Nombre maximal de décimales affichées pour une valeur en ms.
+    pub fn decimals(&self) -> usize {
+        match self {
+            TimeResolution::Millisecond => 0,
+            TimeResolution::TenthMillisecond => 1,
+            TimeResolution::Microsecond => 3,
+        }
+    }
+
+    /// Arrondit un temps en ms à la résolution.
+    pub fn quantize(&self, time_ms: f64) -> f64 {
+        let scale = 10f64.powi(self.decimals() as i32);
+        (time_ms * scale).round() / scale
+    }
+
+    /// Nombre de décimales adapté à une étendue visible de `span_ms` ms : environ
+    /// trois chiffres significatifs sur l’étendue, dans la limite de la résolution.
+    pub fn decimals_for_span(&self, span_ms: f64) -> usize {
+        if span_ms.is_nan() || span_ms <= 0. {
+            return self.decimals();
+        }
+        let needed = (2. - span_ms.log10()).ceil().max(0.) as usize;
+        needed.min(self.decimals())
+    }
+}
+
+/// Paramètres d’affichage des temps de mission.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TimeDisplay {
+    /// Heure H de la mission (ms depuis l’époque Unix), si elle est connue.
+    pub mission_start: Option<i64>,
+    /// Fuseau d’affichage des instants absolus.
+    pub zone: TimeZoneSetting,
+    /// Résolution temporelle des saisies et des affichages.
+    pub resolution: TimeResolution,
+}
+
+impl TimeDisplay {
+    /// Met en forme un temps de mission de façon compacte (exports) à la résolution
+    /// choisie : heure absolue si l’heure H est connue, sinon durée en ms.
+    pub fn format(&self, time_ms: f64) -> String {
+        self.format_decimals(time_ms, self.resolution.decimals())
+    }
+
+    /// Met en forme un temps de mission pour un axe ou une info-bulle dont
+    /// l’étendue visible est `span_ms` : la précision suit le niveau de zoom.
+    pub fn format_at(&self, time_ms: f64, span_ms: f64) -> String {
+        self.format_decimals(time_ms, self.resolution.decimals_for_span(span_ms))
+    }
+
+    /// Met en forme un temps de mission avec `decimals` décimales de ms.
+    fn format_decimals(&self, time_ms: f64, decimals: usize) -> String {
+        match self.mission_start {
+            Some(start) => {
+                let unix_us = start * US_PER_MS + (time_ms * US_PER_MS as f64).round() as i64;
+                format_clock(unix_us, self.zone, decimals)
+            }
+            None => format!("{:.*} ms", decimals, time_ms),
+        }
+    }
+
+    /// Met en forme un temps de mission en DTG si l’heure H est connue, sinon en relatif.
+    pub fn format_long(&self, time_ms: f64) -> String {
+        match self.mission_start {
+            Some(start) => format_dtg_in(start + time_ms.round() as i64, self.zone),
+            None => format_relative(self.resolution.quantize(time_ms)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dtg_day_is_checked_against_the_month_length() {
+        assert!(parse_dtg("311200ZJAN25").is_ok());
+        assert!(parse_dtg("311200ZAPR25").is_err());
+        assert!(parse_dtg("301200ZFEB24").is_err());
+        assert!(parse_dtg("291200ZFEB24").is_ok());
+        assert!(parse_dtg("291200ZFEB25").is_err());
+        assert!(parse_dtg("291200ZFEB2000").is_ok());
+        assert!(parse_dtg("291200ZFEB2100").is_err());
+        assert_eq!(parse_dtg("010000ZMAR24").unwrap() - parse_dtg("290000ZFEB24").unwrap(), 86_400_000);
+    }
+}
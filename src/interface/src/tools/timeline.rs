@@ -0,0 +1,196 @@
+//! Module de la chronologie de la session.
+//!
+//! Chaque événement du protocole et chaque action de l’opérateur modifiant le plan
+//! (chargement, édition, alignement, annulation…) est horodaté et conservé pendant
+//! la session. « Exporter la chronologie » écrit ces entrées en NDJSON (un objet
+//! JSON par ligne) ainsi qu’un diagramme en couloirs au format SVG (un couloir
+//! par type d’événement), pour retracer l’évolution du plan lors du retour
+//! d’expérience de la mission.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::tools::gantt_export::xml_escape;
+use crate::tools::protocol::ProtocolEvent;
+
+/// Nom de base des fichiers exportés (`.ndjson` et `.svg`).
+pub const TIMELINE_PATH: &str = "session_timeline";
+
+/// Nombre maximal d’entrées conservées (les plus anciennes sont oubliées).
+const MAX_ENTRIES: usize = 100_000;
+
+/// Largeur du diagramme SVG en pixels.
+const SVG_WIDTH: f64 = 1200.;
+
+/// Largeur de la colonne des libellés de couloirs en pixels.
+const SVG_LABEL_WIDTH: f64 = 220.;
+
+/// Hauteur d’un couloir en pixels.
+const SVG_LANE_HEIGHT: f64 = 24.;
+
+/// Origine d’une entrée de la chronologie.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineSource {
+    /// Événement émis après le traitement d’un message du protocole.
+    Protocol,
+    /// Action de l’opérateur dans l’interface.
+    User,
+}
+
+impl TimelineSource {
+    /// Couleur des marques de la source dans le diagramme.
+    fn color(&self) -> &'static str {
+        match self {
+            TimelineSource::Protocol => "#3c8cdc",
+            TimelineSource::User => "#f0a000",
+        }
+    }
+}
+
+/// Entrée horodatée de la chronologie.
+#[derive(Clone, Debug, Serialize)]
+pub struct TimelineEntry {
+    /// Temps écoulé depuis le début de la session, en ms.
+    pub elapsed_ms: f64,
+    /// Date et heure UTC (RFC 3339).
+    pub time: String,
+    /// Origine de l’entrée.
+    pub source: TimelineSource,
+    /// Type d’événement (ex. `task_added`) ou d’action (ex. `undo`).
+    pub kind: String,
+    /// Événement du protocole complet, ou description de l’action.
+    pub detail: serde_json::Value,
+}
+
+/// Chronologie de la session en cours.
+pub struct SessionTimeline {
+    /// Début de la session.
+    start: Instant,
+    /// Entrées, de la plus ancienne à la plus récente.
+    pub entries: VecDeque<TimelineEntry>,
+}
+
+impl Default for SessionTimeline {
+    fn default() -> Self {
+        Self { start: Instant::now(), entries: VecDeque::new() }
+    }
+}
+
+impl SessionTimeline {
+    /// Ajoute une entrée horodatée.
+    fn push(&mut self, source: TimelineSource, kind: String, detail: serde_json::Value) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(TimelineEntry {
+            elapsed_ms: self.start.elapsed().as_secs_f64() * 1000.,
+            time: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            source,
+            kind,
+            detail,
+        });
+    }
+
+    /// Enregistre un événement du protocole.
+    pub fn record_event(&mut self, event: &ProtocolEvent) {
+        let detail = serde_json::to_value(event).unwrap_or_default();
+        let kind = detail.get("event").and_then(|k| k.as_str()).unwrap_or("event").to_string();
+        self.push(TimelineSource::Protocol, kind, detail);
+    }
+
+    /// Enregistre une action de l’opérateur, de type `kind`.
+    pub fn record_action(&mut self, kind: &str, description: impl Into<String>) {
+        self.push(TimelineSource::User, kind.into(), serde_json::json!({ "description": description.into() }));
+    }
+
+    /// Met en forme les entrées en NDJSON.
+    pub fn to_ndjson(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entry| serde_json::to_string(entry).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Dessine le diagramme en couloirs au format SVG : un couloir par type
+    /// d’événement, dans l’ordre de première apparition, le temps en abscisse.
+    pub fn to_svg(&self) -> String {
+        let mut lanes: Vec<(TimelineSource, &str)> = Vec::new();
+        for entry in &self.entries {
+            if !lanes.contains(&(entry.source, entry.kind.as_str())) {
+                lanes.push((entry.source, &entry.kind));
+            }
+        }
+        let duration = self.entries.back().map_or(0., |e| e.elapsed_ms).max(1.);
+        let plot_width = SVG_WIDTH - SVG_LABEL_WIDTH - 20.;
+        let height = 40. + SVG_LANE_HEIGHT * lanes.len() as f64 + 30.;
+        let lane_y = |i: usize| 40. + SVG_LANE_HEIGHT * i as f64;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            SVG_WIDTH, height
+        );
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+        svg.push_str(&format!(
+            "<text x=\"10\" y=\"22\" font-size=\"16\">Chronologie de la session ({} entrées)</text>\n",
+            self.entries.len()
+        ));
+        for (i, (source, kind)) in lanes.iter().enumerate() {
+            let y = lane_y(i);
+            if i % 2 == 0 {
+                svg.push_str(&format!(
+                    "<rect x=\"0\" y=\"{:.0}\" width=\"{:.0}\" height=\"{:.0}\" fill=\"#f2f2f2\"/>\n",
+                    y, SVG_WIDTH, SVG_LANE_HEIGHT
+                ));
+            }
+            svg.push_str(&format!(
+                "<text x=\"10\" y=\"{:.0}\" fill=\"{}\">{}</text>\n",
+                y + 16.,
+                source.color(),
+                xml_escape(kind)
+            ));
+        }
+        for entry in &self.entries {
+            let Some(lane) = lanes.iter().position(|&(s, k)| s == entry.source && k == entry.kind) else { continue };
+            let x = SVG_LABEL_WIDTH + entry.elapsed_ms / duration * plot_width;
+            svg.push_str(&format!(
+                "<rect x=\"{:.1}\" y=\"{:.0}\" width=\"3\" height=\"{:.0}\" fill=\"{}\"><title>{}</title></rect>\n",
+                x - 1.5,
+                lane_y(lane) + 4.,
+                SVG_LANE_HEIGHT - 8.,
+                entry.source.color(),
+                xml_escape(&entry.time)
+            ));
+        }
+        let axis_y = lane_y(lanes.len()) + 18.;
+        svg.push_str(&format!("<text x=\"{:.0}\" y=\"{:.0}\">0 s</text>\n", SVG_LABEL_WIDTH, axis_y));
+        svg.push_str(&format!(
+            "<text x=\"{:.0}\" y=\"{:.0}\" text-anchor=\"end\">{:.1} s</text>\n",
+            SVG_LABEL_WIDTH + plot_width,
+            axis_y,
+            duration / 1000.
+        ));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Écrit la chronologie en NDJSON et en SVG à côté du chemin `base` et retourne
+    /// les chemins des fichiers écrits.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si un fichier ne peut pas être écrit.
+    pub fn export(&self, base: &Path) -> Result<[PathBuf; 2], String> {
+        let ndjson = base.with_extension("ndjson");
+        let svg = base.with_extension("svg");
+        for (path, content) in [(&ndjson, self.to_ndjson()), (&svg, self.to_svg())] {
+            fs::write(path, content).map_err(|e| format!("Écriture de {} impossible : {}", path.display(), e))?;
+        }
+        Ok([ndjson, svg])
+    }
+}
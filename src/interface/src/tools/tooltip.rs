@@ -0,0 +1,141 @@
+//! Module des modèles d’info-bulle des tâches.
+//!
+//! Par défaut, l’info-bulle d’une tâche affiche un ensemble de champs fixe. Un
+//! modèle (saisi dans le panneau latéral ou lu depuis [`TOOLTIP_PATH`]) permet à
+//! chaque équipe de choisir les champs affichés, ex.
+//! `{name}\nΔf={bandwidth} MHz\n{metadata.operator}`. Les champs disponibles sont
+//! ceux de la tâche ([`FIELDS`]) et ses métadonnées libres (`{metadata.<clé>}`) ;
+//! un champ absent est remplacé par une chaîne vide, un champ inconnu est laissé
+//! tel quel et `{{` / `}}` produisent des accolades littérales.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::tools::task::Task;
+
+/// Chemin du fichier de configuration des info-bulles.
+pub const TOOLTIP_PATH: &str = "tooltip.json";
+
+/// Champs de tâche utilisables dans un modèle.
+pub const FIELDS: [&str; 21] = [
+    "name", "id", "key", "amplifier", "group", "parent_id", "platform", "antenna", "location", "effect", "priority", "status",
+    "power", "bandwidth", "duration", "freq_start", "freq_end", "time_start", "time_end", "rx_safe", "freq_tolerance",
+];
+
+/// Configuration des info-bulles.
+#[derive(Clone, Default, Deserialize)]
+pub struct TooltipConfig {
+    /// Modèle d’info-bulle (contenu par défaut si vide).
+    #[serde(default)]
+    pub template: String,
+}
+
+/// Met en forme un nombre sans zéros superflus (au plus 3 décimales).
+fn number(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Valeur d’un champ du modèle pour la tâche, ou `None` si le champ est inconnu.
+fn field(task: &Task, name: &str) -> Option<String> {
+    if let Some(key) = name.strip_prefix("metadata.") {
+        return Some(task.metadata.get(key).cloned().unwrap_or_default());
+    }
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+    Some(match name {
+        "name" => task.name.clone(),
+        "id" => text(&task.id),
+        "key" => task.key().to_string(),
+        "amplifier" => task.amplifier.label().to_string(),
+        "group" => text(&task.group),
+        "parent_id" => text(&task.parent_id),
+        "platform" => text(&task.platform),
+        "antenna" => text(&task.antenna),
+        "location" => task.location.map(|l| l.to_string()).unwrap_or_default(),
+        "effect" => task.effect.map(|e| e.label().to_string()).unwrap_or_default(),
+        "priority" => task.priority.map(|p| p.to_string()).unwrap_or_default(),
+        "status" => text(&task.status),
+        "power" => task.power.map(number).unwrap_or_default(),
+        "bandwidth" => number(task.freq_end - task.freq_start),
+        "duration" => number(task.time_end - task.time_start),
+        "freq_start" => number(task.freq_start),
+        "freq_end" => number(task.freq_end),
+        "time_start" => number(task.time_start),
+        "time_end" => number(task.time_end),
+        "rx_safe" => if task.rx_safe { "oui" } else { "non" }.to_string(),
+        "freq_tolerance" => task.freq_tolerance.map(number).unwrap_or_default(),
+        _ => return None,
+    })
+}
+
+/// Remplit le modèle avec les champs de la tâche.
+pub fn render_template(template: &str, task: &Task) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        match (tail.starts_with('{'), tail.find('}')) {
+            (true, Some(end)) => {
+                let name = &tail[1..end];
+                match field(task, name.trim()) {
+                    Some(value) => out.push_str(&value),
+                    None => out.push_str(&tail[..=end]),
+                }
+                rest = &tail[end + 1..];
+            }
+            _ => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    // Les lignes restées vides (champs absents) sont omises
+    out.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>().join("\n")
+}
+
+/// Charge la configuration des info-bulles depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou invalide.
+pub fn load_tooltip(path: &Path) -> Result<TooltipConfig, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Configuration d’info-bulle {} invalide : {}", path.display(), e))
+}
+
+/// Charge la configuration des info-bulles, ou le contenu par défaut si le fichier
+/// est absent ou invalide.
+pub fn load_tooltip_config() -> TooltipConfig {
+    let path = Path::new(TOOLTIP_PATH);
+    if !path.exists() {
+        return TooltipConfig::default();
+    }
+    load_tooltip(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        TooltipConfig::default()
+    })
+}
+
+impl TooltipConfig {
+    /// Affiche l’éditeur du modèle dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::TextEdit::multiline(&mut self.template)
+                .hint_text("{name}\nΔf={bandwidth} MHz\n{metadata.operator}")
+                .desired_rows(3)
+                .code_editor(),
+        );
+        ui.weak("Vide : contenu par défaut")
+            .on_hover_text(format!("Champs : {}, metadata.<clé>", FIELDS.join(", ")));
+    }
+}
@@ -0,0 +1,424 @@
+//! Module du transport réseau (TCP, éventuellement chiffré en TLS).
+//!
+//! En plus de stdin, l’interface peut recevoir ses messages d’émetteurs distants
+//! sur une socket TCP (`--listen <adresse>` ou fichier [`TRANSPORT_PATH`]) : une
+//! ligne JSON par message, avec le même protocole que sur stdin.
+//!
+//! Le canal de contrôle d’un affichage de brouillage ne doit pas circuler en
+//! clair sur le réseau : avec une section `tls`, les connexions sont chiffrées
+//! (rustls) et, si une autorité `client_ca` est configurée, seuls les clients
+//! présentant un certificat signé par cette autorité sont acceptés :
+//!
+//! ```json
+//! {"listen": "0.0.0.0:7878",
+//!  "tls": {"cert": "serveur.pem", "key": "serveur.key", "client_ca": "clients_ca.pem"},
+//!  "auth": {"token": "secret partagé", "allow_read_only": true}}
+//! ```
+//!
+//! Avec une section `auth`, un émetteur distant doit présenter le secret partagé
+//! dans sa poignée de main (`{"hello": {"token": "…"}}`) avant toute commande :
+//! les commandes d’un client non authentifié sont refusées par un événement
+//! `auth_error`. Si `allow_read_only` est vrai, un client non authentifié reste
+//! connecté et reçoit le flux d’événements de l’interface (lecture seule) ;
+//! sinon sa connexion est fermée.
+//!
+//! Chaque connexion autorisée reçoit ce flux d’événements ([`Subscribers`]), une
+//! ligne JSON par événement. Chaque connexion est une source distincte,
+//! identifiée par [`connection_source`] (`tcp:<adresse du client>`) : ses
+//! messages sont soumis aux limites de débit de cette source et les réponses
+//! qu’ils provoquent (poignée de main, demande de rediffusion) ne sont envoyées
+//! qu’à elle.
+
+use std::fs;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, ServerConnection, StreamOwned};
+use serde::{Deserialize, Serialize};
+
+use crate::tools::protocol::{parse_message, read_message, IncomingMessage, ProtocolEvent};
+
+/// Chemin du fichier de configuration du transport réseau.
+pub const TRANSPORT_PATH: &str = "transport.json";
+
+/// Délai de lecture au-delà duquel les événements en attente sont envoyés au client.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Certificat, clé privée et authentification des clients (fichiers PEM).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// Chaîne de certificats du serveur.
+    pub cert: PathBuf,
+    /// Clé privée du serveur.
+    pub key: PathBuf,
+    /// Autorité de certification des clients : si elle est renseignée, les
+    /// clients doivent présenter un certificat qu’elle a signé.
+    #[serde(default)]
+    pub client_ca: Option<PathBuf>,
+}
+
+/// Authentification des émetteurs distants par secret partagé.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Secret partagé attendu dans la poignée de main.
+    pub token: String,
+    /// Autorise les clients non authentifiés à recevoir le flux d’événements.
+    #[serde(default)]
+    pub allow_read_only: bool,
+}
+
+/// Configuration du transport réseau.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransportConfig {
+    /// Adresse d’écoute (ex. `0.0.0.0:7878`) ; aucun transport réseau si absente.
+    pub listen: Option<String>,
+    /// Chiffrement TLS des connexions.
+    pub tls: Option<TlsConfig>,
+    /// Authentification des émetteurs par secret partagé.
+    pub auth: Option<AuthConfig>,
+}
+
+/// Charge la configuration du transport depuis un fichier JSON.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le fichier est illisible ou invalide.
+pub fn load_transport(path: &Path) -> Result<TransportConfig, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Lecture de {} impossible : {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Transport {} invalide : {}", path.display(), e))
+}
+
+/// Charge la configuration du transport, ou aucune écoute si le fichier est absent ou invalide.
+pub fn load_transport_config() -> TransportConfig {
+    let path = Path::new(TRANSPORT_PATH);
+    if !path.exists() {
+        return TransportConfig::default();
+    }
+    load_transport(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        TransportConfig::default()
+    })
+}
+
+/// Lit les certificats d’un fichier PEM.
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Certificats {} invalides : {}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(format!("Aucun certificat dans {}", path.display()));
+    }
+    Ok(certs)
+}
+
+/// Construit la configuration TLS du serveur.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si un fichier PEM est illisible ou si le
+/// certificat ne correspond pas à la clé.
+pub fn tls_server_config(config: &TlsConfig) -> Result<Arc<ServerConfig>, String> {
+    let certs = load_certs(&config.cert)?;
+    let key = PrivateKeyDer::from_pem_file(&config.key)
+        .map_err(|e| format!("Clé privée {} invalide : {}", config.key.display(), e))?;
+    let builder = match &config.client_ca {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(path)? {
+                roots.add(cert).map_err(|e| format!("Autorité {} invalide : {}", path.display(), e))?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .map_err(|e| format!("Authentification des clients impossible : {}", e))?;
+            ServerConfig::builder().with_client_cert_verifier(verifier)
+        }
+        None => ServerConfig::builder().with_no_client_auth(),
+    };
+    let server = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("Certificat {} invalide : {}", config.cert.display(), e))?;
+    Ok(Arc::new(server))
+}
+
+/// Connexion d’un émetteur distant, en clair ou chiffrée.
+pub enum Connection {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ServerConnection, TcpStream>>),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.read(buf),
+            Connection::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.write(buf),
+            Connection::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Plain(stream) => stream.flush(),
+            Connection::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Source des messages d’une connexion distante.
+pub fn connection_source(peer: &str) -> String {
+    format!("tcp:{}", peer)
+}
+
+/// Canaux des connexions abonnées, partagés entre les threads, avec leur source.
+type Senders = Arc<Mutex<Vec<(String, Sender<String>)>>>;
+
+/// Abonnés au flux d’événements de l’interface (connexions distantes).
+#[derive(Clone, Default)]
+pub struct Subscribers {
+    /// Canaux des connexions abonnées, avec leur source.
+    senders: Senders,
+    /// Nombre total d’abonnements depuis le lancement.
+    subscriptions: Arc<AtomicUsize>,
+}
+
+impl Subscribers {
+    /// Abonne la connexion `source` au flux d’événements.
+    pub fn subscribe(&self, source: &str) -> Receiver<String> {
+        let (sender, receiver) = channel();
+        if let Ok(mut senders) = self.senders.lock() {
+            senders.push((source.to_string(), sender));
+            self.subscriptions.fetch_add(1, Ordering::Relaxed);
+        }
+        receiver
+    }
+
+    /// Nombre total d’abonnements depuis le lancement (détection des nouvelles connexions).
+    pub fn subscriptions(&self) -> usize {
+        self.subscriptions.load(Ordering::Relaxed)
+    }
+
+    /// Diffuse un événement aux abonnés.
+    pub fn broadcast(&self, event: &ProtocolEvent) {
+        if let Ok(json) = serde_json::to_string(event) {
+            self.broadcast_json(&json);
+        }
+    }
+
+    /// Diffuse une ligne JSON aux abonnés (les connexions fermées sont oubliées).
+    pub fn broadcast_json(&self, json: &str) {
+        if let Ok(mut senders) = self.senders.lock() {
+            senders.retain(|(_, s)| s.send(json.to_string()).is_ok());
+        }
+    }
+
+    /// Envoie un événement à la seule connexion `source` et indique s’il a pu
+    /// lui être transmis.
+    pub fn send_to(&self, source: &str, event: &ProtocolEvent) -> bool {
+        let (Ok(json), Ok(mut senders)) = (serde_json::to_string(event), self.senders.lock()) else {
+            return false;
+        };
+        let mut sent = false;
+        senders.retain(|(s, sender)| {
+            if s != source {
+                return true;
+            }
+            let delivered = sender.send(json.clone()).is_ok();
+            sent |= delivered;
+            delivered
+        });
+        sent
+    }
+}
+
+/// Connexion dont les lectures bloquantes sont interrompues périodiquement pour
+/// envoyer au client les événements du flux auquel elle est abonnée ; ils sont
+/// aussi envoyés après chaque message traité, afin qu’un client qui émet sans
+/// interruption reçoive tout de même son flux.
+struct Duplex {
+    /// Connexion au client.
+    connection: Connection,
+    /// Flux d’événements, si la connexion y est abonnée.
+    events: Option<Receiver<String>>,
+}
+
+impl Duplex {
+    /// Écrit une ligne sur la connexion.
+    fn send_line(&mut self, line: &str) -> io::Result<()> {
+        self.connection.write_all(line.as_bytes())?;
+        self.connection.write_all(b"\n")?;
+        self.connection.flush()
+    }
+
+    /// Écrit un événement sur la connexion.
+    fn send(&mut self, event: &ProtocolEvent) -> io::Result<()> {
+        let json = serde_json::to_string(event).map_err(io::Error::other)?;
+        self.send_line(&json)
+    }
+
+    /// Envoie les événements du flux en attente.
+    fn flush_events(&mut self) -> io::Result<()> {
+        let pending: Vec<String> = self.events.as_ref().map(|e| e.try_iter().collect()).unwrap_or_default();
+        pending.iter().try_for_each(|line| self.send_line(line))
+    }
+}
+
+impl Read for Duplex {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.connection.read(buf) {
+                Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                    self.flush_events()?
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Compare le secret présenté au secret attendu en temps constant.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Vérifie le message d’un client non authentifié : seule une poignée de main
+/// portant le secret attendu est acceptée, toute autre commande est refusée.
+///
+/// # Erreurs
+///
+/// Retourne le motif du refus.
+fn authenticate(line: &str, auth: &AuthConfig) -> Result<(), String> {
+    match parse_message(line) {
+        Ok(IncomingMessage::Hello(message)) => match message.hello.token {
+            Some(token) if token_matches(&token, &auth.token) => Ok(()),
+            Some(_) => Err("Secret partagé invalide".into()),
+            None => Err("Secret partagé absent de la poignée de main".into()),
+        },
+        Ok(_) => Err("Émetteur non authentifié : commande refusée".into()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Démarre l’écoute du transport réseau : chaque message reçu d’un émetteur
+/// distant (authentifié, le cas échéant) est transmis à `on_message` avec la
+/// source de sa connexion ([`connection_source`]), et les
+/// événements diffusés à `subscribers` sont envoyés aux connexions autorisées.
+///
+/// Ne fait rien si aucune adresse d’écoute n’est configurée.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la configuration TLS est invalide ou si
+/// l’adresse ne peut pas être écoutée.
+pub fn spawn_listener(
+    config: &TransportConfig,
+    subscribers: Subscribers,
+    on_message: impl Fn(String, String) + Send + Sync + 'static,
+) -> Result<(), String> {
+    let Some(address) = &config.listen else {
+        return Ok(());
+    };
+    let tls = config.tls.as_ref().map(tls_server_config).transpose()?;
+    let listener = TcpListener::bind(address).map_err(|e| format!("Écoute sur {} impossible : {}", address, e))?;
+    eprintln!("Écoute des émetteurs sur {}{}.", address, if tls.is_some() { " (TLS)" } else { "" });
+    let auth = config.auth.clone();
+    let on_message = Arc::new(on_message);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Connexion refusée : {}", e);
+                    continue;
+                }
+            };
+            let peer = stream.peer_addr().map_or_else(|_| "?".to_string(), |a| a.to_string());
+            if let Err(e) = stream.set_read_timeout(Some(POLL_INTERVAL)) {
+                eprintln!("Connexion de {} impossible : {}", peer, e);
+                continue;
+            }
+            let connection = match &tls {
+                Some(tls) => match ServerConnection::new(tls.clone()) {
+                    Ok(server) => Connection::Tls(Box::new(StreamOwned::new(server, stream))),
+                    Err(e) => {
+                        eprintln!("Connexion TLS de {} impossible : {}", peer, e);
+                        continue;
+                    }
+                },
+                None => Connection::Plain(stream),
+            };
+            let (auth, subscribers, on_message) = (auth.clone(), subscribers.clone(), on_message.clone());
+            thread::spawn(move || read_connection(connection, &peer, auth.as_ref(), &subscribers, on_message.as_ref()));
+        }
+    });
+    Ok(())
+}
+
+/// Lit les messages d’une connexion jusqu’à sa fermeture, en refusant les
+/// commandes tant que le client ne s’est pas authentifié (si `auth` est configurée).
+fn read_connection(
+    connection: Connection,
+    peer: &str,
+    auth: Option<&AuthConfig>,
+    subscribers: &Subscribers,
+    on_message: &(impl Fn(String, String) + ?Sized),
+) {
+    eprintln!("Émetteur connecté : {}", peer);
+    let source = connection_source(peer);
+    let mut authenticated = auth.is_none();
+    let read_only = auth.is_some_and(|a| a.allow_read_only);
+    let events = (authenticated || read_only).then(|| subscribers.subscribe(&source));
+    let mut reader = BufReader::new(Duplex { connection, events });
+    loop {
+        match read_message(&mut reader) {
+            Ok(Some(Ok(line))) => {
+                if let (false, Some(auth)) = (authenticated, auth) {
+                    let (event, accepted) = match authenticate(&line, auth) {
+                        Ok(()) => (ProtocolEvent::Authenticated, true),
+                        Err(message) => (ProtocolEvent::AuthError { message }, false),
+                    };
+                    eprintln!("{} : {:?}", peer, event);
+                    if reader.get_mut().send(&event).is_err() || !(accepted || read_only) {
+                        break;
+                    }
+                    if !accepted {
+                        continue;
+                    }
+                    authenticated = true;
+                    reader.get_mut().events.get_or_insert_with(|| subscribers.subscribe(&source));
+                }
+                on_message(source.clone(), line);
+                if let Err(e) = reader.get_mut().flush_events() {
+                    eprintln!("Connexion de {} interrompue : {}", peer, e);
+                    break;
+                }
+            }
+            // Ligne trop longue ou illisible : ignorée, la lecture continue
+            Ok(Some(Err(e))) => eprintln!("{} : {}", peer, e),
+            Ok(None) => break,
+            // Fermeture, ou échec de la poignée de main TLS (ex. certificat client refusé)
+            Err(e) => {
+                eprintln!("Connexion de {} interrompue : {}", peer, e);
+                break;
+            }
+        }
+    }
+    eprintln!("Émetteur déconnecté : {}", peer);
+}
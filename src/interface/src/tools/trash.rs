@@ -0,0 +1,142 @@
+//! Module de la corbeille des tâches supprimées.
+//!
+//! Une tâche supprimée, depuis l’interface (menu contextuel, corrections) ou par
+//! le protocole (`remove_where`, plan complet rediffusé sans elle), n’est pas
+//! perdue : elle est placée dans la corbeille, d’où elle peut être restaurée dans
+//! le plan ou purgée définitivement. Les tâches y sont conservées pendant la
+//! durée de rétention réglable dans le panneau latéral, ce qui protège les
+//! affectations critiques d’une suppression accidentelle.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::tools::task::Task;
+
+/// Durée de rétention par défaut, en minutes.
+pub const DEFAULT_RETENTION_MIN: u32 = 60;
+
+/// Nombre maximal de tâches conservées (les plus anciennes sont purgées).
+const MAX_TRASHED: usize = 10_000;
+
+/// Tâche placée dans la corbeille.
+#[derive(Clone, Debug)]
+pub struct TrashedTask {
+    /// Tâche supprimée.
+    pub task: Task,
+    /// Instant de la suppression.
+    pub deleted: Instant,
+    /// Heure UTC de la suppression (`HH:MM:SS`).
+    pub time: String,
+    /// Origine de la suppression.
+    pub source: String,
+}
+
+/// Corbeille des tâches supprimées.
+pub struct Trash {
+    /// Durée de rétention en minutes (0 : conservation illimitée).
+    pub retention_min: u32,
+    /// Tâches supprimées, de la plus ancienne à la plus récente.
+    items: Vec<TrashedTask>,
+}
+
+impl Default for Trash {
+    fn default() -> Self {
+        Self { retention_min: DEFAULT_RETENTION_MIN, items: Vec::new() }
+    }
+}
+
+/// Tâches de `old` absentes de `new` (appariées par leur clé).
+pub fn removed_tasks(old: &[Task], new: &[Task]) -> Vec<Task> {
+    let kept: HashSet<&str> = new.iter().map(Task::key).collect();
+    old.iter().filter(|t| !kept.contains(t.key())).cloned().collect()
+}
+
+impl Trash {
+    /// Place des tâches supprimées par `source` dans la corbeille et retourne leur nombre.
+    pub fn put(&mut self, tasks: Vec<Task>, source: &str, now: Instant) -> usize {
+        let count = tasks.len();
+        let time = chrono::Utc::now().format("%H:%M:%S").to_string();
+        self.items.extend(tasks.into_iter().map(|task| TrashedTask { task, deleted: now, time: time.clone(), source: source.into() }));
+        if self.items.len() > MAX_TRASHED {
+            self.items.drain(..self.items.len() - MAX_TRASHED);
+        }
+        if count > 0 {
+            eprintln!("Corbeille : {} tâche(s) supprimée(s) ({}).", count, source);
+        }
+        count
+    }
+
+    /// Purge les tâches dont la durée de rétention est écoulée.
+    pub fn expire(&mut self, now: Instant) {
+        if self.retention_min == 0 {
+            return;
+        }
+        let retention = Duration::from_secs(u64::from(self.retention_min) * 60);
+        self.items.retain(|item| now.duration_since(item.deleted) < retention);
+    }
+
+    /// Retire de la corbeille la tâche d’indice `index` pour la restaurer dans `tasks`.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si l’indice est invalide ou si une tâche de
+    /// même clé figure déjà dans le plan.
+    pub fn restore(&mut self, index: usize, tasks: &[Task]) -> Result<Task, String> {
+        let item = self.items.get(index).ok_or("Tâche absente de la corbeille")?;
+        if tasks.iter().any(|t| t.key() == item.task.key()) {
+            return Err(format!("Restauration impossible : une tâche « {} » figure déjà dans le plan", item.task.key()));
+        }
+        Ok(self.items.remove(index).task)
+    }
+
+    /// Nombre de tâches dans la corbeille.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Indique si la corbeille est vide.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Affiche la corbeille dans le panneau latéral et retourne l’indice de la
+    /// tâche à restaurer. Les actions sont désactivées si `editable` est faux.
+    pub fn show(&mut self, ui: &mut egui::Ui, editable: bool) -> Option<usize> {
+        ui.horizontal(|ui| {
+            ui.label("Rétention :");
+            ui.add(egui::DragValue::new(&mut self.retention_min).range(0..=10_080).suffix(" min"))
+                .on_hover_text("0 : conservation illimitée");
+        });
+        if self.items.is_empty() {
+            ui.weak("Corbeille vide");
+            return None;
+        }
+        let mut restored = None;
+        let mut purged = None;
+        egui::ScrollArea::vertical().max_height(200.).id_salt("trash").show(ui, |ui| {
+            for (i, item) in self.items.iter().enumerate().rev() {
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(editable, |ui| {
+                        if ui.small_button("↺").on_hover_text("Restaurer dans le plan").clicked() {
+                            restored = Some(i);
+                        }
+                        if ui.small_button("🗑").on_hover_text("Purger définitivement").clicked() {
+                            purged = Some(i);
+                        }
+                    });
+                    ui.label(&item.task.name);
+                    ui.weak(format!("{} ({})", item.time, item.source));
+                });
+            }
+        });
+        if let Some(i) = purged {
+            self.items.remove(i);
+        }
+        ui.add_enabled_ui(editable, |ui| {
+            if ui.button("Vider la corbeille").clicked() {
+                self.items.clear();
+            }
+        });
+        restored
+    }
+}
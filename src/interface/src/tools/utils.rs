@@ -1,5 +1,5 @@
 //! Module utilitaire contenant les constantes de base pour les fréquences
-//! et le temps, ainsi que la fonction d'obtention des bornes X.
+//! et le temps, la fonction d'obtention des bornes X et la mise en forme des fréquences.
 //!
 //! Ce module est utilisé par l'application principale pour déterminer
 //! les limites d'affichage du graphe (fréquence en échelle linéaire ou logarithmique).
@@ -10,18 +10,26 @@ pub const MIN_FREQ: f64 = 20.0;
 pub const MAX_FREQ: f64 = 6000.0;
 /// Temps maximal en millisecondes pour les tâches.
 pub const MAX_TIME: f64 = 1000.0;
+/// Étendue fréquentielle minimale de la vue en MHz (zoom maximal, 1 kHz).
+pub const MIN_FREQ_SPAN: f64 = 0.001;
+/// Étendue minimale de la vue en échelle logarithmique (décades).
+pub const MIN_LOG_SPAN: f64 = 1e-7;
+/// Étendue temporelle minimale de la vue en ms (zoom maximal, 1 µs).
+pub const MIN_TIME_SPAN: f64 = 0.001;
+/// Marge autorisée autour du domaine valide lors des déplacements, en fraction du domaine.
+pub const VIEW_MARGIN: f64 = 0.05;
 
 /// Renvoie les bornes de l'axe X selon l'échelle choisie.
 ///
 /// # Paramètres
 ///
 /// - `log`: si `true`, retourne les bornes en log10 (comprend `MIN_FREQ.log10()` et `MAX_FREQ.log10()`).
-///          sinon, retourne simplement `(MIN_FREQ, MAX_FREQ)`.
+///   sinon, retourne simplement `(MIN_FREQ, MAX_FREQ)`.
 ///
 /// # Exemples
 ///
 /// ```
-/// use crate::utils::{get_bounds, MIN_FREQ, MAX_FREQ};
+/// use egui_test::tools::utils::{get_bounds, MIN_FREQ, MAX_FREQ};
 ///
 /// assert_eq!(get_bounds(false), (MIN_FREQ, MAX_FREQ));
 /// assert_eq!(get_bounds(true), (MIN_FREQ.log10(), MAX_FREQ.log10()));
@@ -33,3 +41,57 @@ pub fn get_bounds(log: bool) -> (f64, f64) {
         (MIN_FREQ, MAX_FREQ)
     }
 }
+
+/// Indique si la fréquence `freq` (MHz) est représentable en échelle
+/// logarithmique, c'est-à-dire finie et strictement positive.
+pub fn is_log_safe(freq: f64) -> bool {
+    freq.is_finite() && freq > 0.
+}
+
+/// Abscisse de la fréquence `freq` (MHz) dans le repère du graphe : son log10 si
+/// `log`, sinon la fréquence elle-même.
+///
+/// En échelle logarithmique, une fréquence nulle, négative ou non finie est
+/// ramenée à [`MIN_FREQ`] au lieu de produire une abscisse infinie ou NaN.
+pub fn freq_to_x(freq: f64, log: bool) -> f64 {
+    match (log, is_log_safe(freq)) {
+        (false, _) => freq,
+        (true, true) => freq.log10(),
+        (true, false) => MIN_FREQ.log10(),
+    }
+}
+
+/// Nombre de décimales (en MHz) nécessaires pour distinguer des fréquences sur une
+/// étendue de `span` MHz : environ trois chiffres significatifs, jusqu'au Hz.
+pub fn freq_decimals(span: f64) -> usize {
+    if span.is_nan() || span <= 0. {
+        return 0;
+    }
+    ((2. - span.log10()).ceil().max(0.) as usize).min(6)
+}
+
+/// Met en forme une fréquence en MHz avec une précision adaptée à l'étendue `span` (MHz),
+/// ex. `1200 MHz` en vue globale et `1200.0125 MHz` sur un canal de 25 kHz.
+pub fn format_freq(freq: f64, span: f64) -> String {
+    format!("{:.*} MHz", freq_decimals(span), freq)
+}
+
+/// Met en forme une largeur de bande, en kHz sous 1 MHz, ex. `12.5 kHz` ou `1.5 MHz`.
+pub fn format_bandwidth(width: f64) -> String {
+    let (value, unit) = if width < 1. { (width * 1000., "kHz") } else { (width, "MHz") };
+    let text = format!("{:.3}", value);
+    format!("{} {}", text.trim_end_matches('0').trim_end_matches('.'), unit)
+}
+
+/// Limite un intervalle de vue `(min, max)` au domaine `[domain_min, domain_max]`
+/// élargi de [`VIEW_MARGIN`], avec une étendue d'au moins `min_span`.
+///
+/// L'étendue est d'abord bornée (zoom minimal et maximal), puis l'intervalle est
+/// décalé pour rester dans le domaine, sans modifier le niveau de zoom.
+pub fn clamp_range(min: f64, max: f64, domain_min: f64, domain_max: f64, min_span: f64) -> (f64, f64) {
+    let margin = (domain_max - domain_min) * VIEW_MARGIN;
+    let (lower, upper) = (domain_min - margin, domain_max + margin);
+    let span = (max - min).clamp(min_span, upper - lower);
+    let center = ((min + max) / 2.).clamp(lower + span / 2., upper - span / 2.);
+    (center - span / 2., center + span / 2.)
+}
@@ -0,0 +1,579 @@
+//! Module du graphe principal.
+//!
+//! Le graphe principal dessine le fond de la mission (phases, bandes cibles, zones,
+//! garde Rx, règle des harmoniques) avec egui_plot, puis les tâches en coordonnées
+//! écran : maillage coloré, contours d'état, couches, détections, écarts à la
+//! référence, corrections proposées, masques d'exclusion et annotations. Il traite
+//! aussi les clics sur les tâches (inspecteur, sélection, épinglage, menu contextuel)
+//! et le placement des tâches par glisser-déposer (Ctrl + glisser).
+
+use eframe::egui;
+use egui::{Color32, RichText, Stroke};
+use egui_plot::{log_grid_spacer, uniform_grid_spacer, GridMark, Line, LineStyle, Plot, PlotBounds, PlotPoint, PlotPoints, Polygon, Text};
+
+use crate::tools::background::{get_background_zones, BackgroundZoneKind};
+use crate::tools::effect::BORDER_COLOR;
+use crate::tools::events::{AppEvent, PlanEvent, UiEvent};
+use crate::tools::guides::{find_guides, guide_shapes, snap};
+use crate::tools::placement::{check_placement, new_task_name};
+use crate::tools::presets::{PresetRole, TARGET_COLOR};
+use crate::tools::repair::{apply_fixes, Fix};
+use crate::tools::split::{join_candidates, SplitAxis};
+use crate::tools::task::Task;
+use crate::tools::task_mesh::{hit_extent, lod_mesh, screen_outline, task_mesh};
+use crate::tools::utils::{format_freq, freq_to_x, get_bounds, MAX_FREQ, MAX_TIME, MIN_FREQ, MIN_TIME_SPAN};
+use crate::tools::view::paint::{paint_brackets, paint_exclusion, paint_tolerance_halos, plot_headroom, plot_phases, plot_rx_guard};
+use crate::tools::view::{clamp_view, handle_wheel, in_viewport, task_hit, FrameView, PlotScene, PlotTools};
+
+/// État de la vue du graphe principal : bornes courantes et forcées, zoom par
+/// bande ou par système, menu contextuel.
+pub struct MainPlot {
+    /// Limites actuelles de la vue en X (bande fréquentielle).
+    pub bounds_x: Option<(f64, f64)>,
+    /// Dernière valeur connue des limites X (pour détection de changement).
+    last_bounds_x: Option<(f64, f64)>,
+    /// Limites actuelles de la vue en temps (ms, couloirs compris).
+    pub bounds_y: Option<(f64, f64)>,
+    /// Étendue temporelle visible en ms (précision des info-bulles).
+    pub time_span: f64,
+    /// Étendue minimale de détection `[largeur, durée]` d'une tâche, en unités du
+    /// graphe.
+    pub hit_extent: [f64; 2],
+    /// Si défini, force l'application de limites X spécifiques.
+    pub force_bounds_x: Option<(f64, f64)>,
+    /// Bornes `[x_min, temps_min, x_max, temps_max]` de la vue.
+    pub view_bounds: Option<[f64; 4]>,
+    /// Si défini, force la vue (zoom sur une période, instance suiveuse).
+    pub force_view: Option<[f64; 4]>,
+    /// Cadre du graphe lors de la dernière image (découpage des captures).
+    pub rect: Option<egui::Rect>,
+    /// Indice de la bande d'amplification actuellement zoomée (si zoom actif).
+    pub zoom_band: Option<usize>,
+    /// Indice du système actuellement zoomé (si zoom actif).
+    pub zoom_system: Option<usize>,
+    /// Tâche du menu contextuel : indice, fréquence (MHz) et temps (ms) du clic droit.
+    context_task: Option<(usize, f64, f64)>,
+}
+
+impl Default for MainPlot {
+    fn default() -> Self {
+        Self {
+            bounds_x: Some(get_bounds(false)),
+            last_bounds_x: Some((0., 1.)),
+            bounds_y: None,
+            time_span: MAX_TIME,
+            hit_extent: [0., 0.],
+            force_bounds_x: Some(get_bounds(false)),
+            view_bounds: None,
+            force_view: None,
+            rect: None,
+            zoom_band: None,
+            zoom_system: None,
+            context_task: None,
+        }
+    }
+}
+
+impl MainPlot {
+    /// Étendue fréquentielle visible en MHz.
+    pub fn freq_span(&self, log: bool) -> f64 {
+        match self.bounds_x {
+            Some((min, max)) if log => 10f64.powf(max) - 10f64.powf(min),
+            Some((min, max)) => max - min,
+            None => MAX_FREQ - MIN_FREQ,
+        }
+    }
+
+    /// Annule le zoom et revient sur toute la plage de fréquences de l'échelle.
+    pub fn reset_zoom(&mut self, log: bool) {
+        self.zoom_band = None;
+        self.zoom_system = None;
+        self.force_bounds_x = Some(get_bounds(log));
+    }
+
+    /// Amène la vue sur la région `[freq_start, time_start, freq_end, time_end]`,
+    /// avec une marge temporelle.
+    pub fn focus_region(&mut self, [freq_start, time_start, freq_end, time_end]: [f64; 4], log: bool) {
+        let margin = ((time_end - time_start) * 0.1).max(MIN_TIME_SPAN);
+        let (xmin, xmax) = (freq_to_x(freq_start, log), freq_to_x(freq_end, log));
+        self.zoom_band = None;
+        self.zoom_system = None;
+        self.force_view = Some([xmin, time_start - margin, xmax, time_end + margin]);
+    }
+
+    /// Indique, pour chaque tâche dessinée, si elle est affichée (`shown`) et
+    /// recoupe la vue courante.
+    pub fn visible_tasks(&self, display_tasks: &[Task], shown: &[bool], log: bool) -> Vec<bool> {
+        match (self.bounds_x, self.bounds_y) {
+            (Some(x_range), Some(time_range)) => display_tasks
+                .iter()
+                .zip(shown)
+                .map(|(task, &shown)| shown && in_viewport(task, log, x_range, time_range, self.hit_extent))
+                .collect(),
+            _ => shown.to_vec(),
+        }
+    }
+
+    /// Affiche le graphe principal sur une hauteur de `height` points et retourne
+    /// le point survolé et les actions demandées (inspecteur, sélection, épinglage,
+    /// modifications du plan).
+    pub fn show(&mut self, ui: &mut egui::Ui, frame: &FrameView, scene: &PlotScene, tools: &mut PlotTools, height: f32) -> (Option<PlotPoint>, Vec<AppEvent>) {
+        let (time_display, log) = (scene.time_display, scene.log_scale);
+        let tasks = scene.tasks;
+        let mut events: Vec<AppEvent> = Vec::new();
+        // Espacement logarithmique des grilles en mode logarithmique, sinon uniforme ;
+        // sur une bande étroite (jusqu'au kHz), le pas uniforme suit l'étendue visible
+        let spacer = if log {
+            log_grid_spacer(10)
+        } else {
+            uniform_grid_spacer(|input| {
+                let step = 10f64.powf((input.bounds.1 - input.bounds.0).log10().floor() - 1.);
+                if step >= 100. { [100.0, 500.0, 1000.0] } else { [step, step * 5., step * 10.] }
+            })
+        };
+        let formatter = move |mark: GridMark, range: &std::ops::RangeInclusive<f64>| {
+            if log {
+                format_freq(10f64.powf(mark.value), 10f64.powf(*range.end()) - 10f64.powf(*range.start()))
+            } else {
+                format_freq(mark.value, range.end() - range.start())
+            }
+        };
+
+        let local_time = frame.local_time();
+        let time_max = frame.time_max;
+        let in_lanes = frame.lanes.is_some();
+        let shown = &frame.shown;
+        let display_tasks = frame.display_tasks(tasks);
+
+        // Règle des harmoniques : tâche sélectionnée, ou à défaut dernière fréquence cliquée
+        let harmonic_markers = if tools.harmonics.enabled {
+            let selected = scene.selected.and_then(|i| tasks.get(i));
+            let source = selected.map(|t| (t.freq_start, t.freq_end)).or(tools.harmonics.clicked.map(|f| (f, f)));
+            source.map_or_else(Vec::new, |(start, end)| tools.harmonics.markers(start, end))
+        } else {
+            Vec::new()
+        };
+
+        let (response, hovered) = ui.allocate_ui(egui::vec2(ui.available_width(), height), |ui| {
+            let mut plot = Plot::new("main")
+                .link_axis("shared_x", [true, false])
+                .x_axis_formatter(formatter)
+                .y_axis_formatter(move |y, range| time_display.format_at(local_time(y.value), range.end() - range.start()))
+                .include_y(0.0)
+                .include_y(time_max)
+                .x_grid_spacer(spacer)
+                .show_grid([false, false])
+                .allow_zoom(false)
+                .allow_scroll(false)
+                .allow_drag(!tools.markups.is_active() && !ui.input(|i| i.modifiers.command) && !tools.task_drag.is_active())
+                .label_formatter(|_, _| String::new());
+
+            // Si le mode logarithmique est activé, on utilise un espacement logarithmique pour l'axe X
+            if let Some((xmin, xmax)) = self.force_bounds_x.take() {
+                plot = plot.default_x_bounds(xmin, xmax);
+            }
+
+            // Affichage du graphe principal
+            let mut clicked_at = None;
+            let plot_response = plot.show(ui, |plot_ui| {
+                if plot_ui.response().clicked() {
+                    clicked_at = plot_ui.pointer_coordinate();
+                }
+                if let Some([x_min, y_min, x_max, y_max]) = self.force_view.take() {
+                    plot_ui.set_plot_bounds(PlotBounds::from_min_max([x_min, y_min], [x_max, y_max]));
+                }
+                clamp_view(plot_ui, log, time_max);
+                handle_wheel(plot_ui);
+
+                // Mur d'images : la vue en fréquence reste sur la plage de l'instance
+                if let Some(tile) = scene.wall {
+                    let (x_min, x_max) = tile.x_bounds(log);
+                    let bounds = plot_ui.plot_bounds();
+                    if (bounds.min()[0], bounds.max()[0]) != (x_min, x_max) {
+                        plot_ui.set_plot_bounds(PlotBounds::from_min_max([x_min, bounds.min()[1]], [x_max, bounds.max()[1]]));
+                    }
+                }
+
+                let bounds = plot_ui.plot_bounds();
+                self.view_bounds = Some([bounds.min()[0], bounds.min()[1], bounds.max()[0], bounds.max()[1]]);
+                let new_bounds_x = (bounds.min()[0], bounds.max()[0]);
+                if self.last_bounds_x != Some(new_bounds_x) {
+                    self.bounds_x = Some(new_bounds_x);
+                    self.last_bounds_x = Some(new_bounds_x);
+                }
+                self.bounds_y = Some((bounds.min()[1], bounds.max()[1]));
+                self.time_span = bounds.height();
+                self.hit_extent = hit_extent(&bounds, plot_ui.response().rect);
+
+                let zones = get_background_zones(scene.zones, &scene.rx_guard);
+                let (x_min, x_max) = get_bounds(log);
+                // Libellés masqués en mode dégradé
+                let labels = !frame.degraded;
+                for &(offset, ref lane) in &frame.lane_offsets {
+                    // Bandes des phases de mission, étiquetées sur le bord droit de la vue
+                    plot_phases(plot_ui, scene.phases, log, offset, labels.then(|| bounds.max()[0]));
+
+                    // Bandes de référence cibles, surlignées sur toute la mission
+                    for preset in scene.presets.with_role(PresetRole::Target) {
+                        let (start, end) = if log { (preset.freq_start.log10(), preset.freq_end.log10()) } else { (preset.freq_start, preset.freq_end) };
+                        let top = offset + MAX_TIME;
+                        plot_ui.polygon(Polygon::new("preset", PlotPoints::from(vec![[start, offset], [end, offset], [end, top], [start, top]]))
+                            .fill_color(TARGET_COLOR)
+                            .stroke(Stroke::new(1., TARGET_COLOR.to_opaque())));
+                        if labels {
+                            plot_ui.text(Text::new("preset", PlotPoint::new(start, top), RichText::new(&preset.name).color(TARGET_COLOR.to_opaque()))
+                                .anchor(egui::Align2::LEFT_TOP));
+                        }
+                    }
+
+                    // Affichage des zones de fond
+                    for zone in zones.iter().filter(|z| scene.band_filter.shows_zone(z)) {
+                        let area: Vec<[f64; 2]> = zone.area.iter()
+                            .map(|[x, y]| [if log { x.log10() } else { *x }, y + offset])
+                            .collect();
+
+                        plot_ui.polygon(Polygon::new("zone", PlotPoints::from(area))
+                            .fill_color(zone.fill)
+                            .stroke(zone.stroke));
+
+                        // Marges de garde estompées à l'intérieur des bords de bande
+                        if let (BackgroundZoneKind::Amplifier(amp), true) = (zone.kind, scene.headroom.enabled) {
+                            let (t0, t1) = zone.area.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p[1]), hi.max(p[1])));
+                            plot_headroom(plot_ui, scene.headroom, amp, log, (t0 + offset, t1 + offset), zone.stroke.color);
+                        }
+
+                        if let Some((text, pos, color)) = zone.label.as_ref().filter(|_| labels) {
+                            let x = if log { pos[0].log10() } else { pos[0] };
+                            plot_ui.text(Text::new(text.clone(), PlotPoint::new(x, pos[1] + offset), RichText::new(text).color(*color)));
+                        }
+                    }
+
+                    // Affichage de la ligne horizontale pour la limite de temps
+                    let end = offset + MAX_TIME;
+                    plot_ui.line(Line::new("hline", PlotPoints::from(vec![[x_min, end], [x_max, end]])).stroke(Stroke::new(1.0, Color32::GRAY)));
+
+                    // Nom de la plateforme du couloir
+                    if in_lanes && labels {
+                        plot_ui.text(Text::new("lane", PlotPoint::new(x_min, end), RichText::new(lane).strong())
+                            .anchor(egui::Align2::LEFT_BOTTOM));
+                    }
+
+                    // Marqueur de fin de la garde Rx
+                    plot_rx_guard(plot_ui, log, offset + scene.rx_guard.duration, labels);
+                }
+
+                // Règle des harmoniques, sur toute la hauteur du graphe
+                let to_x = |f: f64| if log { f.max(MIN_FREQ).log10() } else { f };
+                for marker in &harmonic_markers {
+                    let (start, end) = (to_x(marker.start), to_x(marker.end));
+                    if marker.start == marker.end {
+                        plot_ui.line(Line::new("harmonic", PlotPoints::from(vec![[start, 0.], [start, time_max]]))
+                            .stroke(Stroke::new(1.5, marker.color))
+                            .style(LineStyle::dashed_dense()));
+                    } else {
+                        plot_ui.polygon(Polygon::new("harmonic", PlotPoints::from(vec![[start, 0.], [end, 0.], [end, time_max], [start, time_max]]))
+                            .fill_color(marker.color.gamma_multiply(0.15))
+                            .stroke(Stroke::new(1., marker.color)));
+                    }
+                    if labels {
+                        plot_ui.text(Text::new("harmonic", PlotPoint::new((start + end) / 2., time_max), RichText::new(&marker.label).color(marker.color))
+                            .anchor(egui::Align2::CENTER_TOP));
+                    }
+                }
+            });
+
+            // Tâches : maillage unique en coordonnées écran, tirets autour des tâches provisoires,
+            // bordures selon l'effet recherché, puis contours rouges si violation du cycle de
+            // service ou de la garde Rx, blancs si sélectionnée
+            let transform = plot_response.transform;
+            self.rect = Some(plot_response.response.rect);
+            let painter = ui.painter_at(*transform.frame());
+            // Seules les tâches affichées et dans la vue sont converties en coordonnées écran
+            let in_view = self.visible_tasks(display_tasks, shown, log);
+            // Les tâches parentes sont dessinées en crochets englobant leurs descendantes
+            let filled: Vec<bool> = in_view.iter().enumerate().map(|(i, &v)| v && !frame.hierarchy.is_parent(i)).collect();
+            let visible = display_tasks.iter().zip(frame.colors.colors.iter().copied())
+                .zip(&filled).filter(|(_, s)| **s).map(|(tc, _)| tc);
+            // Halos estompés des tolérances en fréquence (incertitude d'accord, Doppler)
+            let halos = display_tasks.iter().zip(frame.colors.colors.iter().copied()).zip(&filled).filter(|(_, s)| **s).map(|(tc, _)| tc);
+            paint_tolerance_halos(&painter, &transform, log, halos);
+            painter.add(task_mesh(frame.fading.iter().map(|(task, color)| (task, *color)), &transform, log));
+            painter.add(if frame.degraded {
+                lod_mesh(visible, &transform, log)
+            } else {
+                task_mesh(visible, &transform, log)
+            });
+            paint_brackets(&painter, &transform, log, display_tasks, &frame.hierarchy, &frame.colors.colors, shown);
+            for (task, color) in display_tasks.iter().zip(&frame.colors.colors).zip(&filled).filter(|(_, s)| **s).map(|(tc, _)| tc) {
+                if task.tentative {
+                    let mut outline = screen_outline(task, &transform, log);
+                    outline.push(outline[0]);
+                    painter.extend(egui::Shape::dashed_line(&outline, Stroke::new(1.5, color.to_opaque()), 5., 4.));
+                }
+            }
+            for (task, effect) in display_tasks.iter().zip(&filled).filter(|(_, s)| **s).filter_map(|(t, _)| Some((t, t.effect?))) {
+                painter.extend(effect.border(&screen_outline(task, &transform, log), BORDER_COLOR));
+            }
+
+            // Couches exécutée (contours) et observée (marqueurs), dans les mêmes couloirs
+            scene.layers.paint(&painter, &transform, log, |tasks| frame.layout(tasks));
+
+            // Détections ESM, estompées depuis leur réception
+            scene.detections.paint(&painter, &transform, log, &frame.offsets(), frame.now);
+
+            // Tâches mises à jour par l'émetteur : l'ancien contour s'efface et un halo
+            // entoure la nouvelle position (animations suspendues en mode dégradé)
+            for (update, fade) in scene.update_animations.active(frame.now).filter(|_| !frame.degraded) {
+                let previous = frame.layout(std::slice::from_ref(&update.previous)).remove(0);
+                let ghost = Stroke::new(1.5, Color32::from_gray(200).gamma_multiply(fade));
+                painter.add(egui::Shape::closed_line(screen_outline(&previous, &transform, log), ghost));
+                if let Some(i) = tasks.iter().position(|t| t.key() == update.key).filter(|&i| in_view[i]) {
+                    let halo = Stroke::new(1. + 3. * fade, Color32::YELLOW.gamma_multiply(fade));
+                    painter.add(egui::Shape::closed_line(screen_outline(&display_tasks[i], &transform, log), halo));
+                }
+            }
+
+            // Tâches mises en évidence par un contrôleur externe : contour clignotant
+            for key in scene.highlights.lit(frame.now) {
+                if let Some(i) = tasks.iter().position(|t| t.key() == key).filter(|&i| shown[i]) {
+                    let outline = screen_outline(&display_tasks[i], &transform, log);
+                    painter.add(egui::Shape::closed_line(outline, Stroke::new(4., Color32::from_rgb(255, 140, 0))));
+                }
+            }
+
+            let selected = scene.selected;
+            for (i, task) in display_tasks.iter().enumerate() {
+                if !in_view[i] {
+                    continue;
+                }
+                let stroke = if frame.analysis.violations.iter().any(|v| v.task_index == i)
+                    || frame.analysis.rx_violations.contains(&i)
+                    || frame.analysis.antenna_violations.iter().any(|v| v.task_index == i)
+                    || log && frame.analysis.invalid_freqs.contains(&i)
+                {
+                    Stroke::new(2., Color32::RED)
+                } else if frame.headroom.iter().any(|w| w.task_index == i)
+                    || frame.range_shortfalls.iter().any(|s| s.task_index == i)
+                {
+                    Stroke::new(2., Color32::from_rgb(255, 170, 0))
+                } else if selected == Some(i) {
+                    Stroke::new(2., Color32::WHITE)
+                } else if scene.selection.contains(&tasks[i]) {
+                    Stroke::new(2., Color32::from_rgb(255, 220, 0))
+                } else {
+                    continue;
+                };
+                painter.add(egui::Shape::closed_line(screen_outline(task, &transform, log), stroke));
+            }
+
+            // Écarts par rapport à la référence : contour fantôme à la position d'origine
+            // des tâches déplacées, badge « + » sur les nouvelles tâches
+            if scene.baseline.is_set() {
+                for (i, task) in tasks.iter().enumerate() {
+                    if !shown[i] {
+                        continue;
+                    }
+                    if let Some(original) = scene.baseline.moved_from(task) {
+                        let mut ghost = original.clone();
+                        ghost.shift_time(display_tasks[i].time_start - task.time_start);
+                        let mut outline = screen_outline(&ghost, &transform, log);
+                        outline.push(outline[0]);
+                        painter.extend(egui::Shape::dashed_line(&outline, Stroke::new(1., Color32::from_gray(170)), 3., 3.));
+                    } else if scene.baseline.is_new(task) {
+                        let outline = screen_outline(&display_tasks[i], &transform, log);
+                        let corner = outline.iter().fold(outline[0], |a, p| a.min(*p));
+                        painter.text(corner + egui::vec2(2., 0.), egui::Align2::LEFT_TOP, "+", egui::FontId::proportional(14.), Color32::WHITE);
+                    }
+                }
+            }
+
+            // Prévisualisation des corrections : contours pointillés aux positions corrigées
+            if let Some(fixes) = scene.repair_preview {
+                let repaired = frame.layout(&apply_fixes(tasks, fixes));
+                for fix in fixes {
+                    let (Fix::Shift { task_index, .. } | Fix::Reassign { task_index, .. }) = *fix;
+                    let task = &repaired[task_index];
+                    let mut outline = screen_outline(task, &transform, log);
+                    outline.push(outline[0]);
+                    painter.extend(egui::Shape::dashed_line(&outline, Stroke::new(1.5, task.color()), 4., 3.));
+                }
+            }
+
+            // Masques d'exclusion : hachures dessinées en coordonnées écran par-dessus le graphe
+            for (mask, &(offset, _)) in frame.masks.iter().flat_map(|m| frame.lane_offsets.iter().map(move |l| (m, l))) {
+                paint_exclusion(&painter, &transform, log, mask, offset);
+            }
+
+            // Annotations : tracé avec l'outil sélectionné (en MHz, quelle que soit
+            // l'échelle), puis affichage
+            let to_screen = |[f, t]: [f64; 2]| transform.position_from_point(&PlotPoint::new(freq_to_x(f, log), t));
+            if tools.markups.is_active() {
+                let response = &plot_response.response;
+                let pos = response.interact_pointer_pos().or(response.hover_pos());
+                let point = pos.map(|p| {
+                    let value = transform.value_from_position(p);
+                    [if log { 10f64.powf(value.x) } else { value.x }, value.y]
+                });
+                tools.markups.handle_drag(response, point, pos, to_screen);
+            }
+            for markup in tools.markups.all() {
+                markup.paint(&painter, to_screen);
+            }
+
+            // Fréquence cliquée, source de la règle des harmoniques
+            if let Some(pt) = clicked_at.filter(|_| tools.harmonics.enabled && !tools.markups.is_active()) {
+                tools.harmonics.clicked = Some(if log { 10f64.powf(pt.x) } else { pt.x });
+            }
+
+            // Sélection de la tâche cliquée pour l'inspecteur, ajout / retrait de la
+            // sélection multiple avec Maj, ou épinglage de sa fiche avec Alt (sauf en
+            // mode kiosque ou pendant l'annotation)
+            if let Some(pt) = clicked_at.filter(|_| scene.dialogs && !tools.markups.is_active()) {
+                // Tâches sous le clic, de la plus haute (dessinée en dernier) à la plus basse
+                let hits: Vec<usize> = (0..tasks.len()).rev().filter(|&i| {
+                    shown[i] && task_hit(&display_tasks[i], log, pt.x, pt.y, self.hit_extent)
+                }).collect();
+                let modifiers = ui.input(|i| i.modifiers);
+                tools.hit_stack.observe(tasks, &hits);
+                let hit = tools.hit_stack.current(&hits);
+                if modifiers.shift {
+                    if let Some(i) = hit {
+                        events.push(UiEvent::ToggleSelection(i).into());
+                    }
+                } else if modifiers.alt {
+                    if let Some(i) = hit {
+                        events.push(UiEvent::Pin(i).into());
+                    }
+                } else {
+                    events.push(UiEvent::Inspect(tools.hit_stack.click(tasks, &hits)).into());
+                }
+            }
+
+            // Placement par glisser-déposer : Ctrl + glisser déplace la tâche saisie ou
+            // trace une nouvelle tâche ; l'aperçu est vérifié et teinté en continu
+            let response = &plot_response.response;
+            if scene.editable && !tools.markups.is_active() {
+                let point = response.interact_pointer_pos().map(|pos| transform.value_from_position(pos));
+                let to_freq = |x: f64| if log { 10f64.powf(x) } else { x };
+                if let (true, true, Some(pt)) = (response.drag_started(), ui.input(|i| i.modifiers.command), point) {
+                    let hit = (0..tasks.len()).rev().find(|&i| {
+                        shown[i] && !frame.hierarchy.is_parent(i) && task_hit(&display_tasks[i], log, pt.x, pt.y, self.hit_extent)
+                    });
+                    match hit {
+                        Some(i) => {
+                            let lane_shift = display_tasks[i].time_start - tasks[i].time_start;
+                            tools.task_drag.start_move(i, &tasks[i], [to_freq(pt.x), pt.y - lane_shift]);
+                        }
+                        None => tools.task_drag.start_create([to_freq(pt.x), pt.y]),
+                    }
+                }
+                let lane_shift = tools.task_drag.moved_index()
+                    .filter(|&i| i < tasks.len())
+                    .map_or(0., |i| display_tasks[i].time_start - tasks[i].time_start);
+                if tools.task_drag.is_active() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    tools.task_drag.cancel();
+                } else if let Some(pt) = point.filter(|_| tools.task_drag.is_active() && response.dragged()) {
+                    tools.task_drag.update([to_freq(pt.x), pt.y - lane_shift], || new_task_name(tasks));
+                    // Guides vers les repères voisins, aimantation (sauf avec Alt), puis vérification
+                    let moved = tools.task_drag.moved_index();
+                    let others = tasks.iter().enumerate()
+                        .filter(|(i, _)| Some(*i) != moved && !frame.hierarchy.is_parent(*i))
+                        .map(|(_, t)| t);
+                    let magnetic = tools.task_drag.magnetic && !ui.input(|i| i.modifiers.alt);
+                    let drag = &mut *tools.task_drag;
+                    if let Some(preview) = drag.preview.as_mut() {
+                        if magnetic {
+                            snap(preview, &find_guides(preview, others.clone(), scene.rx_guard.duration, log), self.hit_extent, log);
+                        }
+                        preview.quantize_times(time_display.resolution);
+                        drag.guides = find_guides(preview, others.clone(), scene.rx_guard.duration, log);
+                        let placement = check_placement(preview, others, scene.exclusions, &scene.rx_guard, scene.headroom);
+                        drag.placement = Some(placement);
+                    }
+                }
+                if let (Some(preview), Some(placement)) = (&tools.task_drag.preview, &tools.task_drag.placement) {
+                    let color = placement.color();
+                    let rect = transform.rect_from_values(
+                        &PlotPoint::new(freq_to_x(preview.freq_start, log), preview.time_start + lane_shift),
+                        &PlotPoint::new(freq_to_x(preview.freq_end, log), preview.time_end + lane_shift),
+                    );
+                    painter.rect(rect, 0., color.gamma_multiply(0.35), Stroke::new(2., color), egui::StrokeKind::Inside);
+                    let to_screen = |[f, t]: [f64; 2]| transform.position_from_point(&PlotPoint::new(freq_to_x(f, log), t + lane_shift));
+                    painter.extend(guide_shapes(&painter, &tools.task_drag.guides, *transform.frame(), rect.center(), to_screen));
+                    let reasons = placement.reasons().join("\n");
+                    if !reasons.is_empty() {
+                        painter.text(rect.left_top() - egui::vec2(0., 4.), egui::Align2::LEFT_BOTTOM, reasons, egui::FontId::proportional(12.), color);
+                    }
+                }
+                if response.drag_stopped() && tools.task_drag.is_active() {
+                    match tools.task_drag.finish() {
+                        Some(Ok((Some(key), task))) => events.push(PlanEvent::Edit { key, task: Box::new(task) }.into()),
+                        Some(Ok((None, task))) => {
+                            let description = format!("{} tracée sur le graphe", task.name);
+                            events.push(PlanEvent::Extend { tasks: vec![task], action: "create_task", description }.into());
+                        }
+                        Some(Err(e)) => events.push(UiEvent::Status(e).into()),
+                        None => {}
+                    }
+                }
+            }
+
+            // Tâche du menu contextuel (clic droit)
+            if response.secondary_clicked() && scene.dialogs && scene.editable && !tools.markups.is_active() {
+                self.context_task = response.interact_pointer_pos().and_then(|pos| {
+                    let pt = transform.value_from_position(pos);
+                    let i = (0..tasks.len()).find(|&i| {
+                        shown[i] && task_hit(&display_tasks[i], log, pt.x, pt.y, self.hit_extent)
+                    })?;
+                    let lane_shift = display_tasks[i].time_start - tasks[i].time_start;
+                    Some((i, if log { 10f64.powf(pt.x) } else { pt.x }, pt.y - lane_shift))
+                });
+            }
+            let hovered = plot_response.response.hover_pos().map(|pos| transform.value_from_position(pos));
+            (plot_response.response, hovered)
+        }).inner;
+        events.extend(self.show_task_menu(&response, tasks));
+        (hovered, events)
+    }
+
+    /// Menu contextuel de la tâche cliquée (clic droit) : scission au point cliqué,
+    /// fusion avec une tâche adjacente ou suppression.
+    fn show_task_menu(&mut self, response: &egui::Response, tasks: &[Task]) -> Option<AppEvent> {
+        let (i, freq, time) = self.context_task.filter(|(i, _, _)| *i < tasks.len())?;
+        let mut action: Option<AppEvent> = None;
+        response.context_menu(|ui| {
+            ui.label(&tasks[i].name);
+            ui.separator();
+            if ui.button(format!("Scinder à {:.1} ms", time)).clicked() {
+                action = Some(UiEvent::Split { index: i, axis: SplitAxis::Time, at: time }.into());
+                ui.close_menu();
+            }
+            if ui.button(format!("Scinder à {:.1} MHz", freq)).clicked() {
+                action = Some(UiEvent::Split { index: i, axis: SplitAxis::Frequency, at: freq }.into());
+                ui.close_menu();
+            }
+            let candidates = join_candidates(tasks, i);
+            if candidates.is_empty() {
+                ui.weak("Aucune tâche adjacente à fusionner");
+            }
+            for j in candidates {
+                if ui.button(format!("Fusionner avec {}", tasks[j].name)).clicked() {
+                    action = Some(UiEvent::Join { index: i, other: j }.into());
+                    ui.close_menu();
+                    break;
+                }
+            }
+            ui.separator();
+            if ui.button("Supprimer (corbeille)").clicked() {
+                let description = format!("{} supprimée", tasks[i].name);
+                let removed = vec![tasks[i].key().to_string()];
+                action = Some(PlanEvent::Patch { changed: Vec::new(), removed, action: "delete_task", description }.into());
+                ui.close_menu();
+            }
+        });
+        if action.is_some() {
+            self.context_task = None;
+        }
+        action
+    }
+}
@@ -0,0 +1,67 @@
+//! Module du mini graphe.
+//!
+//! Sous le graphe principal, dont il partage l'axe des fréquences, le mini graphe
+//! montre l'ensemble de la mission : fond simplifié (zones, phases, garde Rx,
+//! instant courant) et tâches affichées, sans contours ni annotations.
+
+use eframe::egui;
+use egui_plot::{Plot, PlotPoint};
+
+use crate::tools::background::get_background_zones;
+use crate::tools::mini_overlay::mini_background;
+use crate::tools::task_mesh::{lod_mesh, task_mesh};
+use crate::tools::utils::get_bounds;
+use crate::tools::view::{clamp_view, handle_wheel, FrameView, PlotScene};
+
+/// Affiche le mini graphe sur une hauteur de `height` points et retourne le point
+/// survolé.
+pub fn show_mini_plot(ui: &mut egui::Ui, frame: &FrameView, scene: &PlotScene, height: f32) -> Option<PlotPoint> {
+    let (time_display, log) = (scene.time_display, scene.log_scale);
+    let local_time = frame.local_time();
+    let time_max = frame.time_max;
+    let display_tasks = frame.display_tasks(scene.tasks);
+
+    ui.allocate_ui(egui::vec2(ui.available_width(), height), |ui| {
+        let mini_response = Plot::new("mini")
+            .link_axis("shared_x", [true, false])
+            .show_axes([false, true])
+            .y_axis_formatter(move |y, range| time_display.format_at(local_time(y.value), range.end() - range.start()))
+            .include_y(0.0)
+            .include_y(time_max)
+            .include_x(get_bounds(log).0)
+            .include_x(get_bounds(log).1)
+            .show_grid([false, false])
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .label_formatter(|_, _| String::new())
+            .show(ui, |plot_ui| {
+                clamp_view(plot_ui, log, time_max);
+                handle_wheel(plot_ui);
+            });
+        // Fond simplifié (zones, phases, garde Rx, instant courant) puis tâches
+        let transform = mini_response.transform;
+        let painter = ui.painter_at(*transform.frame());
+        let zones = get_background_zones(scene.zones, &scene.rx_guard);
+        let now = time_display.mission_start.map(|start| (chrono::Utc::now().timestamp_millis() - start) as f64);
+        painter.extend(mini_background(
+            zones.iter().filter(|z| scene.band_filter.shows_zone(z)),
+            scene.phases,
+            scene.rx_guard.duration,
+            now,
+            &frame.offsets(),
+            &transform,
+            log,
+        ));
+        let visible = display_tasks.iter().zip(frame.colors.colors.iter().copied())
+            .zip(&frame.shown).enumerate()
+            .filter(|(i, (_, s))| **s && !frame.hierarchy.is_parent(*i))
+            .map(|(_, (tc, _))| tc);
+        painter.add(if frame.degraded {
+            lod_mesh(visible, &transform, log)
+        } else {
+            task_mesh(visible, &transform, log)
+        });
+        mini_response.response.hover_pos().map(|pos| transform.value_from_position(pos))
+    })
+    .inner
+}
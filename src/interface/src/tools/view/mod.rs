@@ -0,0 +1,389 @@
+//! Module de rendu de l'application.
+//!
+//! `MyApp::update` reçoit les messages, tient l'analyse du plan à jour et ordonne
+//! l'affichage ; le rendu est réparti entre le panneau latéral ([`side_panel`]),
+//! le graphe principal ([`main_plot`]), le mini graphe ([`mini_plot`]) et les
+//! info-bulles ([`tooltips`]), avec des fonctions de dessin communes au widget
+//! réutilisable ([`paint`]). Les graphes partagent un [`FrameView`] : les données
+//! du plan dérivées pour l'image en cours (filtres, couloirs, couleurs, masques),
+//! calculées une seule fois.
+//!
+//! Les vues ne connaissent pas `MyApp` : elles reçoivent l'état qu'elles lisent
+//! ([`PlotScene`]) ou modifient ([`PlotTools`], [`main_plot::MainPlot`],
+//! [`side_panel::SidePanel`]) et retournent leurs actions sous forme
+//! d'événements, répartis par `MyApp::dispatch`.
+
+pub mod main_plot;
+pub mod mini_plot;
+pub mod paint;
+pub mod side_panel;
+pub mod tooltips;
+
+use std::time::Instant;
+
+use eframe::egui;
+use egui_plot::PlotBounds;
+
+use crate::tools::align::Selection;
+use crate::tools::analysis::Analysis;
+use crate::tools::animation::{Highlights, PlanTransition, UpdateAnimations};
+use crate::tools::antenna::Antenna;
+use crate::tools::background::ZoneDefinition;
+use crate::tools::baseline::Baseline;
+use crate::tools::coloring::ColorMap;
+use crate::tools::detection::Detections;
+use crate::tools::effect::Effect;
+use crate::tools::events::AppEvent;
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::filter::BandFilter;
+use crate::tools::harmonics::HarmonicsRuler;
+use crate::tools::headroom::{Headroom, HeadroomWarning};
+use crate::tools::hit_stack::HitStack;
+use crate::tools::range::{PropagationModel, RangeShortfall};
+use crate::tools::hierarchy::Hierarchy;
+use crate::tools::layers::{PlanLayer, PlanLayers};
+use crate::tools::marginal::{show_freq_strip, show_time_strip, MarginalMetric};
+use crate::tools::markup::Markups;
+use crate::tools::phase::Phase;
+use crate::tools::placement::TaskDrag;
+use crate::tools::platform::PlatformLanes;
+use crate::tools::presets::BandPresets;
+use crate::tools::query::{Query, QueryContext};
+use crate::tools::repair::Fix;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::Task;
+use crate::tools::timefmt::TimeDisplay;
+use crate::tools::tooltip::TooltipConfig;
+use crate::tools::utils::*;
+use crate::tools::view::main_plot::MainPlot;
+use crate::tools::view::mini_plot::show_mini_plot;
+use crate::tools::view::tooltips::show_hover_tooltips;
+use crate::tools::wall::WallTile;
+
+/// Plan et réglages d'affichage lus par les graphes et les info-bulles, empruntés
+/// à l'application pour l'image en cours.
+pub struct PlotScene<'a> {
+    /// Tâches du plan.
+    pub tasks: &'a [Task],
+    /// Indice de la tâche ouverte dans l'inspecteur.
+    pub selected: Option<usize>,
+    /// Indique si l'affichage utilise l'échelle logarithmique des fréquences.
+    pub log_scale: bool,
+    /// Heure H de la mission et format des temps.
+    pub time_display: TimeDisplay,
+    /// Fenêtre de garde de réception en début de cycle.
+    pub rx_guard: RxGuard,
+    /// Définitions des zones de fond.
+    pub zones: &'a [ZoneDefinition],
+    /// Phases de mission.
+    pub phases: &'a [Phase],
+    /// Bandes de référence protégées ou cibles.
+    pub presets: &'a BandPresets,
+    /// Marge de garde aux bords de bande des amplificateurs.
+    pub headroom: &'a Headroom,
+    /// Masques d'exclusion reçus, vérifiés lors du placement d'une tâche.
+    pub exclusions: &'a [ExclusionMask],
+    /// Filtres d'affichage par bande.
+    pub band_filter: &'a BandFilter,
+    /// Si défini, seules les tâches de cette plateforme sont affichées.
+    pub platform_filter: Option<&'a str>,
+    /// Si défini, seules les tâches rayonnées par cette antenne sont affichées.
+    pub antenna_filter: Option<&'a str>,
+    /// Si défini, seules les tâches recherchant cet effet sont affichées.
+    pub effect_filter: Option<Effect>,
+    /// Si défini, seules les tâches émettant pendant cette phase sont affichées.
+    pub phase_filter: Option<&'a str>,
+    /// Expression de recherche valide, si saisie.
+    pub search: Option<&'a Query>,
+    /// Couches exécutée et observée.
+    pub layers: &'a PlanLayers,
+    /// Détections ESM reçues.
+    pub detections: &'a Detections,
+    /// Transition animée lors du remplacement du plan.
+    pub transition: &'a PlanTransition,
+    /// Animations des tâches mises à jour par l'émetteur.
+    pub update_animations: &'a UpdateAnimations,
+    /// Tâches mises en évidence par un contrôleur externe.
+    pub highlights: &'a Highlights,
+    /// Sélection multiple.
+    pub selection: &'a Selection,
+    /// Plan de référence figé pour le suivi des écarts.
+    pub baseline: &'a Baseline,
+    /// Corrections suggérées en cours de prévisualisation.
+    pub repair_preview: Option<&'a [Fix]>,
+    /// Modèle de propagation (portée des tâches).
+    pub propagation: &'a PropagationModel,
+    /// Antennes d'émission.
+    pub antennas: &'a [Antenna],
+    /// Modèle de contenu des info-bulles des tâches.
+    pub tooltip: &'a TooltipConfig,
+    /// Plage de fréquences verrouillée (instance d'un mur d'images).
+    pub wall: Option<WallTile>,
+    /// Indique si l'axe des temps est découpé en un couloir par plateforme.
+    pub platform_lanes: bool,
+    /// Indique si le bandeau d'occupation temporelle est affiché.
+    pub time_strip: bool,
+    /// Indique si le bandeau d'occupation fréquentielle est affiché.
+    pub freq_strip: bool,
+    /// Grandeur cumulée par les bandeaux d'occupation.
+    pub marginal_metric: MarginalMetric,
+    /// Indique si le rendu est dégradé (voir [`crate::tools::watchdog`]).
+    pub degraded: bool,
+    /// Indique si le profil actif autorise la modification du plan.
+    pub editable: bool,
+    /// Indique si les fenêtres de dialogue peuvent être ouvertes (inspecteur,
+    /// menu contextuel).
+    pub dialogs: bool,
+}
+
+/// Outils d'interaction des graphes, modifiés par les clics et les glisser-déposer.
+pub struct PlotTools<'a> {
+    /// Règle des harmoniques (fréquence cliquée).
+    pub harmonics: &'a mut HarmonicsRuler,
+    /// Annotations dessinées sur le graphe principal.
+    pub markups: &'a mut Markups,
+    /// Glisser-déposer d'une tâche en cours.
+    pub task_drag: &'a mut TaskDrag,
+    /// Pile des tâches superposées sous le curseur.
+    pub hit_stack: &'a mut HitStack,
+}
+
+/// Données du plan dérivées pour l'image en cours, partagées par les graphes.
+pub struct FrameView {
+    /// Instant de l'image.
+    pub now: Instant,
+    /// Résultat de la dernière analyse du plan.
+    pub analysis: Analysis,
+    /// Masques d'exclusion reçus, complétés des bandes de référence protégées.
+    pub masks: Vec<ExclusionMask>,
+    /// Couleurs des tâches et légende.
+    pub colors: ColorMap,
+    /// Indique, pour chaque tâche, si elle satisfait les filtres d'affichage.
+    pub shown: Vec<bool>,
+    /// Couloirs par plateforme, si l'axe des temps est découpé.
+    pub lanes: Option<PlatformLanes>,
+    /// Tâches telles que dessinées (décalées dans le couloir de leur plateforme,
+    /// en cours de transition), si elles diffèrent des tâches du plan.
+    laid_out: Option<Vec<Task>>,
+    /// Tâches supprimées par le dernier remplacement du plan, en cours
+    /// d'effacement, avec leur couleur estompée.
+    pub fading: Vec<(Task, egui::Color32)>,
+    /// Décalage temporel (ms) et libellé de chaque couloir.
+    pub lane_offsets: Vec<(f64, String)>,
+    /// Hauteur de l'axe des temps en ms.
+    pub time_max: f64,
+    /// Étendue fréquentielle visible du graphe principal en MHz.
+    pub freq_span: f64,
+    /// Liens parent/enfants des tâches.
+    pub hierarchy: Hierarchy,
+    /// Indique si le rendu est dégradé (voir [`crate::tools::watchdog`]).
+    pub degraded: bool,
+    /// Tâches empiétant sur la marge d'un bord de bande.
+    pub headroom: Vec<HeadroomWarning>,
+    /// Tâches trop faibles pour atteindre leur cible.
+    pub range_shortfalls: Vec<RangeShortfall>,
+}
+
+impl FrameView {
+    /// Dérive du plan les données de l'image en cours : tâches affichées selon les
+    /// filtres de bande, de plateforme, d'antenne, d'effet, de phase et la recherche,
+    /// puis transition après remplacement du plan et disposition en couloirs par
+    /// plateforme. `freq_span` est l'étendue fréquentielle visible du graphe principal.
+    pub fn new(now: Instant, analysis: Analysis, masks: Vec<ExclusionMask>, mut colors: ColorMap, scene: &PlotScene, freq_span: f64) -> Self {
+        let query_ctx = QueryContext { rx_guard: scene.rx_guard };
+        let phase_filter = scene.phase_filter.and_then(|name| scene.phases.iter().find(|p| p.name == name));
+        let shown = scene.tasks.iter().map(|t| {
+            scene.layers.is_visible(PlanLayer::Planned)
+                && scene.band_filter.shows_task(t)
+                && scene.platform_filter.is_none_or(|p| t.platform.as_deref() == Some(p))
+                && scene.antenna_filter.is_none_or(|a| t.antenna.as_deref() == Some(a))
+                && scene.effect_filter.is_none_or(|e| t.effect == Some(e))
+                && phase_filter.is_none_or(|p| p.overlaps(t))
+                && scene.search.is_none_or(|query| query.matches(t, &query_ctx))
+        }).collect();
+
+        // Disposition en couloirs : les tâches sont décalées sur l'axe des temps dans le
+        // couloir de leur plateforme, les zones et repères étant répétés dans chaque couloir
+        // Transition après remplacement du plan : les tâches modifiées glissent vers
+        // leur nouvelle position, les ajoutées apparaissent et les supprimées s'estompent
+        let mut fading = Vec::new();
+        let animated = scene.transition.frame(scene.tasks, now).map(|transition| {
+            for (color, opacity) in colors.colors.iter_mut().zip(&transition.opacity) {
+                *color = color.gamma_multiply(*opacity);
+            }
+            fading = transition.removed.into_iter()
+                .map(|task| {
+                    let color = task.color().gamma_multiply(transition.removed_opacity);
+                    (task, color)
+                })
+                .collect();
+            transition.tasks
+        });
+        let tasks = animated.as_deref().unwrap_or(scene.tasks);
+
+        let lanes = scene.platform_lanes.then(|| PlatformLanes::new(scene.tasks));
+        if let Some(lanes) = &lanes {
+            let removed: Vec<Task> = fading.iter().map(|(task, _)| task.clone()).collect();
+            for ((task, _), laid_out) in fading.iter_mut().zip(lanes.layout(&removed)) {
+                *task = laid_out;
+            }
+        }
+        let laid_out = lanes.as_ref().map(|l| l.layout(tasks)).or(animated);
+        let lane_offsets = lanes.as_ref().map_or(vec![(0., String::new())], |l| {
+            l.lanes().into_iter().map(|(offset, label)| (offset, label.to_string())).collect()
+        });
+        let time_max = lanes.as_ref().map_or(MAX_TIME, PlatformLanes::height);
+        FrameView {
+            now,
+            analysis,
+            masks,
+            colors,
+            shown,
+            lanes,
+            laid_out,
+            fading,
+            lane_offsets,
+            time_max,
+            freq_span,
+            hierarchy: Hierarchy::new(scene.tasks),
+            degraded: scene.degraded,
+            headroom: scene.headroom.violations(scene.tasks),
+            range_shortfalls: scene.propagation.shortfalls(scene.tasks, scene.antennas),
+        }
+    }
+
+    /// Tâches telles que dessinées : décalées dans leur couloir, sinon `tasks`.
+    pub fn display_tasks<'a>(&'a self, tasks: &'a [Task]) -> &'a [Task] {
+        self.laid_out.as_deref().unwrap_or(tasks)
+    }
+
+    /// Copie de `tasks` disposée comme les tâches dessinées.
+    pub fn layout(&self, tasks: &[Task]) -> Vec<Task> {
+        match &self.lanes {
+            Some(lanes) => lanes.layout(tasks),
+            None => tasks.to_vec(),
+        }
+    }
+
+    /// Décalages temporels des couloirs en ms.
+    pub fn offsets(&self) -> Vec<f64> {
+        self.lane_offsets.iter().map(|&(offset, _)| offset).collect()
+    }
+
+    /// Conversion d'une ordonnée du graphe en temps de mission.
+    pub fn local_time(&self) -> impl Fn(f64) -> f64 + Copy + 'static {
+        let in_lanes = self.lanes.is_some();
+        move |y| if in_lanes { PlatformLanes::local_time(y) } else { y }
+    }
+}
+
+/// Bornes X de la tâche dans le repère du graphe (log10 des fréquences si `log`).
+fn task_x_span(task: &Task, log: bool) -> (f64, f64) {
+    (freq_to_x(task.freq_start, log), freq_to_x(task.freq_end, log))
+}
+
+/// Indique si le point `(x, time)` du graphe touche la tâche, en tenant compte de
+/// l'étendue minimale de détection `[min_width, min_duration]` (en unités du
+/// graphe) : les tâches plus étroites ou plus courtes, dont les fréquences
+/// ponctuelles et les événements instantanés, sont détectées autour de leur centre.
+pub fn task_hit(task: &Task, log: bool, x: f64, time: f64, [min_width, min_duration]: [f64; 2]) -> bool {
+    let (x0, x1) = task_x_span(task, log);
+    let (t0, t1) = (task.time_start, task.time_end);
+    let (narrow, short) = (x1 - x0 < min_width, t1 - t0 < min_duration);
+    if !narrow && !short {
+        return task.contains(if log { 10f64.powf(x) } else { x }, time);
+    }
+    let in_x = if narrow { (x - (x0 + x1) / 2.).abs() <= min_width / 2. } else { x >= x0 && x <= x1 };
+    let in_time = if short { (time - (t0 + t1) / 2.).abs() <= min_duration / 2. } else { time >= t0 && time <= t1 };
+    in_x && in_time
+}
+
+/// Indique si la tâche recoupe la vue `x_range` × `time_range` du graphe, élargie
+/// de l'étendue minimale `[largeur, durée]` des tâches étroites ou courtes.
+pub fn in_viewport(task: &Task, log: bool, (x_min, x_max): (f64, f64), (t_min, t_max): (f64, f64), [width, duration]: [f64; 2]) -> bool {
+    let (x0, x1) = task_x_span(task, log);
+    x1 >= x_min - width && x0 <= x_max + width && task.time_end >= t_min - duration && task.time_start <= t_max + duration
+}
+
+/// Limite la vue du graphe au domaine valide (fréquences autorisées et temps de
+/// mission jusqu'à `time_max`, avec une petite marge) et entre les niveaux de zoom
+/// minimal et maximal.
+pub fn clamp_view(plot_ui: &mut egui_plot::PlotUi, log: bool, time_max: f64) {
+    let bounds = plot_ui.plot_bounds();
+    let (x_min, x_max) = get_bounds(log);
+    let min_x_span = if log { MIN_LOG_SPAN } else { MIN_FREQ_SPAN };
+    let (x, y) = (
+        clamp_range(bounds.min()[0], bounds.max()[0], x_min, x_max, min_x_span),
+        clamp_range(bounds.min()[1], bounds.max()[1], 0., time_max, MIN_TIME_SPAN),
+    );
+    if x != (bounds.min()[0], bounds.max()[0]) || y != (bounds.min()[1], bounds.max()[1]) {
+        plot_ui.set_plot_bounds(PlotBounds::from_min_max([x.0, y.0], [x.1, y.1]));
+    }
+}
+
+/// Applique la molette de la souris au graphe survolé, à la manière des analyseurs
+/// de spectre : molette seule pour zoomer en fréquence autour du curseur, Ctrl +
+/// molette pour zoomer en temps et Maj + molette pour se déplacer en fréquence.
+pub fn handle_wheel(plot_ui: &mut egui_plot::PlotUi) {
+    if !plot_ui.response().hovered() {
+        return;
+    }
+    let Some(pointer) = plot_ui.pointer_coordinate() else { return };
+    let (scroll, zoom, modifiers) = plot_ui.ctx().input(|i| (i.smooth_scroll_delta, i.zoom_delta(), i.modifiers));
+    if modifiers.alt {
+        // Alt + molette fait défiler les tâches superposées sous le curseur
+        return;
+    }
+    if modifiers.command {
+        // egui convertit Ctrl + molette en facteur de zoom
+        if zoom != 1. {
+            plot_ui.zoom_bounds(egui::vec2(1., zoom), pointer);
+        }
+    } else if modifiers.shift {
+        // Selon la plateforme, Maj + molette produit un défilement horizontal ou vertical
+        let delta = (scroll.x + scroll.y) as f64;
+        if delta != 0. {
+            let units_per_pixel = plot_ui.plot_bounds().width() / plot_ui.response().rect.width().max(1.) as f64;
+            plot_ui.translate_bounds(egui::vec2((-delta * units_per_pixel) as f32, 0.));
+        }
+    } else if scroll.y != 0. {
+        plot_ui.zoom_bounds(egui::vec2((scroll.y / 200.).exp(), 1.), pointer);
+    }
+}
+
+/// Affiche le panneau central : bandeaux d'occupation, graphe principal, mini
+/// graphe et info-bulles, et retourne les actions demandées sur les graphes.
+pub fn show_plots(ctx: &egui::Context, frame: &FrameView, scene: &PlotScene, plot: &mut MainPlot, tools: &mut PlotTools) -> Vec<AppEvent> {
+    egui::CentralPanel::default().show(ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let total_height = ui.available_height();
+            let strip_height = if scene.time_strip { total_height * 0.06 } else { 0. };
+            let freq_strip_height = if scene.freq_strip { total_height * 0.05 } else { 0. };
+            let main_height = total_height * 0.8 - strip_height - freq_strip_height;
+            let mini_height = total_height * 0.18;
+
+            // Bandeau d'occupation fréquentielle, aligné sur la vue du graphe principal
+            // de la frame précédente
+            if scene.freq_strip {
+                let x_range = plot.view_bounds.map_or(get_bounds(scene.log_scale), |b| (b[0], b[2]));
+                let plot_x = plot.rect.map_or(egui::Rangef::EVERYTHING, |r| r.x_range());
+                let visible = scene.tasks.iter().zip(&frame.shown).filter(|(_, s)| **s).map(|(t, _)| t);
+                show_freq_strip(ui, visible, x_range, scene.log_scale, plot_x, freq_strip_height);
+            }
+
+            let (main_hovered, events) = plot.show(ui, frame, scene, tools, main_height);
+            let mini_hovered = show_mini_plot(ui, frame, scene, mini_height);
+
+            // Bandeau d'occupation temporelle des tâches affichées, toutes fréquences confondues
+            if scene.time_strip {
+                let visible = scene.tasks.iter().zip(&frame.shown).filter(|(_, s)| **s).map(|(t, _)| t);
+                show_time_strip(ui, visible, MAX_TIME, scene.marginal_metric, strip_height);
+            }
+
+            show_hover_tooltips(ui, frame, scene, plot, tools.hit_stack, main_hovered.or(mini_hovered));
+            events
+        })
+        .inner
+    })
+    .inner
+}
@@ -0,0 +1,96 @@
+//! Module des fonctions de dessin partagées par le graphe principal et le widget
+//! [`FreqTimeGantt`](crate::tools::widget::FreqTimeGantt).
+//!
+//! Les fonds (phases, garde Rx, marges de bande) sont tracés avec egui_plot, les
+//! décorations des tâches (halos de tolérance, crochets de hiérarchie, masques
+//! d'exclusion) en coordonnées écran.
+
+use eframe::egui;
+use egui::{Color32, Painter, RichText, Stroke};
+use egui_plot::{Line, LineStyle, PlotPoint, PlotPoints, PlotTransform, PlotUi, Polygon, Text};
+
+use crate::tools::exclusion::{hatch_mesh, ExclusionMask, EXCLUSION_COLOR};
+use crate::tools::headroom::Headroom;
+use crate::tools::hierarchy::{bracket_shapes, Hierarchy};
+use crate::tools::phase::Phase;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::utils::{freq_to_x, get_bounds};
+
+/// Rectangle écran couvrant `[f0, f1]` MHz sur `[t0, t1]` ms.
+fn screen_rect(transform: &PlotTransform, log: bool, [f0, t0, f1, t1]: [f64; 4]) -> egui::Rect {
+    egui::Rect::from_two_pos(
+        transform.position_from_point(&PlotPoint::new(freq_to_x(f0, log), t0)),
+        transform.position_from_point(&PlotPoint::new(freq_to_x(f1, log), t1)),
+    )
+}
+
+/// Trace les bandes des phases de mission décalées de `offset` ms, étiquetées à
+/// l'abscisse `label_x` si elle est donnée.
+pub fn plot_phases(plot_ui: &mut PlotUi, phases: &[Phase], log: bool, offset: f64, label_x: Option<f64>) {
+    let (x_min, x_max) = get_bounds(log);
+    for phase in phases {
+        let (start, end) = (phase.t_start + offset, phase.t_end + offset);
+        plot_ui.polygon(Polygon::new("phase", PlotPoints::from(vec![[x_min, start], [x_max, start], [x_max, end], [x_min, end]]))
+            .fill_color(phase.fill())
+            .stroke(Stroke::NONE));
+        if let Some(x) = label_x {
+            plot_ui.text(Text::new("phase", PlotPoint::new(x, end), RichText::new(&phase.name).color(phase.label_color()))
+                .anchor(egui::Align2::RIGHT_TOP));
+        }
+    }
+}
+
+/// Trace les marges de garde de l'amplificateur `amp`, estompées à l'intérieur des
+/// bords de sa bande, entre `t0` et `t1` ms.
+pub fn plot_headroom(plot_ui: &mut PlotUi, headroom: &Headroom, amp: Amplifier, log: bool, (t0, t1): (f64, f64), color: Color32) {
+    for (f0, f1) in headroom.guard_bands(amp) {
+        let (x0, x1) = (freq_to_x(f0, log), freq_to_x(f1, log));
+        plot_ui.polygon(Polygon::new("headroom", PlotPoints::from(vec![[x0, t0], [x1, t0], [x1, t1], [x0, t1]]))
+            .fill_color(color.gamma_multiply(0.15))
+            .stroke(Stroke::NONE));
+    }
+}
+
+/// Trace le marqueur de fin de la garde Rx à `rx_end` ms, étiqueté si `label`.
+pub fn plot_rx_guard(plot_ui: &mut PlotUi, log: bool, rx_end: f64, label: bool) {
+    let (x_min, x_max) = get_bounds(log);
+    plot_ui.line(Line::new("rx_guard", PlotPoints::from(vec![[x_min, rx_end], [x_max, rx_end]]))
+        .stroke(Stroke::new(1.0, Color32::from_gray(160)))
+        .style(LineStyle::dashed_loose()));
+    if label {
+        plot_ui.text(Text::new("rx_guard", PlotPoint::new(x_max, rx_end), RichText::new("Fin garde Rx").color(Color32::from_gray(160)))
+            .anchor(egui::Align2::RIGHT_BOTTOM));
+    }
+}
+
+/// Dessine les halos estompés des tolérances en fréquence (incertitude d'accord,
+/// Doppler) des tâches données avec leur couleur.
+pub fn paint_tolerance_halos<'a>(painter: &Painter, transform: &PlotTransform, log: bool, tasks: impl Iterator<Item = (&'a Task, Color32)>) {
+    for (task, color) in tasks {
+        if task.tolerance() <= 0. {
+            continue;
+        }
+        let (f0, f1) = task.footprint();
+        if log && f0 <= 0. {
+            continue;
+        }
+        let rect = screen_rect(transform, log, [f0, task.time_start, f1, task.time_end]);
+        painter.rect(rect, 0., color.gamma_multiply(0.3), Stroke::new(1., color.gamma_multiply(0.5)), egui::StrokeKind::Inside);
+    }
+}
+
+/// Dessine les tâches parentes affichées (`shown`) en crochets englobant leurs
+/// descendantes, de la couleur de la tâche.
+pub fn paint_brackets(painter: &Painter, transform: &PlotTransform, log: bool, tasks: &[Task], hierarchy: &Hierarchy, colors: &[Color32], shown: &[bool]) {
+    for i in (0..tasks.len()).filter(|&i| shown[i] && hierarchy.is_parent(i)) {
+        let rect = screen_rect(transform, log, hierarchy.span(tasks, i));
+        painter.extend(bracket_shapes(rect, colors[i].to_opaque()));
+    }
+}
+
+/// Dessine un masque d'exclusion décalé de `offset` ms : hachures et contour.
+pub fn paint_exclusion(painter: &Painter, transform: &PlotTransform, log: bool, mask: &ExclusionMask, offset: f64) {
+    let rect = screen_rect(transform, log, [mask.freq_start, mask.time_start + offset, mask.freq_end, mask.time_end + offset]);
+    painter.add(hatch_mesh(rect, *transform.frame(), 8., 1.5, EXCLUSION_COLOR.gamma_multiply(0.6)));
+    painter.rect_stroke(rect, 0., Stroke::new(1., EXCLUSION_COLOR), egui::StrokeKind::Inside);
+}
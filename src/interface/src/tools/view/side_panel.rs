@@ -0,0 +1,825 @@
+//! Module du panneau latéral de contrôles.
+//!
+//! Le panneau regroupe les réglages d'affichage (échelle, heure H, couleurs,
+//! filtres), les zooms par bande et par système, le bilan de l'analyse du plan
+//! (cycles de service, conflits, garde Rx) et les actions sur le plan
+//! (corrections, planification, imports et exports).
+
+use std::path::Path;
+
+use eframe::egui;
+use egui::Color32;
+
+use crate::tools::alerts::AlertEngine;
+use crate::tools::align::{align, Alignment, Selection};
+use crate::tools::amp_timeline::{busy_intervals, show_amp_timeline};
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::analysis::Analysis;
+use crate::tools::animation::PlanTransition;
+use crate::tools::antenna::{antenna_names, Antenna};
+use crate::tools::app::confirm_tentative;
+use crate::tools::baseline::Baseline;
+use crate::tools::briefing::export_briefing;
+use crate::tools::coloring::ColorBy;
+use crate::tools::config_watch::ConfigWatcher;
+use crate::tools::dedup::Deduplicator;
+use crate::tools::detection::Detections;
+use crate::tools::diagnostics::Diagnostics;
+use crate::tools::effect::{Effect, BORDER_COLOR};
+use crate::tools::events::{AppEvent, PlanEvent, UiEvent};
+use crate::tools::exclusion::{ExclusionMask, EXCLUSION_COLOR};
+use crate::tools::filter::BandFilter;
+use crate::tools::gantt_export::{export_gantt, GanttFormat};
+use crate::tools::git_import::GitImporter;
+use crate::tools::harmonics::HarmonicsRuler;
+use crate::tools::headroom::Headroom;
+use crate::tools::hierarchy::{Hierarchy, TaskTree};
+use crate::tools::history::History;
+use crate::tools::kiosk::{Kiosk, REVEAL_KEY};
+use crate::tools::geo_export::{export_geo, GeoFormat};
+use crate::tools::layers::PlanLayers;
+use crate::tools::marginal::MarginalMetric;
+use crate::tools::markup::Markups;
+use crate::tools::phase::Phase;
+use crate::tools::pinned::PinnedCards;
+use crate::tools::placement::TaskDrag;
+use crate::tools::plan::save_plan;
+use crate::tools::platform::platforms;
+use crate::tools::presets::BandPresets;
+use crate::tools::profile::Profile;
+use crate::tools::query::{Query, QueryContext};
+use crate::tools::range::PropagationModel;
+use crate::tools::refresh::RefreshSettings;
+use crate::tools::repair::{apply_fixes, suggest_fixes, Fix};
+use crate::tools::revisions::{PlanRevisions, RevisionAction};
+use crate::tools::robustness::RobustnessPanel;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::sandbox::Sandbox;
+use crate::tools::schedule::{schedule, DEFAULT_CAPACITY};
+use crate::tools::screenshot::{ScreenshotRecorder, SCREENSHOT_DIR};
+use crate::tools::system::System;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::timefmt::{format_dtg, parse_dtg, TimeDisplay, TimeResolution, TimeZoneSetting};
+use crate::tools::timeline::{SessionTimeline, TIMELINE_PATH};
+use crate::tools::tooltip::TooltipConfig;
+use crate::tools::trash::Trash;
+use crate::tools::utils::{format_bandwidth, format_freq, MAX_TIME, MIN_FREQ, MIN_TIME_SPAN};
+use crate::tools::view::main_plot::MainPlot;
+use crate::tools::wall::WallTile;
+use crate::tools::watchdog::FrameWatchdog;
+
+/// État lu et modifié par le panneau latéral, emprunté à l'application pour
+/// l'image en cours.
+pub struct SidePanel<'a> {
+    /// Tâches du plan.
+    pub tasks: &'a [Task],
+    /// Indice de la tâche ouverte dans l'inspecteur.
+    pub selected: Option<usize>,
+    /// Profil d'affichage actif.
+    pub profile: &'a Profile,
+    /// Profils d'affichage disponibles.
+    pub profiles: &'a [Profile],
+    /// Plage de fréquences verrouillée (instance d'un mur d'images).
+    pub wall: Option<WallTile>,
+    /// Moteur de règles d'alerte (badge des alertes actives).
+    pub alerts: &'a AlertEngine,
+    /// Résultat des diagnostics de démarrage.
+    pub diagnostics: &'a Diagnostics,
+    /// Systèmes regroupant les amplificateurs.
+    pub systems: &'a [System],
+    /// Antennes d'émission.
+    pub antennas: &'a [Antenna],
+    /// Modèle de propagation (portée des tâches).
+    pub propagation: &'a PropagationModel,
+    /// Phases de mission.
+    pub phases: &'a [Phase],
+    /// Historique des modifications pour annuler / rétablir.
+    pub history: &'a History,
+    /// Résultat du dernier contrôle d'empreinte du plan.
+    pub plan_sync: Option<&'a Result<String, String>>,
+    /// Nombre de messages reçus de l'émetteur depuis le lancement.
+    pub messages_received: usize,
+    /// Révision du plan analysée.
+    pub revision: u64,
+    /// Indique si une analyse du plan est en cours.
+    pub pending: bool,
+    /// Mode kiosque (panneau escamotable).
+    pub kiosk: &'a mut Kiosk,
+    /// Échelle logarithmique des fréquences.
+    pub log_scale: &'a mut bool,
+    /// Heure H de la mission et fuseau d'affichage des temps absolus.
+    pub time_display: &'a mut TimeDisplay,
+    /// Saisie de l'heure H sous forme de DTG.
+    pub mission_start_text: &'a mut String,
+    /// Affichage du HUD temps réel.
+    pub show_hud: &'a mut bool,
+    /// Message de résultat de la dernière action.
+    pub status: &'a mut Option<String>,
+    /// Réglages de cadence de rafraîchissement.
+    pub refresh: &'a mut RefreshSettings,
+    /// Transition animée lors du remplacement du plan.
+    pub transition: &'a mut PlanTransition,
+    /// Surveillance du temps de rendu.
+    pub watchdog: &'a mut FrameWatchdog,
+    /// Rechargement à chaud des fichiers de configuration.
+    pub config_watcher: &'a mut ConfigWatcher,
+    /// Indique si la fenêtre des diagnostics est ouverte.
+    pub diagnostics_open: &'a mut bool,
+    /// Annotations du graphe principal.
+    pub markups: &'a mut Markups,
+    /// Glisser-déposer des tâches (guides magnétiques).
+    pub task_drag: &'a mut TaskDrag,
+    /// Table arborescente des tâches.
+    pub task_tree: &'a mut TaskTree,
+    /// Corbeille des tâches supprimées.
+    pub trash: &'a mut Trash,
+    /// Bac à sable.
+    pub sandbox: &'a mut Sandbox,
+    /// Chronologie de la session.
+    pub timeline: &'a mut SessionTimeline,
+    /// Sélection multiple.
+    pub selection: &'a mut Selection,
+    /// Révisions nommées du plan.
+    pub revisions: &'a mut PlanRevisions,
+    /// Plan de référence.
+    pub baseline: &'a mut Baseline,
+    /// Règle des harmoniques.
+    pub harmonics: &'a mut HarmonicsRuler,
+    /// Bandes de référence.
+    pub presets: &'a mut BandPresets,
+    /// Modèle de contenu des info-bulles.
+    pub tooltip: &'a mut TooltipConfig,
+    /// Fiches épinglées.
+    pub pinned: &'a mut PinnedCards,
+    /// Couches exécutée et observée.
+    pub layers: &'a mut PlanLayers,
+    /// Détections ESM.
+    pub detections: &'a mut Detections,
+    /// Analyse de robustesse du plan.
+    pub robustness: &'a mut RobustnessPanel,
+    /// Attribut utilisé pour colorer les tâches.
+    pub color_by: &'a mut ColorBy,
+    /// Teinte des tâches selon leur plateforme.
+    pub platform_tint: &'a mut bool,
+    /// Affichage du bandeau d'occupation temporelle.
+    pub time_strip: &'a mut bool,
+    /// Affichage du bandeau d'occupation fréquentielle.
+    pub freq_strip: &'a mut bool,
+    /// Grandeur cumulée par les bandeaux d'occupation.
+    pub marginal_metric: &'a mut MarginalMetric,
+    /// Filtre par effet recherché.
+    pub effect_filter: &'a mut Option<Effect>,
+    /// Filtre par plateforme.
+    pub platform_filter: &'a mut Option<String>,
+    /// Filtre par antenne.
+    pub antenna_filter: &'a mut Option<String>,
+    /// Filtre par phase.
+    pub phase_filter: &'a mut Option<String>,
+    /// Découpage de l'axe des temps en couloirs par plateforme.
+    pub platform_lanes: &'a mut bool,
+    /// Saisie du champ de recherche.
+    pub search_text: &'a mut String,
+    /// Filtre de recherche analysé, ou message d'erreur de l'expression saisie.
+    pub search: &'a mut Result<Option<Query>, String>,
+    /// Vue du graphe principal (zooms par bande et par système).
+    pub main_plot: &'a mut MainPlot,
+    /// Filtres d'affichage par bande.
+    pub band_filter: &'a mut BandFilter,
+    /// Fenêtre de garde de réception.
+    pub rx_guard: &'a mut RxGuard,
+    /// Marge de garde aux bords de bande.
+    pub headroom: &'a mut Headroom,
+    /// Masques d'exclusion reçus.
+    pub exclusions: &'a mut Vec<ExclusionMask>,
+    /// Filtre des messages de tâche répétés.
+    pub dedup: &'a mut Deduplicator,
+    /// Corrections suggérées en cours de prévisualisation.
+    pub repair_preview: &'a mut Option<Vec<Fix>>,
+    /// Chemin du fichier de plan.
+    pub plan_path: &'a mut String,
+    /// Import de plans depuis un dépôt Git.
+    pub git_import: &'a mut GitImporter,
+    /// Captures automatiques du graphe.
+    pub screenshots: &'a mut ScreenshotRecorder,
+    /// Indique si l'éditeur de zones est ouvert.
+    pub zone_editor_open: &'a mut bool,
+    /// Indique si la fenêtre d'import CSV est ouverte.
+    pub csv_dialog_open: &'a mut bool,
+}
+
+impl SidePanel<'_> {
+    /// Affiche le panneau latéral avec les contrôles (masqué sur un mur d'images,
+    /// escamotable en mode kiosque) et retourne les actions demandées.
+    ///
+    /// Le panneau reçoit le résultat de l'analyse du plan, les masques d'exclusion
+    /// (reçus et bandes protégées) et la légende des couleurs des tâches.
+    pub fn show(self, ctx: &egui::Context, analysis: &Analysis, masks: &[ExclusionMask], legend: &[(String, Color32)]) -> Vec<AppEvent> {
+        let SidePanel {
+            tasks, selected, profile, profiles, wall, alerts, diagnostics, systems, antennas, propagation, phases, history,
+            plan_sync, messages_received, revision, pending, kiosk, log_scale, time_display, mission_start_text, show_hud,
+            status, refresh, transition, watchdog, config_watcher, diagnostics_open, markups, task_drag, task_tree, trash,
+            sandbox, timeline, selection, revisions, baseline, harmonics, presets, tooltip, pinned, layers, detections,
+            robustness, color_by, platform_tint, time_strip, freq_strip, marginal_metric, effect_filter, platform_filter,
+            antenna_filter, phase_filter, platform_lanes, search_text, search, main_plot, band_filter, rx_guard, headroom,
+            exclusions, dedup, repair_preview, plan_path, git_import, screenshots, zone_editor_open, csv_dialog_open,
+        } = self;
+        let Analysis { violations, conflicts, rx_violations, intermods, antenna_violations, stats, systems: system_loads, antennas: antenna_loads, invalid_freqs } = analysis;
+        let loads = &stats.amplifiers;
+        let mut events: Vec<AppEvent> = Vec::new();
+        // Plan détaché du plan diffusé : bac à sable ou consultation d'une révision
+        let detached = sandbox.is_active() || revisions.is_viewing();
+        // Profil sans panneau latéral : F2 l'affiche ou le masque, comme en mode kiosque
+        if !profile.side_panel && !kiosk.enabled && ctx.input(|i| i.key_pressed(REVEAL_KEY)) {
+            kiosk.pinned = !kiosk.pinned;
+        }
+        let show_controls = kiosk.show_controls(ctx)
+            && wall.is_none_or(|tile| !tile.hide_controls)
+            && (profile.side_panel || kiosk.pinned);
+        let side_panel = egui::SidePanel::left("side_panel").show_animated(ctx, show_controls, |ui| {
+            ui.heading("Contrôles");
+            ui.label(format!("Nombre de tâches : {}", tasks.len()));
+            alerts.show_badge(ui);
+            ui.separator();
+            ui.checkbox(log_scale, "Échelle logarithmique")
+                .on_hover_text("Molette : zoom en fréquence · Ctrl + molette : zoom en temps · Maj + molette : déplacement");
+            ui.separator();
+            ui.label("Heure H (DTG) :");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(mission_start_text).desired_width(110.));
+                if ui.button("Définir").clicked() {
+                    match parse_dtg(mission_start_text) {
+                        Ok(start) => {
+                            time_display.mission_start = Some(start);
+                            *mission_start_text = format_dtg(start);
+                            *status = None;
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Fuseau :");
+                let zone = &mut time_display.zone;
+                ui.selectable_value(zone, TimeZoneSetting::Utc, "UTC (Z)");
+                ui.selectable_value(zone, TimeZoneSetting::Local, "Local (L)");
+                if ui.selectable_label(matches!(zone, TimeZoneSetting::Offset(_)), "Décalage").clicked() {
+                    *zone = TimeZoneSetting::Offset(0);
+                }
+            });
+            ui.add_enabled(time_display.mission_start.is_some(), egui::Checkbox::new(show_hud, "HUD temps réel"))
+                .on_disabled_hover_text("Nécessite de définir l'heure H");
+            if let TimeZoneSetting::Offset(minutes) = &mut time_display.zone {
+                let mut hours = *minutes as f64 / 60.;
+                ui.add(egui::DragValue::new(&mut hours).range(-12.0..=14.0).speed(0.25).suffix(" h"));
+                *minutes = (hours * 60.).round() as i32;
+            }
+            egui::ComboBox::from_label("Résolution temporelle")
+                .selected_text(time_display.resolution.label())
+                .show_ui(ui, |ui| {
+                    for resolution in TimeResolution::ALL {
+                        ui.selectable_value(&mut time_display.resolution, resolution, resolution.label());
+                    }
+                });
+            let mut name = profile.name.clone();
+            egui::ComboBox::from_label("Profil")
+                .selected_text(&name)
+                .show_ui(ui, |ui| {
+                    for p in profiles {
+                        ui.selectable_value(&mut name, p.name.clone(), &p.name);
+                    }
+                });
+            if name != profile.name {
+                events.push(UiEvent::SelectProfile(name).into());
+            }
+            egui::CollapsingHeader::new("Rafraîchissement").show(ui, |ui| {
+                refresh.show(ui);
+                transition.show(ui);
+                ui.separator();
+                watchdog.show(ui);
+            });
+            let config_title = if config_watcher.is_healthy() { "Configuration" } else { "Configuration ⚠" };
+            egui::CollapsingHeader::new(config_title).id_salt("config_log").show(ui, |ui| {
+                config_watcher.show(ui);
+                ui.separator();
+                if ui.button(diagnostics.summary()).on_hover_text("Ouvre le détail des diagnostics de démarrage").clicked() {
+                    *diagnostics_open = true;
+                }
+            });
+            egui::CollapsingHeader::new("Annotations").show(ui, |ui| markups.show(ui));
+            egui::CollapsingHeader::new(format!("Tâches ({})", tasks.len())).show(ui, |ui| {
+                ui.checkbox(&mut task_drag.magnetic, "Guides magnétiques")
+                    .on_hover_text("Ctrl + glisser sur le graphe : aimante la tâche sur les bords d'amplificateur, les tâches voisines et la garde Rx (Alt pour désactiver)");
+                let hierarchy = Hierarchy::new(tasks);
+                if let Some(i) = task_tree.show(ui, tasks, &hierarchy, selected) {
+                    events.push(UiEvent::Inspect(Some(i)).into());
+                }
+            });
+            egui::CollapsingHeader::new(format!("Corbeille ({})", trash.len())).show(ui, |ui| {
+                if let Some(i) = trash.show(ui, profile.permissions.edit) {
+                    match trash.restore(i, tasks) {
+                        Ok(task) => {
+                            let description = format!("{} restaurée depuis la corbeille", task.name);
+                            events.push(PlanEvent::Extend { tasks: vec![task], action: "restore_task", description }.into());
+                        }
+                        Err(e) => *status = Some(e),
+                    }
+                }
+            });
+            if ui.add_enabled(!detached, egui::Button::new("Ouvrir le bac à sable"))
+                .on_hover_text("Modifie une copie du plan sans diffuser les modifications, puis la valide ou l'abandonne")
+                .clicked()
+            {
+                sandbox.enter(tasks);
+                timeline.record_action("sandbox_enter", "Bac à sable ouvert");
+            }
+            egui::CollapsingHeader::new(format!("Sélection ({})", selection.keys.len()))
+                .id_salt("selection")
+                .show(ui, |ui| {
+                    ui.weak("Maj+clic sur une tâche pour l'ajouter ou la retirer");
+                    let indices = selection.indices(tasks);
+                    for alignment in Alignment::ALL {
+                        if ui.add_enabled(indices.len() >= alignment.min_count(), egui::Button::new(alignment.label())).clicked() {
+                            let mut aligned = tasks.to_vec();
+                            align(&mut aligned, &indices, alignment);
+                            let changed = indices.iter().map(|&i| {
+                                let mut task = aligned[i].clone();
+                                task.quantize_times(time_display.resolution);
+                                task
+                            }).collect();
+                            let description = format!("{} ({} tâches)", alignment.label(), indices.len());
+                            events.push(PlanEvent::Patch { changed, removed: Vec::new(), action: "align", description }.into());
+                        }
+                    }
+                    if ui.add_enabled(!selection.keys.is_empty(), egui::Button::new("Vider la sélection")).clicked() {
+                        selection.keys.clear();
+                    }
+                });
+            revisions.open(plan_path);
+            egui::CollapsingHeader::new(format!("Révisions ({})", revisions.revisions.len()))
+                .id_salt("revisions")
+                .show(ui, |ui| {
+                    match revisions.show(ui, !detached) {
+                        Some(RevisionAction::Commit(label)) => match revisions.commit(&label, tasks) {
+                            Ok(()) => {
+                                timeline.record_action("revision_commit", format!("Révision « {} » validée", label.trim()));
+                            }
+                            Err(e) => *status = Some(e),
+                        },
+                        Some(RevisionAction::View(i)) => events.push(UiEvent::ViewRevision(i).into()),
+                        None => {}
+                    }
+                });
+            egui::CollapsingHeader::new("Référence").show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Définir comme référence").clicked() {
+                        baseline.set(tasks);
+                        timeline.record_action("baseline_set", "Plan défini comme référence");
+                    }
+                    if ui.add_enabled(baseline.is_set(), egui::Button::new("Effacer")).clicked() {
+                        baseline.clear();
+                    }
+                });
+                if baseline.is_set() {
+                    let deviations = baseline.deviations(tasks);
+                    ui.label(format!("Écarts : {}", deviations.len()));
+                    for deviation in &deviations {
+                        ui.label(&deviation.detail);
+                    }
+                    if ui.button("Copier le rapport").clicked() {
+                        ui.ctx().copy_text(baseline.report_markdown(tasks));
+                    }
+                }
+            });
+            egui::CollapsingHeader::new("Règle des harmoniques").show(ui, |ui| harmonics.show(ui));
+            egui::CollapsingHeader::new("Bandes de référence").show(ui, |ui| presets.show(ui));
+            egui::CollapsingHeader::new("Info-bulles").show(ui, |ui| {
+                tooltip.show(ui);
+                ui.weak("Alt+clic sur une tâche pour épingler sa fiche");
+                if ui.add_enabled(!pinned.is_empty(), egui::Button::new(format!("Fermer les fiches épinglées ({})", pinned.len()))).clicked() {
+                    pinned.clear();
+                }
+            });
+            egui::CollapsingHeader::new("Couches").show(ui, |ui| {
+                layers.show(ui, tasks.len());
+                ui.separator();
+                detections.show(ui);
+            });
+            egui::CollapsingHeader::new("Robustesse").show(ui, |ui| {
+                robustness.show(ui, tasks, rx_guard, revision)
+            });
+            ui.separator();
+            egui::ComboBox::from_label("Couleur selon")
+                .selected_text(color_by.label())
+                .show_ui(ui, |ui| {
+                    for by in ColorBy::ALL {
+                        ui.selectable_value(color_by, by, by.label());
+                    }
+                });
+            ui.checkbox(platform_tint, "Teinter selon la plateforme");
+            ui.checkbox(time_strip, "Bandeau d'occupation temporelle")
+                .on_hover_text("Charge du plan par intervalle de temps, toutes fréquences confondues");
+            ui.checkbox(freq_strip, "Bandeau d'occupation fréquentielle")
+                .on_hover_text("Temps de brouillage cumulé par intervalle de la bande visible : rouge si non couvert, orange si sur-servi");
+            egui::ComboBox::from_label("Occupation")
+                .selected_text(marginal_metric.label())
+                .show_ui(ui, |ui| {
+                    for metric in MarginalMetric::ALL {
+                        ui.selectable_value(marginal_metric, metric, metric.label());
+                    }
+                });
+            for (label, color) in legend {
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(12., 12.), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2., *color);
+                    ui.label(label);
+                });
+            }
+            ui.label("Effets (clic : filtrer) :");
+            for effect in Effect::ALL {
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(24., 12.), egui::Sense::hover());
+                    let sample = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()];
+                    ui.painter().extend(effect.border(&sample, BORDER_COLOR));
+                    let filtered = *effect_filter == Some(effect);
+                    if ui.selectable_label(filtered, effect.label()).clicked() {
+                        *effect_filter = if filtered { None } else { Some(effect) };
+                    }
+                });
+            }
+            ui.separator();
+            egui::ComboBox::from_label("Plateforme")
+                .selected_text(platform_filter.as_deref().unwrap_or("Toutes"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(platform_filter, None, "Toutes");
+                    for platform in platforms(tasks).into_iter().flatten() {
+                        let label = platform.clone();
+                        ui.selectable_value(platform_filter, Some(platform), label);
+                    }
+                });
+            let antenna_choices = antenna_names(antennas, tasks);
+            if !antenna_choices.is_empty() {
+                egui::ComboBox::from_label("Antenne")
+                    .selected_text(antenna_filter.as_deref().unwrap_or("Toutes"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(antenna_filter, None, "Toutes");
+                        for antenna in antenna_choices {
+                            let label = antenna.clone();
+                            ui.selectable_value(antenna_filter, Some(antenna), label);
+                        }
+                    });
+            }
+            for load in antenna_loads {
+                ui.horizontal(|ui| {
+                    ui.label(&load.antenna);
+                    ui.add(
+                        egui::ProgressBar::new(load.utilization.clamp(0., 1.) as f32)
+                            .desired_width(90.)
+                            .text(format!("{:.0} % · max {}", load.utilization * 100., load.peak_concurrency)),
+                    )
+                    .on_hover_text(format!("{} tâches", load.task_count));
+                });
+            }
+            if !phases.is_empty() {
+                egui::ComboBox::from_label("Phase")
+                    .selected_text(phase_filter.as_deref().unwrap_or("Toutes"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(phase_filter, None, "Toutes");
+                        for phase in phases {
+                            ui.selectable_value(phase_filter, Some(phase.name.clone()), &phase.name);
+                        }
+                    });
+            }
+            ui.checkbox(platform_lanes, "Un couloir par plateforme")
+                .on_hover_text("Découpe l'axe des temps en un couloir de mission par plateforme");
+            ui.separator();
+            ui.label("Recherche :");
+            let search_edit = ui.add(egui::TextEdit::singleline(search_text)
+                .hint_text(r#"amp == "A20_500" && duration > 200"#));
+            if search_edit.changed() {
+                *search = match search_text.trim() {
+                    "" => Ok(None),
+                    text => Query::parse(text).map(Some),
+                };
+            }
+            if let Err(e) = &*search {
+                ui.colored_label(Color32::RED, e);
+            }
+            let tentative = tasks.iter().filter(|t| t.tentative).count();
+            if tentative > 0 {
+                let confirm = ui.button(format!("Confirmer les tâches provisoires ({})", tentative))
+                    .on_hover_text("Seules les tâches satisfaisant la recherche sont confirmées");
+                if confirm.clicked() {
+                    let ctx = QueryContext { rx_guard: *rx_guard };
+                    let mut changed = tasks.iter().filter(|t| t.tentative).cloned().collect::<Vec<_>>();
+                    let confirmed = confirm_tentative(&mut changed, |t| match &*search {
+                        Ok(Some(query)) => query.matches(t, &ctx),
+                        _ => true,
+                    });
+                    changed.retain(|t| !t.tentative);
+                    let description = format!("{} tâches confirmées", confirmed);
+                    *status = Some(description.clone());
+                    events.push(PlanEvent::Patch { changed, removed: Vec::new(), action: "confirm_tentative", description }.into());
+                }
+            }
+            ui.separator();
+            ui.label("Zoom bande (Ctrl : filtrer les tâches, Maj : zone seule) :");
+            for (i, amp) in Amplifier::ALL.iter().enumerate() {
+                let (start, end) = amp.band();
+                let mut clicked = false;
+                ui.horizontal(|ui| {
+                    clicked = ui.selectable_label(main_plot.zoom_band == Some(i), format!("{:?}", amp)).clicked();
+                    if clicked {
+                        band_filter.on_band_click(*amp, ui.input(|i| i.modifiers));
+                    }
+                    // Barre d'occupation : % du temps de mission avec au moins une tâche et pic de simultanéité
+                    if let Some(load) = loads.iter().find(|l| l.amplifier == *amp) {
+                        ui.add(
+                            egui::ProgressBar::new(load.utilization.clamp(0., 1.) as f32)
+                                .desired_width(90.)
+                                .fill(amplifier_color(*amp))
+                                .text(format!("{:.0} % · max {}", load.utilization * 100., load.peak_concurrency)),
+                        )
+                        .on_hover_text("Part du temps de mission occupée · nombre maximal de tâches simultanées");
+                    }
+                });
+                let (xmin, xmax) = if *log_scale { (start.log10(), end.log10()) } else { (start, end) };
+                // Frise d'activité : un clic zoome sur la bande et la période cliquée
+                if let Some((t0, t1)) = show_amp_timeline(ui, &busy_intervals(tasks, *amp), amplifier_color(*amp)) {
+                    let margin = ((t1 - t0) * 0.1).max(MIN_TIME_SPAN);
+                    main_plot.zoom_band = Some(i);
+                    main_plot.zoom_system = None;
+                    main_plot.force_view = Some([xmin, t0 - margin, xmax, t1 + margin]);
+                }
+                if clicked {
+                    main_plot.zoom_band = Some(i);
+                    main_plot.zoom_system = None;
+                    main_plot.force_bounds_x = Some((xmin, xmax));
+                }
+            }
+            if !systems.is_empty() {
+                ui.label("Zoom système (Ctrl : filtrer les tâches) :");
+            }
+            for (i, system) in systems.iter().enumerate() {
+                let mut clicked = false;
+                ui.horizontal(|ui| {
+                    clicked = ui.selectable_label(main_plot.zoom_system == Some(i), &system.name).clicked();
+                    if clicked {
+                        band_filter.on_system_click(system, ui.input(|i| i.modifiers));
+                    }
+                    // Occupation cumulée des amplificateurs du système
+                    if let Some(load) = system_loads.iter().find(|l| l.system == system.name) {
+                        ui.add(
+                            egui::ProgressBar::new(load.utilization.clamp(0., 1.) as f32)
+                                .desired_width(90.)
+                                .text(format!("{:.0} % · max {}", load.utilization * 100., load.peak_concurrency)),
+                        )
+                        .on_hover_text(format!("{} tâches sur {} amplificateurs", load.task_count, system.amplifiers.len()));
+                    }
+                });
+                if let (true, Some((start, end))) = (clicked, system.band()) {
+                    main_plot.zoom_band = None;
+                    main_plot.zoom_system = Some(i);
+                    main_plot.force_bounds_x = Some(if *log_scale { (start.log10(), end.log10()) } else { (start, end) });
+                }
+            }
+            if ui.selectable_label(main_plot.zoom_band.is_none() && main_plot.zoom_system.is_none(), "Tout").clicked() {
+                main_plot.reset_zoom(*log_scale);
+                *band_filter = BandFilter::default();
+            }
+            if !band_filter.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Filtre : {}", band_filter.describe()));
+                    if ui.small_button("✖").clicked() {
+                        *band_filter = BandFilter::default();
+                    }
+                });
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Garde Rx :");
+                ui.add(egui::DragValue::new(&mut rx_guard.duration).range(0.0..=MAX_TIME).suffix(" ms"));
+            });
+            // Compte à rebours en temps réel, relativement à l'heure H
+            if let Some(start) = time_display.mission_start {
+                let elapsed = (chrono::Utc::now().timestamp_millis() - start) as f64;
+                match rx_guard.remaining(elapsed) {
+                    Some(remaining) => ui.colored_label(Color32::YELLOW, format!("Garde Rx active : encore {:.0} ms", remaining)),
+                    None if elapsed < 0. => ui.label(format!("Garde Rx dans {:.1} s", -elapsed / 1000.)),
+                    None => ui.weak("Garde Rx terminée"),
+                };
+            }
+            for &i in rx_violations {
+                ui.colored_label(Color32::RED, format!("{} émet pendant la garde Rx", tasks[i].name));
+            }
+            headroom.show(ui);
+            for warning in headroom.violations(tasks) {
+                ui.colored_label(Color32::from_rgb(255, 170, 0), format!(
+                    "{} à {} du bord {} MHz (marge {})",
+                    tasks[warning.task_index].name,
+                    format_bandwidth(warning.distance),
+                    warning.edge,
+                    format_bandwidth(warning.margin)
+                ));
+            }
+            for v in antenna_violations {
+                ui.colored_label(Color32::RED, &v.message);
+            }
+            for shortfall in propagation.shortfalls(tasks, antennas) {
+                if let Some(distance) = shortfall.estimate.distance_km {
+                    ui.colored_label(Color32::from_rgb(255, 170, 0), format!(
+                        "{} : cible à {:.1} km, portée {:.1} km (puissance insuffisante)",
+                        tasks[shortfall.task_index].name,
+                        distance,
+                        shortfall.estimate.range_km
+                    ));
+                }
+            }
+            if *log_scale {
+                for &i in invalid_freqs {
+                    ui.colored_label(Color32::RED, format!("{} : fréquence nulle ou négative, ramenée à {} MHz en échelle log", tasks[i].name, MIN_FREQ));
+                }
+            }
+            if !exclusions.is_empty() {
+                ui.label("Masques d'exclusion :");
+                let mut removed = None;
+                for (i, mask) in exclusions.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let span = mask.freq_end - mask.freq_start;
+                        ui.colored_label(EXCLUSION_COLOR, format!("{} ({} – {})", mask.name, format_freq(mask.freq_start, span), format_freq(mask.freq_end, span)));
+                        if ui.small_button("✖").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    exclusions.remove(i);
+                }
+            }
+            ui.separator();
+            ui.label("Cycles de service :");
+            if violations.is_empty() {
+                ui.label("Aucune violation");
+            }
+            for v in violations {
+                let task = &tasks[v.task_index];
+                let fix = match v.suggested_shift {
+                    Some(shift) => format!("décaler de +{:.0} ms", shift),
+                    None => "tâche trop longue".into(),
+                };
+                ui.colored_label(
+                    Color32::RED,
+                    format!("{:?} – {} : {:.0} ms continus, {}", v.amplifier, task.name, v.on_time, fix),
+                );
+            }
+            ui.label(format!("Conflits : {}", conflicts.len()));
+            ui.label(format!("Intermodulation : {}", intermods.len()));
+            for warning in intermods {
+                ui.colored_label(Color32::from_rgb(255, 170, 0), warning.describe(tasks));
+            }
+            ui.horizontal(|ui| {
+                ui.label(format!("Messages reçus : {}", messages_received));
+                ui.checkbox(&mut dedup.enabled, format!("Répétitions ignorées : {}", dedup.suppressed))
+                    .on_hover_text("Ignore les messages de tâche identiques au précédent");
+            });
+            match plan_sync {
+                Some(Ok(hash)) => {
+                    ui.label(format!("Plan synchronisé ({})", hash));
+                }
+                Some(Err(divergence)) => {
+                    ui.colored_label(Color32::from_rgb(255, 170, 0), format!("Plan désynchronisé : {}", divergence));
+                }
+                None => {}
+            }
+            ui.label(format!(
+                "Couverture : {:.0} ms · {:.0} MHz",
+                stats.time_coverage_ms, stats.freq_coverage_mhz
+            ));
+            if pending {
+                ui.weak("Analyse en cours…");
+            }
+            ui.separator();
+
+            // Suggestions de corrections automatiques
+            let has_issues = !violations.is_empty() || !conflicts.is_empty();
+            if ui.add_enabled(has_issues, egui::Button::new("Suggérer des corrections")).clicked() {
+                *repair_preview = Some(suggest_fixes(tasks));
+            }
+            if let Some(fixes) = &*repair_preview {
+                if fixes.is_empty() {
+                    ui.label("Aucune correction trouvée");
+                }
+                for fix in fixes {
+                    ui.label(fix.describe(tasks));
+                }
+                let mut apply = false;
+                let mut discard = false;
+                ui.horizontal(|ui| {
+                    apply = ui.add_enabled(!fixes.is_empty(), egui::Button::new("Appliquer")).clicked();
+                    discard = ui.button("Ignorer").clicked();
+                });
+                if apply {
+                    let description = format!("{} corrections appliquées", fixes.len());
+                    events.push(PlanEvent::diff(tasks, &apply_fixes(tasks, fixes), "apply_fixes", description).into());
+                }
+                if apply || discard {
+                    *repair_preview = None;
+                }
+            }
+            if ui.add_enabled(!tasks.is_empty(), egui::Button::new("Planifier automatiquement"))
+                .on_hover_text("Replace toutes les tâches au plus tôt selon leur bande et leur durée")
+                .clicked()
+            {
+                match schedule(tasks, rx_guard, masks, DEFAULT_CAPACITY) {
+                    Ok(mut scheduled) => {
+                        for task in &mut scheduled {
+                            task.quantize_times(time_display.resolution);
+                        }
+                        events.push(PlanEvent::diff(tasks, &scheduled, "schedule", "Planification automatique".into()).into());
+                    }
+                    Err(e) => *status = Some(e),
+                }
+            }
+            ui.horizontal(|ui| {
+                if ui.add_enabled(history.can_undo(), egui::Button::new("Annuler")).clicked() {
+                    events.push(PlanEvent::Undo.into());
+                }
+                if ui.add_enabled(history.can_redo(), egui::Button::new("Rétablir")).clicked() {
+                    events.push(PlanEvent::Redo.into());
+                }
+            });
+            ui.separator();
+
+            // Imports / exports
+            ui.label("Import / export :");
+            ui.text_edit_singleline(plan_path);
+            ui.horizontal(|ui| {
+                if ui.button("Ouvrir").clicked() {
+                    events.push(UiEvent::OpenPlan.into());
+                }
+                if ui.button("Enregistrer").clicked() {
+                    *status = Some(match save_plan(Path::new(plan_path.as_str()), tasks) {
+                        Ok(()) => format!("Plan enregistré dans {}", plan_path),
+                        Err(e) => e,
+                    });
+                }
+            });
+            egui::CollapsingHeader::new("Import Git").id_salt("git_import").show(ui, |ui| {
+                if git_import.show(ui, tasks, revision, !detached) {
+                    git_import.start(ui.ctx());
+                }
+            });
+            ui.checkbox(&mut screenshots.enabled, "Captures automatiques")
+                .on_hover_text(format!("Image du graphe enregistrée dans {}/ à chaque conflit, chargement de plan ou changement de phase", SCREENSHOT_DIR));
+            if ui.button("Éditer les zones…").clicked() {
+                *zone_editor_open = true;
+            }
+            if ui.button("Importer un CSV…").clicked() {
+                *csv_dialog_open = true;
+            }
+            if ui.button("Exporter le briefing (PDF)").on_hover_text("Enregistré sous le chemin du plan, avec l'extension .pdf").clicked() {
+                let path = Path::new(plan_path.as_str()).with_extension("pdf");
+                *status = Some(match export_briefing(&path, tasks, &markups.items, time_display) {
+                    Ok(()) => format!("Briefing exporté dans {}", path.display()),
+                    Err(e) => e,
+                });
+            }
+            if ui.button("Exporter la chronologie de la session").clicked() {
+                *status = Some(match timeline.export(Path::new(TIMELINE_PATH)) {
+                    Ok([ndjson, svg]) => format!("Chronologie exportée dans {} et {}", ndjson.display(), svg.display()),
+                    Err(e) => e,
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label("Gantt :");
+                for (format, label) in [(GanttFormat::Mermaid, "Mermaid"), (GanttFormat::MsProject, "MS Project")] {
+                    if ui.button(label).clicked() {
+                        let path = Path::new(plan_path.as_str()).with_extension(format.extension());
+                        *status = Some(match export_gantt(&path, tasks, format) {
+                            Ok(()) => format!("Gantt exporté dans {}", path.display()),
+                            Err(e) => e,
+                        });
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Carte :");
+                for (format, label) in [(GeoFormat::Kml, "KML"), (GeoFormat::GeoJson, "GeoJSON")] {
+                    if ui.button(label).clicked() {
+                        let path = Path::new(plan_path.as_str()).with_extension(format.extension());
+                        *status = Some(match export_geo(&path, tasks, time_display, format) {
+                            Ok(count) => format!("{} tâches localisées exportées dans {}", count, path.display()),
+                            Err(e) => e,
+                        });
+                    }
+                }
+            });
+            if let Some(status) = &*status {
+                ui.label(status);
+            }
+        });
+        if let Some(side_panel) = side_panel {
+            kiosk.set_panel_right(side_panel.response.rect.right());
+        }
+        events
+    }
+}
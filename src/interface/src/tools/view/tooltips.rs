@@ -0,0 +1,176 @@
+//! Module des info-bulles du graphe.
+//!
+//! Au survol du graphe, l'info-bulle détaille la tâche sous le curseur (et liste
+//! les tâches superposées), ou à défaut les zones, masques, bandes cibles et
+//! phases survolés, ainsi que les coordonnées du curseur. Le même contenu est
+//! repris par les fiches épinglées.
+
+use eframe::egui;
+use egui_plot::PlotPoint;
+
+use crate::tools::background::get_background_zones;
+use crate::tools::hierarchy::show_parent_summary;
+use crate::tools::hit_stack::HitStack;
+use crate::tools::notes::show_notes;
+use crate::tools::pinned::PinnedCards;
+use crate::tools::presets::PresetRole;
+use crate::tools::range::RangeEstimate;
+use crate::tools::task::Task;
+use crate::tools::timefmt::TimeDisplay;
+use crate::tools::tooltip::render_template;
+use crate::tools::utils::{format_bandwidth, format_freq};
+use crate::tools::view::main_plot::MainPlot;
+use crate::tools::view::{task_hit, FrameView, PlotScene};
+
+/// Portée estimée de la tâche, en rouge si la cible est hors de portée.
+fn show_range(ui: &mut egui::Ui, range: Option<RangeEstimate>) {
+    match range {
+        Some(range) if range.is_short() => {
+            ui.colored_label(egui::Color32::RED, format!("{} – puissance insuffisante", range.label()));
+        }
+        Some(range) => {
+            ui.label(range.label());
+        }
+        None => {}
+    }
+}
+
+/// Contenu de l'info-bulle d'une tâche, repris par les fiches épinglées : le modèle
+/// d'info-bulle s'il est défini, sinon les champs par défaut.
+pub fn show_task_info(
+    ui: &mut egui::Ui,
+    task: &Task,
+    template: &str,
+    range: Option<RangeEstimate>,
+    time_display: &TimeDisplay,
+    time_span: f64,
+    freq_span: f64,
+) {
+    if !template.trim().is_empty() {
+        ui.label(render_template(template, task));
+        show_range(ui, range);
+        return;
+    }
+    ui.label(&task.name);
+    if let Some(platform) = &task.platform {
+        ui.label(format!("Plateforme : {}", platform));
+    }
+    if let Some(antenna) = &task.antenna {
+        ui.label(format!("Antenne : {}", antenna));
+    }
+    if let Some(location) = task.location {
+        ui.label(format!("Position : {}", location));
+    }
+    ui.label(format!(
+        "Amplifier: {:?}\nΔf: {}\nΔt: {:.*}ms\ntmin: {}\ntmax: {}\nfmin: {}\nfmax: {}",
+        task.amplifier,
+        format_bandwidth(task.freq_end - task.freq_start),
+        time_display.resolution.decimals_for_span(time_span),
+        task.time_end - task.time_start,
+        time_display.format_at(task.time_start, time_span),
+        time_display.format_at(task.time_end, time_span),
+        format_freq(task.freq_start, freq_span.min(task.freq_end - task.freq_start)),
+        format_freq(task.freq_end, freq_span.min(task.freq_end - task.freq_start))
+    ));
+    show_range(ui, range);
+    if !task.attachments.is_empty() {
+        ui.weak(format!("{} pièce(s) jointe(s) – inspecteur pour les ouvrir", task.attachments.len()));
+    }
+    if let Some(notes) = &task.notes {
+        ui.separator();
+        ui.set_max_width(320.);
+        show_notes(ui, notes);
+    }
+}
+
+/// Affiche l'info-bulle du point `hovered` survolé sur l'un des graphes ; la pile
+/// des tâches superposées suit les tâches survolées.
+pub fn show_hover_tooltips(ui: &mut egui::Ui, frame: &FrameView, scene: &PlotScene, plot: &MainPlot, hit_stack: &mut HitStack, hovered: Option<PlotPoint>) {
+    let (time_display, log, tasks) = (scene.time_display, scene.log_scale, scene.tasks);
+    let local_time = frame.local_time();
+    let shown = &frame.shown;
+    let display_tasks = frame.display_tasks(tasks);
+
+    if let Some(data_pos) = hovered {
+        let hovered_freq = if log {
+            10f64.powf(data_pos.x)
+        } else {
+            data_pos.x
+        };
+        let hovered_time = local_time(data_pos.y);
+
+        // Tooltip pour les tâches : toutes les tâches superposées sont listées et
+        // Alt + molette fait défiler la tâche détaillée
+        let hits: Vec<usize> = (0..tasks.len()).rev().filter(|&i| {
+            shown[i] && task_hit(&display_tasks[i], log, data_pos.x, data_pos.y, plot.hit_extent)
+        }).collect();
+        hit_stack.observe(tasks, &hits);
+        let (alt, scroll) = ui.input(|i| (i.modifiers.alt, i.raw_scroll_delta));
+        let scroll = scroll.x + scroll.y;
+        if alt && scroll != 0. && hits.len() > 1 {
+            hit_stack.cycle(if scroll < 0. { 1 } else { -1 });
+        }
+        let hovered_task = hit_stack.current(&hits);
+        if let Some(i) = hovered_task {
+            egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), ui.id().with("tooltip"), |ui| {
+                ui.set_min_width(120.);
+                if hits.len() > 1 {
+                    hit_stack.show(ui, tasks, &hits);
+                }
+                let range = scene.propagation.estimate(&tasks[i], scene.antennas);
+                show_task_info(ui, &tasks[i], &scene.tooltip.template, range, &time_display, plot.time_span, frame.freq_span);
+                // Tâche parente : synthèse de ses sous-tâches
+                show_parent_summary(ui, tasks, &frame.hierarchy, i, frame.freq_span);
+            });
+        }
+
+        // Tooltip pour les zones de fond si aucune tâche n'est survolée
+        if hovered_task.is_none() {
+            let zones: Vec<String> = get_background_zones(scene.zones, &scene.rx_guard)
+                .into_iter()
+                .filter(|z| scene.band_filter.shows_zone(z) && z.contains(hovered_freq, hovered_time))
+                .map(|z| z.name())
+                .chain(frame.masks.iter()
+                    .filter(|m| m.contains(hovered_freq, hovered_time))
+                    .map(|m| format!("Exclusion : {}", m.name)))
+                .chain(scene.presets.with_role(PresetRole::Target)
+                    .filter(|p| (p.freq_start..=p.freq_end).contains(&hovered_freq))
+                    .map(|p| format!("Cible : {}", p.name)))
+                .chain(scene.phases.iter()
+                    .filter(|p| p.contains(hovered_time))
+                    .map(|p| format!("Phase : {}", p.name)))
+                .collect();
+
+            // Affichage des zones de fond si elles sont survolées
+            if !zones.is_empty() {
+                egui::show_tooltip_at_pointer(ui.ctx(), ui.layer_id(), ui.id().with("tooltip"), |ui| {
+                    ui.set_min_width(80.);
+                    for label in zones {
+                        ui.label(label);
+                    }
+                });
+            }
+
+            // Affichage des coordonnées du curseur dans tous les cas
+            egui::show_tooltip_at_pointer(
+                ui.ctx(),
+                ui.layer_id(),
+                ui.id().with("tooltip"),
+                |ui| {
+                    ui.set_min_width(70.);
+                    ui.label(format!("{}\n{}", format_freq(hovered_freq, frame.freq_span), time_display.format_at(hovered_time, plot.time_span)));
+                },
+            );
+        }
+    }
+}
+
+/// Affiche les fiches épinglées, déplaçables et fermables individuellement, avec
+/// la précision des temps (`time_span`) et des fréquences (`freq_span`) de la vue.
+pub fn show_pinned_cards(ctx: &egui::Context, pinned: &mut PinnedCards, scene: &PlotScene, time_span: f64, freq_span: f64) {
+    let (template, time_display) = (&scene.tooltip.template, scene.time_display);
+    let (propagation, antennas) = (scene.propagation, scene.antennas);
+    pinned.show(ctx, scene.tasks, |ui, task| {
+        show_task_info(ui, task, template, propagation.estimate(task, antennas), &time_display, time_span, freq_span)
+    });
+}
@@ -0,0 +1,54 @@
+//! Module du mur d’images : chaque instance affiche une sous-bande fixe.
+//!
+//! Sur un mur de plusieurs écrans, plusieurs instances consomment le même flux
+//! de messages et affichent chacune, en plein écran, une bande d’amplification
+//! (`--band A960_1215`) ou une plage de fréquences (`--band 960:1215`, en MHz).
+//! La vue en fréquence est verrouillée sur cette plage (le zoom en temps reste
+//! possible) et `--hide-controls` masque le panneau latéral.
+
+use crate::tools::task::Amplifier;
+
+/// Plage de fréquences affichée par une instance du mur d’images.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct WallTile {
+    /// Fréquence minimale en MHz.
+    pub freq_min: f64,
+    /// Fréquence maximale en MHz.
+    pub freq_max: f64,
+    /// Amplificateur dont seules les tâches sont affichées, si la plage est une bande d’amplification.
+    pub amplifier: Option<Amplifier>,
+    /// Masque le panneau latéral des contrôles.
+    pub hide_controls: bool,
+}
+
+impl WallTile {
+    /// Analyse une plage `<amplificateur>` ou `<fmin>:<fmax>` (en MHz).
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne un message d’erreur si la plage est invalide.
+    pub fn parse(band: &str, hide_controls: bool) -> Result<Self, String> {
+        if let Some(amplifier) = Amplifier::from_str(band) {
+            let (freq_min, freq_max) = amplifier.band();
+            return Ok(Self { freq_min, freq_max, amplifier: Some(amplifier), hide_controls });
+        }
+        let invalid = || format!("Plage « {} » invalide : amplificateur ou <fmin>:<fmax> en MHz attendu", band);
+        let (min, max) = band.split_once(':').ok_or_else(invalid)?;
+        let (freq_min, freq_max) = (min.trim().parse::<f64>(), max.trim().parse::<f64>());
+        match (freq_min, freq_max) {
+            (Ok(freq_min), Ok(freq_max)) if freq_min > 0. && freq_min < freq_max && freq_max.is_finite() => {
+                Ok(Self { freq_min, freq_max, amplifier: None, hide_controls })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Bornes de l’axe X du graphe (en log10 si `log`).
+    pub fn x_bounds(&self, log: bool) -> (f64, f64) {
+        if log {
+            (self.freq_min.log10(), self.freq_max.log10())
+        } else {
+            (self.freq_min, self.freq_max)
+        }
+    }
+}
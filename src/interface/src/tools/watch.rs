@@ -0,0 +1,130 @@
+//! Module de surveillance d’un fichier de plan (`--watch <plan.json>`).
+//!
+//! Le fichier est surveillé par le système de fichiers (crate `notify`) ; il est
+//! chargé au démarrage (remplacement du plan), puis relu à chaque modification et
+//! comparé à sa version précédente : seul l’écart ([`PlanEvent::Patch`] : tâches
+//! ajoutées, modifiées ou supprimées) est publié sur le bus, sans réinitialiser
+//! l’affichage. Un planificateur externe peut ainsi piloter l’interface en
+//! réécrivant simplement un fichier.
+//!
+//! La même surveillance ([`spawn_file_watcher`]) sert au rechargement à chaud
+//! des fichiers de configuration (module `config_watch`).
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::tools::events::PlanEvent;
+use crate::tools::plan::load_plan;
+use crate::tools::task::Task;
+
+/// Délai laissé à l’écrivain pour terminer l’écriture du fichier avant sa relecture.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Relit le plan et vérifie chacune de ses tâches.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si le plan est illisible ou si une tâche est invalide.
+pub fn read_watched_plan(path: &Path) -> Result<Vec<Task>, String> {
+    let tasks = load_plan(path)?;
+    for task in &tasks {
+        task.validate().map_err(|e| format!("Plan {} : tâche « {} » invalide : {}", path.display(), task.name, e))?;
+    }
+    Ok(tasks)
+}
+
+/// Modification du plan entre la version `previous` du fichier (`None` au
+/// premier chargement) et sa version `tasks`, ou `None` si le plan est inchangé.
+pub fn plan_update(path: &Path, previous: Option<&[Task]>, tasks: &[Task]) -> Option<PlanEvent> {
+    match previous {
+        None => Some(PlanEvent::Replace {
+            tasks: tasks.to_vec(),
+            action: "watch_reload",
+            description: format!("Plan {} chargé", path.display()),
+        }),
+        Some(previous) if previous == tasks => None,
+        Some(previous) => Some(PlanEvent::diff(previous, tasks, "watch_reload", format!("Plan {} modifié", path.display()))),
+    }
+}
+
+/// Surveille les fichiers `paths` dans un thread dédié et transmet à `on_change`
+/// chaque fichier créé, modifié ou supprimé.
+///
+/// Les dossiers parents sont surveillés plutôt que les fichiers eux-mêmes, car
+/// de nombreux outils remplacent le fichier au lieu de le réécrire, et un
+/// fichier absent au démarrage peut ainsi être créé par la suite.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la surveillance ne peut pas être mise en place.
+pub fn spawn_file_watcher(paths: Vec<PathBuf>, mut on_change: impl FnMut(&Path) + Send + 'static) -> Result<(), String> {
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| format!("Surveillance des fichiers impossible : {}", e))?;
+    let mut dirs: Vec<&Path> = paths.iter().map(|p| p.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."))).collect();
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Surveillance de {} impossible : {}", dir.display(), e))?;
+    }
+
+    thread::spawn(move || {
+        // Le surveillant doit vivre aussi longtemps que le thread
+        let _watcher = watcher;
+        for result in &rx {
+            let event = match result {
+                Ok(event) => event,
+                Err(e) => {
+                    eprintln!("Surveillance des fichiers : {}", e);
+                    continue;
+                }
+            };
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                continue;
+            }
+            let mut changed: Vec<&PathBuf> = paths.iter().filter(|p| event.paths.iter().any(|e| e.file_name() == p.file_name())).collect();
+            if changed.is_empty() {
+                continue;
+            }
+            // Regroupe les événements d’une même écriture
+            thread::sleep(DEBOUNCE);
+            for event in rx.try_iter().flatten() {
+                changed.extend(paths.iter().filter(|p| event.paths.iter().any(|e| e.file_name() == p.file_name())));
+            }
+            changed.sort();
+            changed.dedup();
+            for path in changed {
+                on_change(path);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Surveille le fichier de plan et transmet à `on_plan` le plan lu au démarrage
+/// puis l’écart apporté par chaque modification effective du fichier.
+///
+/// # Erreurs
+///
+/// Retourne un message d’erreur si la surveillance ne peut pas être mise en place.
+pub fn spawn_plan_watcher(path: PathBuf, on_plan: impl Fn(PlanEvent) + Send + 'static) -> Result<(), String> {
+    let mut last: Option<Vec<Task>> = None;
+    let mut reload = move |path: &Path| match read_watched_plan(path) {
+        Ok(tasks) => {
+            if let Some(event) = plan_update(path, last.as_deref(), &tasks) {
+                on_plan(event);
+            }
+            last = Some(tasks);
+        }
+        Err(e) => eprintln!("{}", e),
+    };
+    reload(&path);
+    spawn_file_watcher(vec![path.clone()], reload)?;
+    eprintln!("Surveillance du plan {}", path.display());
+    Ok(())
+}
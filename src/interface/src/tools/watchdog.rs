@@ -0,0 +1,114 @@
+//! Module de surveillance du temps de rendu.
+//!
+//! En cas de surcharge (plan de plusieurs dizaines de milliers de tâches, rafale
+//! de messages), le temps de construction d’une image peut dépasser le budget
+//! d’une cadence fluide et l’affichage ne plus répondre. Le chien de garde mesure
+//! la durée de chaque image : après [`OVERRUNS_TO_DEGRADE`] dépassements
+//! consécutifs du budget, l’affichage passe en mode dégradé (libellés du graphe
+//! masqués, tâches agrégées par niveau de détail, animations suspendues) et un
+//! indicateur le signale. Le mode normal est rétabli après
+//! [`RECOVERY_FRAMES`] images consécutives sous la moitié du budget.
+
+use std::time::Duration;
+
+use egui::Color32;
+
+/// Budget par défaut d’une image, en ms (30 images/s).
+pub const DEFAULT_BUDGET_MS: f32 = 33.;
+
+/// Nombre de dépassements consécutifs du budget déclenchant le mode dégradé.
+pub const OVERRUNS_TO_DEGRADE: u32 = 3;
+
+/// Nombre d’images consécutives sous la moitié du budget rétablissant le mode normal.
+pub const RECOVERY_FRAMES: u32 = 60;
+
+/// Couleur de l’indicateur du mode dégradé.
+const DEGRADED_COLOR: Color32 = Color32::from_rgb(255, 170, 0);
+
+/// Chien de garde du temps de rendu.
+pub struct FrameWatchdog {
+    /// Indique si la dégradation automatique est active.
+    pub enabled: bool,
+    /// Budget d’une image en ms.
+    pub budget_ms: f32,
+    /// Durée de la dernière image en ms.
+    last_ms: f32,
+    /// Nombre de dépassements consécutifs du budget.
+    overruns: u32,
+    /// Nombre d’images consécutives sous la moitié du budget depuis la dégradation.
+    calm: u32,
+    /// Indique si l’affichage est dégradé.
+    degraded: bool,
+}
+
+impl Default for FrameWatchdog {
+    fn default() -> Self {
+        Self { enabled: true, budget_ms: DEFAULT_BUDGET_MS, last_ms: 0., overruns: 0, calm: 0, degraded: false }
+    }
+}
+
+impl FrameWatchdog {
+    /// Enregistre la durée d’une image et met à jour le mode d’affichage.
+    pub fn record(&mut self, elapsed: Duration) {
+        self.last_ms = elapsed.as_secs_f32() * 1000.;
+        if !self.enabled {
+            self.overruns = 0;
+            self.degraded = false;
+            return;
+        }
+        if self.last_ms > self.budget_ms {
+            self.overruns += 1;
+            self.calm = 0;
+            if self.overruns >= OVERRUNS_TO_DEGRADE && !self.degraded {
+                self.degraded = true;
+                eprintln!("Rendu : {} images au-delà de {:.0} ms, passage en mode dégradé.", self.overruns, self.budget_ms);
+            }
+            return;
+        }
+        self.overruns = 0;
+        if self.degraded && self.last_ms < self.budget_ms / 2. {
+            self.calm += 1;
+            if self.calm >= RECOVERY_FRAMES {
+                self.degraded = false;
+                self.calm = 0;
+                eprintln!("Rendu : retour au mode normal.");
+            }
+        }
+    }
+
+    /// Indique si l’affichage est dégradé.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded
+    }
+
+    /// Affiche les réglages dans le panneau latéral.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, "Dégradation automatique")
+            .on_hover_text("Masque les libellés, agrège les tâches et suspend les animations quand le rendu dépasse le budget");
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Budget par image :");
+                ui.add(egui::DragValue::new(&mut self.budget_ms).range(5.0..=500.0).suffix(" ms"));
+            });
+        });
+        ui.weak(format!("Dernière image : {:.1} ms", self.last_ms));
+    }
+
+    /// Affiche l’indicateur du mode dégradé dans le coin supérieur droit de la fenêtre.
+    pub fn show_indicator(&self, ctx: &egui::Context) {
+        if !self.degraded {
+            return;
+        }
+        egui::Area::new(egui::Id::new("degraded_indicator"))
+            .anchor(egui::Align2::RIGHT_TOP, [-8., 8.])
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.colored_label(DEGRADED_COLOR, "⚠ Mode dégradé").on_hover_text(format!(
+                        "Dernière image : {:.1} ms (budget {:.0} ms) ; libellés masqués, tâches agrégées, animations suspendues",
+                        self.last_ms, self.budget_ms
+                    ));
+                });
+            });
+    }
+}
@@ -0,0 +1,176 @@
+//! Module du widget Gantt fréquence/temps réutilisable.
+//!
+//! [`FreqTimeGantt`] dessine un plan dans n’importe quelle interface egui, sans
+//! lancer l’application ni un processus séparé :
+//!
+//! ```ignore
+//! let response = FreqTimeGantt::new("plan").show(ui, &plan, &view);
+//! if let Some(i) = response.clicked {
+//!     println!("Tâche cliquée : {}", plan.tasks[i].name);
+//! }
+//! view.bounds = Some(response.bounds);
+//! ```
+//!
+//! Le widget dessine avec les fonctions du graphe principal
+//! ([`crate::tools::view::paint`]) : maillage des tâches colorées, crochets des
+//! tâches parentes, halos de tolérance, phases, marges de bande, masques
+//! d’exclusion hachurés et fin de la garde Rx ; il n’a ni les panneaux, ni
+//! l’édition, ni le protocole de l’application.
+
+use egui::{Color32, Stroke, Ui};
+use egui_plot::{log_grid_spacer, uniform_grid_spacer, GridMark, Plot, PlotBounds};
+
+use crate::tools::amplifiers::amplifier_color;
+use crate::tools::coloring::{compute_colors, ColorBy};
+use crate::tools::exclusion::ExclusionMask;
+use crate::tools::headroom::Headroom;
+use crate::tools::hierarchy::Hierarchy;
+use crate::tools::phase::Phase;
+use crate::tools::rx_guard::RxGuard;
+use crate::tools::task::{Amplifier, Task};
+use crate::tools::task_mesh::{hit_extent, screen_outline, task_mesh};
+use crate::tools::utils::{format_freq, get_bounds, MAX_TIME};
+use crate::tools::view::paint::{paint_brackets, paint_exclusion, paint_tolerance_halos, plot_headroom, plot_phases, plot_rx_guard};
+use crate::tools::view::task_hit;
+
+/// Plan affiché par le widget.
+#[derive(Clone, Default)]
+pub struct PlanStore {
+    /// Tâches du plan.
+    pub tasks: Vec<Task>,
+    /// Masques d’exclusion.
+    pub exclusions: Vec<ExclusionMask>,
+    /// Phases de la mission.
+    pub phases: Vec<Phase>,
+    /// Garde Rx, tracée si sa durée est non nulle.
+    pub rx_guard: Option<RxGuard>,
+}
+
+/// Réglages de vue du widget.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct ViewState {
+    /// Axe des fréquences en échelle logarithmique.
+    pub log_scale: bool,
+    /// Limites imposées `[x_min, t_min, x_max, t_max]` (x en log10 des MHz si
+    /// `log_scale`), ou `None` pour la bande complète sur toute la mission.
+    pub bounds: Option<[f64; 4]>,
+    /// Attribut de coloration des tâches.
+    pub color_by: ColorBy,
+    /// Marges aux bords de bande tracées dans la bande des amplificateurs utilisés.
+    pub headroom: Option<Headroom>,
+}
+
+/// Résultat de l’affichage du widget.
+pub struct GanttResponse {
+    /// Réponse egui du graphe.
+    pub response: egui::Response,
+    /// Indice de la tâche survolée.
+    pub hovered: Option<usize>,
+    /// Indice de la tâche cliquée.
+    pub clicked: Option<usize>,
+    /// Limites de la vue après interaction, au format de [`ViewState::bounds`].
+    pub bounds: [f64; 4],
+}
+
+/// Widget Gantt fréquence/temps.
+pub struct FreqTimeGantt {
+    /// Identifiant du graphe (mémoire de zoom d’egui_plot).
+    id: String,
+    /// Hauteur du graphe en points, ou toute la hauteur disponible.
+    height: Option<f32>,
+}
+
+impl FreqTimeGantt {
+    /// Crée un widget d’identifiant `id`, unique dans l’interface hôte.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into(), height: None }
+    }
+
+    /// Fixe la hauteur du graphe en points.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Affiche le plan avec les réglages de vue donnés.
+    pub fn show(&self, ui: &mut Ui, plan: &PlanStore, view: &ViewState) -> GanttResponse {
+        let log = view.log_scale;
+        let colors = compute_colors(&plan.tasks, view.color_by).colors;
+        let hierarchy = Hierarchy::new(&plan.tasks);
+        let filled: Vec<bool> = (0..plan.tasks.len()).map(|i| !hierarchy.is_parent(i)).collect();
+        let spacer = if log { log_grid_spacer(10) } else { uniform_grid_spacer(|_| [10., 100., 1000.]) };
+        let formatter = move |mark: GridMark, range: &std::ops::RangeInclusive<f64>| {
+            let freq = |v: f64| if log { 10f64.powf(v) } else { v };
+            format_freq(freq(mark.value), freq(*range.end()) - freq(*range.start()))
+        };
+
+        let (x_min, x_max) = get_bounds(log);
+        let mut plot = Plot::new(&self.id)
+            .x_axis_formatter(formatter)
+            .x_grid_spacer(spacer)
+            .include_x(x_min)
+            .include_x(x_max)
+            .include_y(0.)
+            .include_y(MAX_TIME)
+            .show_grid([false, false])
+            .label_formatter(|_, _| String::new());
+        if let Some(height) = self.height {
+            plot = plot.height(height);
+        }
+        let plot_response = plot.show(ui, |plot_ui| {
+            if let Some([x0, t0, x1, t1]) = view.bounds {
+                plot_ui.set_plot_bounds(PlotBounds::from_min_max([x0, t0], [x1, t1]));
+            }
+            plot_phases(plot_ui, &plan.phases, log, 0., None);
+            if let Some(headroom) = view.headroom.filter(|h| h.enabled) {
+                for amp in Amplifier::ALL.into_iter().filter(|amp| plan.tasks.iter().any(|t| t.amplifier == *amp)) {
+                    plot_headroom(plot_ui, &headroom, amp, log, (0., MAX_TIME), amplifier_color(amp));
+                }
+            }
+            if let Some(rx_guard) = plan.rx_guard.filter(|g| g.duration > 0.) {
+                plot_rx_guard(plot_ui, log, rx_guard.duration, true);
+            }
+            plot_ui.pointer_coordinate()
+        });
+
+        let transform = plot_response.transform;
+        let painter = ui.painter_at(*transform.frame());
+        // Les tâches parentes sont dessinées en crochets englobant leurs descendantes
+        let visible: Vec<(&Task, Color32)> =
+            plan.tasks.iter().zip(colors.iter().copied()).zip(&filled).filter(|(_, f)| **f).map(|(tc, _)| tc).collect();
+        paint_tolerance_halos(&painter, &transform, log, visible.iter().copied());
+        painter.add(task_mesh(visible.iter().copied(), &transform, log));
+        let shown = vec![true; plan.tasks.len()];
+        paint_brackets(&painter, &transform, log, &plan.tasks, &hierarchy, &colors, &shown);
+        for mask in &plan.exclusions {
+            paint_exclusion(&painter, &transform, log, mask, 0.);
+        }
+
+        // Tâche sous le curseur : la dernière dessinée, donc celle du dessus
+        let extent = hit_extent(transform.bounds(), *transform.frame());
+        let hovered = plot_response.inner.and_then(|pt| {
+            plan.tasks.iter().rposition(|t| task_hit(t, log, pt.x, pt.y, extent))
+        });
+        let response = plot_response.response;
+        if let Some(i) = hovered {
+            let task = &plan.tasks[i];
+            painter.add(egui::Shape::closed_line(screen_outline(task, &transform, log), Stroke::new(2., Color32::WHITE)));
+            response.clone().on_hover_text_at_pointer(format!(
+                "{}\n{} – {}\n{:.0} – {:.0} ms",
+                task.name,
+                format_freq(task.freq_start, task.freq_end - task.freq_start),
+                format_freq(task.freq_end, task.freq_end - task.freq_start),
+                task.time_start,
+                task.time_end
+            ));
+        }
+        let clicked = hovered.filter(|_| response.clicked());
+        let bounds = transform.bounds();
+        GanttResponse {
+            response,
+            hovered,
+            clicked,
+            bounds: [bounds.min()[0], bounds.min()[1], bounds.max()[0], bounds.max()[1]],
+        }
+    }
+}
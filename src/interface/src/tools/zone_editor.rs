@@ -0,0 +1,185 @@
+//! Module de l’éditeur de zones de fond.
+//!
+//! L’éditeur est une fenêtre permettant de créer, modifier et supprimer les zones
+//! de fond (type, sommets du polygone, couleurs, étiquette) pendant l’exécution.
+//! Les modifications sont appliquées immédiatement au graphe et peuvent être
+//! enregistrées dans le fichier de configuration des zones.
+
+use std::path::Path;
+
+use crate::tools::background::{default_zone_definitions, load_zones, save_zones, ZoneDefinition, ZoneLabel, ZONES_PATH};
+use crate::tools::geometry::{format_points, parse_points};
+use crate::tools::task::Amplifier;
+use crate::tools::utils::MAX_TIME;
+
+/// Nom affiché d’une définition de zone.
+fn zone_name(zone: &ZoneDefinition) -> String {
+    match (&zone.label, zone.amplifier) {
+        (Some(label), _) if !label.text.is_empty() => label.text.replace('\n', " "),
+        (_, Some(amp)) => amp.label().into(),
+        _ => "Zone".into(),
+    }
+}
+
+/// État de la fenêtre d’édition des zones.
+pub struct ZoneEditor {
+    /// Chemin du fichier de configuration des zones.
+    pub path: String,
+    /// Indice de la zone en cours d’édition.
+    selected: Option<usize>,
+    /// Saisie des sommets de la zone sélectionnée.
+    points_text: String,
+    /// Message de la dernière action ou erreur de saisie.
+    message: Option<String>,
+}
+
+impl Default for ZoneEditor {
+    fn default() -> Self {
+        Self {
+            path: ZONES_PATH.into(),
+            selected: None,
+            points_text: String::new(),
+            message: None,
+        }
+    }
+}
+
+impl ZoneEditor {
+    /// Sélectionne la zone `index` pour l’édition.
+    fn select(&mut self, index: Option<usize>, zones: &[ZoneDefinition]) {
+        self.selected = index;
+        self.points_text = index.map(|i| format_points(&zones[i].points)).unwrap_or_default();
+    }
+
+    /// Affiche la fenêtre de l’éditeur ; les modifications s’appliquent directement à `zones`.
+    ///
+    /// `open` passe à `false` lorsque la fenêtre est fermée.
+    pub fn show(&mut self, ctx: &egui::Context, zones: &mut Vec<ZoneDefinition>, open: &mut bool) {
+        egui::Window::new("Éditeur de zones").open(open).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Fichier :");
+                ui.text_edit_singleline(&mut self.path);
+                if ui.button("Recharger").clicked() {
+                    match load_zones(Path::new(&self.path)) {
+                        Ok(loaded) => {
+                            *zones = loaded;
+                            self.message = Some(format!("{} zones chargées", zones.len()));
+                        }
+                        Err(e) => self.message = Some(e),
+                    }
+                    self.select(None, zones);
+                }
+                if ui.button("Enregistrer").clicked() {
+                    self.message = Some(match save_zones(Path::new(&self.path), zones) {
+                        Ok(()) => format!("Zones enregistrées dans {}", self.path),
+                        Err(e) => e,
+                    });
+                }
+                if ui.button("Par défaut").clicked() {
+                    *zones = default_zone_definitions();
+                    self.select(None, zones);
+                }
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("zone_editor_selected")
+                    .selected_text(self.selected.map(|i| zone_name(&zones[i])).unwrap_or("—".into()))
+                    .show_ui(ui, |ui| {
+                        for i in 0..zones.len() {
+                            if ui.selectable_label(self.selected == Some(i), zone_name(&zones[i])).clicked() {
+                                self.select(Some(i), zones);
+                            }
+                        }
+                    });
+                if ui.button("Nouvelle zone").clicked() {
+                    zones.push(ZoneDefinition {
+                        amplifier: None,
+                        points: vec![[100., 0.], [200., 0.], [200., MAX_TIME], [100., MAX_TIME]],
+                        stroke_width: 1.,
+                        stroke_color: [255, 255, 255, 255],
+                        fill: [255, 255, 255, 40],
+                        label: Some(ZoneLabel { text: "Nouvelle zone".into(), position: [150., MAX_TIME / 2.], color: [255, 255, 255, 255] }),
+                    });
+                    self.select(Some(zones.len() - 1), zones);
+                }
+                if ui.add_enabled(self.selected.is_some(), egui::Button::new("Supprimer")).clicked() {
+                    if let Some(i) = self.selected {
+                        zones.remove(i);
+                    }
+                    self.select(None, zones);
+                }
+            });
+
+            let Some(zone) = self.selected.and_then(|i| zones.get_mut(i)) else {
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+                return;
+            };
+            egui::Grid::new("zone_editor_fields").num_columns(2).show(ui, |ui| {
+                ui.label("Type");
+                egui::ComboBox::from_id_salt("zone_editor_kind")
+                    .selected_text(zone.amplifier.map_or("Zone libre".into(), |a| format!("{:?}", a)))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut zone.amplifier, None, "Zone libre");
+                        for amp in Amplifier::ALL {
+                            ui.selectable_value(&mut zone.amplifier, Some(amp), format!("{:?}", amp));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Sommets (f,t; …)");
+                if ui.text_edit_multiline(&mut self.points_text).changed() {
+                    match parse_points(&self.points_text) {
+                        Ok(points) => {
+                            zone.points = points;
+                            self.message = None;
+                        }
+                        Err(e) => self.message = Some(e),
+                    }
+                }
+                ui.end_row();
+
+                ui.label("Contour");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut zone.stroke_width).range(0.0..=10.0).speed(0.1));
+                    ui.color_edit_button_srgba_unmultiplied(&mut zone.stroke_color);
+                });
+                ui.end_row();
+
+                ui.label("Remplissage");
+                ui.color_edit_button_srgba_unmultiplied(&mut zone.fill);
+                ui.end_row();
+
+                ui.label("Étiquette");
+                let mut has_label = zone.label.is_some();
+                if ui.checkbox(&mut has_label, "").changed() {
+                    zone.label = has_label.then(|| ZoneLabel {
+                        text: zone_name(zone),
+                        position: zone.points.first().copied().unwrap_or_default(),
+                        color: zone.stroke_color,
+                    });
+                }
+                ui.end_row();
+
+                if let Some(label) = &mut zone.label {
+                    ui.label("Texte");
+                    ui.text_edit_singleline(&mut label.text);
+                    ui.end_row();
+
+                    ui.label("Position (MHz, ms)");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut label.position[0]).suffix(" MHz"));
+                        ui.add(egui::DragValue::new(&mut label.position[1]).suffix(" ms"));
+                        ui.color_edit_button_srgba_unmultiplied(&mut label.color);
+                    });
+                    ui.end_row();
+                }
+            });
+            if let Some(message) = &self.message {
+                ui.label(message);
+            }
+        });
+    }
+}
@@ -0,0 +1,379 @@
+//! Tests de bout en bout du contrat d’échange entre l’émetteur et l’interface.
+//!
+//! L’interface est lancée en mode `--headless` dans un processus séparé ; le test
+//! joue le rôle de l’émetteur et vérifie les événements émis et l’état final.
+
+use std::process::Command;
+
+use egui_test::tools::compression::Compression;
+use egui_test::tools::headless::{check_self_test, run_script, SELF_TEST_SCRIPT};
+use egui_test::tools::plan_hash::plan_hash;
+use egui_test::tools::task::{Amplifier, Task};
+use egui_test::tools::protocol::{ProtocolEvent, MAX_MESSAGE_BYTES};
+
+fn interface() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_egui_test"))
+}
+
+#[test]
+fn self_test_script_produces_expected_state() {
+    let (events, snapshot) = run_script(interface(), &SELF_TEST_SCRIPT).unwrap();
+    check_self_test(&events, &snapshot).unwrap();
+}
+
+#[test]
+fn self_test_mode_succeeds() {
+    let status = interface().arg("--self-test").status().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn demo_sender_messages_are_upserted_by_name() {
+    // Messages cycliques de l’émetteur de démonstration, puis rediffusion modifiée
+    let script = [
+        r#"{"name":"Init capteurs","freq_start":100.0,"freq_end":300.0,"time_start":0.0,"time_end":300.0,"amplifier":"A20_500"}"#,
+        r#"{"name":"Transmission","freq_start":1000.0,"freq_end":2500.0,"time_start":300.0,"time_end":600.0,"amplifier":"A1000_2500"}"#,
+        r#"{"name":"Sleep mode","freq_start":5000.0,"freq_end":5500.0,"time_start":0.0,"time_end":1000.0,"amplifier":"A2400_6000"}"#,
+        r#"{"name":"Init capteurs","freq_start":100.0,"freq_end":300.0,"time_start":50.0,"time_end":350.0,"amplifier":"A20_500"}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    let added = |key: &str| ProtocolEvent::TaskAdded { key: key.into() };
+    assert_eq!(
+        events,
+        vec![
+            added("Init capteurs"),
+            added("Transmission"),
+            added("Sleep mode"),
+            ProtocolEvent::TaskUpdated { key: "Init capteurs".into() },
+        ]
+    );
+    assert_eq!(snapshot.messages_received, 4);
+    assert_eq!(snapshot.tasks.len(), 3);
+    assert_eq!(snapshot.tasks[0].name, "Init capteurs");
+    assert_eq!(snapshot.tasks[0].time_start, 50.);
+}
+
+#[test]
+fn tasks_with_an_id_are_keyed_by_id() {
+    let script = [
+        r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#,
+        r#"{"id":"T2","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":200,"time_end":300,"amplifier":"A960_1215"}"#,
+        r#"{"id":"T1","name":"Radar bis","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":150,"amplifier":"A960_1215"}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events[2], ProtocolEvent::TaskUpdated { key: "T1".into() });
+    assert_eq!(snapshot.tasks.len(), 2);
+    assert_eq!(snapshot.tasks[0].name, "Radar bis");
+}
+
+#[test]
+fn invalid_messages_emit_error_events_without_changing_state() {
+    let script = ["{", r#"{"rx_guard_ms": "long"}"#, r#"{"remove_where": "fmin >"}"#];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| matches!(e, ProtocolEvent::Error { .. })));
+    assert!(snapshot.tasks.is_empty());
+}
+
+#[test]
+fn oversized_messages_are_rejected_and_reading_continues() {
+    let oversized = format!("{{\"name\":\"{}\"}}", "x".repeat(MAX_MESSAGE_BYTES));
+    let script = [oversized.as_str(), r#"{"rx_guard_ms": 80}"#];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(matches!(events[0], ProtocolEvent::Error { .. }));
+    assert_eq!(events[1], ProtocolEvent::RxGuardSet { duration_ms: 80. });
+    assert_eq!(snapshot.rx_guard_ms, 80.);
+}
+
+#[test]
+fn repeated_task_messages_are_ignored() {
+    let task = r#"{"name":"Init capteurs","freq_start":100.0,"freq_end":300.0,"time_start":0.0,"time_end":300.0,"amplifier":"A20_500"}"#;
+    let reordered = r#"{"amplifier":"A20_500","time_end":300.0,"time_start":0.0,"freq_end":300.0,"freq_start":100.0,"name":"Init capteurs"}"#;
+    let (events, snapshot) = run_script(interface(), &[task, reordered, task]).unwrap();
+
+    assert_eq!(
+        events,
+        vec![
+            ProtocolEvent::TaskAdded { key: "Init capteurs".into() },
+            ProtocolEvent::DuplicateIgnored,
+            ProtocolEvent::DuplicateIgnored,
+        ]
+    );
+    assert_eq!(snapshot.tasks.len(), 1);
+}
+
+#[test]
+fn rebroadcast_plan_converges_and_hash_checks_follow_it() {
+    let a = r#"{"name":"A","freq_start":100,"freq_end":300,"time_start":0,"time_end":300,"amplifier":"A20_500"}"#;
+    let b = r#"{"name":"B","freq_start":1000,"freq_end":2500,"time_start":300,"time_end":600,"amplifier":"A1000_2500"}"#;
+    let c = r#"{"name":"C","freq_start":5000,"freq_end":5500,"time_start":0,"time_end":1000,"amplifier":"A2400_6000"}"#;
+    let expected: [Task; 2] = [serde_json::from_str(b).unwrap(), serde_json::from_str(c).unwrap()];
+    let hash = plan_hash(&expected);
+    let plan = format!(r#"{{"plan": [{}, {}], "hash": "{}"}}"#, b, c, hash);
+    let check = format!(r#"{{"plan_hash": "{}"}}"#, hash);
+    let (events, snapshot) = run_script(interface(), &[a, b, plan.as_str(), check.as_str()]).unwrap();
+
+    assert_eq!(events[2], ProtocolEvent::PlanApplied { added: 1, updated: 0, removed: 1, hash_mismatch: false });
+    assert_eq!(events[3], ProtocolEvent::PlanVerified);
+    assert_eq!(snapshot.tasks.len(), 2);
+}
+
+#[test]
+fn plan_hash_mismatch_requests_a_resync() {
+    let a = r#"{"name":"A","freq_start":100,"freq_end":300,"time_start":0,"time_end":300,"amplifier":"A20_500"}"#;
+    let (events, snapshot) = run_script(interface(), &[a, r#"{"plan_hash": "0000000000000000"}"#]).unwrap();
+
+    assert_eq!(
+        events[1],
+        ProtocolEvent::ResyncRequested { local_hash: plan_hash(&snapshot.tasks), remote_hash: "0000000000000000".into() }
+    );
+}
+
+#[test]
+fn compressed_plan_is_applied_after_negotiation() {
+    let a = r#"{"name":"A","freq_start":100,"freq_end":300,"time_start":0,"time_end":300,"amplifier":"A20_500"}"#;
+    let plan = format!(r#"{{"plan": [{}]}}"#, a);
+    let compressed = format!(r#"{{"compressed": "gzip", "data": "{}"}}"#, Compression::Gzip.compress(&plan));
+    let hello = r#"{"hello": {"client": "test", "compression": ["brotli", "gzip"]}}"#;
+    let (events, snapshot) = run_script(interface(), &[compressed.as_str(), hello, compressed.as_str()]).unwrap();
+
+    // Avant la poignée de main, la compression n’est pas négociée
+    assert!(matches!(events[0], ProtocolEvent::Error { .. }));
+    assert_eq!(events[1], ProtocolEvent::Welcome { compression: Some("gzip".into()) });
+    assert_eq!(events[2], ProtocolEvent::PlanApplied { added: 1, updated: 0, removed: 0, hash_mismatch: false });
+    assert_eq!(snapshot.tasks.len(), 1);
+}
+
+#[test]
+fn split_then_join_restores_the_task() {
+    let script = [
+        r#"{"name":"Balayage","freq_start":30,"freq_end":90,"time_start":100,"time_end":500,"amplifier":"A20_500"}"#,
+        r#"{"split":{"key":"Balayage","at":300}}"#,
+        r#"{"split":{"key":"Balayage","axis":"frequency","at":200}}"#,
+        r#"{"join":["Balayage","Balayage (2)"]}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events[1], ProtocolEvent::TaskSplit { key: "Balayage".into(), new_key: "Balayage (2)".into() });
+    assert!(matches!(events[2], ProtocolEvent::Error { .. }));
+    assert_eq!(events[3], ProtocolEvent::TasksJoined { key: "Balayage".into(), removed_key: "Balayage (2)".into() });
+    assert_eq!(snapshot.tasks.len(), 1);
+    assert_eq!((snapshot.tasks[0].time_start, snapshot.tasks[0].time_end), (100., 500.));
+}
+
+#[test]
+fn detections_are_acknowledged_and_validated() {
+    let script = [
+        r#"{"detection":{"time":420,"freq":2950,"bearing":135,"label":"Radar SA"}}"#,
+        r#"{"detection":{"time":100,"freq":-5}}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events[0], ProtocolEvent::DetectionReceived { label: "Radar SA".into() });
+    assert!(matches!(events[1], ProtocolEvent::Error { .. }));
+    assert!(snapshot.tasks.is_empty());
+}
+
+#[test]
+fn built_tasks_round_trip_through_the_protocol() {
+    let radar = Task::builder("Radar L", Amplifier::A960_1215)
+        .id("R1")
+        .freq(1000., 1200.)
+        .time(50., 250.)
+        .priority(3)
+        .metadata("opérateur", "OPS 2")
+        .build()
+        .unwrap();
+    let sweep = Task::sweep("Balayage VHF", Amplifier::A20_500, (30., 90.), (600., 900.), 10.);
+    let block = Task::block("Sleep mode", Amplifier::A2400_6000, (5000., 5500.), (0., 1000.));
+    let expected = [radar, sweep, block];
+    let messages: Vec<String> = expected.iter().map(|t| serde_json::to_string(t).unwrap()).collect();
+    let script: Vec<&str> = messages.iter().map(String::as_str).collect();
+    let (_, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(snapshot.tasks, expected);
+    assert!(Task::builder("Inversée", Amplifier::A20_500).freq(300., 100.).time(0., 10.).build().is_err());
+}
+
+#[test]
+fn sub_tasks_keep_their_parent_and_do_not_conflict_with_it() {
+    let mission = Task::builder("Mission", Amplifier::A1000_2500).id("M1").freq(1000., 2000.).time(0., 600.).build().unwrap();
+    let serial = Task::builder("Série 1", Amplifier::A1000_2500).parent("M1").freq(1100., 1300.).time(100., 300.).build().unwrap();
+    let messages: Vec<String> = [&mission, &serial].iter().map(|t| serde_json::to_string(t).unwrap()).collect();
+    let script: Vec<&str> = messages.iter().map(String::as_str).collect();
+    let (_, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(snapshot.tasks[1].parent_id.as_deref(), Some("M1"));
+    assert!(egui_test::tools::constraints::find_conflicts(&snapshot.tasks).is_empty());
+}
+
+#[test]
+fn highlight_command_targets_a_known_task() {
+    let script = [
+        r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#,
+        r#"{"cmd":"highlight","id":"T1","duration_ms":3000,"zoom":true}"#,
+        r#"{"cmd":"highlight","id":"T9"}"#,
+    ];
+    let (events, _) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events[1], ProtocolEvent::TaskHighlighted { key: "T1".into() });
+    assert!(matches!(events[2], ProtocolEvent::Error { .. }));
+}
+
+#[test]
+fn check_mode_fails_on_an_invalid_configuration() {
+    let dir = std::env::temp_dir().join(format!("gantt_check_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let status = interface().arg("--check").current_dir(&dir).status().unwrap();
+    assert!(status.success());
+
+    std::fs::write(dir.join("amplifiers.json"), "{").unwrap();
+    let output = interface().arg("--check").current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[ÉCHEC] Configuration amplifiers.json"));
+}
+
+#[test]
+fn removed_tasks_are_moved_to_the_trash() {
+    let script = [
+        r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#,
+        r#"{"id":"T2","name":"Liaison","freq_start":5200,"freq_end":5500,"time_start":0,"time_end":100,"amplifier":"A2400_6000"}"#,
+        r#"{"remove_where": "fmin > 5000"}"#,
+        r#"{"plan": []}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(events[2], ProtocolEvent::TasksRemoved { count: 1 });
+    assert!(snapshot.tasks.is_empty());
+    assert_eq!(snapshot.trashed, 2);
+}
+
+#[test]
+fn frequency_tolerance_widens_the_conflict_footprint() {
+    let script = [
+        r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1100,"time_start":0,"time_end":100,"amplifier":"A960_1215","freq_tolerance":5}"#,
+        r#"{"id":"T2","name":"Balise","freq_start":1103,"freq_end":1150,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#,
+    ];
+    let (_, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert_eq!(snapshot.tasks[0].freq_tolerance, Some(5.));
+    assert_eq!(egui_test::tools::constraints::find_conflicts(&snapshot.tasks).len(), 1);
+}
+
+#[test]
+fn empty_shape_is_handled_as_a_rectangle() {
+    let script = [
+        r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215","shape":[]}"#,
+        r#"{"cmd":"highlight","id":"T1"}"#,
+    ];
+    let (events, _) = run_script(interface(), &script).unwrap();
+    assert_eq!(events.len(), 2);
+
+    let mut task = Task::builder("Radar", Amplifier::A960_1215).freq(1000., 1200.).time(0., 100.).build().unwrap();
+    task.shape = Some(Vec::new());
+    assert_eq!(task.outline().len(), 4);
+    assert!(egui_test::tools::view::task_hit(&task, false, 1100., 50., [0., 0.]));
+}
+
+#[test]
+fn shapes_with_fewer_than_three_vertices_are_rejected() {
+    let script = [
+        r#"{"name":"Segment","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215","shape":[[1000,0],[1200,100]]}"#,
+        r#"{"name":"Triangle","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215","shape":[[1000,0],[1200,0],[1100,100]]}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(matches!(events[0], ProtocolEvent::Error { .. }));
+    assert_eq!(snapshot.tasks.len(), 1);
+    assert_eq!(snapshot.tasks[0].name, "Triangle");
+}
+
+#[test]
+fn negative_or_non_finite_frequency_tolerance_is_rejected() {
+    let script = [
+        r#"{"name":"Négative","freq_start":1000,"freq_end":1100,"time_start":0,"time_end":100,"amplifier":"A960_1215","freq_tolerance":-500}"#,
+        r#"{"name":"Infinie","freq_start":1000,"freq_end":1100,"time_start":0,"time_end":100,"amplifier":"A960_1215","freq_tolerance":1e400}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(events.iter().all(|e| matches!(e, ProtocolEvent::Error { .. })));
+    assert!(snapshot.tasks.is_empty());
+}
+
+#[test]
+fn spot_frequency_and_instantaneous_tasks_are_accepted_and_hittable() {
+    let script = [
+        r#"{"name":"Balise","freq_start":1100,"freq_end":1100,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#,
+        r#"{"name":"Impulsion","freq_start":2000,"freq_end":2200,"time_start":50,"time_end":50,"amplifier":"A1000_2500"}"#,
+    ];
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(events.iter().all(|e| !matches!(e, ProtocolEvent::Error { .. })));
+    assert_eq!(snapshot.tasks.len(), 2);
+    let hit = egui_test::tools::view::task_hit;
+    assert!(hit(&snapshot.tasks[0], false, 1100.5, 50., [2., 5.]));
+    assert!(!hit(&snapshot.tasks[0], false, 1105., 50., [2., 5.]));
+    assert!(hit(&snapshot.tasks[1], false, 2100., 51., [2., 5.]));
+    assert!(!hit(&snapshot.tasks[1], false, 2100., 60., [2., 5.]));
+}
+
+#[test]
+fn burst_of_distinct_tasks_is_applied_without_loss() {
+    let messages: Vec<String> = (0..500)
+        .map(|i| format!(r#"{{"name":"Rafale {i}","freq_start":1000,"freq_end":1100,"time_start":{i},"time_end":{},"amplifier":"A960_1215"}}"#, i + 1))
+        .collect();
+    let script: Vec<&str> = messages.iter().map(String::as_str).collect();
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(events.iter().all(|e| !matches!(e, ProtocolEvent::Error { .. })));
+    assert_eq!(snapshot.tasks.len(), 500);
+}
+
+#[test]
+fn held_commands_do_not_replace_held_task_messages() {
+    let mut messages: Vec<String> = (0..200)
+        .map(|i| format!(r#"{{"name":"Remplissage {i}","freq_start":1000,"freq_end":1100,"time_start":{i},"time_end":{},"amplifier":"A960_1215"}}"#, i + 1))
+        .collect();
+    messages.push(r#"{"id":"T1","name":"Radar","freq_start":1000,"freq_end":1200,"time_start":0,"time_end":100,"amplifier":"A960_1215"}"#.into());
+    messages.push(r#"{"cmd":"highlight","id":"T1"}"#.into());
+    let script: Vec<&str> = messages.iter().map(String::as_str).collect();
+    let (events, snapshot) = run_script(interface(), &script).unwrap();
+
+    assert!(events.iter().all(|e| !matches!(e, ProtocolEvent::Error { .. })));
+    assert!(events.contains(&ProtocolEvent::TaskHighlighted { key: "T1".into() }));
+    assert!(snapshot.tasks.iter().any(|t| t.key() == "T1"));
+    assert_eq!(snapshot.messages_received, 202);
+}
+
+#[test]
+fn schedule_subcommand_avoids_exclusions_and_protected_bands() {
+    let dir = std::env::temp_dir().join(format!("gantt_schedule_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("plan.json"),
+        r#"[{"name":"Radar","freq_start":1000,"freq_end":1100,"time_start":0,"time_end":100,"amplifier":"A960_1215"}]"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("masks.json"), r#"[{"name":"Radar sol","freq_start":950,"freq_end":1150,"time_start":0,"time_end":500}]"#).unwrap();
+    std::fs::write(
+        dir.join("gps.json"),
+        r#"[{"name":"Brouillage L1","freq_start":1570,"freq_end":1580,"time_start":0,"time_end":100,"amplifier":"A1000_2500"}]"#,
+    )
+    .unwrap();
+
+    let masked = interface().args(["schedule", "plan.json", "--exclusions", "masks.json"]).current_dir(&dir).output().unwrap();
+    let protected = interface().args(["schedule", "gps.json", "--protect", "GPS L1"]).current_dir(&dir).output().unwrap();
+    let unprotected = interface().args(["schedule", "gps.json"]).current_dir(&dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(masked.status.success());
+    let tasks: Vec<Task> = serde_json::from_slice(&masked.stdout).unwrap();
+    assert!(tasks[0].time_start >= 500.);
+    assert!(!protected.status.success());
+    assert!(unprotected.status.success());
+}
@@ -1,7 +1,10 @@
-use subprocess::{Popen, PopenConfig, Redirection};
-use std::io::Write;
-use std::{thread, time::Duration};
+use subprocess::{Exec, ExitStatus, Popen, PopenConfig, Redirection};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use base64::Engine;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -14,8 +17,143 @@ struct Task {
     amplifier: String, // Pour simplifier : représente Amplifier sous forme de String
 }
 
+/// Contrôle de synchronisation : empreinte du plan courant.
+#[derive(Serialize)]
+struct PlanHashMessage {
+    plan_hash: String,
+}
+
+/// Plan complet rediffusé périodiquement, accompagné de son empreinte.
+#[derive(Serialize)]
+struct PlanMessage<'a> {
+    plan: &'a [Task],
+    hash: String,
+}
+
+/// Poignée de main : algorithmes de compression proposés à l'interface.
+#[derive(Serialize)]
+struct HelloMessage {
+    hello: Hello,
+}
+
+#[derive(Serialize)]
+struct Hello {
+    client: &'static str,
+    compression: &'static [&'static str],
+}
+
+/// Message compressé en gzip et encodé en base64.
+#[derive(Serialize)]
+struct CompressedMessage {
+    compressed: &'static str,
+    data: String,
+}
+
+/// Réponse de l'interface reçue sur sa sortie standard.
+enum Request {
+    /// Rediffusion du plan (`{"event": "resync_requested", …}`).
+    Resync,
+    /// Réponse à la poignée de main (`{"event": "welcome", …}`) : compression retenue.
+    Welcome(Option<String>),
+}
+
+/// Taille à partir de laquelle un plan est compressé, si gzip a été négocié.
+const COMPRESSION_THRESHOLD: usize = 4096;
+
+/// Intervalle d'envoi des tâches une par une.
+const TASK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Empreinte du plan : FNV-1a 64 bits, en hexadécimal, des tâches sérialisées en
+/// JSON (clés triées, une tâche par ligne) et triées par nom.
+fn plan_hash(tasks: &[Task]) -> String {
+    let mut lines: Vec<(&str, String)> = tasks
+        .iter()
+        .map(|t| (t.name.as_str(), serde_json::to_value(t).expect("Erreur sérialisation JSON").to_string()))
+        .collect();
+    lines.sort();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (_, line) in lines {
+        for byte in line.bytes().chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Compresse un message en gzip et l'encapsule dans un message `compressed`.
+fn compress(json: &str) -> String {
+    let mut encoder = flate2::read::GzEncoder::new(json.as_bytes(), flate2::Compression::default());
+    let mut bytes = Vec::new();
+    encoder.read_to_end(&mut bytes).expect("Erreur compression gzip");
+    let message = CompressedMessage {
+        compressed: "gzip",
+        data: base64::engine::general_purpose::STANDARD.encode(bytes),
+    };
+    serde_json::to_string(&message).expect("Erreur sérialisation JSON")
+}
+
+/// Relaie la sortie standard de l'interface et signale ses réponses : demandes de
+/// rediffusion du plan et réponse à la poignée de main.
+fn spawn_stdout_reader(stdout: impl Read + Send + 'static, requests: Sender<Request>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let event = serde_json::from_str::<serde_json::Value>(&line).ok();
+            let request = match event.as_ref().and_then(|e| e.get("event")).and_then(|e| e.as_str()) {
+                Some("resync_requested") => {
+                    println!("Rediffusion demandée par l'interface : {}", line);
+                    Request::Resync
+                }
+                Some("welcome") => {
+                    let compression = event.as_ref().and_then(|e| e.get("compression")).and_then(|c| c.as_str());
+                    println!("Compression négociée : {}", compression.unwrap_or("aucune"));
+                    Request::Welcome(compression.map(str::to_string))
+                }
+                _ => {
+                    println!("{}", line);
+                    continue;
+                }
+            };
+            if requests.send(request).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Écrit un message JSON sur une ligne de l'entrée standard de l'interface.
+fn send(stdin: &mut impl Write, json: &str) {
+    stdin.write_all(json.as_bytes()).expect("Échec write");
+    stdin.write_all(b"\n").expect("Échec write newline");
+    stdin.flush().expect("Échec flush");
+}
+
 fn main() -> subprocess::Result<()> {
-    // Lancer le sous-processus avec stdin redirigé
+    // `--dump-schema` : le schéma JSON du protocole est généré par l'interface,
+    // qui définit les types des messages
+    // `--self-test` : l'interface lance une instance sans fenêtre et vérifie sa
+    // réponse à un script de messages
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(flag @ ("--dump-schema" | "--self-test")) = args.first().map(String::as_str) {
+        let status = Exec::cmd("cargo")
+            .args(&["run", "-q", "--manifest-path", "src/interface/Cargo.toml", "--", flag])
+            .join()?;
+        std::process::exit(if let ExitStatus::Exited(code) = status { code as i32 } else { 1 });
+    }
+
+    // `--rebroadcast <secondes>` : rediffusion périodique du plan complet, afin qu'un
+    // affichage lancé ou relancé en cours de route converge vers le plan courant
+    let rebroadcast = args.iter().position(|a| a == "--rebroadcast").map(|i| {
+        match args.get(i + 1).and_then(|n| n.parse::<u64>().ok()).filter(|&n| n > 0) {
+            Some(n) => Duration::from_secs(n),
+            None => {
+                eprintln!("usage : --rebroadcast <secondes>");
+                std::process::exit(2);
+            }
+        }
+    });
+
+    // Lancer le sous-processus avec stdin et stdout redirigés
     let mut p = Popen::create(
         &[
             "cargo",
@@ -25,14 +163,23 @@ fn main() -> subprocess::Result<()> {
         ],
         PopenConfig {
             stdin: Redirection::Pipe,
+            stdout: Redirection::Pipe,
             ..Default::default()
         },
     )?;
 
+    let (request_tx, request_rx) = channel();
+    spawn_stdout_reader(p.stdout.take().expect("Échec ouverture stdout"), request_tx);
+
     let stdin = p.stdin.as_mut().expect("Échec ouverture stdin");
 
+    // Poignée de main : les plans volumineux sont compressés si l'interface accepte gzip
+    let hello = HelloMessage { hello: Hello { client: "demo", compression: &["gzip"] } };
+    send(stdin, &serde_json::to_string(&hello).expect("Erreur sérialisation JSON"));
+    let mut gzip = false;
+
     // Liste des tâches à envoyer une par une
-    let tasks = vec![
+    let tasks = [
         Task {
             name: "Init capteurs".into(),
             freq_start: 100.0,
@@ -60,18 +207,53 @@ fn main() -> subprocess::Result<()> {
     ];
 
     let mut step = 0;
+    let mut next_task = Instant::now();
+    let mut next_plan = rebroadcast.map(|interval| Instant::now() + interval);
+    let mut resync = false;
 
     loop {
-        let task = &tasks[step % tasks.len()];  // Avance dans la liste cycliquement
-        let json = serde_json::to_string(task).expect("Erreur sérialisation JSON");
+        let now = Instant::now();
+
+        if now >= next_task {
+            let task = &tasks[step % tasks.len()];  // Avance dans la liste cycliquement
+            let json = serde_json::to_string(task).expect("Erreur sérialisation JSON");
+            send(stdin, &json);
+            println!("Tâche envoyée : {}", task.name);
+
+            step += 1;
+            next_task += TASK_INTERVAL;
 
-        stdin.write_all(json.as_bytes()).expect("Échec write");
-        stdin.write_all(b"\n").expect("Échec write newline");
-        stdin.flush().expect("Échec flush");
+            // Contrôle de synchronisation : empreinte du plan courant
+            let check = PlanHashMessage { plan_hash: plan_hash(&tasks[..step.min(tasks.len())]) };
+            send(stdin, &serde_json::to_string(&check).expect("Erreur sérialisation JSON"));
+        }
 
-        println!("Tâche envoyée : {}", task.name);
+        // Rediffusion du plan courant (les tâches déjà envoyées), périodique ou
+        // demandée par l'interface
+        let periodic = next_plan.is_some_and(|at| now >= at);
+        if periodic || resync {
+            let plan = &tasks[..step.min(tasks.len())];
+            let message = PlanMessage { plan, hash: plan_hash(plan) };
+            let json = serde_json::to_string(&message).expect("Erreur sérialisation JSON");
+            if gzip && json.len() >= COMPRESSION_THRESHOLD {
+                send(stdin, &compress(&json));
+            } else {
+                send(stdin, &json);
+            }
+            println!("Plan rediffusé : {} tâches ({})", plan.len(), message.hash);
+            if let (true, Some(at), Some(interval)) = (periodic, next_plan, rebroadcast) {
+                next_plan = Some(at + interval);
+            }
+            resync = false;
+        }
 
-        step += 1;
-        thread::sleep(Duration::from_secs(5));
+        let wake = next_plan.map_or(next_task, |at| at.min(next_task));
+        match request_rx.recv_timeout(wake.saturating_duration_since(Instant::now())) {
+            Ok(Request::Resync) => resync = true,
+            Ok(Request::Welcome(compression)) => gzip = compression.as_deref() == Some("gzip"),
+            Err(RecvTimeoutError::Timeout) => {}
+            // Sortie de l'interface fermée : plus de demande possible
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(wake.saturating_duration_since(Instant::now())),
+        }
     }
 }